@@ -32,8 +32,11 @@ where
             "GL_ARB_bindless_texture",
             "GL_ARB_blend_func_extended",
             "GL_ARB_buffer_storage",
+            "GL_ARB_clear_texture",
             "GL_ARB_compute_shader",
+            "GL_ARB_compute_variable_group_size",
             "GL_ARB_copy_buffer",
+            "GL_ARB_copy_image",
             "GL_ARB_debug_output",
             "GL_ARB_depth_texture",
             "GL_ARB_direct_state_access",
@@ -50,17 +53,20 @@ where
             "GL_ARB_invalidate_subdata",
             "GL_ARB_multi_draw_indirect",
             "GL_ARB_occlusion_query",
+            "GL_ARB_parallel_shader_compile",
             "GL_ARB_pixel_buffer_object",
             "GL_ARB_robustness",
             "GL_ARB_seamless_cube_map",
             "GL_ARB_shader_image_load_store",
             "GL_ARB_shader_objects",
+            "GL_ARB_sparse_texture",
             "GL_ARB_texture_buffer_object",
             "GL_ARB_texture_float",
             "GL_ARB_texture_multisample",
             "GL_ARB_texture_rg",
             "GL_ARB_texture_rgb10_a2ui",
             "GL_ARB_texture_storage",
+            "GL_ARB_texture_view",
             "GL_ARB_transform_feedback3",
             "GL_ARB_vertex_buffer_object",
             "GL_ARB_vertex_shader",
@@ -87,7 +93,9 @@ where
             "GL_EXT_texture_sRGB",
             "GL_EXT_transform_feedback",
             "GL_GREMEDY_string_marker",
+            "GL_KHR_parallel_shader_compile",
             "GL_KHR_robustness",
+            "GL_KHR_texture_compression_astc_ldr",
             "GL_NVX_gpu_memory_info",
             "GL_NV_conditional_render",
             "GL_NV_vertex_attrib_integer_64bit",
@@ -112,6 +120,7 @@ where
             "GL_EXT_primitive_bounding_box",
             "GL_EXT_robustness",
             "GL_KHR_debug",
+            "GL_KHR_parallel_shader_compile",
             "GL_NV_copy_buffer",
             "GL_NV_framebuffer_multisample",
             "GL_NV_internalformat_sample_query",
@@ -134,6 +134,31 @@ pub fn build_texture_file<W: Write>(dest: &mut W) {
     build_texture(dest, TextureType::DepthStencil, TextureDimensions::CubemapArray);
 }
 
+/// Returns the type of the value passed to the `clear` functions of a texture type, and the
+/// expression that turns `value` into a `ClearBufferData`.
+fn clear_value_type(ty: TextureType) -> Option<(&'static str, &'static str)> {
+    match ty {
+        TextureType::Regular | TextureType::Srgb => {
+            Some(("(f32, f32, f32, f32)",
+                  "ClearBufferData::Float([value.0, value.1, value.2, value.3])"))
+        },
+        TextureType::Integral => {
+            Some(("(i32, i32, i32, i32)",
+                  "ClearBufferData::Integral([value.0, value.1, value.2, value.3])"))
+        },
+        TextureType::Unsigned => {
+            Some(("(u32, u32, u32, u32)",
+                  "ClearBufferData::Unsigned([value.0, value.1, value.2, value.3])"))
+        },
+        TextureType::Depth => Some(("f32", "ClearBufferData::Depth(value)")),
+        TextureType::Stencil => Some(("i32", "ClearBufferData::Stencil(value)")),
+        TextureType::DepthStencil => {
+            Some(("(f32, i32)", "ClearBufferData::DepthStencil(value.0, value.1)"))
+        },
+        TextureType::Compressed | TextureType::CompressedSrgb => None,
+    }
+}
+
 fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDimensions) {
     // building the name of the module
     let module_name: String = {
@@ -308,6 +333,7 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
 
             use crate::texture::any::{{self, TextureAny, TextureAnyLayer, TextureAnyMipmap}};
             use crate::texture::any::{{TextureAnyLayerMipmap, TextureAnyImage, Dimensions}};
+            use crate::texture::any::{{ClearTextureError, CopyImageError}};
             use crate::texture::bindless::{{ResidentTexture, BindlessTexturesNotSupportedError}};
             use crate::texture::get_format::{{InternalFormat, InternalFormatType, GetFormatError}};
             use crate::texture::pixel_buffer::PixelBuffer;
@@ -315,6 +341,8 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
             use crate::texture::{{Texture3dDataSource, Texture2dDataSink, MipmapsOption, CompressedMipmapsOption}};
             use crate::texture::{{RawImage1d, RawImage2d, RawImage3d, CubeLayer}};
             use crate::texture::pixel::PixelValue;
+            #[cfg(feature = \"block_compression\")]
+            use crate::texture::compress::{{self, CompressionQuality}};
 
             use crate::image_format::{{ClientFormatAny, TextureFormatRequest}};
             use crate::image_format::{{UncompressedFloatFormat, UncompressedIntFormat}};
@@ -324,6 +352,7 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
             use crate::backend::Facade;
             use crate::uniforms::{{UniformValue, AsUniformValue, Sampler, ImageUnit, ImageUnitError, ImageUnitFormat}};
             use crate::framebuffer;
+            use crate::fbo::ClearBufferData;
 
             use crate::Rect;
 
@@ -411,6 +440,55 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
                 }}
             ", name)).unwrap();
 
+    // `TextureViewTarget` trait impl
+    {
+        let view_dimensions = match dimensions {
+            TextureDimensions::Texture1d => "Dimensions::Texture1d { width }",
+            TextureDimensions::Texture2d => "Dimensions::Texture2d { width, height }",
+            TextureDimensions::Texture2dMultisample => {
+                "Dimensions::Texture2dMultisample { width, height, samples }"
+            },
+            TextureDimensions::Texture3d => "Dimensions::Texture3d { width, height, depth }",
+            TextureDimensions::Texture1dArray => {
+                "Dimensions::Texture1dArray { width, array_size: layers }"
+            },
+            TextureDimensions::Texture2dArray => {
+                "Dimensions::Texture2dArray { width, height, array_size: layers }"
+            },
+            TextureDimensions::Texture2dMultisampleArray => {
+                "Dimensions::Texture2dMultisampleArray { width, height, array_size: layers, samples }"
+            },
+            TextureDimensions::Cubemap => "Dimensions::Cubemap { dimension: width }",
+            TextureDimensions::CubemapArray => {
+                "Dimensions::CubemapArray { dimension: width, array_size: layers / 6 }"
+            },
+        };
+
+        (writeln!(dest, "
+                impl crate::texture::TextureViewTarget for {name} {{
+                    type Format = {format};
+
+                    #[inline]
+                    fn format_to_texture_format(format: {format}) -> crate::texture::TextureFormat {{
+                        format.to_texture_format()
+                    }}
+
+                    #[inline]
+                    #[allow(unused_variables)]
+                    fn view_dimensions(width: u32, height: u32, depth: u32, layers: u32,
+                                       samples: u32) -> Dimensions
+                    {{
+                        {dims}
+                    }}
+
+                    #[inline]
+                    fn from_any(texture: TextureAny) -> {name} {{
+                        {name}(texture)
+                    }}
+                }}
+            ", name = name, format = relevant_format, dims = view_dimensions)).unwrap();
+    }
+
     // `UniformValue` trait impl for samplers
     {
         match ty {
@@ -668,6 +746,40 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
                mipmaps = mipmaps_option_ty)).unwrap();
     }
 
+    // writing the `with_format_and_quality` function
+    if dimensions == TextureDimensions::Texture2d && is_compressed {
+        (writeln!(dest, "
+                /// Builds a new texture with a specific format, compressing the data on the CPU
+                /// with the given quality if the format is supported by the encoder.
+                ///
+                /// See the `compress` module for the list of formats that can be compressed on
+                /// the CPU. Other formats are compressed by the driver.
+                #[cfg(feature = \"block_compression\")]
+                pub fn with_format_and_quality<'a, F: ?Sized, T>(facade: &F, data: T,
+                                          format: {format}, mipmaps: {mipmaps},
+                                          quality: CompressionQuality)
+                                          -> Result<{name}, TextureCreationError>
+                                          where T: Texture2dDataSource<'a>, F: Facade
+                {{
+                    let RawImage2d {{ data, width, height, format: client_format }} =
+                                            data.into_raw();
+                    let format = TextureFormatRequest::Specific(format.to_texture_format());
+
+                    if let Some(texture) = compress::new_texture_2d(facade, format, client_format,
+                                                                    &data, width, height,
+                                                                    mipmaps.into(), quality)?
+                    {{
+                        return Ok({name}(texture));
+                    }}
+
+                    let client_format = ClientFormatAny::ClientFormat(client_format);
+                    Ok({name}(any::new_texture(facade, format, Some((client_format, data)),
+                                               mipmaps.into(), {dim_params_passing})?))
+                }}
+            ", format = relevant_format, name = name, mipmaps = mipmaps_option_ty,
+               dim_params_passing = dimensions_parameters_passing)).unwrap();
+    }
+
     // writing the `new_impl` function
     if !dimensions.is_multisample() && !dimensions.is_cube() {
         let param = match dimensions {
@@ -725,6 +837,22 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
             _ => unreachable!()
         }
 
+        // compressing on the CPU if possible
+        if dimensions == TextureDimensions::Texture2d && is_compressed {
+            (write!(dest, "
+                    #[cfg(feature = \"block_compression\")]
+                    {{
+                        if let Some(texture) = compress::new_texture_2d(facade, format, client_format,
+                                                                        &data, width, height,
+                                                                        mipmaps.into(),
+                                                                        Default::default())?
+                        {{
+                            return Ok({}(texture));
+                        }}
+                    }}
+                ", name)).unwrap();
+        }
+
         (write!(dest, "let client_format = ClientFormatAny::ClientFormat(client_format);")).unwrap();
 
         // writing the constructor
@@ -1052,6 +1180,51 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
             "#, format = relevant_format)).unwrap();
     }
 
+    // writing the `clear` and `clear_region` functions
+    if let Some((clear_value, _)) = clear_value_type(ty) {
+        (write!(dest, r#"
+                /// Clears all the mipmap levels and layers of the texture to a value, without
+                /// going through a framebuffer if `GL_ARB_clear_texture` is available.
+                ///
+                /// See `TextureAnyMipmap::raw_clear_region` for more infos.
+                pub fn clear(&self, value: {clear_value}) -> Result<(), ClearTextureError> {{
+                    for level in 0 .. self.get_mipmap_levels() {{
+                        self.mipmap(level).unwrap().clear(value)?;
+                    }}
+
+                    Ok(())
+                }}
+
+                /// Clears a rectangle of the main mipmap level to a value, in all the layers
+                /// of the texture.
+                ///
+                /// See `TextureAnyMipmap::raw_clear_region` for more infos.
+                #[inline]
+                pub fn clear_region(&self, rect: Rect, value: {clear_value})
+                                    -> Result<(), ClearTextureError>
+                {{
+                    self.main_level().clear_region(rect, value)
+                }}
+            "#, clear_value = clear_value)).unwrap();
+    }
+
+    // writing the `copy_region_to` function
+    (write!(dest, r#"
+            /// Copies a rectangle of the main mipmap level to the main mipmap level of another
+            /// texture, in all the layers of the texture.
+            ///
+            /// See `TextureAnyMipmap::copy_region_to` for more infos.
+            #[inline]
+            pub fn copy_region_to(&self, rect: Rect, target: &TextureAny, target_left: u32,
+                                  target_bottom: u32) -> Result<(), CopyImageError>
+            {{
+                let source = self.0.main_level();
+                let (_, _, layers) = source.get_region_extent();
+                source.copy_region_to((rect.left, rect.bottom, 0), (rect.width, rect.height, layers),
+                                      &target.main_level(), (target_left, target_bottom, 0))
+            }}
+        "#)).unwrap();
+
     // `resident_if_supported`
     (write!(dest, r#"
             /// Turns the texture into a `ResidentTexture`.
@@ -1189,6 +1362,32 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
         // dimensions getters
         write_dimensions_getters(dest, dimensions, "self.0", true);
 
+        // writing the `clear` and `clear_region` functions for mipmaps
+        if let Some((clear_value, clear_data)) = clear_value_type(ty) {
+            (write!(dest, r#"
+                    /// Clears this mipmap level, including all its layers, to a value.
+                    ///
+                    /// See `TextureAnyMipmap::raw_clear_region` for more infos.
+                    #[inline]
+                    pub fn clear(&self, value: {clear_value}) -> Result<(), ClearTextureError> {{
+                        self.0.raw_clear({clear_data})
+                    }}
+
+                    /// Clears a rectangle of this mipmap level to a value, in all the layers
+                    /// of the texture.
+                    ///
+                    /// See `TextureAnyMipmap::raw_clear_region` for more infos.
+                    #[inline]
+                    pub fn clear_region(&self, rect: Rect, value: {clear_value})
+                                        -> Result<(), ClearTextureError>
+                    {{
+                        let (_, _, layers) = self.0.get_region_extent();
+                        self.0.raw_clear_region((rect.left, rect.bottom, 0),
+                                                (rect.width, rect.height, layers), {clear_data})
+                    }}
+                "#, clear_value = clear_value, clear_data = clear_data)).unwrap();
+        }
+
         // writing the `write` function for mipmaps.
         // TODO: implement for other types too
         if dimensions == TextureDimensions::Texture2d &&
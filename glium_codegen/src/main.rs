@@ -0,0 +1,103 @@
+/*!
+Generates the Rust structs matching the vertex inputs and the blocks of GLSL shaders.
+
+```notrust
+glium-codegen [-o <output.rs>] [--vertex-name <name>] <shader>...
+```
+
+The shaders are parsed without an OpenGL context, with `glium::program::codegen`. The inputs
+are only collected from the vertex shaders, which are the files with a `.vert` or `.vs`
+extension. The vertex struct of `teapot.vert` is named `TeapotVertex`, unless another name is
+passed with `--vertex-name`.
+
+The code is written to the standard output if no output file is given.
+
+*/
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process;
+
+use glium::program::codegen::Generator;
+
+const USAGE: &str = "usage: glium-codegen [-o <output.rs>] [--vertex-name <name>] <shader>...";
+
+fn main() {
+    if let Err(message) = run(env::args().skip(1)) {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+}
+
+fn run<I>(mut args: I) -> Result<(), String> where I: Iterator<Item = String> {
+    let mut output = None;
+    let mut vertex_name = None;
+    let mut shaders = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match &*arg {
+            "-o" | "--output" => output = Some(args.next().ok_or(USAGE)?),
+            "--vertex-name" => vertex_name = Some(args.next().ok_or(USAGE)?),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            },
+            _ if arg.starts_with('-') => return Err(format!("unknown option {}\n{}", arg, USAGE)),
+            _ => shaders.push(arg),
+        }
+    }
+
+    if shaders.is_empty() {
+        return Err(USAGE.to_owned());
+    }
+
+    let mut generator = Generator::new();
+
+    for shader in &shaders {
+        let path = Path::new(shader);
+        let source = fs::read_to_string(path).map_err(|err| format!("{}: {}", shader, err))?;
+
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let result = if extension == "vert" || extension == "vs" {
+            let name = match vertex_name {
+                Some(ref name) => name.clone(),
+                None => default_vertex_name(path),
+            };
+            generator.add_vertex_shader_source(&name, &source)
+        } else {
+            generator.add_shader_source(&source)
+        };
+
+        result.map_err(|err| format!("{}: {}", shader, err))?;
+    }
+
+    let code = generator.generate().map_err(|err| err.to_string())?;
+
+    match output {
+        Some(output) => fs::write(&output, code).map_err(|err| format!("{}: {}", output, err)),
+        None => {
+            print!("{}", code);
+            Ok(())
+        },
+    }
+}
+
+/// Turns `shaders/teapot_lit.vert` into `TeapotLitVertex`.
+fn default_vertex_name(path: &Path) -> String {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    let mut name = stem.split(|c: char| !c.is_ascii_alphanumeric())
+                       .filter(|part| !part.is_empty())
+                       .map(|part| {
+                           let mut chars = part.chars();
+                           chars.next().map(|c| c.to_ascii_uppercase()).into_iter()
+                                .chain(chars).collect::<String>()
+                       })
+                       .collect::<String>();
+
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+
+    name.push_str("Vertex");
+    name
+}
@@ -0,0 +1,138 @@
+//! Parsing of the `#[glium(...)]` attributes.
+
+use proc_macro2::TokenStream;
+use syn::{Data, DeriveInput, Expr, Fields, Generics, Ident, LitStr, Path, Type};
+
+/// Attributes of the struct itself.
+pub struct ContainerAttributes {
+    /// Path of the glium crate in the generated code.
+    pub crate_path: Path,
+}
+
+impl ContainerAttributes {
+    pub fn parse(input: &DeriveInput) -> syn::Result<ContainerAttributes> {
+        let mut crate_path = syn::parse_quote!(::glium);
+
+        for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("glium")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("crate") {
+                    crate_path = meta.value()?.parse::<LitStr>()?.parse()?;
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown glium attribute"))
+                }
+            })?;
+        }
+
+        Ok(ContainerAttributes { crate_path })
+    }
+}
+
+/// Kind of field attribute, used to reject the attributes that don't apply to a derive.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum FieldAttribute {
+    Name,
+    Normalize,
+    Location,
+    Flatten,
+}
+
+impl FieldAttribute {
+    fn from_ident(ident: &Ident) -> Option<FieldAttribute> {
+        match &*ident.to_string() {
+            "name" => Some(FieldAttribute::Name),
+            "normalize" => Some(FieldAttribute::Normalize),
+            "location" => Some(FieldAttribute::Location),
+            "flatten" => Some(FieldAttribute::Flatten),
+            _ => None,
+        }
+    }
+}
+
+/// A named field of the struct and its attributes.
+pub struct Field<'a> {
+    pub ident: &'a Ident,
+    pub ty: &'a Type,
+    /// Name of the field in the shader.
+    pub name: String,
+    pub normalize: bool,
+    pub location: Option<Expr>,
+    pub flatten: bool,
+}
+
+/// Returns the fields of a struct with named fields, or an error if `input` is something else.
+///
+/// `allowed` is the list of field attributes that the derive accepts.
+pub fn named_fields<'a>(input: &'a DeriveInput, derive: &str, allowed: &[FieldAttribute])
+                        -> syn::Result<Vec<Field<'a>>>
+{
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            Fields::Unit => return Ok(Vec::new()),
+            Fields::Unnamed(_) => return Err(not_supported(input, derive)),
+        },
+        _ => return Err(not_supported(input, derive)),
+    };
+
+    fields.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        let mut result = Field {
+            ident,
+            ty: &field.ty,
+            name: ident.to_string(),
+            normalize: false,
+            location: None,
+            flatten: false,
+        };
+
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("glium")) {
+            attr.parse_nested_meta(|meta| {
+                let kind = meta.path.get_ident().and_then(FieldAttribute::from_ident);
+                let kind = match kind {
+                    Some(kind) if allowed.contains(&kind) => kind,
+                    Some(_) => return Err(meta.error(format!("this attribute is not supported \
+                                                               by #[derive({})]", derive))),
+                    None => return Err(meta.error("unknown glium attribute")),
+                };
+
+                match kind {
+                    FieldAttribute::Name => result.name = meta.value()?.parse::<LitStr>()?.value(),
+                    FieldAttribute::Normalize => result.normalize = true,
+                    FieldAttribute::Location => result.location = Some(meta.value()?.parse()?),
+                    FieldAttribute::Flatten => result.flatten = true,
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(result)
+    }).collect()
+}
+
+/// Returns the generics of the struct with a `field: bound` predicate for each field.
+///
+/// The predicates are only needed when the struct is generic, so the generics are returned
+/// unchanged otherwise.
+pub fn with_field_bounds(generics: &Generics, fields: &[Field<'_>], bound: &TokenStream)
+                         -> Generics
+{
+    let mut generics = generics.clone();
+    if generics.params.is_empty() {
+        return generics;
+    }
+
+    let where_clause = generics.make_where_clause();
+    for field in fields {
+        let ty = field.ty;
+        where_clause.predicates.push(syn::parse_quote!(#ty: #bound));
+    }
+
+    generics
+}
+
+fn not_supported(input: &DeriveInput, derive: &str) -> syn::Error {
+    syn::Error::new_spanned(&input.ident, format!("#[derive({})] can only be used on structs \
+                                                   with named fields", derive))
+}
@@ -0,0 +1,116 @@
+//! `#[derive(Std140Layout)]` and `#[derive(Std430Layout)]`
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::DeriveInput;
+
+use crate::attributes::{self, ContainerAttributes};
+
+/// Name of one of the two layouts, used to build the names of the trait and of its constants.
+#[derive(Copy, Clone)]
+pub enum Layout {
+    Std140,
+    Std430,
+}
+
+impl Layout {
+    fn name(self) -> &'static str {
+        match self {
+            Layout::Std140 => "std140",
+            Layout::Std430 => "std430",
+        }
+    }
+
+    fn trait_name(self) -> &'static str {
+        match self {
+            Layout::Std140 => "Std140Layout",
+            Layout::Std430 => "Std430Layout",
+        }
+    }
+}
+
+pub fn expand(input: &DeriveInput, layout: Layout) -> syn::Result<TokenStream> {
+    let krate = ContainerAttributes::parse(input)?.crate_path;
+    let layout_name = layout.name();
+    let upper_name = layout_name.to_uppercase();
+    let fields = attributes::named_fields(input, layout.trait_name(), &[])?;
+
+    // the compiler is free to reorder the fields otherwise
+    if !is_repr_c(input)? {
+        return Err(syn::Error::new_spanned(&input.ident, format!("#[derive({})] requires \
+                                                                  #[repr(C)]", layout.trait_name())));
+    }
+
+    let trait_ident = format_ident!("{}", layout.trait_name());
+    let trait_path = quote!(#krate::uniforms::layout::#trait_ident);
+    let alignment = format_ident!("{}_ALIGNMENT", upper_name);
+    let size = format_ident!("{}_SIZE", upper_name);
+    let struct_alignment = format_ident!("{}_struct_alignment", layout_name);
+
+    let generics = attributes::with_field_bounds(&input.generics, &fields, &trait_path);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let struct_name = &input.ident;
+
+    let alignments = fields.iter().map(|field| {
+        let ty = field.ty;
+        quote!(<#ty as #trait_path>::#alignment)
+    });
+
+    let offset_checks = fields.iter().map(|field| {
+        let ident = field.ident;
+        let ty = field.ty;
+        let message = format!("The field `{}` of `{}` is not at the offset required by the {} \
+                               layout", ident, struct_name, layout_name);
+
+        quote! {
+            offset = #krate::uniforms::layout::align_offset(offset, <#ty as #trait_path>::#alignment);
+            if offset != ::std::mem::offset_of!(Self, #ident) {
+                panic!(#message);
+            }
+            offset += <#ty as #trait_path>::#size;
+        }
+    });
+
+    // non-generic structs are checked where they are defined, generic ones when they are used
+    let check = if input.generics.params.is_empty() {
+        quote! {
+            const _: usize = <#struct_name as #trait_path>::#size;
+        }
+    } else {
+        quote!()
+    };
+
+    Ok(quote! {
+        unsafe impl #impl_generics #trait_path for #struct_name #ty_generics #where_clause {
+            const #alignment: usize = #krate::uniforms::layout::#struct_alignment(&[#(#alignments),*]);
+
+            const #size: usize = {
+                #[allow(unused_mut)]
+                let mut offset = 0;
+                #(#offset_checks)*
+                #krate::uniforms::layout::align_offset(offset, Self::#alignment)
+            };
+        }
+
+        #check
+    })
+}
+
+fn is_repr_c(input: &DeriveInput) -> syn::Result<bool> {
+    let mut repr_c = false;
+
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") {
+                repr_c = true;
+            } else if meta.input.peek(syn::token::Paren) {
+                // `align(N)` or `packed(N)`
+                let _content;
+                syn::parenthesized!(_content in meta.input);
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(repr_c)
+}
@@ -0,0 +1,83 @@
+/*!
+Derive macros for the `Vertex`, `Uniforms`, `UniformBlock`, `Std140Layout` and `Std430Layout`
+traits of glium.
+
+These derives generate the same trait implementations as the `implement_vertex!`, `uniform!`
+and `implement_uniform_block!` macros, but also work with generic structs and can be customized
+with `#[glium(...)]` attributes on the fields.
+
+Use them through the `derive` feature of glium, which re-exports them at the root of the crate:
+
+```ignore
+#[derive(Copy, Clone, glium::Vertex)]
+struct Vertex {
+    position: [f32; 2],
+    #[glium(name = "color", normalize)]
+    rgba: [u8; 4],
+}
+```
+
+# Field attributes
+
+ - `#[glium(name = "...")]` uses a different name in the shader than the name of the field.
+ - `#[glium(normalize)]` normalizes the integer values of a vertex attribute to the `[0, 1]` or
+   `[-1, 1]` range. Only for `Vertex`.
+ - `#[glium(location = ...)]` binds a vertex attribute to an explicit location. Only for `Vertex`.
+ - `#[glium(flatten)]` forwards all the uniforms of a field that implements `Uniforms`. Only for
+   `Uniforms`.
+
+Nested structs in uniform blocks only need to implement `UniformBlock` themselves.
+
+# Container attributes
+
+ - `#[glium(crate = "path")]` changes the path of the glium crate in the generated code, for
+   example when glium is re-exported by another crate.
+
+*/
+#![warn(missing_docs)]
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod attributes;
+mod layout;
+mod uniform_block;
+mod uniforms;
+mod vertex;
+
+/// Implements `glium::vertex::Vertex` for a struct.
+#[proc_macro_derive(Vertex, attributes(glium))]
+pub fn derive_vertex(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    vertex::expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Implements `glium::uniforms::Uniforms` for a struct.
+#[proc_macro_derive(Uniforms, attributes(glium))]
+pub fn derive_uniforms(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    uniforms::expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Implements `glium::uniforms::layout::Std140Layout` for a struct, and checks at compile time
+/// that the offsets of the fields follow the `std140` rules.
+#[proc_macro_derive(Std140Layout, attributes(glium))]
+pub fn derive_std140_layout(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    layout::expand(&input, layout::Layout::Std140).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Implements `glium::uniforms::layout::Std430Layout` for a struct, and checks at compile time
+/// that the offsets of the fields follow the `std430` rules.
+#[proc_macro_derive(Std430Layout, attributes(glium))]
+pub fn derive_std430_layout(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    layout::expand(&input, layout::Layout::Std430).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Implements `glium::uniforms::UniformBlock` for a struct.
+#[proc_macro_derive(UniformBlock, attributes(glium))]
+pub fn derive_uniform_block(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    uniform_block::expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
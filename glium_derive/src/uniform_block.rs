@@ -0,0 +1,96 @@
+//! `#[derive(UniformBlock)]`
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::DeriveInput;
+
+use crate::attributes::{self, ContainerAttributes, FieldAttribute};
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let krate = ContainerAttributes::parse(input)?.crate_path;
+    let fields = attributes::named_fields(input, "UniformBlock", &[FieldAttribute::Name])?;
+
+    let generics = attributes::with_field_bounds(&input.generics, &fields,
+                                                 &quote!(#krate::uniforms::UniformBlock));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let struct_name = &input.ident;
+
+    let names = fields.iter().map(|field| &field.name).collect::<Vec<_>>();
+
+    // checking that each field of the struct is correct in the reflection
+    let member_checks = fields.iter().map(|field| {
+        let ident = field.ident;
+        let ty = field.ty;
+        let name = &field.name;
+
+        quote! {
+            let reflected_ty = match members.iter().find(|&(name, _)| name == #name) {
+                Some(member) => &member.1,
+                None => return Err(LayoutMismatchError::MissingField {
+                    name: #name.to_owned(),
+                }),
+            };
+
+            let offset = base_offset + ::std::mem::offset_of!(Self, #ident);
+            if let Err(err) = <#ty as #krate::uniforms::UniformBlock>::matches(reflected_ty, offset) {
+                return Err(LayoutMismatchError::MemberMismatch {
+                    member: #name.to_owned(),
+                    err: Box::new(err),
+                });
+            }
+        }
+    });
+
+    let member_layouts = fields.iter().map(|field| {
+        let ident = field.ident;
+        let ty = field.ty;
+        let name = &field.name;
+
+        quote! {
+            (
+                #name.to_owned(),
+                <#ty as #krate::uniforms::UniformBlock>::build_layout(
+                    base_offset + ::std::mem::offset_of!(Self, #ident)
+                ),
+            )
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics #krate::uniforms::UniformBlock for #struct_name #ty_generics #where_clause {
+            fn matches(layout: &#krate::program::BlockLayout, base_offset: usize)
+                       -> ::std::result::Result<(), #krate::uniforms::LayoutMismatchError>
+            {
+                use #krate::program::BlockLayout;
+                use #krate::uniforms::LayoutMismatchError;
+
+                if let BlockLayout::Struct { members } = layout {
+                    // checking that each member exists in the struct
+                    for (name, _) in members {
+                        if #(name != #names &&)* true {
+                            return Err(LayoutMismatchError::MissingField {
+                                name: name.clone(),
+                            });
+                        }
+                    }
+
+                    #(#member_checks)*
+
+                    Ok(())
+
+                } else {
+                    Err(LayoutMismatchError::LayoutMismatch {
+                        expected: layout.clone(),
+                        obtained: <Self as #krate::uniforms::UniformBlock>::build_layout(base_offset),
+                    })
+                }
+            }
+
+            fn build_layout(base_offset: usize) -> #krate::program::BlockLayout {
+                #krate::program::BlockLayout::Struct {
+                    members: vec![#(#member_layouts),*],
+                }
+            }
+        }
+    })
+}
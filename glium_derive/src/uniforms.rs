@@ -0,0 +1,56 @@
+//! `#[derive(Uniforms)]`
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::DeriveInput;
+
+use crate::attributes::{self, ContainerAttributes, FieldAttribute};
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let krate = ContainerAttributes::parse(input)?.crate_path;
+    let fields = attributes::named_fields(input, "Uniforms", &[FieldAttribute::Name,
+                                                               FieldAttribute::Flatten])?;
+
+    let mut generics = input.generics.clone();
+    if !generics.params.is_empty() {
+        let where_clause = generics.make_where_clause();
+        for field in &fields {
+            let ty = field.ty;
+            where_clause.predicates.push(if field.flatten {
+                syn::parse_quote!(#ty: #krate::uniforms::Uniforms)
+            } else {
+                syn::parse_quote!(#ty: #krate::uniforms::AsUniformValue)
+            });
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let struct_name = &input.ident;
+
+    let visits = fields.iter().map(|field| {
+        let ident = field.ident;
+        let name = &field.name;
+
+        if field.flatten {
+            quote! {
+                #krate::uniforms::Uniforms::visit_values(&self.#ident, &mut output);
+            }
+        } else {
+            quote! {
+                output(#name, #krate::uniforms::AsUniformValue::as_uniform_value(&self.#ident));
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics #krate::uniforms::Uniforms for #struct_name #ty_generics #where_clause {
+            #[inline]
+            #[allow(unused_mut, unused_variables)]
+            fn visit_values<'__glium, __GliumF>(&'__glium self, mut output: __GliumF)
+                where __GliumF: FnMut(&str, #krate::uniforms::UniformValue<'__glium>)
+            {
+                #(#visits)*
+            }
+        }
+    })
+}
@@ -0,0 +1,54 @@
+//! `#[derive(Vertex)]`
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::DeriveInput;
+
+use crate::attributes::{self, ContainerAttributes, FieldAttribute};
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let krate = ContainerAttributes::parse(input)?.crate_path;
+    let fields = attributes::named_fields(input, "Vertex", &[FieldAttribute::Name,
+                                                             FieldAttribute::Normalize,
+                                                             FieldAttribute::Location])?;
+
+    let generics = attributes::with_field_bounds(&input.generics, &fields,
+                                                 &quote!(#krate::vertex::Attribute));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let struct_name = &input.ident;
+
+    let bindings = fields.iter().map(|field| {
+        let ident = field.ident;
+        let ty = field.ty;
+        let name = &field.name;
+        let normalize = field.normalize;
+        let location = match field.location {
+            Some(ref location) => quote!(#location),
+            None => quote!(-1),
+        };
+
+        quote! {
+            (
+                ::std::borrow::Cow::Borrowed(#name),
+                ::std::mem::offset_of!(Self, #ident),
+                #location,
+                <#ty as #krate::vertex::Attribute>::TYPE,
+                #normalize,
+            )
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            #[doc(hidden)]
+            const __GLIUM_VERTEX_BINDINGS: #krate::vertex::VertexFormat = &[#(#bindings),*];
+        }
+
+        impl #impl_generics #krate::vertex::Vertex for #struct_name #ty_generics #where_clause {
+            #[inline]
+            fn build_bindings() -> #krate::vertex::VertexFormat {
+                Self::__GLIUM_VERTEX_BINDINGS
+            }
+        }
+    })
+}
@@ -125,6 +125,14 @@ pub struct Capabilities {
     /// Number of work groups for compute shaders.
     pub max_compute_work_group_count: (gl::types::GLint, gl::types::GLint, gl::types::GLint),
 
+    /// Maximum local size of compute shaders that use a variable local size. `None` if
+    /// `ARB_compute_variable_group_size` is not supported.
+    pub max_compute_variable_group_size: Option<(gl::types::GLint, gl::types::GLint, gl::types::GLint)>,
+
+    /// Maximum number of invocations in a single local work group of compute shaders that use a
+    /// variable local size. `None` if `ARB_compute_variable_group_size` is not supported.
+    pub max_compute_variable_group_invocations: Option<gl::types::GLint>,
+
     /// Maximum number of color attachment bind points.
     pub max_color_attachments: gl::types::GLint,
 
@@ -531,6 +539,28 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             (0, 0, 0)
         },
 
+        max_compute_variable_group_size: if extensions.gl_arb_compute_variable_group_size {
+            let mut val1 = 0;
+            let mut val2 = 0;
+            let mut val3 = 0;
+            gl.GetIntegeri_v(gl::MAX_COMPUTE_VARIABLE_GROUP_SIZE_ARB, 0, &mut val1);
+            gl.GetIntegeri_v(gl::MAX_COMPUTE_VARIABLE_GROUP_SIZE_ARB, 1, &mut val2);
+            gl.GetIntegeri_v(gl::MAX_COMPUTE_VARIABLE_GROUP_SIZE_ARB, 2, &mut val3);
+            Some((val1, val2, val3))
+
+        } else {
+            None
+        },
+
+        max_compute_variable_group_invocations: if extensions.gl_arb_compute_variable_group_size {
+            let mut val = 0;
+            gl.GetIntegerv(gl::MAX_COMPUTE_VARIABLE_GROUP_INVOCATIONS_ARB, &mut val);
+            Some(val)
+
+        } else {
+            None
+        },
+
         max_color_attachments: {
             if version >= &Version(Api::Gl, 3, 0) || version >= &Version(Api::GlEs, 3, 0) ||
                extensions.gl_arb_framebuffer_object || extensions.gl_ext_framebuffer_object ||
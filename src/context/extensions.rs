@@ -57,9 +57,12 @@ extensions! {
     "GL_APPLE_vertex_array_object" => gl_apple_vertex_array_object,
     "GL_ARB_bindless_texture" => gl_arb_bindless_texture,
     "GL_ARB_buffer_storage" => gl_arb_buffer_storage,
+    "GL_ARB_clear_texture" => gl_arb_clear_texture,
     "GL_ARB_clip_control" => gl_arb_clip_control,
     "GL_ARB_compute_shader" => gl_arb_compute_shader,
+    "GL_ARB_compute_variable_group_size" => gl_arb_compute_variable_group_size,
     "GL_ARB_copy_buffer" => gl_arb_copy_buffer,
+    "GL_ARB_copy_image" => gl_arb_copy_image,
     "GL_ARB_debug_output" => gl_arb_debug_output,
     "GL_ARB_depth_clamp" => gl_arb_depth_clamp,
     "GL_ARB_depth_texture" => gl_arb_depth_texture,
@@ -85,6 +88,7 @@ extensions! {
     "GL_ARB_invalidate_subdata" => gl_arb_invalidate_subdata,
     "GL_ARB_occlusion_query" => gl_arb_occlusion_query,
     "GL_ARB_occlusion_query2" => gl_arb_occlusion_query2,
+    "GL_ARB_parallel_shader_compile" => gl_arb_parallel_shader_compile,
     "GL_ARB_pixel_buffer_object" => gl_arb_pixel_buffer_object,
     "GL_ARB_program_interface_query" => gl_arb_program_interface_query,
     "GL_ARB_query_buffer_object" => gl_arb_query_buffer_object,
@@ -95,11 +99,13 @@ extensions! {
     "GL_ARB_robust_buffer_access_behavior" => gl_arb_robust_buffer_access_behavior,
     "GL_ARB_sampler_objects" => gl_arb_sampler_objects,
     "GL_ARB_seamless_cube_map" => gl_arb_seamless_cube_map,
+    "GL_ARB_separate_shader_objects" => gl_arb_separate_shader_objects,
     "GL_ARB_shader_atomic_counters" => gl_arb_shader_atomic_counters,
     "GL_ARB_shader_image_load_store" => gl_arb_shader_image_load_store,
     "GL_ARB_shader_objects" => gl_arb_shader_objects,
     "GL_ARB_shader_storage_buffer_object" => gl_arb_shader_storage_buffer_object,
     "GL_ARB_shader_subroutine" => gl_arb_shader_subroutine,
+    "GL_ARB_sparse_texture" => gl_arb_sparse_texture,
     "GL_ARB_sync" => gl_arb_sync,
     "GL_ARB_tessellation_shader" => gl_arb_tessellation_shader,
     "GL_ARB_texture_buffer_object" => gl_arb_texture_buffer_object,
@@ -114,6 +120,7 @@ extensions! {
     "GL_ARB_texture_rgb10_a2ui" => gl_arb_texture_rgb10_a2ui,
     "GL_ARB_texture_stencil8" => gl_arb_texture_stencil8,
     "GL_ARB_texture_storage" => gl_arb_texture_storage,
+    "GL_ARB_texture_view" => gl_arb_texture_view,
     "GL_ARB_timer_query" => gl_arb_timer_query,
     "GL_ARB_transform_feedback3" => gl_arb_transform_feedback3,
     "GL_ARB_uniform_buffer_object" => gl_arb_uniform_buffer_object,
@@ -168,8 +175,10 @@ extensions! {
     "GL_GREMEDY_string_marker" => gl_gremedy_string_marker,
     "GL_KHR_debug" => gl_khr_debug,
     "GL_KHR_context_flush_control" => gl_khr_context_flush_control,
+    "GL_KHR_parallel_shader_compile" => gl_khr_parallel_shader_compile,
     "GL_KHR_robustness" => gl_khr_robustness,
     "GL_KHR_robust_buffer_access_behavior" => gl_khr_robust_buffer_access_behavior,
+    "GL_KHR_texture_compression_astc_ldr" => gl_khr_texture_compression_astc_ldr,
     "GL_NV_fbo_color_attachments" => gl_nv_fbo_color_attachments,
     "GL_NV_conditional_render" => gl_nv_conditional_render,
     "GL_NV_copy_buffer" => gl_nv_copy_buffer,
@@ -187,6 +196,16 @@ extensions! {
     "GL_NV_transform_feedback" => gl_nv_transform_feedback,
     "GL_NV_vertex_attrib_integer_64bit" => gl_nv_vertex_attrib_integer_64bit,
     "GL_NVX_gpu_memory_info" => gl_nvx_gpu_memory_info,
+    "GL_OES_compressed_EAC_R11_signed_texture" => gl_oes_compressed_eac_r11_signed_texture,
+    "GL_OES_compressed_EAC_R11_unsigned_texture" => gl_oes_compressed_eac_r11_unsigned_texture,
+    "GL_OES_compressed_EAC_RG11_signed_texture" => gl_oes_compressed_eac_rg11_signed_texture,
+    "GL_OES_compressed_EAC_RG11_unsigned_texture" => gl_oes_compressed_eac_rg11_unsigned_texture,
+    "GL_OES_compressed_ETC2_punchthroughA_RGBA8_texture" => gl_oes_compressed_etc2_punchthrougha_rgba8_texture,
+    "GL_OES_compressed_ETC2_punchthroughA_sRGB8_alpha8_texture" => gl_oes_compressed_etc2_punchthrougha_srgb8_alpha8_texture,
+    "GL_OES_compressed_ETC2_RGB8_texture" => gl_oes_compressed_etc2_rgb8_texture,
+    "GL_OES_compressed_ETC2_RGBA8_texture" => gl_oes_compressed_etc2_rgba8_texture,
+    "GL_OES_compressed_ETC2_sRGB8_alpha8_texture" => gl_oes_compressed_etc2_srgb8_alpha8_texture,
+    "GL_OES_compressed_ETC2_sRGB8_texture" => gl_oes_compressed_etc2_srgb8_texture,
     "GL_OES_depth_texture" => gl_oes_depth_texture,
     "GL_OES_draw_elements_base_vertex" => gl_oes_draw_elements_base_vertex,
     "GL_OES_element_index_uint" => gl_oes_element_index_uint,
@@ -200,6 +219,7 @@ extensions! {
     "GL_OES_tessellation_shader" => gl_oes_tessellation_shader,
     "GL_OES_texture_3D" => gl_oes_texture_3d,
     "GL_OES_texture_buffer" => gl_oes_texture_buffer,
+    "GL_OES_texture_compression_astc" => gl_oes_texture_compression_astc,
     "GL_OES_texture_cube_map_array" => gl_oes_texture_cube_map_array,
     "GL_OES_texture_stencil8" => gl_oes_texture_stencil8,
     "GL_OES_texture_storage_multisample_2d_array" => gl_oes_texture_storage_multisample_2d_array,
@@ -13,6 +13,7 @@ use std::ffi::CStr;
 use std::rc::Rc;
 use std::os::raw;
 use std::hash::BuildHasherDefault;
+use std::task::Waker;
 
 use fnv::FnvHasher;
 
@@ -20,6 +21,7 @@ use crate::IncompatibleOpenGl;
 use crate::SwapBuffersError;
 use crate::CapabilitiesSource;
 use crate::ContextExt;
+use crate::ToGlEnum;
 use crate::backend::Backend;
 use crate::version;
 use crate::version::Api;
@@ -97,6 +99,10 @@ pub struct Context {
     /// List of images handles that are resident. We need to call `MakeImageHandleResidentARB`
     /// when rebuilding the context.
     resident_image_handles: RefCell<Vec<(gl::types::GLuint64, gl::types::GLenum)>>,
+
+    /// Wakers of the tasks that are waiting for a `SyncFenceFuture`. They are woken up by
+    /// `poll_fences`.
+    fence_wakers: RefCell<Vec<Waker>>,
 }
 
 /// This struct is a guard that is returned when you want to access the OpenGL backend.
@@ -212,6 +218,7 @@ impl Context {
             samplers,
             resident_texture_handles,
             resident_image_handles,
+            fence_wakers: RefCell::new(Vec::new()),
         });
 
         if context.debug_callback.is_some() {
@@ -590,6 +597,91 @@ impl Context {
         unsafe { ctxt.gl.Flush(); }
     }
 
+    /// Calls `glMemoryBarrier` with the given kinds of accesses.
+    ///
+    /// glium automatically inserts the required barriers between a draw or compute command that
+    /// writes to a buffer or a texture and the next command that accesses it. You only need
+    /// this function for writes that glium doesn't know about, for example through bindless
+    /// textures or raw OpenGL calls.
+    ///
+    /// Does nothing if the backend doesn't support image load/store or shader storage
+    /// buffers, as in this situation shaders can't write to memory and no barrier is needed.
+    /// Similarly, the `shader_storage`, `query_buffer` and `client_mapped_buffer` barriers are
+    /// ignored if the backend doesn't support the corresponding kind of buffer.
+    pub fn memory_barrier(&self, mut barriers: crate::MemoryBarrier) {
+        let mut ctxt = self.make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 4, 2) ||
+             ctxt.version >= &Version(Api::GlEs, 3, 1) ||
+             ctxt.extensions.gl_arb_shader_image_load_store)
+        {
+            return;
+        }
+
+        // these bits generate a `GL_INVALID_VALUE` if they are not supported
+        if !(ctxt.version >= &Version(Api::Gl, 4, 3) ||
+             ctxt.version >= &Version(Api::GlEs, 3, 1) ||
+             ctxt.extensions.gl_arb_shader_storage_buffer_object)
+        {
+            barriers.shader_storage = false;
+        }
+
+        if !(ctxt.version >= &Version(Api::Gl, 4, 4) || ctxt.extensions.gl_arb_query_buffer_object) {
+            barriers.query_buffer = false;
+        }
+
+        if !(ctxt.version >= &Version(Api::Gl, 4, 4) || ctxt.extensions.gl_arb_buffer_storage ||
+             ctxt.extensions.gl_ext_buffer_storage)
+        {
+            barriers.client_mapped_buffer = false;
+        }
+
+        if barriers.is_empty() {
+            return;
+        }
+
+        unsafe { ctxt.gl.MemoryBarrier(barriers.to_glenum()); }
+
+        // updating the state so that the automatic barriers aren't issued a second time
+        let id = ctxt.state.next_draw_call_id;
+        let state = &mut *ctxt.state;
+        if barriers.vertex_attrib_array { state.latest_memory_barrier_vertex_attrib_array = id; }
+        if barriers.element_array { state.latest_memory_barrier_element_array = id; }
+        if barriers.uniform { state.latest_memory_barrier_uniform = id; }
+        if barriers.texture_fetch { state.latest_memory_barrier_texture_fetch = id; }
+        if barriers.shader_image_access { state.latest_memory_barrier_shader_image_access = id; }
+        if barriers.command { state.latest_memory_barrier_command = id; }
+        if barriers.pixel_buffer { state.latest_memory_barrier_pixel_buffer = id; }
+        if barriers.texture_update { state.latest_memory_barrier_texture_update = id; }
+        if barriers.buffer_update { state.latest_memory_barrier_buffer_update = id; }
+        if barriers.framebuffer { state.latest_memory_barrier_framebuffer = id; }
+        if barriers.transform_feedback { state.latest_memory_barrier_transform_feedback = id; }
+        if barriers.atomic_counter { state.latest_memory_barrier_atomic_counter = id; }
+        if barriers.shader_storage { state.latest_memory_barrier_shader_storage = id; }
+        if barriers.query_buffer { state.latest_memory_barrier_query_buffer = id; }
+    }
+
+    /// Wakes up all the tasks that are waiting for a `SyncFenceFuture` created from this
+    /// context, so that they check whether their fence has been signaled.
+    ///
+    /// The futures obtained from a `SyncFence` can't be woken up by the OpenGL
+    /// implementation. You should call this function regularly, for example once per frame,
+    /// if you use them.
+    pub fn poll_fences(&self) {
+        let wakers = mem::take(&mut *self.fence_wakers.borrow_mut());
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    /// Registers a waker that will be woken up by the next call to `poll_fences`.
+    pub(crate) fn register_fence_waker(&self, waker: &Waker) {
+        let mut wakers = self.fence_wakers.borrow_mut();
+        if !wakers.iter().any(|w| w.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+    }
+
     /// Inserts a debugging string in the commands queue. If you use an OpenGL debugger, you will
     /// be able to see that string.
     ///
@@ -84,6 +84,9 @@ pub struct GlState {
     /// The latest value passed to `glUseProgram`.
     pub program: Handle,
 
+    /// The latest value passed to `glBindProgramPipeline`.
+    pub program_pipeline: gl::types::GLuint,
+
     /// The latest value passed to `glBindVertexArray`.
     pub vertex_array: gl::types::GLuint,
 
@@ -405,6 +408,7 @@ impl Default for GlState {
             enabled_clip_planes: 0,
 
             program: Handle::Id(0),
+            program_pipeline: 0,
             vertex_array: 0,
             clear_color: (0.0, 0.0, 0.0, 0.0),
             clear_depth: 1.0,
@@ -84,7 +84,8 @@ use std::ops::Range;
 
 pub use self::blend::{Blend, BlendingFunction, LinearBlendingFactor};
 pub use self::depth::{Depth, DepthTest, DepthClamp};
-pub use self::query::{QueryCreationError};
+pub use self::query::{QueryCreationError, ToBufferError, PoolableQuery};
+pub use self::query_pool::QueryPool;
 pub use self::query::{SamplesPassedQuery, TimeElapsedQuery, PrimitivesGeneratedQuery};
 pub use self::query::{AnySamplesPassedQuery, TransformFeedbackPrimitivesWrittenQuery};
 pub use self::stencil::{StencilTest, StencilOperation, Stencil};
@@ -92,6 +93,7 @@ pub use self::stencil::{StencilTest, StencilOperation, Stencil};
 mod blend;
 mod depth;
 mod query;
+mod query_pool;
 mod stencil;
 
 /// Describes how triangles should be filtered before the fragment processing. Backface culling
@@ -337,6 +337,16 @@ impl RawQuery {
         self.get_u32() != 0
     }
 
+    /// Marks the query as unused so that it can be started again.
+    ///
+    /// The result of the previous use must have been retrieved or written to a buffer first,
+    /// otherwise it will be lost.
+    pub(crate) fn reset(&self) {
+        let mut ctxt = self.context.make_current();
+        self.deactivate(&mut ctxt);
+        self.has_been_used.set(false);
+    }
+
     /// If the query is active, unactivates it.
     fn deactivate(&self, ctxt: &mut CommandContext<'_>) {
         if ctxt.state.samples_passed_query == self.id {
@@ -714,8 +724,29 @@ unsafe fn raw_end_query(ctxt: &mut CommandContext<'_>, ty: gl::types::GLenum) {
     }
 }
 
+/// Query types that can be recycled by a `QueryPool`.
+///
+/// This trait is implemented on all the query types of glium and can't be implemented on
+/// other types.
+pub trait PoolableQuery: GlObject<Id = gl::types::GLuint> + Sized {
+    /// The type of the result of the query.
+    type Output;
+
+    /// Builds a new query.
+    #[doc(hidden)]
+    fn new_pooled(context: &Rc<Context>) -> Result<Self, QueryCreationError>;
+
+    /// Returns the underlying query object.
+    #[doc(hidden)]
+    fn raw_query(&self) -> &RawQuery;
+
+    /// Returns the value of the query without consuming it. Blocks until it is available.
+    #[doc(hidden)]
+    fn read_result(&self) -> Self::Output;
+}
+
 macro_rules! impl_helper {
-    ($name:ident, $ret:ty, $get_fn:ident) => {
+    ($name:ident, $ret:ty, $get_fn:ident, $ctor:expr) => {
         impl $name {
             /// Queries the counter to see if the result is already available.
             #[inline]
@@ -801,6 +832,25 @@ macro_rules! impl_helper {
                 self.query.is_unused()
             }
         }
+
+        impl PoolableQuery for $name {
+            type Output = $ret;
+
+            #[inline]
+            fn new_pooled(context: &Rc<Context>) -> Result<$name, QueryCreationError> {
+                $ctor(context)
+            }
+
+            #[inline]
+            fn raw_query(&self) -> &RawQuery {
+                &self.query
+            }
+
+            #[inline]
+            fn read_result(&self) -> $ret {
+                self.query.$get_fn()
+            }
+        }
     };
 }
 
@@ -822,7 +872,7 @@ impl SamplesPassedQuery {
     }
 }
 
-impl_helper!(SamplesPassedQuery, u32, get_u32);
+impl_helper!(SamplesPassedQuery, u32, get_u32, SamplesPassedQuery::new);
 
 /// A query that allows you to know the number of nanoseconds that have elapsed
 /// during the draw operations.
@@ -841,7 +891,7 @@ impl TimeElapsedQuery {
     }
 }
 
-impl_helper!(TimeElapsedQuery, u32, get_u32);
+impl_helper!(TimeElapsedQuery, u32, get_u32, TimeElapsedQuery::new);
 
 /// A query type that allows you to know whether any sample has been written to the output during
 /// the operations executed with this query.
@@ -884,7 +934,8 @@ impl AnySamplesPassedQuery {
     }
 }
 
-impl_helper!(AnySamplesPassedQuery, bool, get_bool);
+impl_helper!(AnySamplesPassedQuery, bool, get_bool,
+             |c: &Rc<Context>| AnySamplesPassedQuery::new(c, false));
 
 /// Query that allows you to know the number of primitives generated by the geometry shader.
 /// Will stay at `0` if you use it without any active geometry shader.
@@ -904,7 +955,7 @@ impl PrimitivesGeneratedQuery {
     }
 }
 
-impl_helper!(PrimitivesGeneratedQuery, u32, get_u32);
+impl_helper!(PrimitivesGeneratedQuery, u32, get_u32, PrimitivesGeneratedQuery::new);
 
 /// Query that allows you to know the number of primitives generated by transform feedback.
 #[derive(Debug)]
@@ -923,4 +974,5 @@ impl TransformFeedbackPrimitivesWrittenQuery {
     }
 }
 
-impl_helper!(TransformFeedbackPrimitivesWrittenQuery, u32, get_u32);
+impl_helper!(TransformFeedbackPrimitivesWrittenQuery, u32, get_u32,
+             TransformFeedbackPrimitivesWrittenQuery::new);
@@ -0,0 +1,206 @@
+use crate::backend::Facade;
+use crate::context::Context;
+use crate::buffer::BufferSlice;
+use crate::QueryExt;
+
+use crate::draw_parameters::query::{PoolableQuery, QueryCreationError, ToBufferError};
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::rc::Rc;
+
+/// A pool of query objects of the same type that are recycled once their result has been
+/// retrieved.
+///
+/// The typical usage is to obtain a query from the pool with `acquire` at the start of a frame,
+/// draw with it, then give it back to the pool with `submit`. Later on, `collect_ready` returns
+/// the results of all the queries that the GPU has finished processing, without blocking,
+/// and puts the query objects back in the pool so that they can be reused.
+///
+/// ```no_run
+/// # use glium::Surface;
+/// # use glutin::surface::{ResizeableSurface, SurfaceTypeTrait};
+/// # fn example<T>(display: glium::Display<T>, program: glium::Program,
+/// #               vb: glium::vertex::VertexBufferAny, ib: glium::index::IndexBuffer<u16>)
+/// #     where T: SurfaceTypeTrait + ResizeableSurface {
+/// use glium::draw_parameters::{QueryPool, TimeElapsedQuery};
+///
+/// let mut pool = QueryPool::<TimeElapsedQuery>::new(&display);
+///
+/// loop {
+///     let query = pool.acquire().unwrap();
+///
+///     let params = glium::DrawParameters {
+///         time_elapsed_query: Some(&query),
+///         .. Default::default()
+///     };
+///
+///     let mut frame = display.draw();
+///     frame.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params).unwrap();
+///     frame.finish().unwrap();
+///
+///     pool.submit(query);
+///
+///     for (frame_id, time) in pool.collect_ready() {
+///         println!("Frame #{} took {}ns", frame_id, time);
+///     }
+/// }
+/// # }
+/// ```
+pub struct QueryPool<T> where T: PoolableQuery {
+    context: Rc<Context>,
+
+    // queries that are ready to be used again
+    free: Vec<T>,
+
+    // queries that have been submitted and whose result hasn't been retrieved yet, in the order
+    // of submission
+    pending: VecDeque<(u64, T)>,
+
+    // identifier that will be assigned to the next submitted query
+    next_id: u64,
+}
+
+impl<T> QueryPool<T> where T: PoolableQuery {
+    /// Builds a new empty pool.
+    ///
+    /// Query objects are only created when needed by `acquire`.
+    #[inline]
+    pub fn new<F>(facade: &F) -> QueryPool<T> where F: Facade + ?Sized {
+        QueryPool {
+            context: facade.get_context().clone(),
+            free: Vec::new(),
+            pending: VecDeque::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Returns an unused query, either by recycling a query whose result has been collected or
+    /// by creating a new one.
+    ///
+    /// The query must later be passed back to the pool with `submit` or `submit_to_buffer`.
+    /// Dropping it instead simply destroys the query object.
+    #[inline]
+    pub fn acquire(&mut self) -> Result<T, QueryCreationError> {
+        match self.free.pop() {
+            Some(query) => Ok(query),
+            None => T::new_pooled(&self.context),
+        }
+    }
+
+    /// Gives back a query to the pool after it has been used.
+    ///
+    /// Returns the identifier that will be associated to the result of this query when
+    /// it is returned by `collect_ready`. Identifiers start at 0 and are incremented by one
+    /// for each submission. If the query hasn't been used by any draw command, it is recycled
+    /// immediately and no result is returned for this identifier.
+    pub fn submit(&mut self, query: T) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        if query.raw_query().is_unused() {
+            // the query has not been used by any draw command, there is nothing to wait for
+            self.free.push(query);
+        } else {
+            self.pending.push_back((id, query));
+        }
+
+        id
+    }
+
+    /// Gives back a query to the pool after it has been used, and orders the GPU to write its
+    /// result to a buffer once it is available.
+    ///
+    /// This function doesn't block, and the query can be reused immediately. Its result will
+    /// not be returned by `collect_ready`.
+    ///
+    /// If writing to a buffer is not supported by the backend, the query is submitted as if
+    /// `submit` had been called and an error is returned.
+    pub fn submit_to_buffer(&mut self, query: T, target: BufferSlice<'_, u32>)
+                            -> Result<(), ToBufferError>
+    {
+        if query.raw_query().is_unused() {
+            self.free.push(query);
+            return Ok(());
+        }
+
+        match query.raw_query().write_u32_to_buffer(target) {
+            Ok(()) => {
+                query.raw_query().reset();
+                self.free.push(query);
+                Ok(())
+            },
+            Err(err) => {
+                self.submit(query);
+                Err(err)
+            },
+        }
+    }
+
+    /// Returns the results of all the submitted queries that are available, in the order of
+    /// submission, and recycles them.
+    ///
+    /// This function never blocks. Since the GPU processes commands in order, this function
+    /// stops at the first query whose result is not yet available, even if later ones are.
+    pub fn collect_ready(&mut self) -> Vec<(u64, T::Output)> {
+        let mut results = Vec::new();
+
+        while let Some((_, query)) = self.pending.front() {
+            if !query.raw_query().is_ready() {
+                break;
+            }
+
+            let (id, query) = self.pending.pop_front().unwrap();
+            results.push((id, query.read_result()));
+            query.raw_query().reset();
+            self.free.push(query);
+        }
+
+        results
+    }
+
+    /// Returns the results of all the submitted queries, in the order of submission, and
+    /// recycles them.
+    ///
+    /// Contrary to `collect_ready`, this function blocks until all the results are available.
+    pub fn collect_all(&mut self) -> Vec<(u64, T::Output)> {
+        let mut results = Vec::with_capacity(self.pending.len());
+
+        for (id, query) in self.pending.drain(..) {
+            results.push((id, query.read_result()));
+            query.raw_query().reset();
+            self.free.push(query);
+        }
+
+        results
+    }
+
+    /// Returns the number of queries that have been submitted and whose result hasn't been
+    /// collected yet.
+    #[inline]
+    pub fn num_pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns the number of query objects that are ready to be reused.
+    #[inline]
+    pub fn num_free(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Destroys the query objects that are ready to be reused.
+    #[inline]
+    pub fn shrink(&mut self) {
+        self.free.clear();
+    }
+}
+
+impl<T> fmt::Debug for QueryPool<T> where T: PoolableQuery {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        fmt.debug_struct("QueryPool")
+            .field("free", &self.free.len())
+            .field("pending", &self.pending.len())
+            .field("next_id", &self.next_id)
+            .finish()
+    }
+}
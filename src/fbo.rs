@@ -59,11 +59,14 @@ use std::collections::HashMap;
 use fnv::FnvHasher;
 use smallvec::SmallVec;
 
+use crate::BlitMask;
 use crate::CapabilitiesSource;
 use crate::GlObject;
+use crate::TextureAccess;
 use crate::TextureExt;
 
 use crate::texture::CubeLayer;
+use crate::texture::TextureAny;
 use crate::texture::TextureAnyImage;
 use crate::texture::TextureAnyMipmap;
 use crate::texture::TextureKind;
@@ -201,6 +204,7 @@ impl<'a> FramebufferAttachments<'a> {
                             default_samples: Some(samples.unwrap_or(0)),
                             default_samples_fixed: Some(fixed_samples),
                         },
+                        textures: Vec::new(),
                         dimensions: (width, height),
                         layers,
                         depth_buffer_bits: None,
@@ -222,6 +226,9 @@ impl<'a> FramebufferAttachments<'a> {
     {
         // TODO: make sure that all attachments are layered
 
+        // textures that are attached, so that memory barriers can be issued before drawing
+        let mut textures = Vec::new();
+
         macro_rules! handle_tex {
             ($tex:ident, $dim:ident, $samples:ident, $num_bits:ident) => ({
                 $num_bits = Some($tex.get_texture().get_internal_format()
@@ -263,6 +270,8 @@ impl<'a> FramebufferAttachments<'a> {
                     },
                 }
 
+                textures.push($tex.get_texture());
+
                 RawAttachment::Texture {
                     texture: $tex.get_texture().get_id(),
                     bind_point: $tex.get_texture().get_bind_point(),
@@ -342,6 +351,7 @@ impl<'a> FramebufferAttachments<'a> {
 
         Ok(ValidatedAttachments {
             raw: raw_attachments,
+            textures,
             dimensions,
             layers: None,       // FIXME: count layers
             depth_buffer_bits: depth_bits,
@@ -355,6 +365,9 @@ impl<'a> FramebufferAttachments<'a> {
                         -> Result<ValidatedAttachments<'a>, ValidationError>
                         where C: CapabilitiesSource
     {
+        // textures that are attached, so that memory barriers can be issued before drawing
+        let mut textures = Vec::new();
+
         macro_rules! handle_tex {
             ($tex:ident, $dim:ident, $samples:ident, $num_bits:ident) => ({
                 $num_bits = Some($tex.get_texture().get_internal_format()
@@ -396,6 +409,8 @@ impl<'a> FramebufferAttachments<'a> {
                     },
                 }
 
+                textures.push($tex.get_texture());
+
                 RawAttachment::Texture {
                     texture: $tex.get_texture().get_id(),
                     bind_point: $tex.get_texture().get_bind_point(),
@@ -531,6 +546,7 @@ impl<'a> FramebufferAttachments<'a> {
 
         Ok(ValidatedAttachments {
             raw: raw_attachments,
+            textures,
             dimensions,
             layers: None,
             depth_buffer_bits: depth_bits,
@@ -544,6 +560,7 @@ impl<'a> FramebufferAttachments<'a> {
 #[derive(Clone)]
 pub struct ValidatedAttachments<'a> {
     raw: RawAttachments,
+    textures: Vec<&'a TextureAny>,
     dimensions: (u32, u32),
     layers: Option<u32>,
     depth_buffer_bits: Option<u16>,
@@ -577,6 +594,34 @@ impl<'a> ValidatedAttachments<'a> {
     pub fn get_stencil_buffer_bits(&self) -> Option<u16> {
         self.stencil_buffer_bits
     }
+
+    /// Returns the attachment points of the framebuffer object that hold the buffers of `mask`,
+    /// as passed to `glInvalidateFramebuffer`.
+    pub fn get_attachment_points(&self, mask: BlitMask) -> SmallVec<[gl::types::GLenum; 8]> {
+        let mut points = SmallVec::new();
+
+        if mask.color {
+            for pos in 0 .. self.raw.color.len() {
+                points.push(gl::COLOR_ATTACHMENT0 + pos as u32);
+            }
+        }
+
+        let has_depth = self.raw.depth.is_some() || self.raw.depth_stencil.is_some();
+        let has_stencil = self.raw.stencil.is_some() || self.raw.depth_stencil.is_some();
+
+        if mask.depth && mask.stencil && self.raw.depth_stencil.is_some() {
+            points.push(gl::DEPTH_STENCIL_ATTACHMENT);
+        } else {
+            if mask.depth && has_depth {
+                points.push(gl::DEPTH_ATTACHMENT);
+            }
+            if mask.stencil && has_stencil {
+                points.push(gl::STENCIL_ATTACHMENT);
+            }
+        }
+
+        points
+    }
 }
 
 /// An error that can happen while validating attachments.
@@ -711,6 +756,20 @@ impl From<[u32; 4]> for ClearBufferData {
     }
 }
 
+impl From<f32> for ClearBufferData {
+    #[inline]
+    fn from(depth: f32) -> ClearBufferData {
+        ClearBufferData::Depth(depth)
+    }
+}
+
+impl From<(f32, i32)> for ClearBufferData {
+    #[inline]
+    fn from((depth, stencil): (f32, i32)) -> ClearBufferData {
+        ClearBufferData::DepthStencil(depth, stencil)
+    }
+}
+
 /// Manages all the framebuffer objects.
 ///
 /// `cleanup` **must** be called when destroying the container, otherwise `Drop` will panic.
@@ -815,6 +874,10 @@ impl FramebuffersContainer {
                                        -> gl::types::GLuint
     {
         if let Some(attachments) = attachments {
+            for texture in attachments.textures.iter() {
+                texture.prepare_for_access(ctxt, TextureAccess::Framebuffer);
+            }
+
             FramebuffersContainer::get_framebuffer(ctxt, attachments)
         } else {
             0
@@ -14,7 +14,7 @@ use crate::ToGlEnum;
 use crate::ops;
 use crate::uniforms;
 
-use crate::{Program, Surface};
+use crate::{program, Program, Surface};
 use crate::DrawError;
 
 use crate::fbo;
@@ -52,6 +52,37 @@ impl DefaultFramebuffer {
             attachment: DefaultFramebufferAttachment::BackLeft,
         }
     }
+
+    /// Draws with a program or a program pipeline.
+    fn draw_program<'a, 'b, V, I, U>(&mut self, vertex_buffer: V,
+                         index_buffer: I, program: program::DrawProgram<'_>, uniforms: &U,
+                         draw_parameters: &DrawParameters<'_>) -> Result<(), DrawError>
+                         where I: Into<index::IndicesSource<'a>>, U: uniforms::Uniforms,
+                         V: vertex::MultiVerticesSource<'b>
+    {
+        if !self.has_depth_buffer() && (draw_parameters.depth.test.requires_depth_buffer() ||
+                draw_parameters.depth.write)
+        {
+            return Err(DrawError::NoDepthBuffer);
+        }
+
+        if let Some(viewport) = draw_parameters.viewport {
+            if viewport.width > self.context.capabilities().max_viewport_dims.0
+                    as u32
+            {
+                return Err(DrawError::ViewportTooLarge);
+            }
+            if viewport.height > self.context.capabilities().max_viewport_dims.1
+                    as u32
+            {
+                return Err(DrawError::ViewportTooLarge);
+            }
+        }
+
+        // TODO: wrong attachment
+        ops::draw(&self.context, None, vertex_buffer, index_buffer.into(), program,
+                  uniforms, draw_parameters, self.get_dimensions())
+    }
 }
 
 impl Surface for DefaultFramebuffer {
@@ -75,34 +106,24 @@ impl Surface for DefaultFramebuffer {
         self.context.capabilities().stencil_bits
     }
 
+    #[inline]
     fn draw<'a, 'b, V, I, U>(&mut self, vertex_buffer: V,
                          index_buffer: I, program: &Program, uniforms: &U,
                          draw_parameters: &DrawParameters<'_>) -> Result<(), DrawError>
                          where I: Into<index::IndicesSource<'a>>, U: uniforms::Uniforms,
                          V: vertex::MultiVerticesSource<'b>
     {
-        if !self.has_depth_buffer() && (draw_parameters.depth.test.requires_depth_buffer() ||
-                draw_parameters.depth.write)
-        {
-            return Err(DrawError::NoDepthBuffer);
-        }
-
-        if let Some(viewport) = draw_parameters.viewport {
-            if viewport.width > self.context.capabilities().max_viewport_dims.0
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-            if viewport.height > self.context.capabilities().max_viewport_dims.1
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-        }
+        self.draw_program(vertex_buffer, index_buffer, program.into(), uniforms, draw_parameters)
+    }
 
-        // TODO: wrong attachment
-        ops::draw(&self.context, None, vertex_buffer, index_buffer.into(), program,
-                  uniforms, draw_parameters, self.get_dimensions())
+    #[inline]
+    fn draw_pipeline<'a, 'b, V, I, U>(&mut self, vertex_buffer: V,
+                         index_buffer: I, pipeline: &program::ProgramPipeline, uniforms: &U,
+                         draw_parameters: &DrawParameters<'_>) -> Result<(), DrawError>
+                         where I: Into<index::IndicesSource<'a>>, U: uniforms::Uniforms,
+                         V: vertex::MultiVerticesSource<'b>
+    {
+        self.draw_program(vertex_buffer, index_buffer, pipeline.into(), uniforms, draw_parameters)
     }
 
     #[inline]
@@ -268,32 +268,28 @@ impl<'a> SimpleFrameBuffer<'a> {
             attachments,
         })
     }
-}
-
-impl<'a> Surface for SimpleFrameBuffer<'a> {
-    #[inline]
-    fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>, color_srgb: bool,
-             depth: Option<f32>, stencil: Option<i32>)
-    {
-        ops::clear(&self.context, Some(&self.attachments), rect, color, color_srgb, depth, stencil);
-    }
-
-    #[inline]
-    fn get_dimensions(&self) -> (u32, u32) {
-        self.attachments.get_dimensions()
-    }
 
+    /// Invalidates the content of the buffers of `mask`, which becomes undefined.
+    ///
+    /// Call this at the end of a pass when the content of some buffers is no longer needed,
+    /// for example the depth buffer. This lets tile-based GPUs skip writing them back to memory.
+    /// Nothing happens if invalidation is not supported by the backend.
     #[inline]
-    fn get_depth_buffer_bits(&self) -> Option<u16> {
-        self.attachments.get_depth_buffer_bits()
+    pub fn invalidate_attachments(&mut self, mask: BlitMask) {
+        ops::invalidate(&self.context, Some(&self.attachments), mask, None);
     }
 
+    /// Invalidates the content of a region of the buffers of `mask`, which becomes undefined.
+    ///
+    /// See `invalidate_attachments`.
     #[inline]
-    fn get_stencil_buffer_bits(&self) -> Option<u16> {
-        self.attachments.get_stencil_buffer_bits()
+    pub fn invalidate_attachments_region(&mut self, mask: BlitMask, rect: &Rect) {
+        ops::invalidate(&self.context, Some(&self.attachments), mask, Some(rect));
     }
 
-    fn draw<'b, 'v, V, I, U>(&mut self, vb: V, ib: I, program: &crate::Program,
+    /// Draws with a program or a program pipeline.
+    fn draw_program<'b, 'v, V, I, U>(&mut self, vb: V, ib: I,
+        program: crate::program::DrawProgram<'_>,
         uniforms: &U, draw_parameters: &crate::DrawParameters<'_>) -> Result<(), DrawError>
         where I: Into<crate::index::IndicesSource<'b>>, U: crate::uniforms::Uniforms,
         V: crate::vertex::MultiVerticesSource<'v>
@@ -320,6 +316,49 @@ impl<'a> Surface for SimpleFrameBuffer<'a> {
         ops::draw(&self.context, Some(&self.attachments), vb,
                   ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
     }
+}
+
+impl<'a> Surface for SimpleFrameBuffer<'a> {
+    #[inline]
+    fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>, color_srgb: bool,
+             depth: Option<f32>, stencil: Option<i32>)
+    {
+        ops::clear(&self.context, Some(&self.attachments), rect, color, color_srgb, depth, stencil);
+    }
+
+    #[inline]
+    fn get_dimensions(&self) -> (u32, u32) {
+        self.attachments.get_dimensions()
+    }
+
+    #[inline]
+    fn get_depth_buffer_bits(&self) -> Option<u16> {
+        self.attachments.get_depth_buffer_bits()
+    }
+
+    #[inline]
+    fn get_stencil_buffer_bits(&self) -> Option<u16> {
+        self.attachments.get_stencil_buffer_bits()
+    }
+
+    #[inline]
+    fn draw<'b, 'v, V, I, U>(&mut self, vb: V, ib: I, program: &crate::Program,
+        uniforms: &U, draw_parameters: &crate::DrawParameters<'_>) -> Result<(), DrawError>
+        where I: Into<crate::index::IndicesSource<'b>>, U: crate::uniforms::Uniforms,
+        V: crate::vertex::MultiVerticesSource<'v>
+    {
+        self.draw_program(vb, ib, program.into(), uniforms, draw_parameters)
+    }
+
+    #[inline]
+    fn draw_pipeline<'b, 'v, V, I, U>(&mut self, vb: V, ib: I,
+        pipeline: &crate::program::ProgramPipeline,
+        uniforms: &U, draw_parameters: &crate::DrawParameters<'_>) -> Result<(), DrawError>
+        where I: Into<crate::index::IndicesSource<'b>>, U: crate::uniforms::Uniforms,
+        V: crate::vertex::MultiVerticesSource<'v>
+    {
+        self.draw_program(vb, ib, pipeline.into(), uniforms, draw_parameters)
+    }
 
     #[inline]
     fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
@@ -510,6 +549,24 @@ impl<'a> MultiOutputFrameBuffer<'a> {
         })
     }
 
+    /// Invalidates the content of the buffers of `mask`, which becomes undefined.
+    ///
+    /// Call this at the end of a pass when the content of some buffers is no longer needed,
+    /// for example the depth buffer. This lets tile-based GPUs skip writing them back to memory.
+    /// Nothing happens if invalidation is not supported by the backend.
+    #[inline]
+    pub fn invalidate_attachments(&mut self, mask: BlitMask) {
+        ops::invalidate(&self.context, Some(&self.example_attachments), mask, None);
+    }
+
+    /// Invalidates the content of a region of the buffers of `mask`, which becomes undefined.
+    ///
+    /// See `invalidate_attachments`.
+    #[inline]
+    pub fn invalidate_attachments_region(&mut self, mask: BlitMask, rect: &Rect) {
+        ops::invalidate(&self.context, Some(&self.example_attachments), mask, Some(rect));
+    }
+
     fn build_attachments(&self, program: &Program) -> fbo::ValidatedAttachments<'_> {
         let mut colors = SmallVec::new();
 
@@ -527,6 +584,36 @@ impl<'a> MultiOutputFrameBuffer<'a> {
             depth_stencil: self.depth_stencil_attachments,
         }).validate(&self.context).unwrap()
     }
+
+    /// Draws with a program or a program pipeline.
+    fn draw_program<'i, 'v, V, I, U>(&mut self, vb: V, ib: I,
+        program: crate::program::DrawProgram<'_>,
+        uniforms: &U, draw_parameters: &crate::DrawParameters<'_>) -> Result<(), DrawError>
+        where I: Into<crate::index::IndicesSource<'i>>,
+        U: crate::uniforms::Uniforms, V: crate::vertex::MultiVerticesSource<'v>
+    {
+        if !self.has_depth_buffer() && (draw_parameters.depth.test.requires_depth_buffer() ||
+                draw_parameters.depth.write)
+        {
+            return Err(DrawError::NoDepthBuffer);
+        }
+
+        if let Some(viewport) = draw_parameters.viewport {
+            if viewport.width > self.context.capabilities().max_viewport_dims.0
+                    as u32
+            {
+                return Err(DrawError::ViewportTooLarge);
+            }
+            if viewport.height > self.context.capabilities().max_viewport_dims.1
+                    as u32
+            {
+                return Err(DrawError::ViewportTooLarge);
+            }
+        }
+
+        ops::draw(&self.context, Some(&self.build_attachments(program.fragment_program())), vb,
+                  ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
+    }
 }
 
 impl<'a> Surface for MultiOutputFrameBuffer<'a> {
@@ -553,32 +640,23 @@ impl<'a> Surface for MultiOutputFrameBuffer<'a> {
         self.example_attachments.get_stencil_buffer_bits()
     }
 
+    #[inline]
     fn draw<'i, 'v, V, I, U>(&mut self, vb: V, ib: I, program: &crate::Program,
         uniforms: &U, draw_parameters: &crate::DrawParameters<'_>) -> Result<(), DrawError>
         where I: Into<crate::index::IndicesSource<'i>>,
         U: crate::uniforms::Uniforms, V: crate::vertex::MultiVerticesSource<'v>
     {
-        if !self.has_depth_buffer() && (draw_parameters.depth.test.requires_depth_buffer() ||
-                draw_parameters.depth.write)
-        {
-            return Err(DrawError::NoDepthBuffer);
-        }
-
-        if let Some(viewport) = draw_parameters.viewport {
-            if viewport.width > self.context.capabilities().max_viewport_dims.0
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-            if viewport.height > self.context.capabilities().max_viewport_dims.1
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-        }
+        self.draw_program(vb, ib, program.into(), uniforms, draw_parameters)
+    }
 
-        ops::draw(&self.context, Some(&self.build_attachments(program)), vb,
-                  ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
+    #[inline]
+    fn draw_pipeline<'i, 'v, V, I, U>(&mut self, vb: V, ib: I,
+        pipeline: &crate::program::ProgramPipeline,
+        uniforms: &U, draw_parameters: &crate::DrawParameters<'_>) -> Result<(), DrawError>
+        where I: Into<crate::index::IndicesSource<'i>>,
+        U: crate::uniforms::Uniforms, V: crate::vertex::MultiVerticesSource<'v>
+    {
+        self.draw_program(vb, ib, pipeline.into(), uniforms, draw_parameters)
     }
 
     #[inline]
@@ -689,6 +767,36 @@ impl<'a> EmptyFrameBuffer {
             attachments,
         })
     }
+
+    /// Draws with a program or a program pipeline.
+    fn draw_program<'b, 'v, V, I, U>(&mut self, vb: V, ib: I,
+        program: crate::program::DrawProgram<'_>,
+        uniforms: &U, draw_parameters: &crate::DrawParameters<'_>) -> Result<(), DrawError>
+        where I: Into<crate::index::IndicesSource<'b>>, U: crate::uniforms::Uniforms,
+        V: crate::vertex::MultiVerticesSource<'v>
+    {
+        if !self.has_depth_buffer() && (draw_parameters.depth.test.requires_depth_buffer() ||
+                        draw_parameters.depth.write)
+        {
+            return Err(DrawError::NoDepthBuffer);
+        }
+
+        if let Some(viewport) = draw_parameters.viewport {
+            if viewport.width > self.context.capabilities().max_viewport_dims.0
+                    as u32
+            {
+                return Err(DrawError::ViewportTooLarge);
+            }
+            if viewport.height > self.context.capabilities().max_viewport_dims.1
+                    as u32
+            {
+                return Err(DrawError::ViewportTooLarge);
+            }
+        }
+
+        ops::draw(&self.context, Some(&self.attachments), vb,
+                  ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
+    }
 }
 
 impl Surface for EmptyFrameBuffer {
@@ -714,32 +822,23 @@ impl Surface for EmptyFrameBuffer {
         None
     }
 
+    #[inline]
     fn draw<'b, 'v, V, I, U>(&mut self, vb: V, ib: I, program: &crate::Program,
         uniforms: &U, draw_parameters: &crate::DrawParameters<'_>) -> Result<(), DrawError>
         where I: Into<crate::index::IndicesSource<'b>>, U: crate::uniforms::Uniforms,
         V: crate::vertex::MultiVerticesSource<'v>
     {
-        if !self.has_depth_buffer() && (draw_parameters.depth.test.requires_depth_buffer() ||
-                        draw_parameters.depth.write)
-        {
-            return Err(DrawError::NoDepthBuffer);
-        }
-
-        if let Some(viewport) = draw_parameters.viewport {
-            if viewport.width > self.context.capabilities().max_viewport_dims.0
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-            if viewport.height > self.context.capabilities().max_viewport_dims.1
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-        }
+        self.draw_program(vb, ib, program.into(), uniforms, draw_parameters)
+    }
 
-        ops::draw(&self.context, Some(&self.attachments), vb,
-                  ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
+    #[inline]
+    fn draw_pipeline<'b, 'v, V, I, U>(&mut self, vb: V, ib: I,
+        pipeline: &crate::program::ProgramPipeline,
+        uniforms: &U, draw_parameters: &crate::DrawParameters<'_>) -> Result<(), DrawError>
+        where I: Into<crate::index::IndicesSource<'b>>, U: crate::uniforms::Uniforms,
+        V: crate::vertex::MultiVerticesSource<'v>
+    {
+        self.draw_program(vb, ib, pipeline.into(), uniforms, draw_parameters)
     }
 
     #[inline]
@@ -0,0 +1,142 @@
+/*!
+Implementations of glium's traits for the half-precision floats of the `half` crate.
+
+`f16` can be used in vertices, as the pixels of textures with a `F16` format, and as uniforms.
+Vertex attributes and pixels are transferred as `GL_HALF_FLOAT` without any conversion, while
+uniforms are converted to single-precision floats since OpenGL has no half-precision uniforms.
+
+Half-precision vertex attributes require OpenGL 3.0, OpenGL ES 3.0 or one of the
+`GL_ARB_vertex_half_float`, `GL_NV_half_float` or `GL_OES_vertex_half_float` extensions.
+
+This module is only available if the `half` feature is enabled.
+
+*/
+#![cfg(feature = "half")]
+
+use half::f16;
+
+use crate::texture::{ClientFormat, PixelValue, ToClientFormat};
+use crate::uniforms::{AsUniformValue, UniformValue};
+use crate::vertex::{Attribute, AttributeType};
+
+unsafe impl Attribute for f16 {
+    const TYPE: AttributeType = AttributeType::F16;
+}
+
+unsafe impl Attribute for (f16, f16) {
+    const TYPE: AttributeType = AttributeType::F16F16;
+}
+
+unsafe impl Attribute for [f16; 2] {
+    const TYPE: AttributeType = AttributeType::F16F16;
+}
+
+unsafe impl Attribute for (f16, f16, f16) {
+    const TYPE: AttributeType = AttributeType::F16F16F16;
+}
+
+unsafe impl Attribute for [f16; 3] {
+    const TYPE: AttributeType = AttributeType::F16F16F16;
+}
+
+unsafe impl Attribute for (f16, f16, f16, f16) {
+    const TYPE: AttributeType = AttributeType::F16F16F16F16;
+}
+
+unsafe impl Attribute for [f16; 4] {
+    const TYPE: AttributeType = AttributeType::F16F16F16F16;
+}
+
+unsafe impl Attribute for [[f16; 2]; 2] {
+    const TYPE: AttributeType = AttributeType::F16x2x2;
+}
+
+unsafe impl Attribute for [[f16; 3]; 3] {
+    const TYPE: AttributeType = AttributeType::F16x3x3;
+}
+
+unsafe impl Attribute for [[f16; 4]; 4] {
+    const TYPE: AttributeType = AttributeType::F16x4x4;
+}
+
+unsafe impl PixelValue for f16 {
+    #[inline]
+    fn get_format() -> ClientFormat {
+        ClientFormat::F16
+    }
+}
+
+unsafe impl PixelValue for (f16, f16) {
+    #[inline]
+    fn get_format() -> ClientFormat {
+        ClientFormat::F16F16
+    }
+}
+
+unsafe impl PixelValue for (f16, f16, f16) {
+    #[inline]
+    fn get_format() -> ClientFormat {
+        ClientFormat::F16F16F16
+    }
+}
+
+unsafe impl PixelValue for (f16, f16, f16, f16) {
+    #[inline]
+    fn get_format() -> ClientFormat {
+        ClientFormat::F16F16F16F16
+    }
+}
+
+impl ToClientFormat for f16 {
+    fn rgb_format() -> ClientFormat { ClientFormat::F16F16F16 }
+    fn rgba_format() -> ClientFormat { ClientFormat::F16F16F16F16 }
+}
+
+impl AsUniformValue for f16 {
+    #[inline]
+    fn as_uniform_value(&self) -> UniformValue<'_> {
+        UniformValue::Float(self.to_f32())
+    }
+}
+
+impl AsUniformValue for [f16; 2] {
+    #[inline]
+    fn as_uniform_value(&self) -> UniformValue<'_> {
+        UniformValue::Vec2(self.map(f16::to_f32))
+    }
+}
+
+impl AsUniformValue for [f16; 3] {
+    #[inline]
+    fn as_uniform_value(&self) -> UniformValue<'_> {
+        UniformValue::Vec3(self.map(f16::to_f32))
+    }
+}
+
+impl AsUniformValue for [f16; 4] {
+    #[inline]
+    fn as_uniform_value(&self) -> UniformValue<'_> {
+        UniformValue::Vec4(self.map(f16::to_f32))
+    }
+}
+
+impl AsUniformValue for (f16, f16) {
+    #[inline]
+    fn as_uniform_value(&self) -> UniformValue<'_> {
+        UniformValue::Vec2([self.0.to_f32(), self.1.to_f32()])
+    }
+}
+
+impl AsUniformValue for (f16, f16, f16) {
+    #[inline]
+    fn as_uniform_value(&self) -> UniformValue<'_> {
+        UniformValue::Vec3([self.0.to_f32(), self.1.to_f32(), self.2.to_f32()])
+    }
+}
+
+impl AsUniformValue for (f16, f16, f16, f16) {
+    #[inline]
+    fn as_uniform_value(&self) -> UniformValue<'_> {
+        UniformValue::Vec4([self.0.to_f32(), self.1.to_f32(), self.2.to_f32(), self.3.to_f32()])
+    }
+}
@@ -1042,6 +1042,50 @@ pub enum CompressedFormat {
     S3tcDxt3Alpha,
     /// S3TC DXT5, see <https://www.opengl.org/wiki/S3_Texture_Compression>.
     S3tcDxt5Alpha,
+
+    /// ETC2 format with three components (no alpha).
+    Etc2Rgb8,
+    /// ETC2 format with three components and 1-bit alpha.
+    Etc2Rgb8A1,
+    /// ETC2 format with four components, the alpha being stored with EAC.
+    Etc2Rgba8,
+    /// EAC format with one unsigned component.
+    EacR11U,
+    /// EAC format with one signed component.
+    EacR11I,
+    /// EAC format with two unsigned components.
+    EacRg11U,
+    /// EAC format with two signed components.
+    EacRg11I,
+
+    /// ASTC LDR format with blocks of 4 by 4 texels.
+    Astc4x4,
+    /// ASTC LDR format with blocks of 5 by 4 texels.
+    Astc5x4,
+    /// ASTC LDR format with blocks of 5 by 5 texels.
+    Astc5x5,
+    /// ASTC LDR format with blocks of 6 by 5 texels.
+    Astc6x5,
+    /// ASTC LDR format with blocks of 6 by 6 texels.
+    Astc6x6,
+    /// ASTC LDR format with blocks of 8 by 5 texels.
+    Astc8x5,
+    /// ASTC LDR format with blocks of 8 by 6 texels.
+    Astc8x6,
+    /// ASTC LDR format with blocks of 8 by 8 texels.
+    Astc8x8,
+    /// ASTC LDR format with blocks of 10 by 5 texels.
+    Astc10x5,
+    /// ASTC LDR format with blocks of 10 by 6 texels.
+    Astc10x6,
+    /// ASTC LDR format with blocks of 10 by 8 texels.
+    Astc10x8,
+    /// ASTC LDR format with blocks of 10 by 10 texels.
+    Astc10x10,
+    /// ASTC LDR format with blocks of 12 by 10 texels.
+    Astc12x10,
+    /// ASTC LDR format with blocks of 12 by 12 texels.
+    Astc12x12,
 }
 
 impl CompressedFormat {
@@ -1060,6 +1104,27 @@ impl CompressedFormat {
             CompressedFormat::S3tcDxt1Alpha,
             CompressedFormat::S3tcDxt3Alpha,
             CompressedFormat::S3tcDxt5Alpha,
+            CompressedFormat::Etc2Rgb8,
+            CompressedFormat::Etc2Rgb8A1,
+            CompressedFormat::Etc2Rgba8,
+            CompressedFormat::EacR11U,
+            CompressedFormat::EacR11I,
+            CompressedFormat::EacRg11U,
+            CompressedFormat::EacRg11I,
+            CompressedFormat::Astc4x4,
+            CompressedFormat::Astc5x4,
+            CompressedFormat::Astc5x5,
+            CompressedFormat::Astc6x5,
+            CompressedFormat::Astc6x6,
+            CompressedFormat::Astc8x5,
+            CompressedFormat::Astc8x6,
+            CompressedFormat::Astc8x8,
+            CompressedFormat::Astc10x5,
+            CompressedFormat::Astc10x6,
+            CompressedFormat::Astc10x8,
+            CompressedFormat::Astc10x10,
+            CompressedFormat::Astc12x10,
+            CompressedFormat::Astc12x12,
         ]
     }
 
@@ -1108,6 +1173,44 @@ impl CompressedFormat {
             CompressedFormat::S3tcDxt5Alpha => {
                 extensions.gl_ext_texture_compression_s3tc
             },
+            CompressedFormat::Etc2Rgb8 => {
+                is_etc2_supported(context) || extensions.gl_oes_compressed_etc2_rgb8_texture
+            },
+            CompressedFormat::Etc2Rgb8A1 => {
+                is_etc2_supported(context) ||
+                    extensions.gl_oes_compressed_etc2_punchthrougha_rgba8_texture
+            },
+            CompressedFormat::Etc2Rgba8 => {
+                is_etc2_supported(context) || extensions.gl_oes_compressed_etc2_rgba8_texture
+            },
+            CompressedFormat::EacR11U => {
+                is_etc2_supported(context) || extensions.gl_oes_compressed_eac_r11_unsigned_texture
+            },
+            CompressedFormat::EacR11I => {
+                is_etc2_supported(context) || extensions.gl_oes_compressed_eac_r11_signed_texture
+            },
+            CompressedFormat::EacRg11U => {
+                is_etc2_supported(context) || extensions.gl_oes_compressed_eac_rg11_unsigned_texture
+            },
+            CompressedFormat::EacRg11I => {
+                is_etc2_supported(context) || extensions.gl_oes_compressed_eac_rg11_signed_texture
+            },
+            CompressedFormat::Astc4x4 |
+            CompressedFormat::Astc5x4 |
+            CompressedFormat::Astc5x5 |
+            CompressedFormat::Astc6x5 |
+            CompressedFormat::Astc6x6 |
+            CompressedFormat::Astc8x5 |
+            CompressedFormat::Astc8x6 |
+            CompressedFormat::Astc8x8 |
+            CompressedFormat::Astc10x5 |
+            CompressedFormat::Astc10x6 |
+            CompressedFormat::Astc10x8 |
+            CompressedFormat::Astc10x10 |
+            CompressedFormat::Astc12x10 |
+            CompressedFormat::Astc12x12 => {
+                is_astc_ldr_supported(context)
+            },
         }
     }
 
@@ -1124,6 +1227,58 @@ impl CompressedFormat {
             CompressedFormat::S3tcDxt1Alpha => gl::COMPRESSED_RGBA_S3TC_DXT1_EXT,
             CompressedFormat::S3tcDxt3Alpha => gl::COMPRESSED_RGBA_S3TC_DXT3_EXT,
             CompressedFormat::S3tcDxt5Alpha => gl::COMPRESSED_RGBA_S3TC_DXT5_EXT,
+            CompressedFormat::Etc2Rgb8 => gl::COMPRESSED_RGB8_ETC2,
+            CompressedFormat::Etc2Rgb8A1 => gl::COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2,
+            CompressedFormat::Etc2Rgba8 => gl::COMPRESSED_RGBA8_ETC2_EAC,
+            CompressedFormat::EacR11U => gl::COMPRESSED_R11_EAC,
+            CompressedFormat::EacR11I => gl::COMPRESSED_SIGNED_R11_EAC,
+            CompressedFormat::EacRg11U => gl::COMPRESSED_RG11_EAC,
+            CompressedFormat::EacRg11I => gl::COMPRESSED_SIGNED_RG11_EAC,
+            CompressedFormat::Astc4x4 => gl::COMPRESSED_RGBA_ASTC_4x4,
+            CompressedFormat::Astc5x4 => gl::COMPRESSED_RGBA_ASTC_5x4,
+            CompressedFormat::Astc5x5 => gl::COMPRESSED_RGBA_ASTC_5x5,
+            CompressedFormat::Astc6x5 => gl::COMPRESSED_RGBA_ASTC_6x5,
+            CompressedFormat::Astc6x6 => gl::COMPRESSED_RGBA_ASTC_6x6,
+            CompressedFormat::Astc8x5 => gl::COMPRESSED_RGBA_ASTC_8x5,
+            CompressedFormat::Astc8x6 => gl::COMPRESSED_RGBA_ASTC_8x6,
+            CompressedFormat::Astc8x8 => gl::COMPRESSED_RGBA_ASTC_8x8,
+            CompressedFormat::Astc10x5 => gl::COMPRESSED_RGBA_ASTC_10x5,
+            CompressedFormat::Astc10x6 => gl::COMPRESSED_RGBA_ASTC_10x6,
+            CompressedFormat::Astc10x8 => gl::COMPRESSED_RGBA_ASTC_10x8,
+            CompressedFormat::Astc10x10 => gl::COMPRESSED_RGBA_ASTC_10x10,
+            CompressedFormat::Astc12x10 => gl::COMPRESSED_RGBA_ASTC_12x10,
+            CompressedFormat::Astc12x12 => gl::COMPRESSED_RGBA_ASTC_12x12,
+        }
+    }
+
+    /// Returns the width and height in texels of a block, and its size in bytes.
+    fn get_block_layout(&self) -> (u32, u32, usize) {
+        match *self {
+            CompressedFormat::S3tcDxt1NoAlpha | CompressedFormat::S3tcDxt1Alpha |
+            CompressedFormat::RgtcFormatU | CompressedFormat::RgtcFormatI |
+            CompressedFormat::Etc2Rgb8 | CompressedFormat::Etc2Rgb8A1 |
+            CompressedFormat::EacR11U | CompressedFormat::EacR11I => (4, 4, 8),
+
+            CompressedFormat::S3tcDxt3Alpha | CompressedFormat::S3tcDxt5Alpha |
+            CompressedFormat::BptcUnorm4 | CompressedFormat::BptcSignedFloat3 |
+            CompressedFormat::BptcUnsignedFloat3 | CompressedFormat::RgtcFormatUU |
+            CompressedFormat::RgtcFormatII | CompressedFormat::Etc2Rgba8 |
+            CompressedFormat::EacRg11U | CompressedFormat::EacRg11I => (4, 4, 16),
+
+            CompressedFormat::Astc4x4 => (4, 4, 16),
+            CompressedFormat::Astc5x4 => (5, 4, 16),
+            CompressedFormat::Astc5x5 => (5, 5, 16),
+            CompressedFormat::Astc6x5 => (6, 5, 16),
+            CompressedFormat::Astc6x6 => (6, 6, 16),
+            CompressedFormat::Astc8x5 => (8, 5, 16),
+            CompressedFormat::Astc8x6 => (8, 6, 16),
+            CompressedFormat::Astc8x8 => (8, 8, 16),
+            CompressedFormat::Astc10x5 => (10, 5, 16),
+            CompressedFormat::Astc10x6 => (10, 6, 16),
+            CompressedFormat::Astc10x8 => (10, 8, 16),
+            CompressedFormat::Astc10x10 => (10, 10, 16),
+            CompressedFormat::Astc12x10 => (12, 10, 16),
+            CompressedFormat::Astc12x12 => (12, 12, 16),
         }
     }
 }
@@ -1138,6 +1293,26 @@ pub enum CompressedSrgbFormat {
     S3tcDxt1Alpha,
     S3tcDxt3Alpha,
     S3tcDxt5Alpha,
+    /// ETC2 format. sRGB without alpha.
+    Etc2Rgb8,
+    /// ETC2 format. sRGB with 1-bit alpha.
+    Etc2Rgb8A1,
+    /// ETC2 format. sRGB with alpha.
+    Etc2Rgba8,
+    Astc4x4,
+    Astc5x4,
+    Astc5x5,
+    Astc6x5,
+    Astc6x6,
+    Astc8x5,
+    Astc8x6,
+    Astc8x8,
+    Astc10x5,
+    Astc10x6,
+    Astc10x8,
+    Astc10x10,
+    Astc12x10,
+    Astc12x12,
 }
 
 impl CompressedSrgbFormat {
@@ -1150,6 +1325,23 @@ impl CompressedSrgbFormat {
             CompressedSrgbFormat::S3tcDxt1Alpha,
             CompressedSrgbFormat::S3tcDxt3Alpha,
             CompressedSrgbFormat::S3tcDxt5Alpha,
+            CompressedSrgbFormat::Etc2Rgb8,
+            CompressedSrgbFormat::Etc2Rgb8A1,
+            CompressedSrgbFormat::Etc2Rgba8,
+            CompressedSrgbFormat::Astc4x4,
+            CompressedSrgbFormat::Astc5x4,
+            CompressedSrgbFormat::Astc5x5,
+            CompressedSrgbFormat::Astc6x5,
+            CompressedSrgbFormat::Astc6x6,
+            CompressedSrgbFormat::Astc8x5,
+            CompressedSrgbFormat::Astc8x6,
+            CompressedSrgbFormat::Astc8x8,
+            CompressedSrgbFormat::Astc10x5,
+            CompressedSrgbFormat::Astc10x6,
+            CompressedSrgbFormat::Astc10x8,
+            CompressedSrgbFormat::Astc10x10,
+            CompressedSrgbFormat::Astc12x10,
+            CompressedSrgbFormat::Astc12x12,
         ]
     }
 
@@ -1180,6 +1372,32 @@ impl CompressedSrgbFormat {
             CompressedSrgbFormat::S3tcDxt5Alpha => {
                 extensions.gl_ext_texture_compression_s3tc && extensions.gl_ext_texture_srgb
             },
+            CompressedSrgbFormat::Etc2Rgb8 => {
+                is_etc2_supported(context) || extensions.gl_oes_compressed_etc2_srgb8_texture
+            },
+            CompressedSrgbFormat::Etc2Rgb8A1 => {
+                is_etc2_supported(context) ||
+                    extensions.gl_oes_compressed_etc2_punchthrougha_srgb8_alpha8_texture
+            },
+            CompressedSrgbFormat::Etc2Rgba8 => {
+                is_etc2_supported(context) || extensions.gl_oes_compressed_etc2_srgb8_alpha8_texture
+            },
+            CompressedSrgbFormat::Astc4x4 |
+            CompressedSrgbFormat::Astc5x4 |
+            CompressedSrgbFormat::Astc5x5 |
+            CompressedSrgbFormat::Astc6x5 |
+            CompressedSrgbFormat::Astc6x6 |
+            CompressedSrgbFormat::Astc8x5 |
+            CompressedSrgbFormat::Astc8x6 |
+            CompressedSrgbFormat::Astc8x8 |
+            CompressedSrgbFormat::Astc10x5 |
+            CompressedSrgbFormat::Astc10x6 |
+            CompressedSrgbFormat::Astc10x8 |
+            CompressedSrgbFormat::Astc10x10 |
+            CompressedSrgbFormat::Astc12x10 |
+            CompressedSrgbFormat::Astc12x12 => {
+                is_astc_ldr_supported(context)
+            },
         }
     }
 
@@ -1190,10 +1408,71 @@ impl CompressedSrgbFormat {
             CompressedSrgbFormat::S3tcDxt1Alpha => gl::COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT,
             CompressedSrgbFormat::S3tcDxt3Alpha => gl::COMPRESSED_SRGB_ALPHA_S3TC_DXT3_EXT,
             CompressedSrgbFormat::S3tcDxt5Alpha => gl::COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT,
+            CompressedSrgbFormat::Etc2Rgb8 => gl::COMPRESSED_SRGB8_ETC2,
+            CompressedSrgbFormat::Etc2Rgb8A1 => gl::COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2,
+            CompressedSrgbFormat::Etc2Rgba8 => gl::COMPRESSED_SRGB8_ALPHA8_ETC2_EAC,
+            CompressedSrgbFormat::Astc4x4 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_4x4,
+            CompressedSrgbFormat::Astc5x4 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_5x4,
+            CompressedSrgbFormat::Astc5x5 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_5x5,
+            CompressedSrgbFormat::Astc6x5 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_6x5,
+            CompressedSrgbFormat::Astc6x6 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_6x6,
+            CompressedSrgbFormat::Astc8x5 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_8x5,
+            CompressedSrgbFormat::Astc8x6 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_8x6,
+            CompressedSrgbFormat::Astc8x8 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_8x8,
+            CompressedSrgbFormat::Astc10x5 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x5,
+            CompressedSrgbFormat::Astc10x6 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x6,
+            CompressedSrgbFormat::Astc10x8 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x8,
+            CompressedSrgbFormat::Astc10x10 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x10,
+            CompressedSrgbFormat::Astc12x10 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_12x10,
+            CompressedSrgbFormat::Astc12x12 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_12x12,
+        }
+    }
+
+    /// Returns the width and height in texels of a block, and its size in bytes.
+    fn get_block_layout(&self) -> (u32, u32, usize) {
+        match *self {
+            CompressedSrgbFormat::S3tcDxt1NoAlpha | CompressedSrgbFormat::S3tcDxt1Alpha |
+            CompressedSrgbFormat::Etc2Rgb8 | CompressedSrgbFormat::Etc2Rgb8A1 => (4, 4, 8),
+
+            CompressedSrgbFormat::Bptc | CompressedSrgbFormat::S3tcDxt3Alpha |
+            CompressedSrgbFormat::S3tcDxt5Alpha | CompressedSrgbFormat::Etc2Rgba8 => (4, 4, 16),
+
+            CompressedSrgbFormat::Astc4x4 => (4, 4, 16),
+            CompressedSrgbFormat::Astc5x4 => (5, 4, 16),
+            CompressedSrgbFormat::Astc5x5 => (5, 5, 16),
+            CompressedSrgbFormat::Astc6x5 => (6, 5, 16),
+            CompressedSrgbFormat::Astc6x6 => (6, 6, 16),
+            CompressedSrgbFormat::Astc8x5 => (8, 5, 16),
+            CompressedSrgbFormat::Astc8x6 => (8, 6, 16),
+            CompressedSrgbFormat::Astc8x8 => (8, 8, 16),
+            CompressedSrgbFormat::Astc10x5 => (10, 5, 16),
+            CompressedSrgbFormat::Astc10x6 => (10, 6, 16),
+            CompressedSrgbFormat::Astc10x8 => (10, 8, 16),
+            CompressedSrgbFormat::Astc10x10 => (10, 10, 16),
+            CompressedSrgbFormat::Astc12x10 => (12, 10, 16),
+            CompressedSrgbFormat::Astc12x12 => (12, 12, 16),
         }
     }
 }
 
+/// Returns true if the ETC2 and EAC formats are all supported by the backend.
+fn is_etc2_supported<C: ?Sized>(context: &C) -> bool where C: CapabilitiesSource {
+    let version = context.get_version();
+    let extensions = context.get_extensions();
+
+    version >= &Version(Api::Gl, 4, 3) || version >= &Version(Api::GlEs, 3, 0) ||
+        extensions.gl_arb_es3_compatibility
+}
+
+/// Returns true if the ASTC LDR formats are supported by the backend.
+fn is_astc_ldr_supported<C: ?Sized>(context: &C) -> bool where C: CapabilitiesSource {
+    let version = context.get_version();
+    let extensions = context.get_extensions();
+
+    version >= &Version(Api::GlEs, 3, 2) || extensions.gl_khr_texture_compression_astc_ldr ||
+        extensions.gl_oes_texture_compression_astc
+}
+
 /// List of formats available for depth textures.
 ///
 /// `I16`, `I24` and `I32` are still treated as if they were floating points.
@@ -1529,58 +1808,37 @@ impl ClientFormatAny {
                                 depth.unwrap_or(1) as usize * array_size.unwrap_or(1) as usize
             },
 
-            // 8 bytes per 4x4 block
-            ClientFormatAny::CompressedFormat(CompressedFormat::S3tcDxt1Alpha) |
-            ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt1Alpha) |
-            ClientFormatAny::CompressedFormat(CompressedFormat::S3tcDxt1NoAlpha) |
-            ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt1NoAlpha) |
-            ClientFormatAny::CompressedFormat(CompressedFormat::RgtcFormatU) |
-            ClientFormatAny::CompressedFormat(CompressedFormat::RgtcFormatI) => {
-
-                let width = if width < 4 { 4 } else { width as usize };
-                let height = height.map(|height| if height < 4 { 4 } else { height as usize })
-                                   .expect("ST3C, RGTC and BPTC textures must have 2 dimensions");
-                if (width % 4) != 0 || (height % 4) != 0 {
-                    panic!("ST3C, RGTC and BPTC textures must have a width and height multiple of 4.");
-                }
-                if depth.is_some() { // allow `array_size` (2D textures arrays) but not depth (3D textures)
-                    panic!("ST3C, RGTC and BPTC textures are 2 dimension only.")
-                }
+            ClientFormatAny::CompressedFormat(_) | ClientFormatAny::CompressedSrgbFormat(_) => {
+                let (block_width, block_height, block_size) = match *self {
+                    ClientFormatAny::CompressedFormat(ref format) => format.get_block_layout(),
+                    ClientFormatAny::CompressedSrgbFormat(ref format) => format.get_block_layout(),
+                    ClientFormatAny::ClientFormat(_) => unreachable!(),
+                };
 
-                let uncompressed_bit_size =  4 * width as usize * height as usize *
-                                            depth.unwrap_or(1) as usize * array_size.unwrap_or(1) as usize;
-                uncompressed_bit_size / 8   // Apply 8:1 compression ratio
-            },
-
-            // 16 bytes per 4x4 block
-            ClientFormatAny::CompressedFormat(CompressedFormat::S3tcDxt3Alpha) |
-            ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt3Alpha) |
-            ClientFormatAny::CompressedFormat(CompressedFormat::S3tcDxt5Alpha) |
-            ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt5Alpha) |
-            ClientFormatAny::CompressedFormat(CompressedFormat::BptcUnorm4) |
-            ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Bptc) |
-            ClientFormatAny::CompressedFormat(CompressedFormat::BptcSignedFloat3) |
-            ClientFormatAny::CompressedFormat(CompressedFormat::BptcUnsignedFloat3) |
-            ClientFormatAny::CompressedFormat(CompressedFormat::RgtcFormatUU) |
-            ClientFormatAny::CompressedFormat(CompressedFormat::RgtcFormatII) => {
-
-                let width = if width < 4 { 4 } else { width as usize };
-                let height = height.map(|height| if height < 4 { 4 } else { height as usize })
-                                   .expect("ST3C, RGTC and BPTC textures must have 2 dimensions");
-                if (width % 4) != 0 || (height % 4) != 0 {
-                    panic!("ST3C, RGTC and BPTC textures must have a width and height multiple of 4.");
-                }
+                let height = height.expect("Compressed textures must have 2 dimensions");
                 if depth.is_some() { // allow `array_size` (2D textures arrays) but not depth (3D textures)
-                    panic!("ST3C, RGTC and BPTC textures are 2 dimension only.")
+                    panic!("Compressed textures are 2 dimension only.")
                 }
 
-                let uncompressed_bit_size =  4 * width as usize * height as usize *
-                                            depth.unwrap_or(1) as usize * array_size.unwrap_or(1) as usize;
-                uncompressed_bit_size / 4   // Apply 4:1 compression ratio
+                // partial blocks at the border of the image are stored as whole blocks
+                let blocks = width.div_ceil(block_width) as usize *
+                             height.div_ceil(block_height) as usize;
+                blocks * block_size * array_size.unwrap_or(1) as usize
             },
         }
     }
 
+    /// Returns the width and height in texels of a block of a compressed format, and its size in
+    /// bytes. Returns `None` for uncompressed formats.
+    #[inline]
+    pub(crate) fn get_block_layout(&self) -> Option<(u32, u32, usize)> {
+        match *self {
+            ClientFormatAny::ClientFormat(_) => None,
+            ClientFormatAny::CompressedFormat(ref format) => Some(format.get_block_layout()),
+            ClientFormatAny::CompressedSrgbFormat(ref format) => Some(format.get_block_layout()),
+        }
+    }
+
     #[inline]
     pub fn get_num_components(&self) -> u8 {
         match *self {
@@ -1608,6 +1866,44 @@ impl ClientFormatAny {
             gl::COMPRESSED_SIGNED_RED_RGTC1 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::RgtcFormatI)),
             gl::COMPRESSED_RG_RGTC2 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::RgtcFormatUU)),
             gl::COMPRESSED_SIGNED_RG_RGTC2 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::RgtcFormatII)),
+            gl::COMPRESSED_RGB8_ETC2 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Etc2Rgb8)),
+            gl::COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Etc2Rgb8A1)),
+            gl::COMPRESSED_RGBA8_ETC2_EAC => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Etc2Rgba8)),
+            gl::COMPRESSED_R11_EAC => Some(ClientFormatAny::CompressedFormat(CompressedFormat::EacR11U)),
+            gl::COMPRESSED_SIGNED_R11_EAC => Some(ClientFormatAny::CompressedFormat(CompressedFormat::EacR11I)),
+            gl::COMPRESSED_RG11_EAC => Some(ClientFormatAny::CompressedFormat(CompressedFormat::EacRg11U)),
+            gl::COMPRESSED_SIGNED_RG11_EAC => Some(ClientFormatAny::CompressedFormat(CompressedFormat::EacRg11I)),
+            gl::COMPRESSED_SRGB8_ETC2 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Etc2Rgb8)),
+            gl::COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Etc2Rgb8A1)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ETC2_EAC => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Etc2Rgba8)),
+            gl::COMPRESSED_RGBA_ASTC_4x4 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc4x4)),
+            gl::COMPRESSED_RGBA_ASTC_5x4 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc5x4)),
+            gl::COMPRESSED_RGBA_ASTC_5x5 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc5x5)),
+            gl::COMPRESSED_RGBA_ASTC_6x5 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc6x5)),
+            gl::COMPRESSED_RGBA_ASTC_6x6 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc6x6)),
+            gl::COMPRESSED_RGBA_ASTC_8x5 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc8x5)),
+            gl::COMPRESSED_RGBA_ASTC_8x6 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc8x6)),
+            gl::COMPRESSED_RGBA_ASTC_8x8 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc8x8)),
+            gl::COMPRESSED_RGBA_ASTC_10x5 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc10x5)),
+            gl::COMPRESSED_RGBA_ASTC_10x6 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc10x6)),
+            gl::COMPRESSED_RGBA_ASTC_10x8 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc10x8)),
+            gl::COMPRESSED_RGBA_ASTC_10x10 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc10x10)),
+            gl::COMPRESSED_RGBA_ASTC_12x10 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc12x10)),
+            gl::COMPRESSED_RGBA_ASTC_12x12 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc12x12)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_4x4 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc4x4)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_5x4 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc5x4)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_5x5 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc5x5)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_6x5 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc6x5)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_6x6 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc6x6)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_8x5 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc8x5)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_8x6 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc8x6)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_8x8 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc8x8)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x5 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc10x5)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x6 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc10x6)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x8 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc10x8)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x10 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc10x10)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_12x10 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc12x10)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_12x12 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc12x12)),
             _ => None,
         }
     }
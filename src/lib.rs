@@ -68,8 +68,10 @@ result to the user.
  - **Instancing**: Instancing is done either by passing a `vertex::EmptyInstanceAttributes` marker
    or one or several references to vertex buffers wrapped inside a `PerInstance` struct. See the
    `vertex` module for more infos.
- - **Memory barriers**: Calling `glMemoryBarrier` is automatically handled by glium, however you
-   still need to call `memoryBarrier()` in your GLSL code in some situations.
+ - **Memory barriers**: Calling `glMemoryBarrier` is automatically handled by glium for the
+   buffers and textures that are written by a draw command or a compute shader, however you
+   still need to call `memoryBarrier()` in your GLSL code in some situations. For the other
+   situations, `Context::memory_barrier` lets you insert a barrier manually.
  - **Programs**: See the `program` module.
  - **Query objects**: The corresponding structs are in the `draw_parameters` module. They are
    passed as draw parameters.
@@ -120,10 +122,12 @@ pub use crate::index::IndexBuffer;
 pub use crate::vertex::{VertexBuffer, Vertex, VertexFormat};
 pub use crate::program::{Program, ProgramCreationError};
 pub use crate::program::ProgramCreationError::{CompilationError, LinkingError, ShaderTypeNotSupported};
-pub use crate::sync::{LinearSyncFence, SyncFence};
+pub use crate::sync::{FenceWaitResult, LinearSyncFence, SyncFence, SyncFenceFuture};
 pub use crate::texture::Texture2d;
 pub use crate::version::{Api, Version, get_supported_glsl_version};
 pub use crate::ops::ReadError;
+#[cfg(feature = "derive")]
+pub use glium_derive::{Std140Layout, Std430Layout, UniformBlock, Uniforms, Vertex};
 
 use std::rc::Rc;
 use std::thread;
@@ -157,6 +161,7 @@ pub mod field;
 
 mod context;
 mod fbo;
+mod half_float;
 mod image_format;
 mod ops;
 mod sampler_object;
@@ -324,7 +329,7 @@ trait ProgramExt {
     /// Changes the subroutine uniform bindings of a program.
     fn set_subroutine_uniforms_for_stage(&self, ctxt: &mut context::CommandContext<'_>,
                                          stage: program::ShaderStage,
-                                         indices: &[gl::types::GLuint]);
+                                         indices: &[gl::types::GLuint]) -> Result<(), DrawError>;
 
     fn get_uniform(&self, name: &str) -> Option<&program::Uniform>;
 
@@ -385,6 +390,8 @@ enum TextureAccess {
     ImageUnit { will_write: bool },
     /// Texture being used as a framebuffer object
     Framebuffer,
+    /// Texture being updated or read by the client, for example with `glTexSubImage`
+    TextureUpdate,
 }
 
 /// Internal trait for textures.
@@ -571,6 +578,121 @@ impl ToGlEnum for BlitMask {
     }
 }
 
+/// Kinds of accesses that must see the data previously written by shaders through image
+/// stores, shader storage blocks or atomic counters. Passed to `Context::memory_barrier`.
+///
+/// Each field corresponds to one of the bits of `glMemoryBarrier`. Use `Default::default()`
+/// for an empty barrier and enable the fields you need.
+///
+/// ```no_run
+/// # use glutin::surface::{ResizeableSurface, SurfaceTypeTrait};
+/// # fn example<T>(display: glium::Display<T>) where T: SurfaceTypeTrait + ResizeableSurface {
+/// display.memory_barrier(glium::MemoryBarrier {
+///     texture_fetch: true,
+///     shader_storage: true,
+///     .. Default::default()
+/// });
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct MemoryBarrier {
+    /// Reading vertex attributes from a vertex buffer. `GL_VERTEX_ATTRIB_ARRAY_BARRIER_BIT`.
+    pub vertex_attrib_array: bool,
+
+    /// Reading indices from an index buffer. `GL_ELEMENT_ARRAY_BARRIER_BIT`.
+    pub element_array: bool,
+
+    /// Reading a uniform buffer. `GL_UNIFORM_BARRIER_BIT`.
+    pub uniform: bool,
+
+    /// Sampling a texture. `GL_TEXTURE_FETCH_BARRIER_BIT`.
+    pub texture_fetch: bool,
+
+    /// Image loads, stores and atomics. `GL_SHADER_IMAGE_ACCESS_BARRIER_BIT`.
+    pub shader_image_access: bool,
+
+    /// Reading draw or dispatch parameters from an indirect buffer. `GL_COMMAND_BARRIER_BIT`.
+    pub command: bool,
+
+    /// Transferring pixels from or to a pixel buffer. `GL_PIXEL_BUFFER_BARRIER_BIT`.
+    pub pixel_buffer: bool,
+
+    /// Uploading data to or reading data from a texture. `GL_TEXTURE_UPDATE_BARRIER_BIT`.
+    pub texture_update: bool,
+
+    /// Writing, copying, reading or mapping a buffer. `GL_BUFFER_UPDATE_BARRIER_BIT`.
+    pub buffer_update: bool,
+
+    /// Drawing to or reading from a framebuffer. `GL_FRAMEBUFFER_BARRIER_BIT`.
+    pub framebuffer: bool,
+
+    /// Writing to a buffer through transform feedback. `GL_TRANSFORM_FEEDBACK_BARRIER_BIT`.
+    pub transform_feedback: bool,
+
+    /// Accessing an atomic counter buffer. `GL_ATOMIC_COUNTER_BARRIER_BIT`.
+    pub atomic_counter: bool,
+
+    /// Accessing a shader storage buffer. `GL_SHADER_STORAGE_BARRIER_BIT`.
+    pub shader_storage: bool,
+
+    /// Writing the result of a query to a buffer. `GL_QUERY_BUFFER_BARRIER_BIT`.
+    pub query_buffer: bool,
+
+    /// Accessing a buffer through a persistent mapping. `GL_CLIENT_MAPPED_BUFFER_BARRIER_BIT`.
+    pub client_mapped_buffer: bool,
+}
+
+impl MemoryBarrier {
+    /// Constructs a barrier that covers all kinds of accesses.
+    pub fn all() -> Self {
+        MemoryBarrier {
+            vertex_attrib_array: true,
+            element_array: true,
+            uniform: true,
+            texture_fetch: true,
+            shader_image_access: true,
+            command: true,
+            pixel_buffer: true,
+            texture_update: true,
+            buffer_update: true,
+            framebuffer: true,
+            transform_feedback: true,
+            atomic_counter: true,
+            shader_storage: true,
+            query_buffer: true,
+            client_mapped_buffer: true,
+        }
+    }
+
+    /// Returns true if no kind of access is enabled.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        *self == MemoryBarrier::default()
+    }
+}
+
+impl ToGlEnum for MemoryBarrier {
+    fn to_glenum(&self) -> gl::types::GLenum {
+        let mut mask = 0;
+        if self.vertex_attrib_array { mask |= gl::VERTEX_ATTRIB_ARRAY_BARRIER_BIT; }
+        if self.element_array { mask |= gl::ELEMENT_ARRAY_BARRIER_BIT; }
+        if self.uniform { mask |= gl::UNIFORM_BARRIER_BIT; }
+        if self.texture_fetch { mask |= gl::TEXTURE_FETCH_BARRIER_BIT; }
+        if self.shader_image_access { mask |= gl::SHADER_IMAGE_ACCESS_BARRIER_BIT; }
+        if self.command { mask |= gl::COMMAND_BARRIER_BIT; }
+        if self.pixel_buffer { mask |= gl::PIXEL_BUFFER_BARRIER_BIT; }
+        if self.texture_update { mask |= gl::TEXTURE_UPDATE_BARRIER_BIT; }
+        if self.buffer_update { mask |= gl::BUFFER_UPDATE_BARRIER_BIT; }
+        if self.framebuffer { mask |= gl::FRAMEBUFFER_BARRIER_BIT; }
+        if self.transform_feedback { mask |= gl::TRANSFORM_FEEDBACK_BARRIER_BIT; }
+        if self.atomic_counter { mask |= gl::ATOMIC_COUNTER_BARRIER_BIT; }
+        if self.shader_storage { mask |= gl::SHADER_STORAGE_BARRIER_BIT; }
+        if self.query_buffer { mask |= gl::QUERY_BUFFER_BARRIER_BIT; }
+        if self.client_mapped_buffer { mask |= gl::CLIENT_MAPPED_BUFFER_BARRIER_BIT; }
+        mask
+    }
+}
+
 /// Object that can be drawn upon.
 ///
 /// # What does the GPU do when you draw?
@@ -874,6 +996,22 @@ pub trait Surface {
         V: vertex::MultiVerticesSource<'b>, I: Into<index::IndicesSource<'a>>,
         U: uniforms::Uniforms;
 
+    /// Draws with a pipeline of separable programs instead of a program.
+    ///
+    /// Works the same way as `draw`. The uniforms are dispatched to the stages that use them.
+    ///
+    /// All the surfaces of glium implement this method. The default implementation, which is
+    /// there so that the surfaces implemented outside of glium keep compiling, returns
+    /// `DrawError::PipelinesNotSupported`.
+    #[inline]
+    fn draw_pipeline<'a, 'b, V, I, U>(&mut self, _: V, _: I, _: &program::ProgramPipeline,
+        _: &U, _: &DrawParameters<'_>) -> Result<(), DrawError> where
+        V: vertex::MultiVerticesSource<'b>, I: Into<index::IndicesSource<'a>>,
+        U: uniforms::Uniforms
+    {
+        Err(DrawError::PipelinesNotSupported)
+    }
+
     /// Blits from the default framebuffer.
     #[inline]
     fn blit_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget,
@@ -1037,6 +1175,16 @@ pub enum DrawError {
         name: String
     },
 
+    /// Tried to set subroutine uniforms on a program pipeline. Pipelines don't support them.
+    SubroutinesNotSupported,
+
+    /// Tried to draw with a program built with `Program::new_separable` outside of a program
+    /// pipeline.
+    SeparableProgram,
+
+    /// The surface doesn't support drawing with program pipelines.
+    PipelinesNotSupported,
+
     /// The number of vertices per patch that has been requested is not supported.
     UnsupportedVerticesPerPatch,
 
@@ -1128,6 +1276,12 @@ impl fmt::Display for DrawError {
                 "Not all subroutine uniforms of a shader stage were set",
             SubroutineNotFound { .. } =>
                 "A non-existent subroutine was referenced",
+            SubroutinesNotSupported =>
+                "Program pipelines don't support subroutine uniforms",
+            SeparableProgram =>
+                "Separable programs can only be used in a program pipeline",
+            PipelinesNotSupported =>
+                "The surface doesn't support drawing with program pipelines",
             UnsupportedVerticesPerPatch =>
                 "The number of vertices per patch that has been requested is not supported",
             TessellationNotSupported =>
@@ -1279,30 +1433,28 @@ impl Frame {
         self.destroyed = true;
         self.context.swap_buffers()
     }
-}
 
-impl Surface for Frame {
+    /// Invalidates the content of the buffers of `mask`, which becomes undefined.
+    ///
+    /// Call this at the end of a pass when the content of some buffers is no longer needed,
+    /// for example the depth buffer. This lets tile-based GPUs skip writing them back to memory.
+    /// Nothing happens if invalidation is not supported by the backend.
     #[inline]
-    fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>, color_srgb: bool,
-             depth: Option<f32>, stencil: Option<i32>)
-    {
-        ops::clear(&self.context, None, rect, color, color_srgb, depth, stencil);
-    }
-
-    fn get_dimensions(&self) -> (u32, u32) {
-        self.dimensions
+    pub fn invalidate_attachments(&mut self, mask: BlitMask) {
+        ops::invalidate(&self.context, None, mask, None);
     }
 
-    fn get_depth_buffer_bits(&self) -> Option<u16> {
-        self.context.capabilities().depth_bits
-    }
-
-    fn get_stencil_buffer_bits(&self) -> Option<u16> {
-        self.context.capabilities().stencil_bits
+    /// Invalidates the content of a region of the buffers of `mask`, which becomes undefined.
+    ///
+    /// See `invalidate_attachments`.
+    #[inline]
+    pub fn invalidate_attachments_region(&mut self, mask: BlitMask, rect: &Rect) {
+        ops::invalidate(&self.context, None, mask, Some(rect));
     }
 
-    fn draw<'a, 'b, V, I, U>(&mut self, vertex_buffer: V,
-                         index_buffer: I, program: &Program, uniforms: &U,
+    /// Draws with a program or a program pipeline.
+    fn draw_program<'a, 'b, V, I, U>(&mut self, vertex_buffer: V,
+                         index_buffer: I, program: program::DrawProgram<'_>, uniforms: &U,
                          draw_parameters: &DrawParameters<'_>) -> Result<(), DrawError>
                          where I: Into<index::IndicesSource<'a>>, U: uniforms::Uniforms,
                          V: vertex::MultiVerticesSource<'b>
@@ -1329,6 +1481,47 @@ impl Surface for Frame {
         ops::draw(&self.context, None, vertex_buffer, index_buffer.into(), program,
                   uniforms, draw_parameters, self.dimensions)
     }
+}
+
+impl Surface for Frame {
+    #[inline]
+    fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>, color_srgb: bool,
+             depth: Option<f32>, stencil: Option<i32>)
+    {
+        ops::clear(&self.context, None, rect, color, color_srgb, depth, stencil);
+    }
+
+    fn get_dimensions(&self) -> (u32, u32) {
+        self.dimensions
+    }
+
+    fn get_depth_buffer_bits(&self) -> Option<u16> {
+        self.context.capabilities().depth_bits
+    }
+
+    fn get_stencil_buffer_bits(&self) -> Option<u16> {
+        self.context.capabilities().stencil_bits
+    }
+
+    #[inline]
+    fn draw<'a, 'b, V, I, U>(&mut self, vertex_buffer: V,
+                         index_buffer: I, program: &Program, uniforms: &U,
+                         draw_parameters: &DrawParameters<'_>) -> Result<(), DrawError>
+                         where I: Into<index::IndicesSource<'a>>, U: uniforms::Uniforms,
+                         V: vertex::MultiVerticesSource<'b>
+    {
+        self.draw_program(vertex_buffer, index_buffer, program.into(), uniforms, draw_parameters)
+    }
+
+    #[inline]
+    fn draw_pipeline<'a, 'b, V, I, U>(&mut self, vertex_buffer: V,
+                         index_buffer: I, pipeline: &program::ProgramPipeline, uniforms: &U,
+                         draw_parameters: &DrawParameters<'_>) -> Result<(), DrawError>
+                         where I: Into<index::IndicesSource<'a>>, U: uniforms::Uniforms,
+                         V: vertex::MultiVerticesSource<'b>
+    {
+        self.draw_program(vertex_buffer, index_buffer, pipeline.into(), uniforms, draw_parameters)
+    }
 
     #[inline]
     fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
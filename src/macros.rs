@@ -111,6 +111,9 @@ macro_rules! dynamic_uniform{
 ///
 /// The parameters must be the name of the struct and the names of its fields.
 ///
+/// If the `derive` feature is enabled, `#[derive(glium::Vertex)]` can be used instead, including
+/// on generic structs.
+///
 /// ## Safety
 ///
 /// You must not use this macro on any struct with fields that cannot be zeroed.
@@ -301,7 +304,7 @@ macro_rules! implement_buffer_content {
     (__as_item $i:item) => {$i};
 
     (__impl $struct_name:ident [$($gs:tt)*]) => {
-        implement_buffer_content! { __as_item
+        $crate::implement_buffer_content! { __as_item
             unsafe impl<$($gs)*> $crate::buffer::Content for $struct_name<$($gs)*> {
                 type Owned = Box<$struct_name<$($gs)*>>;
 
@@ -392,6 +395,8 @@ macro_rules! implement_buffer_content {
 ///
 /// The parameters must be the name of the struct and the names of its fields.
 ///
+/// If the `derive` feature is enabled, `#[derive(glium::UniformBlock)]` can be used instead.
+///
 /// ## Example
 ///
 /// ```
@@ -412,7 +417,7 @@ macro_rules! implement_uniform_block {
     (__as_item $i:item) => {$i};
 
     (__impl $struct_name:ident [$($gs:tt)*], $($field_name:ident),+) => (
-        implement_uniform_block! { __as_item
+        $crate::implement_uniform_block! { __as_item
             impl<$($gs)*> $crate::uniforms::UniformBlock for $struct_name<$($gs)*> {
                 fn matches(layout: &$crate::program::BlockLayout, base_offset: usize)
                            -> ::std::result::Result<(), $crate::uniforms::LayoutMismatchError>
@@ -1,6 +1,6 @@
-use crate::fbo::{self, ValidatedAttachments};
+use crate::fbo::{self, ClearBufferData, ValidatedAttachments};
 
-use crate::context::Context;
+use crate::context::{CommandContext, Context};
 use crate::ContextExt;
 use crate::Rect;
 
@@ -18,53 +18,7 @@ pub fn clear(context: &Context, framebuffer: Option<&ValidatedAttachments<'_>>,
 {
     unsafe {
         let mut ctxt = context.make_current();
-
-        let fbo_id = fbo::FramebuffersContainer::get_framebuffer_for_drawing(&mut ctxt, framebuffer);
-        fbo::bind_framebuffer(&mut ctxt, fbo_id, true, false);
-
-        if ctxt.state.enabled_rasterizer_discard {
-            ctxt.gl.Disable(gl::RASTERIZER_DISCARD);
-            ctxt.state.enabled_rasterizer_discard = false;
-        }
-
-        if ctxt.state.color_mask != (1, 1, 1, 1) {
-            ctxt.state.color_mask = (1, 1, 1, 1);
-            ctxt.gl.ColorMask(1, 1, 1, 1);
-        }
-
-        if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.extensions.gl_arb_framebuffer_srgb ||
-           ctxt.extensions.gl_ext_framebuffer_srgb || ctxt.extensions.gl_ext_srgb_write_control
-        {
-            if !color_srgb && !ctxt.state.enabled_framebuffer_srgb {
-                ctxt.gl.Enable(gl::FRAMEBUFFER_SRGB);
-                ctxt.state.enabled_framebuffer_srgb = true;
-
-            } else if color_srgb && ctxt.state.enabled_framebuffer_srgb {
-                ctxt.gl.Disable(gl::FRAMEBUFFER_SRGB);
-                ctxt.state.enabled_framebuffer_srgb = false;
-            }
-        }
-
-        TimeElapsedQuery::end_conditional_render(&mut ctxt);
-
-        if let Some(rect) = rect {
-            let rect = (rect.left as gl::types::GLint, rect.bottom as gl::types::GLint,
-                        rect.width as gl::types::GLsizei, rect.height as gl::types::GLsizei);
-
-            if ctxt.state.scissor != Some(rect) {
-                ctxt.gl.Scissor(rect.0, rect.1, rect.2, rect.3);
-                ctxt.state.scissor = Some(rect);
-            }
-
-            if !ctxt.state.enabled_scissor_test {
-                ctxt.gl.Enable(gl::SCISSOR_TEST);
-                ctxt.state.enabled_scissor_test = true;
-            }
-
-        } else if ctxt.state.enabled_scissor_test {
-            ctxt.gl.Disable(gl::SCISSOR_TEST);
-            ctxt.state.enabled_scissor_test = false;
-        }
+        prepare(&mut ctxt, framebuffer, rect, color_srgb);
 
         let mut flags = 0;
 
@@ -97,10 +51,7 @@ pub fn clear(context: &Context, framebuffer: Option<&ValidatedAttachments<'_>>,
                 ctxt.state.clear_depth = depth;
             }
 
-            if !ctxt.state.depth_mask {
-                ctxt.gl.DepthMask(gl::TRUE);
-                ctxt.state.depth_mask = true;
-            }
+            enable_depth_mask(&mut ctxt);
         }
 
         if let Some(stencil) = stencil {
@@ -117,3 +68,98 @@ pub fn clear(context: &Context, framebuffer: Option<&ValidatedAttachments<'_>>,
         ctxt.gl.Clear(flags);
     }
 }
+
+/// Clears the first color attachment, or the depth and/or stencil attachment of a framebuffer
+/// with `glClearBuffer*`. Contrary to `clear`, this works with integral and unsigned attachments.
+///
+/// The value is written as-is, without any sRGB conversion.
+pub fn clear_buffer(context: &Context, framebuffer: &ValidatedAttachments<'_>, rect: &Rect,
+                    data: ClearBufferData)
+{
+    unsafe {
+        let mut ctxt = context.make_current();
+        prepare(&mut ctxt, Some(framebuffer), Some(rect), true);
+
+        match data {
+            ClearBufferData::Float(value) => {
+                ctxt.gl.ClearBufferfv(gl::COLOR, 0, value.as_ptr());
+            },
+            ClearBufferData::Integral(value) => {
+                ctxt.gl.ClearBufferiv(gl::COLOR, 0, value.as_ptr());
+            },
+            ClearBufferData::Unsigned(value) => {
+                ctxt.gl.ClearBufferuiv(gl::COLOR, 0, value.as_ptr());
+            },
+            ClearBufferData::Depth(depth) => {
+                enable_depth_mask(&mut ctxt);
+                ctxt.gl.ClearBufferfv(gl::DEPTH, 0, &depth);
+            },
+            ClearBufferData::Stencil(stencil) => {
+                ctxt.gl.ClearBufferiv(gl::STENCIL, 0, &stencil);
+            },
+            ClearBufferData::DepthStencil(depth, stencil) => {
+                enable_depth_mask(&mut ctxt);
+                ctxt.gl.ClearBufferfi(gl::DEPTH_STENCIL, 0, depth, stencil);
+            },
+        }
+    }
+}
+
+/// Binds the framebuffer and sets the state that affects clearing operations.
+unsafe fn prepare(ctxt: &mut CommandContext<'_>, framebuffer: Option<&ValidatedAttachments<'_>>,
+                  rect: Option<&Rect>, color_srgb: bool)
+{
+    let fbo_id = fbo::FramebuffersContainer::get_framebuffer_for_drawing(ctxt, framebuffer);
+    fbo::bind_framebuffer(ctxt, fbo_id, true, false);
+
+    if ctxt.state.enabled_rasterizer_discard {
+        ctxt.gl.Disable(gl::RASTERIZER_DISCARD);
+        ctxt.state.enabled_rasterizer_discard = false;
+    }
+
+    if ctxt.state.color_mask != (1, 1, 1, 1) {
+        ctxt.state.color_mask = (1, 1, 1, 1);
+        ctxt.gl.ColorMask(1, 1, 1, 1);
+    }
+
+    if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.extensions.gl_arb_framebuffer_srgb ||
+       ctxt.extensions.gl_ext_framebuffer_srgb || ctxt.extensions.gl_ext_srgb_write_control
+    {
+        if !color_srgb && !ctxt.state.enabled_framebuffer_srgb {
+            ctxt.gl.Enable(gl::FRAMEBUFFER_SRGB);
+            ctxt.state.enabled_framebuffer_srgb = true;
+
+        } else if color_srgb && ctxt.state.enabled_framebuffer_srgb {
+            ctxt.gl.Disable(gl::FRAMEBUFFER_SRGB);
+            ctxt.state.enabled_framebuffer_srgb = false;
+        }
+    }
+
+    TimeElapsedQuery::end_conditional_render(ctxt);
+
+    if let Some(rect) = rect {
+        let rect = (rect.left as gl::types::GLint, rect.bottom as gl::types::GLint,
+                    rect.width as gl::types::GLsizei, rect.height as gl::types::GLsizei);
+
+        if ctxt.state.scissor != Some(rect) {
+            ctxt.gl.Scissor(rect.0, rect.1, rect.2, rect.3);
+            ctxt.state.scissor = Some(rect);
+        }
+
+        if !ctxt.state.enabled_scissor_test {
+            ctxt.gl.Enable(gl::SCISSOR_TEST);
+            ctxt.state.enabled_scissor_test = true;
+        }
+
+    } else if ctxt.state.enabled_scissor_test {
+        ctxt.gl.Disable(gl::SCISSOR_TEST);
+        ctxt.state.enabled_scissor_test = false;
+    }
+}
+
+unsafe fn enable_depth_mask(ctxt: &mut CommandContext<'_>) {
+    if !ctxt.state.depth_mask {
+        ctxt.gl.DepthMask(gl::TRUE);
+        ctxt.state.depth_mask = true;
+    }
+}
@@ -13,7 +13,8 @@ use crate::TransformFeedbackSessionExt;
 use crate::fbo::{self, ValidatedAttachments};
 
 use crate::uniforms::Uniforms;
-use crate::{Program, ToGlEnum};
+use crate::ToGlEnum;
+use crate::program::DrawProgram;
 use crate::index::{self, IndicesSource};
 use crate::vertex::{MultiVerticesSource, VerticesSource, TransformFeedbackSession};
 use crate::vertex_array_object::VertexAttributesSystem;
@@ -27,10 +28,17 @@ use crate::version::Api;
 /// Draws everything.
 pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachments<'_>>,
                       vertex_buffers: V, indices: IndicesSource<'_>,
-                      program: &Program, uniforms: &U, draw_parameters: &DrawParameters<'_>,
+                      program: DrawProgram<'_>, uniforms: &U, draw_parameters: &DrawParameters<'_>,
                       dimensions: (u32, u32)) -> Result<(), DrawError>
                       where U: Uniforms, V: MultiVerticesSource<'a>
 {
+    // separable programs only contain some of the stages and must go through a pipeline
+    if let DrawProgram::Program(program) = program {
+        if program.separable_stage().is_some() {
+            return Err(DrawError::SeparableProgram);
+        }
+    }
+
     // this contains the list of fences that will need to be fulfilled after the draw command
     // has started
     let mut fences = Vec::with_capacity(0);
@@ -89,8 +97,8 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
         };
 
         // object that is used to build the bindings
-        let mut binder = VertexAttributesSystem::start(&mut ctxt, program, index_buffer,
-                                                       use_base_vertex);
+        let mut binder = VertexAttributesSystem::start(&mut ctxt, program.vertex_program(),
+                                                       index_buffer, use_base_vertex);
         // number of vertices in the vertices sources, or `None` if there is a mismatch
         let mut vertices_count: Option<usize> = None;
         // number of instances to draw
@@ -167,7 +175,12 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
 
     // binding the program and uniforms
     program.use_program(&mut ctxt);
-    uniforms.bind_uniforms(&mut ctxt, program, &mut fences)?;
+    uniforms.bind_uniforms(&mut ctxt, &program, &mut fences)?;
+
+    // the uniforms of a pipeline are set by making each of its programs current
+    if let DrawProgram::Pipeline(_) = program {
+        program.use_program(&mut ctxt);
+    }
 
     // sync-ing draw_parameters
     unsafe {
@@ -0,0 +1,63 @@
+use crate::BlitMask;
+use crate::Rect;
+
+use crate::context::Context;
+use crate::ContextExt;
+
+use crate::fbo::{self, ValidatedAttachments};
+
+use smallvec::SmallVec;
+
+use crate::gl;
+use crate::version::Version;
+use crate::version::Api;
+
+/// Invalidates the buffers of `mask` in a framebuffer, or in the default framebuffer if
+/// `framebuffer` is `None`. If `rect` is `Some`, only this region is invalidated.
+///
+/// Invalidation is only a hint, so nothing happens if it is not supported by the backend.
+pub fn invalidate(context: &Context, framebuffer: Option<&ValidatedAttachments<'_>>,
+                  mask: BlitMask, rect: Option<&Rect>)
+{
+    unsafe {
+        let mut ctxt = context.make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 4, 3) || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+             ctxt.extensions.gl_arb_invalidate_subdata)
+        {
+            return;
+        }
+
+        let attachments = match framebuffer {
+            Some(framebuffer) => framebuffer.get_attachment_points(mask),
+            None => {
+                let mut attachments = SmallVec::new();
+                if mask.color { attachments.push(gl::COLOR); }
+                if mask.depth { attachments.push(gl::DEPTH); }
+                if mask.stencil { attachments.push(gl::STENCIL); }
+                attachments
+            },
+        };
+
+        if attachments.is_empty() {
+            return;
+        }
+
+        let fbo_id = fbo::FramebuffersContainer::get_framebuffer_for_drawing(&mut ctxt, framebuffer);
+        fbo::bind_framebuffer(&mut ctxt, fbo_id, true, false);
+
+        if let Some(rect) = rect {
+            ctxt.gl.InvalidateSubFramebuffer(gl::DRAW_FRAMEBUFFER,
+                                             attachments.len() as gl::types::GLsizei,
+                                             attachments.as_ptr(),
+                                             rect.left as gl::types::GLint,
+                                             rect.bottom as gl::types::GLint,
+                                             rect.width as gl::types::GLsizei,
+                                             rect.height as gl::types::GLsizei);
+        } else {
+            ctxt.gl.InvalidateFramebuffer(gl::DRAW_FRAMEBUFFER,
+                                          attachments.len() as gl::types::GLsizei,
+                                          attachments.as_ptr());
+        }
+    }
+}
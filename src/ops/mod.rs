@@ -1,9 +1,11 @@
 pub use self::blit::blit;
-pub use self::clear::clear;
+pub use self::clear::{clear, clear_buffer};
 pub use self::draw::draw;
+pub use self::invalidate::invalidate;
 pub use self::read::{read, ReadError, Source};
 
 mod blit;
 mod clear;
 mod draw;
+mod invalidate;
 mod read;
@@ -0,0 +1,316 @@
+use std::fs;
+use std::hash::Hasher;
+use std::io;
+use std::path::PathBuf;
+use std::process;
+
+use fnv::FnvHasher;
+
+use crate::gl;
+use crate::backend::Facade;
+use crate::CapabilitiesSource;
+use crate::ContextExt;
+
+use crate::program::{Binary, ComputeShader, GetBinaryError, Preprocessor, Program};
+use crate::program::{ProgramCreationError, ProgramCreationInput, TransformFeedbackMode};
+use crate::program::is_binary_supported;
+
+/// Changed whenever the content of the cache files changes.
+const CACHE_VERSION: &[u8] = b"glium program cache 1";
+
+/// Stores the binaries of the programs in a directory, so that they don't need to be compiled
+/// again the next time the application starts.
+///
+/// The binaries are identified by a hash of the source code of the shaders and of the vendor,
+/// renderer and version strings of the OpenGL implementation. If the implementation rejects a
+/// binary, for example after an update of the driver, the program is compiled from source and
+/// the binary is replaced.
+///
+/// Only programs created from GLSL source code are cached. The cache is bypassed when the
+/// backend doesn't support retrieving binaries. Errors while reading or writing the files are
+/// ignored, as the program can always be compiled instead.
+///
+/// ```no_run
+/// # use glutin::surface::{ResizeableSurface, SurfaceTypeTrait};
+/// # fn example<T>(display: glium::Display<T>) where T: SurfaceTypeTrait + ResizeableSurface {
+/// # let vertex_source = ""; let fragment_source = "";
+/// use glium::program::ProgramCache;
+///
+/// let cache = ProgramCache::new("cache/shaders");
+/// let program = cache.program(&display, glium::program::SourceCode {
+///     vertex_shader: vertex_source,
+///     fragment_shader: fragment_source,
+///     geometry_shader: None,
+///     tessellation_control_shader: None,
+///     tessellation_evaluation_shader: None,
+/// }).unwrap();
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProgramCache {
+    directory: PathBuf,
+}
+
+impl ProgramCache {
+    /// Builds a cache that stores its files in `directory`. The directory is created when the
+    /// first binary is written.
+    pub fn new<P>(directory: P) -> ProgramCache where P: Into<PathBuf> {
+        ProgramCache {
+            directory: directory.into(),
+        }
+    }
+
+    /// Returns the directory of the cache.
+    #[inline]
+    pub fn directory(&self) -> &PathBuf {
+        &self.directory
+    }
+
+    /// Loads the program from the cache, or builds it with `Program::new` and stores its
+    /// binary.
+    pub fn program<'a, F: ?Sized, I>(&self, facade: &F, input: I)
+                                     -> Result<Program, ProgramCreationError>
+                                     where I: Into<ProgramCreationInput<'a>>, F: Facade
+    {
+        let input = input.into();
+
+        let (key, outputs_srgb, uses_point_size) = match input {
+            ProgramCreationInput::SourceCode { vertex_shader, tessellation_control_shader,
+                                               tessellation_evaluation_shader, geometry_shader,
+                                               fragment_shader, ref transform_feedback_varyings,
+                                               outputs_srgb, uses_point_size } =>
+            {
+                let key = cache_key(facade, &[vertex_shader],
+                                    &[tessellation_control_shader, tessellation_evaluation_shader,
+                                      geometry_shader, Some(fragment_shader)],
+                                    transform_feedback_varyings);
+                (key, outputs_srgb, uses_point_size)
+            },
+            _ => return Program::new(facade, input),
+        };
+
+        self.load_or_build(facade, key,
+                           |binary| Program::new(facade, ProgramCreationInput::Binary {
+                               data: binary,
+                               outputs_srgb,
+                               uses_point_size,
+                           }),
+                           || Program::new(facade, input),
+                           Program::get_binary)
+    }
+
+    /// Loads the program from the cache, or builds it with `Program::new_preprocessed` and
+    /// stores its binary.
+    ///
+    /// The sources are hashed after being processed, so that modifying an included file or a
+    /// define doesn't load a stale binary.
+    pub fn program_preprocessed<'a, F: ?Sized, I>(&self, facade: &F, preprocessor: &Preprocessor,
+                                                  input: I)
+                                                  -> Result<Program, ProgramCreationError>
+                                                  where I: Into<ProgramCreationInput<'a>>,
+                                                        F: Facade
+    {
+        let input = input.into();
+
+        let (key, outputs_srgb, uses_point_size) = match input {
+            ProgramCreationInput::SourceCode { vertex_shader, tessellation_control_shader,
+                                               tessellation_evaluation_shader, geometry_shader,
+                                               fragment_shader, ref transform_feedback_varyings,
+                                               outputs_srgb, uses_point_size } =>
+            {
+                let process = |source: Option<&str>, name| {
+                    source.map(|s| preprocessor.process(name, s).map(|p| p.code)).transpose()
+                };
+
+                let sources = (process(Some(vertex_shader), "vertex"),
+                               process(tessellation_control_shader, "tessellation_control"),
+                               process(tessellation_evaluation_shader, "tessellation_evaluation"),
+                               process(geometry_shader, "geometry"),
+                               process(Some(fragment_shader), "fragment"));
+
+                match sources {
+                    (Ok(Some(vs)), Ok(tcs), Ok(tes), Ok(gs), Ok(Some(fs))) => {
+                        let key = cache_key(facade, &[&vs], &[tcs.as_deref(), tes.as_deref(),
+                                                              gs.as_deref(), Some(&fs)],
+                                            transform_feedback_varyings);
+                        (key, outputs_srgb, uses_point_size)
+                    },
+                    // reports the error of the preprocessor
+                    _ => return Program::new_preprocessed(facade, preprocessor, input),
+                }
+            },
+            _ => return Program::new(facade, input),
+        };
+
+        self.load_or_build(facade, key,
+                           |binary| Program::new(facade, ProgramCreationInput::Binary {
+                               data: binary,
+                               outputs_srgb,
+                               uses_point_size,
+                           }),
+                           || Program::new_preprocessed(facade, preprocessor, input),
+                           Program::get_binary)
+    }
+
+    /// Loads the compute shader from the cache, or builds it with `ComputeShader::from_source`
+    /// and stores its binary.
+    pub fn compute_shader<F: ?Sized>(&self, facade: &F, source: &str)
+                                     -> Result<ComputeShader, ProgramCreationError>
+                                     where F: Facade
+    {
+        let key = cache_key(facade, &[source], &[], &None);
+
+        self.load_or_build(facade, key,
+                           |binary| ComputeShader::from_binary(facade, binary),
+                           || ComputeShader::from_source(facade, source),
+                           ComputeShader::get_binary)
+    }
+
+    /// Removes all the binaries of the cache.
+    pub fn clear(&self) -> io::Result<()> {
+        let entries = match fs::read_dir(&self.directory) {
+            Ok(entries) => entries,
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err),
+        };
+
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|e| e == "bin") {
+                fs::remove_file(path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn load_or_build<F: ?Sized, T, L, B, G>(&self, facade: &F, key: u64, load: L, build: B,
+                                           get_binary: G) -> Result<T, ProgramCreationError>
+        where F: Facade,
+              L: FnOnce(Binary) -> Result<T, ProgramCreationError>,
+              B: FnOnce() -> Result<T, ProgramCreationError>,
+              G: FnOnce(&T) -> Result<Binary, GetBinaryError>
+    {
+        if !is_binary_supported(facade.get_context()) {
+            return build();
+        }
+
+        let path = self.directory.join(format!("{:016x}.bin", key));
+
+        if let Some(binary) = read_binary(&path) {
+            if is_format_supported(facade, binary.format) {
+                if let Ok(program) = load(binary) {
+                    return Ok(program);
+                }
+            }
+
+            // the binary has been rejected and is going to be replaced
+            let _ = fs::remove_file(&path);
+        }
+
+        let program = build()?;
+
+        if let Ok(binary) = get_binary(&program) {
+            let _ = self.write_binary(&path, &binary);
+        }
+
+        Ok(program)
+    }
+
+    fn write_binary(&self, path: &PathBuf, binary: &Binary) -> io::Result<()> {
+        fs::create_dir_all(&self.directory)?;
+
+        let mut data = Vec::with_capacity(4 + binary.content.len());
+        data.extend_from_slice(&binary.format.to_le_bytes());
+        data.extend_from_slice(&binary.content);
+
+        // written to a temporary file first, so that other processes never read a partial file
+        let temporary = path.with_extension(format!("{}.tmp", process::id()));
+        fs::write(&temporary, data)?;
+        fs::rename(&temporary, path).inspect_err(|_| {
+            let _ = fs::remove_file(&temporary);
+        })
+    }
+}
+
+/// Returns true if the implementation still accepts binaries of this format. Loading a binary
+/// of an unknown format would generate an OpenGL error instead of a linking error.
+fn is_format_supported<F: ?Sized>(facade: &F, format: u32) -> bool where F: Facade {
+    let ctxt = facade.get_context().make_current();
+
+    unsafe {
+        let mut num_formats = 0;
+        ctxt.gl.GetIntegerv(gl::NUM_PROGRAM_BINARY_FORMATS, &mut num_formats);
+        if num_formats <= 0 {
+            return false;
+        }
+
+        let mut formats = vec![0; num_formats as usize];
+        ctxt.gl.GetIntegerv(gl::PROGRAM_BINARY_FORMATS, formats.as_mut_ptr());
+        formats.contains(&(format as gl::types::GLint))
+    }
+}
+
+fn read_binary(path: &PathBuf) -> Option<Binary> {
+    let data = fs::read(path).ok()?;
+
+    // the format, the glium header, and at least one byte of the binary itself
+    if data.len() < 6 {
+        return None;
+    }
+
+    Some(Binary {
+        format: u32::from_le_bytes([data[0], data[1], data[2], data[3]]),
+        content: data[4 ..].to_vec(),
+    })
+}
+
+/// Hashes the sources with the strings of the OpenGL implementation. FNV is used because its
+/// result, contrary to the one of the default hasher, is stable across Rust versions.
+fn cache_key<F: ?Sized>(facade: &F, required: &[&str], optional: &[Option<&str>],
+                        transform_feedback_varyings: &Option<(Vec<String>, TransformFeedbackMode)>)
+                        -> u64 where F: Facade
+{
+    fn write_str(hasher: &mut FnvHasher, s: &str) {
+        hasher.write_u64(s.len() as u64);
+        hasher.write(s.as_bytes());
+    }
+
+    let mut hasher = FnvHasher::default();
+    hasher.write(CACHE_VERSION);
+
+    let capabilities = facade.get_context().get_capabilities();
+    write_str(&mut hasher, &capabilities.vendor);
+    write_str(&mut hasher, &capabilities.renderer);
+    write_str(&mut hasher, &capabilities.version);
+
+    for source in required {
+        write_str(&mut hasher, source);
+    }
+
+    for source in optional {
+        match *source {
+            Some(source) => {
+                hasher.write_u8(1);
+                write_str(&mut hasher, source);
+            },
+            None => hasher.write_u8(0),
+        }
+    }
+
+    match *transform_feedback_varyings {
+        Some((ref varyings, mode)) => {
+            hasher.write_u8(match mode {
+                TransformFeedbackMode::Interleaved => 1,
+                TransformFeedbackMode::Separate => 2,
+            });
+            hasher.write_u64(varyings.len() as u64);
+            for varying in varyings {
+                write_str(&mut hasher, varying);
+            }
+        },
+        None => hasher.write_u8(0),
+    }
+
+    hasher.finish()
+}
@@ -0,0 +1,752 @@
+/*!
+Generation of Rust definitions from the interface of a program.
+
+The `Generator` collects the vertex attributes and the uniform and shader storage blocks of
+programs, and writes Rust structs whose layouts match them, along with the corresponding
+`implement_vertex!` and `implement_uniform_block!` invocations. This way, the Rust definitions
+can be regenerated every time the shaders change instead of being kept in sync by hand.
+
+The interface can be obtained in two ways:
+
+ - With `add_program`, from the reflection of a `Program` that was compiled by the OpenGL
+   implementation, for example with a headless context.
+ - With `add_vertex_shader_source` and `add_shader_source`, by parsing the GLSL source code
+   without any context. This only looks at the declarations of the inputs of the vertex shader
+   and of the blocks, and doesn't run the preprocessor.
+
+Uniform blocks are expected to use the `std140` layout and shader storage blocks the `std430`
+layout. The members of the generated structs are placed at the offsets of the GLSL members by
+inserting padding, and the arrays and matrices use the wrappers of the
+[`uniforms::layout`](crate::uniforms::layout) module whenever their stride differs in Rust and
+in GLSL.
+
+The generator is meant to be used from a build script:
+
+```no_run
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+use glium::program::codegen::Generator;
+
+println!("cargo:rerun-if-changed=shaders");
+
+let mut generator = Generator::new();
+generator.add_vertex_shader_source("Vertex", &std::fs::read_to_string("shaders/teapot.vert")?)?;
+generator.add_shader_source(&std::fs::read_to_string("shaders/teapot.frag")?)?;
+
+let out_dir = std::env::var("OUT_DIR")?;
+std::fs::write(std::path::Path::new(&out_dir).join("shaders.rs"), generator.generate()?)?;
+# Ok(())
+# }
+```
+
+The generated file can then be included with
+`include!(concat!(env!("OUT_DIR"), "/shaders.rs"));`.
+
+*/
+use std::error::Error;
+use std::fmt;
+use std::fmt::Write;
+
+use crate::program::{BlockLayout, Program};
+use crate::uniforms::UniformType;
+use crate::uniforms::layout::align_offset;
+use crate::vertex::AttributeType;
+
+mod parser;
+
+/// Packing rules of the members of a block.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Packing {
+    /// The `std140` layout, used by uniform blocks.
+    Std140,
+    /// The `std430` layout, used by shader storage blocks.
+    Std430,
+}
+
+/// Error that can happen while collecting the interface of a program or generating the code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CodegenError {
+    /// The GLSL source code couldn't be parsed.
+    ParseError {
+        /// Line of the source code, starting at 1.
+        line: usize,
+        /// Description of the error.
+        message: String,
+    },
+
+    /// A member or an attribute has a type that has no Rust equivalent in glium.
+    UnsupportedType {
+        /// Path of the member or name of the attribute.
+        path: String,
+        /// Name of the type.
+        ty: String,
+    },
+
+    /// The offsets of the members of a block can't be reproduced with a Rust struct.
+    UnsupportedLayout {
+        /// Path of the member.
+        path: String,
+    },
+
+    /// The name of a member or of an attribute is a Rust keyword.
+    ReservedName(String),
+
+    /// Two blocks or vertex structs with the same name have different definitions.
+    ConflictingDefinitions(String),
+}
+
+impl fmt::Display for CodegenError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            CodegenError::ParseError { line, message } =>
+                write!(fmt, "line {}: {}", line, message),
+            CodegenError::UnsupportedType { path, ty } =>
+                write!(fmt, "{}: the type {} is not supported", path, ty),
+            CodegenError::UnsupportedLayout { path } =>
+                write!(fmt, "{}: the layout of this member can't be reproduced in Rust", path),
+            CodegenError::ReservedName(name) =>
+                write!(fmt, "{} is a reserved keyword in Rust", name),
+            CodegenError::ConflictingDefinitions(name) =>
+                write!(fmt, "{} has different definitions", name),
+        }
+    }
+}
+
+impl Error for CodegenError {}
+
+/// Collects the interface of programs and generates the corresponding Rust definitions.
+#[derive(Debug, Clone, Default)]
+pub struct Generator {
+    vertices: Vec<VertexInput>,
+    blocks: Vec<BlockInput>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct VertexInput {
+    name: String,
+    attributes: Vec<AttributeInput>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AttributeInput {
+    name: String,
+    ty: AttributeType,
+    size: usize,
+    location: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BlockInput {
+    name: String,
+    layout: BlockLayout,
+    packing: Packing,
+}
+
+impl Generator {
+    /// Builds an empty generator.
+    #[inline]
+    pub fn new() -> Generator {
+        Generator::default()
+    }
+
+    /// Adds the attributes, the uniform blocks and the shader storage blocks of a program.
+    ///
+    /// The vertex struct is named `vertex_name`, and is only generated if the program has
+    /// attributes. Its fields are sorted by location.
+    pub fn add_program(&mut self, vertex_name: &str, program: &Program) -> &mut Generator {
+        let mut attributes = program.attributes()
+                                    .filter(|(name, _)| !name.starts_with("gl_"))
+                                    .collect::<Vec<_>>();
+        attributes.sort_by_key(|(_, attribute)| attribute.location);
+
+        if !attributes.is_empty() {
+            self.vertices.push(VertexInput {
+                name: vertex_name.to_owned(),
+                attributes: attributes.into_iter().map(|(name, attribute)| {
+                    AttributeInput {
+                        name: name.clone(),
+                        ty: attribute.ty,
+                        size: attribute.size,
+                        location: None,
+                    }
+                }).collect(),
+            });
+        }
+
+        let mut blocks = program.get_uniform_blocks().iter()
+                                .map(|(name, block)| (name, &block.layout, Packing::Std140))
+                                .chain(program.get_shader_storage_blocks().iter()
+                                    .map(|(name, block)| (name, &block.layout, Packing::Std430)))
+                                .collect::<Vec<_>>();
+        blocks.sort_by(|a, b| a.0.cmp(b.0));
+
+        for (name, layout, packing) in blocks {
+            self.add_block(name, layout, packing);
+        }
+
+        self
+    }
+
+    /// Parses the source code of a vertex shader and adds its inputs and its blocks.
+    ///
+    /// The vertex struct is named `vertex_name`, and is only generated if the shader has inputs.
+    /// The fields are in the order of the declarations, and use the locations given with
+    /// `layout(location = ...)` if all the inputs have one.
+    pub fn add_vertex_shader_source(&mut self, vertex_name: &str, source: &str)
+                                    -> Result<&mut Generator, CodegenError>
+    {
+        let interface = parser::parse(source, true)?;
+
+        if !interface.attributes.is_empty() {
+            self.vertices.push(VertexInput {
+                name: vertex_name.to_owned(),
+                attributes: interface.attributes,
+            });
+        }
+
+        self.blocks.extend(interface.blocks);
+        Ok(self)
+    }
+
+    /// Parses the source code of a shader of any stage and adds its blocks.
+    pub fn add_shader_source(&mut self, source: &str) -> Result<&mut Generator, CodegenError> {
+        let interface = parser::parse(source, false)?;
+        self.blocks.extend(interface.blocks);
+        Ok(self)
+    }
+
+    /// Adds a block whose layout was obtained by other means.
+    ///
+    /// `layout` must be a `BlockLayout::Struct`.
+    pub fn add_block(&mut self, name: &str, layout: &BlockLayout, packing: Packing)
+                     -> &mut Generator
+    {
+        self.blocks.push(BlockInput {
+            name: name.to_owned(),
+            layout: layout.clone(),
+            packing,
+        });
+
+        self
+    }
+
+    /// Generates the Rust source code.
+    ///
+    /// Blocks and vertex structs that were added multiple times with the same definition, for
+    /// example because they are shared by multiple shaders, are only generated once.
+    pub fn generate(&self) -> Result<String, CodegenError> {
+        let mut output = String::from("// Automatically generated by glium. Do not edit.\n");
+
+        let mut vertices: Vec<&VertexInput> = Vec::new();
+        for vertex in &self.vertices {
+            match vertices.iter().find(|v| v.name == vertex.name) {
+                Some(v) if *v == vertex => (),
+                Some(_) => return Err(CodegenError::ConflictingDefinitions(vertex.name.clone())),
+                None => vertices.push(vertex),
+            }
+        }
+
+        let mut blocks: Vec<&BlockInput> = Vec::new();
+        for block in &self.blocks {
+            match blocks.iter().find(|b| b.name == block.name) {
+                Some(b) if *b == block => (),
+                Some(_) => return Err(CodegenError::ConflictingDefinitions(block.name.clone())),
+                None => blocks.push(block),
+            }
+        }
+
+        for vertex in vertices {
+            output.push('\n');
+            output.push_str(&generate_vertex(vertex)?);
+        }
+
+        for block in blocks {
+            let mut emitter = Emitter { packing: block.packing, output: String::new() };
+            let name = to_camel_case(&block.name);
+
+            match block.layout {
+                BlockLayout::Struct { ref members } => {
+                    let size = size_alignment(&block.layout, block.packing).map(|(s, _)| s);
+                    emitter.emit_struct(&name, &block.name, members, 0, size)?;
+                },
+                _ => return Err(CodegenError::UnsupportedLayout { path: block.name.clone() }),
+            }
+
+            output.push_str(&emitter.output);
+        }
+
+        Ok(output)
+    }
+}
+
+fn generate_vertex(vertex: &VertexInput) -> Result<String, CodegenError> {
+    let mut output = String::new();
+    let with_locations = vertex.attributes.iter().all(|a| a.location.is_some());
+
+    writeln!(output, "#[derive(Copy, Clone, Debug)]").unwrap();
+    if vertex.attributes.iter().any(|a| !is_snake_case(&a.name)) {
+        writeln!(output, "#[allow(non_snake_case)]").unwrap();
+    }
+    writeln!(output, "pub struct {} {{", vertex.name).unwrap();
+
+    for attribute in &vertex.attributes {
+        check_name(&attribute.name)?;
+
+        let ty = match attribute_rust_type(attribute.ty) {
+            Some(ty) if attribute.size == 1 => ty,
+            _ => return Err(CodegenError::UnsupportedType {
+                path: attribute.name.clone(),
+                ty: if attribute.size == 1 { format!("{:?}", attribute.ty) }
+                    else { format!("[{:?}; {}]", attribute.ty, attribute.size) },
+            }),
+        };
+
+        writeln!(output, "    pub {}: {},", attribute.name, ty).unwrap();
+    }
+
+    writeln!(output, "}}\n").unwrap();
+
+    let fields = vertex.attributes.iter().map(|attribute| {
+        match attribute.location {
+            Some(location) if with_locations => format!("{} location({})", attribute.name, location),
+            _ => attribute.name.clone(),
+        }
+    }).collect::<Vec<_>>();
+
+    writeln!(output, "glium::implement_vertex!({}, {});", vertex.name, fields.join(", ")).unwrap();
+    Ok(output)
+}
+
+/// Writes the structs of a block. Nested structs are written before the structs that
+/// contain them.
+struct Emitter {
+    packing: Packing,
+    output: String,
+}
+
+struct Field {
+    name: String,
+    ty: String,
+    /// Size of the field if it is padding.
+    padding: Option<usize>,
+}
+
+impl Emitter {
+    /// Writes a struct whose members start at `base_offset` in the block and whose size in GLSL
+    /// is `size`, or `None` if it ends with an array of unknown size.
+    fn emit_struct(&mut self, name: &str, path: &str, members: &[(String, BlockLayout)],
+                   base_offset: usize, size: Option<usize>) -> Result<(), CodegenError>
+    {
+        let mut members = members.iter().collect::<Vec<_>>();
+        members.sort_by_key(|(_, layout)| first_offset(layout));
+
+        let mut fields = Vec::with_capacity(members.len());
+        let mut offset = 0;
+        let mut unsized_array = false;
+
+        for (member_name, layout) in members {
+            let member_path = format!("{}.{}", path, member_name);
+            check_name(member_name)?;
+
+            if unsized_array {
+                return Err(CodegenError::UnsupportedLayout { path: member_path });
+            }
+
+            let member_offset = match first_offset(layout) {
+                Some(o) if o >= base_offset + offset => o - base_offset,
+                _ => return Err(CodegenError::UnsupportedLayout { path: member_path }),
+            };
+
+            if member_offset > offset {
+                fields.push(padding_field(&fields, member_offset - offset));
+                offset = member_offset;
+            }
+
+            let nested_name = format!("{}{}", name, to_camel_case(member_name));
+            let (ty, member_size) = self.rust_type(layout, &member_path, &nested_name)?;
+            unsized_array = matches!(layout, BlockLayout::DynamicSizedArray { .. });
+
+            fields.push(Field { name: member_name.clone(), ty, padding: None });
+            offset += member_size;
+        }
+
+        let size = match size {
+            Some(size) if !unsized_array => Some(size.max(offset)),
+            _ => None,
+        };
+
+        if let Some(size) = size {
+            if size > offset {
+                fields.push(padding_field(&fields, size - offset));
+            }
+        }
+
+        self.write_struct(name, path, &fields, size);
+        Ok(())
+    }
+
+    fn write_struct(&mut self, name: &str, path: &str, fields: &[Field], size: Option<usize>) {
+        let members = fields.iter().filter(|f| f.padding.is_none()).collect::<Vec<_>>();
+        let snake_case = members.iter().all(|f| is_snake_case(&f.name));
+        let output = &mut self.output;
+
+        writeln!(output, "\n/// Generated from `{}`.", path).unwrap();
+        if size.is_some() {
+            writeln!(output, "#[derive(Copy, Clone)]").unwrap();
+        }
+        if !snake_case {
+            writeln!(output, "#[allow(non_snake_case)]").unwrap();
+        }
+        writeln!(output, "#[repr(C)]").unwrap();
+        writeln!(output, "pub struct {} {{", name).unwrap();
+        for field in fields {
+            let visibility = if field.padding.is_some() { "" } else { "pub " };
+            writeln!(output, "    {}{}: {},", visibility, field.name, field.ty).unwrap();
+        }
+        writeln!(output, "}}").unwrap();
+
+        if let Some(size) = size {
+            writeln!(output).unwrap();
+            if !snake_case {
+                writeln!(output, "#[allow(non_snake_case)]").unwrap();
+            }
+            writeln!(output, "impl {} {{", name).unwrap();
+            writeln!(output, "    /// Builds a `{}`, with its padding set to zero.", name).unwrap();
+            if members.len() > 7 {
+                writeln!(output, "    #[allow(clippy::too_many_arguments)]").unwrap();
+            }
+            writeln!(output, "    pub const fn new(").unwrap();
+            for field in &members {
+                writeln!(output, "        {}: {},", field.name, field.ty).unwrap();
+            }
+            writeln!(output, "    ) -> {} {{", name).unwrap();
+            writeln!(output, "        {} {{", name).unwrap();
+            for field in fields {
+                match field.padding {
+                    Some(size) => writeln!(output, "            {}: [0; {}],", field.name, size),
+                    None => writeln!(output, "            {},", field.name),
+                }.unwrap();
+            }
+            writeln!(output, "        }}").unwrap();
+            writeln!(output, "    }}").unwrap();
+            writeln!(output, "}}\n").unwrap();
+            writeln!(output, "const _: () = assert!(::std::mem::size_of::<{}>() == {});",
+                     name, size).unwrap();
+        } else {
+            writeln!(output).unwrap();
+            writeln!(output, "glium::implement_buffer_content!({});", name).unwrap();
+        }
+
+        let names = members.iter().map(|f| &*f.name).collect::<Vec<_>>();
+        writeln!(output, "glium::implement_uniform_block!({}, {});", name, names.join(", ")).unwrap();
+    }
+
+    /// Returns the Rust type of a member and its size in bytes.
+    fn rust_type(&mut self, layout: &BlockLayout, path: &str, nested_name: &str)
+                 -> Result<(String, usize), CodegenError>
+    {
+        match *layout {
+            BlockLayout::BasicType { ty, .. } => {
+                match basic_rust_type(ty, self.packing) {
+                    Some((ty, size)) => Ok((ty.to_owned(), size)),
+                    None => Err(CodegenError::UnsupportedType {
+                        path: path.to_owned(),
+                        ty: format!("{:?}", ty),
+                    }),
+                }
+            },
+
+            BlockLayout::Struct { ref members } => {
+                let size = size_alignment(layout, self.packing).map(|(s, _)| s);
+                let base_offset = first_offset(layout).unwrap_or(0);
+                self.emit_struct(nested_name, path, members, base_offset, size)?;
+                match size {
+                    Some(size) => Ok((nested_name.to_owned(), size)),
+                    None => Err(CodegenError::UnsupportedLayout { path: path.to_owned() }),
+                }
+            },
+
+            BlockLayout::Array { ref content, length } => {
+                let (ty, stride) = self.array_element(content, path, nested_name)?;
+                Ok((format!("glium::uniforms::layout::Array<{}, {}>", ty, length), stride * length))
+            },
+
+            BlockLayout::DynamicSizedArray { ref content } => {
+                let (ty, _) = self.array_element(content, path, nested_name)?;
+                Ok((format!("[{}]", ty), 0))
+            },
+        }
+    }
+
+    /// Returns the Rust type of the elements of an array and their stride.
+    fn array_element(&mut self, content: &BlockLayout, path: &str, nested_name: &str)
+                     -> Result<(String, usize), CodegenError>
+    {
+        let (ty, size) = self.rust_type(content, path, nested_name)?;
+        let stride = match size_alignment(content, self.packing) {
+            Some((size, alignment)) => align_offset(size, array_alignment(alignment, self.packing)),
+            None => return Err(CodegenError::UnsupportedLayout { path: path.to_owned() }),
+        };
+
+        if size == stride {
+            Ok((ty, stride))
+        } else if align_offset(size, 16) == stride {
+            Ok((format!("glium::uniforms::layout::Padded<{}>", ty), stride))
+        } else {
+            Err(CodegenError::UnsupportedLayout { path: path.to_owned() })
+        }
+    }
+}
+
+fn padding_field(fields: &[Field], size: usize) -> Field {
+    let index = fields.iter().filter(|f| f.padding.is_some()).count();
+
+    Field {
+        name: format!("_padding{}", index),
+        ty: format!("[u8; {}]", size),
+        padding: Some(size),
+    }
+}
+
+/// Returns the offset of the first basic element of a layout.
+fn first_offset(layout: &BlockLayout) -> Option<usize> {
+    match *layout {
+        BlockLayout::BasicType { offset_in_buffer, .. } => Some(offset_in_buffer),
+        BlockLayout::Array { ref content, .. } => first_offset(content),
+        BlockLayout::DynamicSizedArray { ref content } => first_offset(content),
+        BlockLayout::Struct { ref members } => {
+            members.iter().filter_map(|(_, layout)| first_offset(layout)).min()
+        },
+    }
+}
+
+/// Returns the size of the scalars, the number of columns and the number of rows of a type
+/// that can be used in a block.
+fn basic_components(ty: UniformType) -> Option<(usize, usize, usize)> {
+    use crate::uniforms::UniformType::*;
+
+    Some(match ty {
+        Float | Int | UnsignedInt | Bool => (4, 1, 1),
+        FloatVec2 | IntVec2 | UnsignedIntVec2 | BoolVec2 => (4, 1, 2),
+        FloatVec3 | IntVec3 | UnsignedIntVec3 | BoolVec3 => (4, 1, 3),
+        FloatVec4 | IntVec4 | UnsignedIntVec4 | BoolVec4 => (4, 1, 4),
+        Double | Int64 | UnsignedInt64 => (8, 1, 1),
+        DoubleVec2 | Int64Vec2 | UnsignedInt64Vec2 => (8, 1, 2),
+        DoubleVec3 | Int64Vec3 | UnsignedInt64Vec3 => (8, 1, 3),
+        DoubleVec4 | Int64Vec4 | UnsignedInt64Vec4 => (8, 1, 4),
+        FloatMat2 => (4, 2, 2),
+        FloatMat3 => (4, 3, 3),
+        FloatMat4 => (4, 4, 4),
+        FloatMat2x3 => (4, 2, 3),
+        FloatMat2x4 => (4, 2, 4),
+        FloatMat3x2 => (4, 3, 2),
+        FloatMat3x4 => (4, 3, 4),
+        FloatMat4x2 => (4, 4, 2),
+        FloatMat4x3 => (4, 4, 3),
+        DoubleMat2 => (8, 2, 2),
+        DoubleMat3 => (8, 3, 3),
+        DoubleMat4 => (8, 4, 4),
+        DoubleMat2x3 => (8, 2, 3),
+        DoubleMat2x4 => (8, 2, 4),
+        DoubleMat3x2 => (8, 3, 2),
+        DoubleMat3x4 => (8, 3, 4),
+        DoubleMat4x2 => (8, 4, 2),
+        DoubleMat4x3 => (8, 4, 3),
+        _ => return None,
+    })
+}
+
+/// Returns the alignment of the elements of an array whose type has the given alignment.
+fn array_alignment(alignment: usize, packing: Packing) -> usize {
+    match packing {
+        Packing::Std140 => align_offset(alignment, 16),
+        Packing::Std430 => alignment,
+    }
+}
+
+/// Returns the size and the alignment of a layout according to the packing rules, or `None` if
+/// it contains a type that can't be in a block.
+///
+/// The size of an array of unknown size is zero.
+fn size_alignment(layout: &BlockLayout, packing: Packing) -> Option<(usize, usize)> {
+    match *layout {
+        BlockLayout::BasicType { ty, .. } => {
+            let (scalar, columns, rows) = basic_components(ty)?;
+            let vector_alignment = scalar * if rows == 3 { 4 } else { rows };
+
+            if columns == 1 {
+                Some((scalar * rows, vector_alignment))
+            } else {
+                // a matrix is laid out like an array of column vectors
+                let alignment = array_alignment(vector_alignment, packing);
+                Some((columns * align_offset(scalar * rows, alignment), alignment))
+            }
+        },
+
+        BlockLayout::Array { ref content, length } => {
+            let (size, alignment) = size_alignment(content, packing)?;
+            let alignment = array_alignment(alignment, packing);
+            Some((length * align_offset(size, alignment), alignment))
+        },
+
+        BlockLayout::DynamicSizedArray { ref content } => {
+            let (_, alignment) = size_alignment(content, packing)?;
+            Some((0, array_alignment(alignment, packing)))
+        },
+
+        BlockLayout::Struct { ref members } => {
+            let mut offset = 0;
+            let mut struct_alignment = 1;
+
+            for (_, member) in members {
+                let (size, alignment) = size_alignment(member, packing)?;
+                offset = align_offset(offset, alignment) + size;
+                struct_alignment = struct_alignment.max(alignment);
+            }
+
+            let struct_alignment = match packing {
+                Packing::Std140 => align_offset(struct_alignment, 16),
+                Packing::Std430 => struct_alignment,
+            };
+
+            Some((align_offset(offset, struct_alignment), struct_alignment))
+        },
+    }
+}
+
+/// Returns a copy of `layout` whose members are placed one after another from `offset`
+/// according to the packing rules.
+fn place(layout: &BlockLayout, offset: usize, packing: Packing) -> BlockLayout {
+    match *layout {
+        BlockLayout::BasicType { ty, .. } => {
+            BlockLayout::BasicType { ty, offset_in_buffer: offset }
+        },
+
+        BlockLayout::Array { ref content, length } => {
+            BlockLayout::Array { content: Box::new(place(content, offset, packing)), length }
+        },
+
+        BlockLayout::DynamicSizedArray { ref content } => {
+            BlockLayout::DynamicSizedArray { content: Box::new(place(content, offset, packing)) }
+        },
+
+        BlockLayout::Struct { ref members } => {
+            let mut member_offset = offset;
+            let members = members.iter().map(|(name, member)| {
+                // the parser only builds layouts made of types that can be in a block
+                let (size, alignment) = size_alignment(member, packing).unwrap();
+                member_offset = align_offset(member_offset, alignment);
+                let member = place(member, member_offset, packing);
+                member_offset += size;
+                (name.clone(), member)
+            }).collect();
+
+            BlockLayout::Struct { members }
+        },
+    }
+}
+
+/// Returns the Rust type of a member of a block and its size.
+fn basic_rust_type(ty: UniformType, packing: Packing) -> Option<(&'static str, usize)> {
+    use crate::uniforms::UniformType::*;
+
+    Some(match ty {
+        Float => ("f32", 4),
+        FloatVec2 => ("[f32; 2]", 8),
+        FloatVec3 => ("[f32; 3]", 12),
+        FloatVec4 => ("[f32; 4]", 16),
+        Double => ("f64", 8),
+        DoubleVec2 => ("[f64; 2]", 16),
+        DoubleVec3 => ("[f64; 3]", 24),
+        DoubleVec4 => ("[f64; 4]", 32),
+        Int => ("i32", 4),
+        IntVec2 => ("[i32; 2]", 8),
+        IntVec3 => ("[i32; 3]", 12),
+        IntVec4 => ("[i32; 4]", 16),
+        UnsignedInt => ("u32", 4),
+        UnsignedIntVec2 => ("[u32; 2]", 8),
+        UnsignedIntVec3 => ("[u32; 3]", 12),
+        UnsignedIntVec4 => ("[u32; 4]", 16),
+        Int64 => ("i64", 8),
+        Int64Vec2 => ("[i64; 2]", 16),
+        Int64Vec3 => ("[i64; 3]", 24),
+        Int64Vec4 => ("[i64; 4]", 32),
+        UnsignedInt64 => ("u64", 8),
+        UnsignedInt64Vec2 => ("[u64; 2]", 16),
+        UnsignedInt64Vec3 => ("[u64; 3]", 24),
+        UnsignedInt64Vec4 => ("[u64; 4]", 32),
+        FloatMat2 if packing == Packing::Std140 => ("glium::uniforms::layout::Mat2", 32),
+        FloatMat2 => ("[[f32; 2]; 2]", 16),
+        FloatMat3 => ("glium::uniforms::layout::Mat3", 48),
+        FloatMat4 => ("[[f32; 4]; 4]", 64),
+        DoubleMat2 => ("[[f64; 2]; 2]", 32),
+        DoubleMat4 => ("[[f64; 4]; 4]", 128),
+        _ => return None,
+    })
+}
+
+/// Returns the Rust type of an attribute.
+fn attribute_rust_type(ty: AttributeType) -> Option<&'static str> {
+    use crate::vertex::AttributeType::*;
+
+    Some(match ty {
+        F32 => "f32",
+        F32F32 => "[f32; 2]",
+        F32F32F32 => "[f32; 3]",
+        F32F32F32F32 => "[f32; 4]",
+        F64 => "f64",
+        F64F64 => "[f64; 2]",
+        F64F64F64 => "[f64; 3]",
+        F64F64F64F64 => "[f64; 4]",
+        I32 => "i32",
+        I32I32 => "[i32; 2]",
+        I32I32I32 => "[i32; 3]",
+        I32I32I32I32 => "[i32; 4]",
+        U32 => "u32",
+        U32U32 => "[u32; 2]",
+        U32U32U32 => "[u32; 3]",
+        U32U32U32U32 => "[u32; 4]",
+        I64 => "i64",
+        I64I64 => "[i64; 2]",
+        I64I64I64 => "[i64; 3]",
+        I64I64I64I64 => "[i64; 4]",
+        U64 => "u64",
+        U64U64 => "[u64; 2]",
+        U64U64U64 => "[u64; 3]",
+        U64U64U64U64 => "[u64; 4]",
+        F32x2x2 => "[[f32; 2]; 2]",
+        F32x3x3 => "[[f32; 3]; 3]",
+        F32x4x4 => "[[f32; 4]; 4]",
+        F64x2x2 => "[[f64; 2]; 2]",
+        F64x3x3 => "[[f64; 3]; 3]",
+        F64x4x4 => "[[f64; 4]; 4]",
+        _ => return None,
+    })
+}
+
+/// Turns `light_color` or `lightColor` into `LightColor`.
+fn to_camel_case(name: &str) -> String {
+    name.split('_').filter(|part| !part.is_empty()).map(|part| {
+        let mut chars = part.chars();
+        chars.next().map(|c| c.to_ascii_uppercase()).into_iter().chain(chars).collect::<String>()
+    }).collect()
+}
+
+fn is_snake_case(name: &str) -> bool {
+    !name.chars().any(|c| c.is_ascii_uppercase())
+}
+
+fn check_name(name: &str) -> Result<(), CodegenError> {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else",
+        "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod",
+        "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super",
+        "trait", "true", "try", "type", "unsafe", "use", "where", "while", "yield", "abstract",
+        "become", "do", "final", "macro", "override", "priv", "typeof", "unsized", "virtual",
+    ];
+
+    if KEYWORDS.contains(&name) {
+        Err(CodegenError::ReservedName(name.to_owned()))
+    } else {
+        Ok(())
+    }
+}
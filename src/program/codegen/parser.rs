@@ -0,0 +1,567 @@
+//! Minimal parser of the interface of a GLSL shader.
+//!
+//! Only the declarations at the global scope are looked at: struct definitions, uniform and
+//! buffer blocks, and the inputs of vertex shaders. Everything else, including function bodies,
+//! is skipped. Preprocessor directives are ignored.
+
+use std::collections::HashMap;
+
+use crate::program::BlockLayout;
+use crate::uniforms::UniformType;
+use crate::vertex::AttributeType;
+
+use super::{place, AttributeInput, BlockInput, CodegenError, Packing};
+
+/// Declarations found in a shader.
+pub struct Interface {
+    pub attributes: Vec<AttributeInput>,
+    pub blocks: Vec<BlockInput>,
+}
+
+/// Parses the source code of a shader. The inputs are only collected if `vertex_inputs` is true.
+pub fn parse(source: &str, vertex_inputs: bool) -> Result<Interface, CodegenError> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        position: 0,
+        structs: HashMap::new(),
+        default_uniform_packing: Packing::Std140,
+        default_buffer_packing: Packing::Std430,
+        vertex_inputs,
+        interface: Interface { attributes: Vec::new(), blocks: Vec::new() },
+    };
+
+    while parser.peek().is_some() {
+        parser.parse_global_declaration()?;
+    }
+
+    Ok(parser.interface)
+}
+
+struct Token<'a> {
+    text: &'a str,
+    line: usize,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token<'_>>, CodegenError> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut line_start = true;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+
+        if c == b'\n' {
+            line += 1;
+            line_start = true;
+            i += 1;
+        } else if c.is_ascii_whitespace() {
+            i += 1;
+        } else if c == b'#' && line_start {
+            // skipping the directive, including its continuation lines
+            while i < bytes.len() && bytes[i] != b'\n' {
+                if bytes[i] == b'\\' && bytes.get(i + 1) == Some(&b'\n') {
+                    line += 1;
+                    i += 1;
+                }
+                i += 1;
+            }
+        } else if c == b'/' && bytes.get(i + 1) == Some(&b'/') {
+            while i < bytes.len() && bytes[i] != b'\n' {
+                i += 1;
+            }
+        } else if c == b'/' && bytes.get(i + 1) == Some(&b'*') {
+            let start_line = line;
+            i += 2;
+            loop {
+                if i >= bytes.len() {
+                    return Err(CodegenError::ParseError {
+                        line: start_line,
+                        message: "unterminated comment".to_owned(),
+                    });
+                }
+                if bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/') {
+                    i += 2;
+                    break;
+                }
+                if bytes[i] == b'\n' {
+                    line += 1;
+                }
+                i += 1;
+            }
+        } else if c.is_ascii_alphanumeric() || c == b'_' {
+            let start = i;
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_' ||
+                                      (bytes[start].is_ascii_digit() && bytes[i] == b'.'))
+            {
+                i += 1;
+            }
+            tokens.push(Token { text: &source[start .. i], line });
+            line_start = false;
+        } else {
+            let len = source[i ..].chars().next().unwrap().len_utf8();
+            tokens.push(Token { text: &source[i .. i + len], line });
+            line_start = false;
+            i += len;
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Qualifiers that don't change anything to the interface.
+const IGNORED_QUALIFIERS: &[&str] = &[
+    "const", "flat", "smooth", "noperspective", "centroid", "sample", "patch", "invariant",
+    "precise", "highp", "mediump", "lowp", "readonly", "writeonly", "coherent", "volatile",
+    "restrict", "shared",
+];
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    position: usize,
+    /// Layouts of the structs that were defined, with all their offsets set to zero.
+    structs: HashMap<String, BlockLayout>,
+    default_uniform_packing: Packing,
+    default_buffer_packing: Packing,
+    vertex_inputs: bool,
+    interface: Interface,
+}
+
+/// Qualifiers of a declaration.
+#[derive(Default)]
+struct Qualifiers<'a> {
+    storage: Option<&'a str>,
+    packing: Option<Packing>,
+    location: Option<i32>,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.position).map(|t| t.text)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&'a str> {
+        self.tokens.get(self.position + offset).map(|t| t.text)
+    }
+
+    fn next(&mut self) -> Result<&'a str, CodegenError> {
+        match self.tokens.get(self.position) {
+            Some(token) => {
+                self.position += 1;
+                Ok(token.text)
+            },
+            None => Err(self.error("unexpected end of file")),
+        }
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), CodegenError> {
+        let token = self.next()?;
+        if token == expected {
+            Ok(())
+        } else {
+            self.position -= 1;
+            Err(self.error(&format!("expected `{}`, found `{}`", expected, token)))
+        }
+    }
+
+    fn identifier(&mut self) -> Result<&'a str, CodegenError> {
+        let token = self.next()?;
+        if token.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+            Ok(token)
+        } else {
+            self.position -= 1;
+            Err(self.error(&format!("expected an identifier, found `{}`", token)))
+        }
+    }
+
+    fn error(&self, message: &str) -> CodegenError {
+        let line = self.tokens.get(self.position).or_else(|| self.tokens.last())
+                              .map(|t| t.line).unwrap_or(1);
+        CodegenError::ParseError { line, message: message.to_owned() }
+    }
+
+    fn parse_global_declaration(&mut self) -> Result<(), CodegenError> {
+        if self.peek() == Some("struct") {
+            return self.parse_struct_definition();
+        }
+
+        let qualifiers = self.parse_qualifiers()?;
+
+        match (qualifiers.storage, self.peek(), self.peek_at(1)) {
+            // default layout, for example `layout(std140) uniform;`
+            (Some(storage), Some(";"), _) => {
+                self.position += 1;
+                if let Some(packing) = qualifiers.packing {
+                    match storage {
+                        "uniform" => self.default_uniform_packing = packing,
+                        "buffer" => self.default_buffer_packing = packing,
+                        _ => (),
+                    }
+                }
+                Ok(())
+            },
+            (Some("uniform"), Some(_), Some("{")) => {
+                let packing = qualifiers.packing.unwrap_or(self.default_uniform_packing);
+                self.parse_block(packing)
+            },
+            (Some("buffer"), Some(_), Some("{")) => {
+                let packing = qualifiers.packing.unwrap_or(self.default_buffer_packing);
+                self.parse_block(packing)
+            },
+            (Some("in"), _, _) | (Some("attribute"), _, _) if self.vertex_inputs => {
+                self.parse_inputs(qualifiers.location)
+            },
+            _ => self.skip_declaration(),
+        }
+    }
+
+    fn parse_qualifiers(&mut self) -> Result<Qualifiers<'a>, CodegenError> {
+        let mut qualifiers = Qualifiers::default();
+
+        loop {
+            match self.peek() {
+                Some("layout") => {
+                    self.position += 1;
+                    self.parse_layout_qualifier(&mut qualifiers)?;
+                },
+                Some(storage @ ("in" | "out" | "inout" | "attribute" | "varying" | "uniform" |
+                                "buffer")) =>
+                {
+                    self.position += 1;
+                    qualifiers.storage = Some(storage);
+                },
+                Some(qualifier) if IGNORED_QUALIFIERS.contains(&qualifier) => {
+                    self.position += 1;
+                },
+                _ => return Ok(qualifiers),
+            }
+        }
+    }
+
+    fn parse_layout_qualifier(&mut self, qualifiers: &mut Qualifiers<'a>)
+                              -> Result<(), CodegenError>
+    {
+        self.expect("(")?;
+
+        loop {
+            let name = self.identifier()?;
+            let value = if self.peek() == Some("=") {
+                self.position += 1;
+                Some(self.next()?)
+            } else {
+                None
+            };
+
+            match name {
+                "std140" => qualifiers.packing = Some(Packing::Std140),
+                "std430" => qualifiers.packing = Some(Packing::Std430),
+                "shared" | "packed" => {
+                    return Err(self.error(&format!("the `{}` layout is not supported", name)));
+                },
+                "row_major" => return Err(self.error("row-major matrices are not supported")),
+                "location" => {
+                    let value = value.and_then(parse_integer)
+                                     .ok_or_else(|| self.error("invalid location"))?;
+                    qualifiers.location = Some(value as i32);
+                },
+                _ => (),
+            }
+
+            match self.next()? {
+                "," => continue,
+                ")" => return Ok(()),
+                token => {
+                    self.position -= 1;
+                    return Err(self.error(&format!("expected `,` or `)`, found `{}`", token)));
+                },
+            }
+        }
+    }
+
+    /// Skips a declaration or a function definition.
+    fn skip_declaration(&mut self) -> Result<(), CodegenError> {
+        let mut depth = 0usize;
+
+        loop {
+            match self.next()? {
+                ";" if depth == 0 => return Ok(()),
+                "(" | "[" => depth += 1,
+                ")" | "]" => depth = depth.saturating_sub(1),
+                "{" if depth == 0 => return self.skip_braces(),
+                _ => (),
+            }
+        }
+    }
+
+    fn skip_braces(&mut self) -> Result<(), CodegenError> {
+        let mut depth = 1;
+
+        while depth != 0 {
+            match self.next()? {
+                "{" => depth += 1,
+                "}" => depth -= 1,
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse_struct_definition(&mut self) -> Result<(), CodegenError> {
+        self.expect("struct")?;
+        let name = self.identifier()?;
+        self.expect("{")?;
+        let members = self.parse_members()?;
+        self.structs.insert(name.to_owned(), BlockLayout::Struct { members });
+
+        // the definition can also declare variables
+        self.skip_declaration()
+    }
+
+    fn parse_block(&mut self, packing: Packing) -> Result<(), CodegenError> {
+        let name = self.identifier()?;
+        self.expect("{")?;
+        let members = self.parse_members()?;
+
+        // instance name
+        if self.peek() != Some(";") {
+            self.identifier()?;
+            if self.peek() == Some("[") {
+                return Err(self.error("arrays of blocks are not supported"));
+            }
+        }
+        self.expect(";")?;
+
+        self.interface.blocks.push(BlockInput {
+            name: name.to_owned(),
+            layout: place(&BlockLayout::Struct { members }, 0, packing),
+            packing,
+        });
+
+        Ok(())
+    }
+
+    /// Parses the members of a struct or of a block, after the opening brace.
+    fn parse_members(&mut self) -> Result<Vec<(String, BlockLayout)>, CodegenError> {
+        let mut members = Vec::new();
+
+        while self.peek() != Some("}") {
+            let qualifiers = self.parse_qualifiers()?;
+            if qualifiers.storage.is_some() {
+                return Err(self.error("unexpected storage qualifier"));
+            }
+
+            let ty = self.parse_member_type()?;
+
+            loop {
+                let name = self.identifier()?;
+                let layout = self.parse_array(ty.clone())?;
+                members.push((name.to_owned(), layout));
+
+                match self.next()? {
+                    "," => continue,
+                    ";" => break,
+                    token => {
+                        self.position -= 1;
+                        return Err(self.error(&format!("expected `,` or `;`, found `{}`", token)));
+                    },
+                }
+            }
+        }
+
+        self.expect("}")?;
+        Ok(members)
+    }
+
+    fn parse_member_type(&mut self) -> Result<BlockLayout, CodegenError> {
+        let name = self.identifier()?;
+
+        let layout = if let Some(ty) = uniform_type(name) {
+            BlockLayout::BasicType { ty, offset_in_buffer: 0 }
+        } else if let Some(layout) = self.structs.get(name) {
+            layout.clone()
+        } else {
+            self.position -= 1;
+            return Err(self.error(&format!("unknown type `{}`", name)));
+        };
+
+        self.parse_array(layout)
+    }
+
+    /// Parses the optional array specifier after a type or a name.
+    fn parse_array(&mut self, layout: BlockLayout) -> Result<BlockLayout, CodegenError> {
+        if self.peek() != Some("[") {
+            return Ok(layout);
+        }
+        self.position += 1;
+
+        let layout = if self.peek() == Some("]") {
+            BlockLayout::DynamicSizedArray { content: Box::new(layout) }
+        } else {
+            let length = self.next().ok().and_then(parse_integer);
+            let length = length.ok_or_else(|| self.error("array sizes must be integer literals"))?;
+            BlockLayout::Array { content: Box::new(layout), length: length as usize }
+        };
+        self.expect("]")?;
+
+        if self.peek() == Some("[") {
+            return Err(self.error("arrays of arrays are not supported"));
+        }
+
+        Ok(layout)
+    }
+
+    fn parse_inputs(&mut self, location: Option<i32>) -> Result<(), CodegenError> {
+        let type_name = self.identifier()?;
+        let ty = match attribute_type(type_name) {
+            Some(ty) => ty,
+            None => {
+                self.position -= 1;
+                return Err(self.error(&format!("unsupported input type `{}`", type_name)));
+            },
+        };
+
+        loop {
+            let name = self.identifier()?;
+            let size = match self.parse_array(BlockLayout::Struct { members: Vec::new() })? {
+                BlockLayout::Array { length, .. } => length,
+                BlockLayout::DynamicSizedArray { .. } => {
+                    return Err(self.error("inputs can't be arrays of unknown size"));
+                },
+                _ => 1,
+            };
+
+            self.interface.attributes.push(AttributeInput {
+                name: name.to_owned(),
+                ty,
+                size,
+                location,
+            });
+
+            match self.next()? {
+                "," => continue,
+                ";" => return Ok(()),
+                token => {
+                    self.position -= 1;
+                    return Err(self.error(&format!("expected `,` or `;`, found `{}`", token)));
+                },
+            }
+        }
+    }
+}
+
+fn parse_integer(text: &str) -> Option<u32> {
+    let text = text.trim_end_matches(['u', 'U']);
+    if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        u32::from_str_radix(hex, 16).ok()
+    } else {
+        text.parse().ok()
+    }
+}
+
+/// Returns the type of a member of a block.
+fn uniform_type(name: &str) -> Option<UniformType> {
+    use crate::uniforms::UniformType::*;
+
+    Some(match name {
+        "float" => Float,
+        "vec2" => FloatVec2,
+        "vec3" => FloatVec3,
+        "vec4" => FloatVec4,
+        "double" => Double,
+        "dvec2" => DoubleVec2,
+        "dvec3" => DoubleVec3,
+        "dvec4" => DoubleVec4,
+        "int" => Int,
+        "ivec2" => IntVec2,
+        "ivec3" => IntVec3,
+        "ivec4" => IntVec4,
+        "uint" => UnsignedInt,
+        "uvec2" => UnsignedIntVec2,
+        "uvec3" => UnsignedIntVec3,
+        "uvec4" => UnsignedIntVec4,
+        "int64_t" => Int64,
+        "i64vec2" => Int64Vec2,
+        "i64vec3" => Int64Vec3,
+        "i64vec4" => Int64Vec4,
+        "uint64_t" => UnsignedInt64,
+        "u64vec2" => UnsignedInt64Vec2,
+        "u64vec3" => UnsignedInt64Vec3,
+        "u64vec4" => UnsignedInt64Vec4,
+        "bool" => Bool,
+        "bvec2" => BoolVec2,
+        "bvec3" => BoolVec3,
+        "bvec4" => BoolVec4,
+        "mat2" | "mat2x2" => FloatMat2,
+        "mat3" | "mat3x3" => FloatMat3,
+        "mat4" | "mat4x4" => FloatMat4,
+        "mat2x3" => FloatMat2x3,
+        "mat2x4" => FloatMat2x4,
+        "mat3x2" => FloatMat3x2,
+        "mat3x4" => FloatMat3x4,
+        "mat4x2" => FloatMat4x2,
+        "mat4x3" => FloatMat4x3,
+        "dmat2" | "dmat2x2" => DoubleMat2,
+        "dmat3" | "dmat3x3" => DoubleMat3,
+        "dmat4" | "dmat4x4" => DoubleMat4,
+        "dmat2x3" => DoubleMat2x3,
+        "dmat2x4" => DoubleMat2x4,
+        "dmat3x2" => DoubleMat3x2,
+        "dmat3x4" => DoubleMat3x4,
+        "dmat4x2" => DoubleMat4x2,
+        "dmat4x3" => DoubleMat4x3,
+        _ => return None,
+    })
+}
+
+/// Returns the type of an input of a vertex shader.
+fn attribute_type(name: &str) -> Option<AttributeType> {
+    use crate::vertex::AttributeType::*;
+
+    Some(match name {
+        "float" => F32,
+        "vec2" => F32F32,
+        "vec3" => F32F32F32,
+        "vec4" => F32F32F32F32,
+        "double" => F64,
+        "dvec2" => F64F64,
+        "dvec3" => F64F64F64,
+        "dvec4" => F64F64F64F64,
+        "int" => I32,
+        "ivec2" => I32I32,
+        "ivec3" => I32I32I32,
+        "ivec4" => I32I32I32I32,
+        "uint" => U32,
+        "uvec2" => U32U32,
+        "uvec3" => U32U32U32,
+        "uvec4" => U32U32U32U32,
+        "int64_t" => I64,
+        "i64vec2" => I64I64,
+        "i64vec3" => I64I64I64,
+        "i64vec4" => I64I64I64I64,
+        "uint64_t" => U64,
+        "u64vec2" => U64U64,
+        "u64vec3" => U64U64U64,
+        "u64vec4" => U64U64U64U64,
+        "mat2" | "mat2x2" => F32x2x2,
+        "mat3" | "mat3x3" => F32x3x3,
+        "mat4" | "mat4x4" => F32x4x4,
+        "mat2x3" => F32x2x3,
+        "mat2x4" => F32x2x4,
+        "mat3x2" => F32x3x2,
+        "mat3x4" => F32x3x4,
+        "mat4x2" => F32x4x2,
+        "mat4x3" => F32x4x3,
+        "dmat2" | "dmat2x2" => F64x2x2,
+        "dmat3" | "dmat3x3" => F64x3x3,
+        "dmat4" | "dmat4x4" => F64x4x4,
+        "dmat2x3" => F64x2x3,
+        "dmat2x4" => F64x2x4,
+        "dmat3x2" => F64x3x2,
+        "dmat3x4" => F64x3x4,
+        "dmat4x2" => F64x4x2,
+        "dmat4x3" => F64x4x3,
+        _ => return None,
+    })
+}
@@ -3,6 +3,7 @@ use crate::gl;
 use crate::context::CommandContext;
 use crate::backend::Facade;
 
+use std::error::Error;
 use std::fmt;
 use std::collections::hash_map::{self, HashMap};
 use std::os::raw;
@@ -15,6 +16,7 @@ use crate::GlObject;
 use crate::ProgramExt;
 use crate::Handle;
 use crate::RawUniformValue;
+use crate::DrawError;
 
 use crate::program::{COMPILER_GLOBAL_LOCK, ProgramCreationError, Binary, GetBinaryError, SpirvEntryPoint};
 
@@ -30,6 +32,9 @@ use crate::uniforms::Uniforms;
 /// A combination of compute shaders linked together.
 pub struct ComputeShader {
     raw: RawProgram,
+
+    // local size declared by the shader, or `None` if it uses a variable local size
+    work_group_size: Option<(u32, u32, u32)>,
 }
 
 impl ComputeShader {
@@ -48,9 +53,8 @@ impl ComputeShader {
 
         let shader = build_shader(facade, gl::COMPUTE_SHADER, src)?;
 
-        Ok(ComputeShader {
-            raw: RawProgram::from_shaders(facade, &[shader], false, false, false, None)?
-        })
+        let raw = RawProgram::from_shaders(facade, &[shader], false, false, false, None, false)?;
+        Ok(ComputeShader::from_raw(raw))
     }
 
     /// Builds a new compute shader from SPIR-V module.
@@ -62,9 +66,8 @@ impl ComputeShader {
 
         let shader = build_spirv_shader(facade, gl::COMPUTE_SHADER, spirv)?;
 
-        Ok(ComputeShader {
-            raw: RawProgram::from_shaders(facade, &[shader], false, false, false, None)?
-        })
+        let raw = RawProgram::from_shaders(facade, &[shader], false, false, false, None, false)?;
+        Ok(ComputeShader::from_raw(raw))
     }
 
     /// Builds a new compute shader from some binary.
@@ -74,9 +77,8 @@ impl ComputeShader {
     {
         let _lock = COMPILER_GLOBAL_LOCK.lock();
 
-        Ok(ComputeShader {
-            raw: RawProgram::from_binary(facade, data)?
-        })
+        let raw = RawProgram::from_binary(facade, data)?;
+        Ok(ComputeShader::from_raw(raw))
     }
 
     /// Executes the compute shader.
@@ -84,11 +86,95 @@ impl ComputeShader {
     /// `x * y * z` work groups will be started. The current work group can be retrieved with
     /// `gl_WorkGroupID`. Inside each work group, additional local work groups can be started
     /// depending on the attributes of the compute shader itself.
+    ///
+    /// The buffers and textures that are bound as shader storage blocks, atomic counters or
+    /// writable image units are recorded as written by this dispatch. glium then automatically
+    /// calls `glMemoryBarrier` with the appropriate bit before the next command that accesses
+    /// one of them, whether it is a draw, another dispatch, an upload or a read. See
+    /// `Context::memory_barrier` for the situations where this isn't enough.
     #[inline]
     pub fn execute<U>(&self, uniforms: U, x: u32, y: u32, z: u32) where U: Uniforms {
         unsafe { self.raw.dispatch_compute(uniforms, x, y, z) }.unwrap();       // FIXME: return error
     }
 
+    /// Executes the compute shader with enough work groups to cover at least
+    /// `width * height * depth` invocations.
+    ///
+    /// The number of work groups in each dimension is obtained by dividing the number of
+    /// invocations by the local size of the shader, rounding up. The shader should therefore
+    /// check `gl_GlobalInvocationID` against the actual dimensions of the data it processes.
+    ///
+    /// Returns an error if the shader uses a variable local size, or if the number of work
+    /// groups exceeds the limits of the backend.
+    pub fn execute_for_invocations<U>(&self, uniforms: U, width: u32, height: u32, depth: u32)
+                                      -> Result<(), ComputeDispatchError> where U: Uniforms
+    {
+        let (local_x, local_y, local_z) = match self.work_group_size {
+            Some(size) => size,
+            None => return Err(ComputeDispatchError::VariableWorkGroupSize),
+        };
+
+        let groups = (width.div_ceil(local_x), height.div_ceil(local_y),
+                      depth.div_ceil(local_z));
+        self.check_work_group_count(groups)?;
+
+        unsafe { self.raw.dispatch_compute(uniforms, groups.0, groups.1, groups.2) }
+            .map_err(ComputeDispatchError::DrawError)
+    }
+
+    /// Executes a compute shader that uses a variable local size
+    /// (`layout(local_size_variable) in;`).
+    ///
+    /// `groups` is the number of work groups to start and `local_size` is the local size of each
+    /// work group. This requires the `GL_ARB_compute_variable_group_size` extension, whose
+    /// limits can be found in `max_compute_variable_group_size` and
+    /// `max_compute_variable_group_invocations` of the capabilities.
+    pub fn execute_with_group_size<U>(&self, uniforms: U, groups: (u32, u32, u32),
+                                      local_size: (u32, u32, u32))
+                                      -> Result<(), ComputeDispatchError> where U: Uniforms
+    {
+        if self.work_group_size.is_some() {
+            return Err(ComputeDispatchError::FixedWorkGroupSize);
+        }
+
+        let caps = self.raw.get_context().get_capabilities();
+
+        let (max_size, max_invocations) = match (caps.max_compute_variable_group_size,
+                                                 caps.max_compute_variable_group_invocations)
+        {
+            (Some(size), Some(invocations)) => (size, invocations),
+            _ => return Err(ComputeDispatchError::VariableWorkGroupSizeNotSupported),
+        };
+
+        let invocations = local_size.0 as u64 * local_size.1 as u64 * local_size.2 as u64;
+        if local_size.0 == 0 || local_size.1 == 0 || local_size.2 == 0 ||
+           local_size.0 > max_size.0 as u32 || local_size.1 > max_size.1 as u32 ||
+           local_size.2 > max_size.2 as u32 || invocations > max_invocations as u64
+        {
+            return Err(ComputeDispatchError::WorkGroupSizeTooLarge {
+                requested: local_size,
+                maximum: (max_size.0 as u32, max_size.1 as u32, max_size.2 as u32),
+                max_invocations: max_invocations as u32,
+            });
+        }
+
+        self.check_work_group_count(groups)?;
+
+        unsafe {
+            self.raw.dispatch_compute_group_size(uniforms, groups.0, groups.1, groups.2,
+                                                 local_size.0, local_size.1, local_size.2)
+        }.map_err(ComputeDispatchError::DrawError)
+    }
+
+    /// Returns the local size of the work groups declared by the shader with
+    /// `layout(local_size_x = ..., local_size_y = ..., local_size_z = ...) in;`.
+    ///
+    /// Returns `None` if the shader uses a variable local size.
+    #[inline]
+    pub fn get_work_group_size(&self) -> Option<(u32, u32, u32)> {
+        self.work_group_size
+    }
+
     /// Executes the compute shader.
     ///
     /// This is similar to `execute`, except that the parameters are stored in a buffer.
@@ -165,6 +251,33 @@ impl ComputeShader {
     }
 }
 
+impl ComputeShader {
+    fn from_raw(raw: RawProgram) -> ComputeShader {
+        let work_group_size = unsafe { raw.get_compute_work_group_size() };
+
+        ComputeShader {
+            raw,
+            work_group_size,
+        }
+    }
+
+    fn check_work_group_count(&self, groups: (u32, u32, u32))
+                              -> Result<(), ComputeDispatchError>
+    {
+        let max = self.raw.get_context().get_capabilities().max_compute_work_group_count;
+        let max = (max.0 as u32, max.1 as u32, max.2 as u32);
+
+        if groups.0 > max.0 || groups.1 > max.1 || groups.2 > max.2 {
+            return Err(ComputeDispatchError::WorkGroupCountTooLarge {
+                requested: groups,
+                maximum: max,
+            });
+        }
+
+        Ok(())
+    }
+}
+
 impl fmt::Debug for ComputeShader {
     #[inline]
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
@@ -212,9 +325,9 @@ impl ProgramExt for ComputeShader {
     #[inline]
     fn set_subroutine_uniforms_for_stage(&self, ctxt: &mut CommandContext<'_>,
                                          stage: ShaderStage,
-                                         indices: &[gl::types::GLuint])
+                                         indices: &[gl::types::GLuint]) -> Result<(), DrawError>
     {
-        self.raw.set_subroutine_uniforms_for_stage(ctxt, stage, indices);
+        self.raw.set_subroutine_uniforms_for_stage(ctxt, stage, indices)
     }
 
     #[inline]
@@ -258,3 +371,70 @@ pub struct ComputeCommand {
 }
 
 implement_uniform_block!(ComputeCommand, num_groups_x, num_groups_y, num_groups_z);
+
+/// Error that can happen when executing a compute shader.
+#[derive(Clone, Debug)]
+pub enum ComputeDispatchError {
+    /// The number of work groups exceeds `max_compute_work_group_count`.
+    WorkGroupCountTooLarge {
+        /// Number of work groups that was requested.
+        requested: (u32, u32, u32),
+        /// Maximum number of work groups supported by the backend.
+        maximum: (u32, u32, u32),
+    },
+
+    /// The requested local size exceeds the limits of the backend, or one of its
+    /// dimensions is zero.
+    WorkGroupSizeTooLarge {
+        /// Local size that was requested.
+        requested: (u32, u32, u32),
+        /// Maximum local size in each dimension.
+        maximum: (u32, u32, u32),
+        /// Maximum number of invocations in a single work group.
+        max_invocations: u32,
+    },
+
+    /// The shader uses a variable local size, and the local size must be passed explicitly.
+    VariableWorkGroupSize,
+
+    /// The shader declares a fixed local size, which can't be overridden.
+    FixedWorkGroupSize,
+
+    /// `GL_ARB_compute_variable_group_size` is not supported by the backend.
+    VariableWorkGroupSizeNotSupported,
+
+    /// Error while binding the uniforms.
+    DrawError(DrawError),
+}
+
+impl fmt::Display for ComputeDispatchError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        use self::ComputeDispatchError::*;
+        match self {
+            WorkGroupCountTooLarge { requested, maximum } =>
+                write!(fmt, "The number of work groups {:?} exceeds the maximum supported {:?}",
+                       requested, maximum),
+            WorkGroupSizeTooLarge { requested, maximum, max_invocations } =>
+                write!(fmt, "The local size {:?} exceeds the maximum supported {:?} \
+                             or {} invocations", requested, maximum, max_invocations),
+            VariableWorkGroupSize =>
+                fmt.write_str("The compute shader uses a variable local size"),
+            FixedWorkGroupSize =>
+                fmt.write_str("The compute shader declares a fixed local size"),
+            VariableWorkGroupSizeNotSupported =>
+                fmt.write_str("Variable local sizes are not supported by the backend"),
+            DrawError(err) =>
+                write!(fmt, "Error while binding the uniforms: {}", err),
+        }
+    }
+}
+
+impl Error for ComputeDispatchError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ComputeDispatchError::DrawError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use std::fmt;
+
+use crate::program::ShaderType;
+
+/// Severity of a `ShaderDiagnostic`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Informative message.
+    Info,
+    /// Warning that doesn't prevent the shader from compiling.
+    Warning,
+    /// Error that makes the compilation or the linking fail.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(match *self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// A message of the info log of the GLSL compiler or linker.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShaderDiagnostic {
+    /// Severity of the message.
+    pub severity: Severity,
+
+    /// The shader stage that produced the message, or `None` for the messages of the linker.
+    pub stage: Option<ShaderType>,
+
+    /// The index of the source string, usually `0`, or the name of the file if the log was
+    /// rewritten with a `SourceMap`.
+    pub file: Option<String>,
+
+    /// Line of the message, starting at 1.
+    pub line: Option<u32>,
+
+    /// Column of the message, if the implementation reports it.
+    pub column: Option<u32>,
+
+    /// The message itself, including the error code of the implementation if any.
+    pub message: String,
+}
+
+impl ShaderDiagnostic {
+    /// Parses the info log of a shader or of a program.
+    ///
+    /// The `stage` of the diagnostics is left to `None`.
+    ///
+    /// The formats of Mesa (`0:12(5): error: ...`), NVIDIA (`0(12) : error C1008: ...`), and
+    /// AMD, ANGLE, Apple and Intel (`ERROR: 0:12: ...`) are recognized. The lines that don't
+    /// have a location are kept as diagnostics without a location, except for the lines that
+    /// only continue the previous message.
+    pub fn parse_log(log: &str) -> Vec<ShaderDiagnostic> {
+        let mut diagnostics: Vec<ShaderDiagnostic> = Vec::new();
+
+        for line in log.lines() {
+            let line = line.trim_end().trim_start_matches('\0');
+            if line.trim().is_empty() || line.trim().chars().all(|c| c == '-') {
+                continue;
+            }
+
+            // summaries such as `ERROR: 1 compilation errors.  No code generated.`
+            if line.contains("No code generated") {
+                continue;
+            }
+
+            match parse_line(line) {
+                Some(diagnostic) => diagnostics.push(diagnostic),
+                None => match diagnostics.last_mut() {
+                    Some(last) if line.starts_with(char::is_whitespace) => {
+                        last.message.push('\n');
+                        last.message.push_str(line.trim());
+                    },
+                    _ => diagnostics.push(ShaderDiagnostic {
+                        severity: Severity::Info,
+                        stage: None,
+                        file: None,
+                        line: None,
+                        column: None,
+                        message: line.trim().to_owned(),
+                    }),
+                },
+            }
+        }
+
+        diagnostics
+    }
+
+    /// Returns true if the severity is `Error`.
+    #[inline]
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for ShaderDiagnostic {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ref file) = self.file {
+            write!(fmt, "{}:", file)?;
+        }
+        if let Some(line) = self.line {
+            write!(fmt, "{}:", line)?;
+        }
+        if let Some(column) = self.column {
+            write!(fmt, "{}:", column)?;
+        }
+        if self.file.is_some() || self.line.is_some() {
+            fmt.write_str(" ")?;
+        }
+        write!(fmt, "{}: {}", self.severity, self.message)
+    }
+}
+
+/// Parses a line that has a severity or a location. Returns `None` otherwise.
+fn parse_line(line: &str) -> Option<ShaderDiagnostic> {
+    // `ERROR: 0:12: message`
+    let (mut severity, rest) = if let Some(rest) = line.strip_prefix("ERROR:") {
+        (Some(Severity::Error), rest.trim_start())
+    } else if let Some(rest) = line.strip_prefix("WARNING:") {
+        (Some(Severity::Warning), rest.trim_start())
+    } else if let Some(rest) = line.strip_prefix("INFO:") {
+        (Some(Severity::Info), rest.trim_start())
+    } else {
+        (None, line.trim_start())
+    };
+
+    let (file, line_number, column, rest) = match parse_location(rest) {
+        Some((file, line, column, rest)) => (Some(file.to_owned()), Some(line), column, rest),
+        None => (None, None, None, rest),
+    };
+
+    // `error: message` or `error C1008: message`
+    let mut message = rest.trim_start();
+    for (prefix, prefix_severity) in [("error", Severity::Error), ("warning", Severity::Warning),
+                                      ("info", Severity::Info)]
+    {
+        let matches = message.get(.. prefix.len())
+                             .is_some_and(|p| p.eq_ignore_ascii_case(prefix));
+        if !matches {
+            continue;
+        }
+
+        let after = &message[prefix.len() ..];
+        if let Some(after) = after.strip_prefix(':') {
+            message = after.trim_start();
+        } else if is_error_code(after) {
+            // error code such as `C1008`, which is kept in the message
+            message = after.trim_start();
+        } else {
+            continue;
+        }
+
+        severity = severity.or(Some(prefix_severity));
+        break;
+    }
+
+    if severity.is_none() && line_number.is_none() {
+        return None;
+    }
+
+    Some(ShaderDiagnostic {
+        // a message with a location but without severity is most likely an error
+        severity: severity.unwrap_or(Severity::Error),
+        stage: None,
+        file,
+        line: line_number,
+        column,
+        message: message.to_owned(),
+    })
+}
+
+/// Parses `file:line(column):`, `file:line:` or `file(line) :` at the start of `text`.
+///
+/// Returns the file, the line, the column and the rest of the text.
+fn parse_location(text: &str) -> Option<(&str, u32, Option<u32>, &str)> {
+    let separator = text.find([':', '('])?;
+    let file = &text[.. separator];
+    if file.is_empty() || file.contains(char::is_whitespace) {
+        return None;
+    }
+
+    let after_file = &text[separator + 1 ..];
+    let (line, rest) = split_number(after_file)?;
+
+    let (column, rest) = if text[separator ..].starts_with('(') {
+        // `file(line)`
+        (None, rest.strip_prefix(')')?)
+    } else if let Some(after) = rest.strip_prefix('(') {
+        // `file:line(column)`
+        let (column, rest) = split_number(after)?;
+        (Some(column), rest.strip_prefix(')')?)
+    } else {
+        (None, rest)
+    };
+
+    let rest = rest.trim_start().strip_prefix(':')?;
+    Some((file, line, column, rest))
+}
+
+/// Returns true if `text` starts with a space followed by something like `C1008:`.
+fn is_error_code(text: &str) -> bool {
+    if !text.starts_with(' ') {
+        return false;
+    }
+
+    let word = text.trim_start().split(char::is_whitespace).next().unwrap_or("");
+    match word.strip_suffix(':') {
+        Some(code) => !code.is_empty() && code.chars().all(|c| c.is_ascii_alphanumeric()),
+        None => false,
+    }
+}
+
+fn split_number(text: &str) -> Option<(u32, &str)> {
+    let end = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    let number = text[.. end].parse().ok()?;
+    Some((number, &text[end ..]))
+}
@@ -4,21 +4,39 @@ use std::fmt;
 use std::error::Error;
 use std::sync::Mutex;
 use crate::CapabilitiesSource;
+use crate::ContextExt;
+use crate::backend::Facade;
 
 use crate::gl;
 use crate::version::Api;
 use crate::version::Version;
 
-pub use self::compute::{ComputeShader, ComputeCommand};
+pub use self::cache::ProgramCache;
+pub use self::compute::{ComputeShader, ComputeCommand, ComputeDispatchError};
+pub use self::diagnostics::{ShaderDiagnostic, Severity};
+pub use self::pending::PendingProgram;
+pub use self::preprocessor::{Preprocessor, PreprocessedSource, PreprocessorError, SourceMap};
+pub use self::preprocessor::{IncludeProvider, FileSystemProvider};
+pub use self::pipeline::{ProgramPipeline, PipelineStages, PipelineCreationError};
+pub(crate) use self::pipeline::DrawProgram;
 pub use self::program::Program;
+pub use self::reloadable::{ReloadableProgram, ReloadError, ShaderFiles};
 pub use self::reflection::{Uniform, UniformBlock, BlockLayout, OutputPrimitives};
 pub use self::reflection::{Attribute, TransformFeedbackVarying, TransformFeedbackBuffer, TransformFeedbackMode};
 pub use self::reflection::{ShaderStage, SubroutineData, SubroutineUniform};
 
+pub mod codegen;
+
+mod cache;
 mod compute;
+mod diagnostics;
+mod pending;
+mod pipeline;
+mod preprocessor;
 mod program;
 mod raw;
 mod reflection;
+mod reloadable;
 mod shader;
 mod uniforms_storage;
 mod binary_header;
@@ -42,6 +60,44 @@ pub fn is_binary_supported<C: ?Sized>(ctxt: &C) -> bool where C: CapabilitiesSou
         || ctxt.get_extensions().gl_arb_get_programy_binary
 }
 
+/// Returns true if the backend supports separable programs and program pipelines.
+#[inline]
+pub fn is_separable_program_supported<C: ?Sized>(ctxt: &C) -> bool where C: CapabilitiesSource {
+    ctxt.get_version() >= &Version(Api::Gl, 4, 1) || ctxt.get_version() >= &Version(Api::GlEs, 3, 1)
+        || ctxt.get_extensions().gl_arb_separate_shader_objects
+}
+
+/// Returns true if the backend can compile and link the programs of `Program::new_async` in
+/// background threads.
+#[inline]
+pub fn is_parallel_compilation_supported<C: ?Sized>(ctxt: &C) -> bool where C: CapabilitiesSource {
+    ctxt.get_extensions().gl_khr_parallel_shader_compile
+        || ctxt.get_extensions().gl_arb_parallel_shader_compile
+}
+
+/// Sets the maximum number of background threads that the driver uses to compile shaders.
+///
+/// `0` disables the background compilation, and `u32::MAX` restores the default of the
+/// implementation. Returns false if the backend doesn't support
+/// `GL_KHR_parallel_shader_compile`.
+pub fn set_max_shader_compiler_threads<F: ?Sized>(facade: &F, count: u32) -> bool
+                                                  where F: Facade
+{
+    let ctxt = facade.get_context().make_current();
+
+    unsafe {
+        if ctxt.extensions.gl_khr_parallel_shader_compile {
+            ctxt.gl.MaxShaderCompilerThreadsKHR(count);
+        } else if ctxt.extensions.gl_arb_parallel_shader_compile {
+            ctxt.gl.MaxShaderCompilerThreadsARB(count);
+        } else {
+            return false;
+        }
+    }
+
+    true
+}
+
 /// Returns true if the backend supports shader subroutines.
 #[inline]
 pub fn is_subroutine_supported<C: ?Sized>(ctxt: &C) -> bool where C: CapabilitiesSource {
@@ -132,6 +188,10 @@ pub enum ProgramCreationError {
 
     /// The glium-specific binary header was not found or is corrupt.
     BinaryHeaderError,
+
+    /// You have requested a separable program, but separable programs are not supported by the
+    /// backend.
+    SeparableProgramsNotSupported,
 }
 
 impl fmt::Display for ProgramCreationError {
@@ -160,6 +220,8 @@ impl fmt::Display for ProgramCreationError {
                 "Point size is not supported by the backend.",
             BinaryHeaderError =>
                 "The glium-specific binary header was not found or is corrupt.",
+            SeparableProgramsNotSupported =>
+                "Separable programs are not supported by the backend.",
         };
         match *self {
             CompilationError(ref s, _) =>
@@ -174,6 +236,27 @@ impl fmt::Display for ProgramCreationError {
 
 impl Error for ProgramCreationError {}
 
+impl ProgramCreationError {
+    /// Parses the info log of a `CompilationError` or of a `LinkingError` into a list of
+    /// diagnostics. Returns an empty list for the other errors.
+    ///
+    /// The log usually contains the warnings alongside the errors. Use
+    /// `ShaderDiagnostic::is_error` to tell them apart.
+    pub fn diagnostics(&self) -> Vec<ShaderDiagnostic> {
+        match *self {
+            ProgramCreationError::CompilationError(ref log, ty) => {
+                let mut diagnostics = ShaderDiagnostic::parse_log(log);
+                for diagnostic in &mut diagnostics {
+                    diagnostic.stage = Some(ty);
+                }
+                diagnostics
+            },
+            ProgramCreationError::LinkingError(ref log) => ShaderDiagnostic::parse_log(log),
+            _ => Vec::new(),
+        }
+    }
+}
+
 /// Error type that is returned by the `program!` macro.
 #[derive(Clone, Debug)]
 pub enum ProgramChooserCreationError {
@@ -0,0 +1,150 @@
+use std::fmt;
+
+use crate::backend::Facade;
+use crate::version::{Api, Version};
+use crate::CapabilitiesSource;
+use crate::GlObject;
+
+use crate::program::{COMPILER_GLOBAL_LOCK, Program, ProgramCreationError, ProgramCreationInput};
+use crate::program::ShaderType;
+use crate::program::raw::{LinkingProgram, RawProgram};
+use crate::program::shader::{Shader, start_shader_compilation};
+
+/// A program that the driver is compiling and linking in the background.
+///
+/// Built with `Program::new_async`. Poll `is_ready` every frame, then call `finish` to retrieve
+/// the program or its compilation error. Dropping a `PendingProgram` cancels the creation of the
+/// program.
+pub struct PendingProgram {
+    state: State,
+}
+
+enum State {
+    Linking {
+        // kept alive to retrieve their compilation errors if linking fails
+        shaders: Vec<(Shader, ShaderType)>,
+        program: LinkingProgram,
+        outputs_srgb: bool,
+        uses_point_size: bool,
+    },
+    Ready(Box<Program>),
+}
+
+impl PendingProgram {
+    pub(crate) fn new<F: ?Sized>(facade: &F, input: ProgramCreationInput<'_>)
+                                 -> Result<PendingProgram, ProgramCreationError> where F: Facade
+    {
+        let (vertex_shader, tessellation_control_shader, tessellation_evaluation_shader,
+             geometry_shader, fragment_shader, transform_feedback_varyings, outputs_srgb,
+             uses_point_size) = match input
+        {
+            ProgramCreationInput::SourceCode { vertex_shader, tessellation_control_shader,
+                                               tessellation_evaluation_shader, geometry_shader,
+                                               fragment_shader, transform_feedback_varyings,
+                                               outputs_srgb, uses_point_size } =>
+            {
+                (vertex_shader, tessellation_control_shader, tessellation_evaluation_shader,
+                 geometry_shader, fragment_shader, transform_feedback_varyings, outputs_srgb,
+                 uses_point_size)
+            },
+
+            // there is nothing to compile
+            input => {
+                let program = Program::new(facade, input)?;
+                return Ok(PendingProgram { state: State::Ready(Box::new(program)) });
+            },
+        };
+
+        let mut sources = vec![
+            (vertex_shader, ShaderType::Vertex),
+            (fragment_shader, ShaderType::Fragment)
+        ];
+
+        if let Some(gs) = geometry_shader {
+            sources.push((gs, ShaderType::Geometry));
+        }
+
+        if let Some(ts) = tessellation_control_shader {
+            sources.push((ts, ShaderType::TesselationControl));
+        }
+
+        if let Some(ts) = tessellation_evaluation_shader {
+            sources.push((ts, ShaderType::TesselationEvaluation));
+        }
+
+        if transform_feedback_varyings.is_some() &&
+            !(facade.get_context().get_version() >= &Version(Api::Gl, 3, 0)) &&
+            !facade.get_context().get_extensions().gl_ext_transform_feedback
+        {
+            return Err(ProgramCreationError::TransformFeedbackNotSupported);
+        }
+
+        if uses_point_size && (facade.get_context().get_version().0 == Api::Gl) && !(facade.get_context().get_version() >= &Version(Api::Gl, 2, 0)) {
+            return Err(ProgramCreationError::PointSizeNotSupported);
+        }
+
+        let _lock = COMPILER_GLOBAL_LOCK.lock();
+
+        let shaders = {
+            let mut shaders = Vec::with_capacity(sources.len());
+            for (src, ty) in sources.into_iter() {
+                shaders.push((start_shader_compilation(facade, ty.to_opengl_type(), src)?, ty));
+            }
+            shaders
+        };
+
+        // the driver waits for the shaders to be compiled before linking them
+        let program = RawProgram::start_linking(facade, shaders.iter().map(|(s, _)| s),
+                                                geometry_shader.is_some(),
+                                                tessellation_control_shader.is_some(),
+                                                tessellation_evaluation_shader.is_some(),
+                                                transform_feedback_varyings, false);
+
+        Ok(PendingProgram {
+            state: State::Linking { shaders, program, outputs_srgb, uses_point_size },
+        })
+    }
+
+    /// Returns true if the program has been compiled and linked, in which case `finish` doesn't
+    /// block.
+    ///
+    /// Always returns true if the backend doesn't support `GL_KHR_parallel_shader_compile`.
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        match self.state {
+            State::Linking { ref program, .. } => program.is_complete(),
+            State::Ready(_) => true,
+        }
+    }
+
+    /// Returns the program, or the error that happened while compiling or linking it.
+    ///
+    /// Blocks until the driver has finished if `is_ready` returns false.
+    pub fn finish(self) -> Result<Program, ProgramCreationError> {
+        match self.state {
+            State::Linking { shaders, program, outputs_srgb, uses_point_size } => {
+                let _lock = COMPILER_GLOBAL_LOCK.lock();
+
+                // a compilation error also makes the linking fail, but is more helpful
+                for (shader, ty) in shaders.iter() {
+                    shader.check_compilation(ty.to_opengl_type())?;
+                }
+
+                let raw = program.finish()?;
+                Ok(Program::from_raw(raw, outputs_srgb, uses_point_size))
+            },
+            State::Ready(program) => Ok(*program),
+        }
+    }
+}
+
+impl fmt::Debug for PendingProgram {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self.state {
+            State::Linking { .. } => write!(formatter, "PendingProgram (in progress)"),
+            State::Ready(ref program) => {
+                write!(formatter, "PendingProgram (ready: {:?})", program.get_id())
+            },
+        }
+    }
+}
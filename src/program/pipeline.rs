@@ -0,0 +1,728 @@
+use crate::gl;
+
+use std::collections::hash_map::{self, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::hash::BuildHasherDefault;
+use std::ptr;
+use std::rc::Rc;
+
+use fnv::FnvHasher;
+use smallvec::SmallVec;
+
+use crate::backend::Facade;
+use crate::context::{CommandContext, Context};
+use crate::version::{Api, Version};
+use crate::ContextExt;
+use crate::DrawError;
+use crate::GlObject;
+use crate::Handle;
+use crate::ProgramExt;
+use crate::RawUniformValue;
+
+use crate::program::{is_separable_program_supported, Program, ShaderType};
+use crate::program::program::sync_point_size_and_srgb;
+use crate::program::reflection::{Attribute, ShaderStage, SubroutineData, Uniform, UniformBlock};
+
+/// The separable programs of each stage of a `ProgramPipeline`.
+///
+/// The programs must have been built with `Program::new_separable` for the matching stage. The
+/// same program can be shared by any number of pipelines.
+#[derive(Clone)]
+pub struct PipelineStages {
+    /// Program of the vertex shader.
+    pub vertex_shader: Rc<Program>,
+
+    /// Program of the optional tessellation control shader.
+    pub tessellation_control_shader: Option<Rc<Program>>,
+
+    /// Program of the optional tessellation evaluation shader.
+    pub tessellation_evaluation_shader: Option<Rc<Program>>,
+
+    /// Program of the optional geometry shader.
+    pub geometry_shader: Option<Rc<Program>>,
+
+    /// Program of the fragment shader.
+    pub fragment_shader: Rc<Program>,
+
+    /// See [`ProgramCreationInput::SourceCode::outputs_srgb`](crate::program::ProgramCreationInput::SourceCode::outputs_srgb).
+    pub outputs_srgb: bool,
+
+    /// See [`ProgramCreationInput::SourceCode::uses_point_size`](crate::program::ProgramCreationInput::SourceCode::uses_point_size).
+    pub uses_point_size: bool,
+}
+
+impl PipelineStages {
+    /// Returns the stages made of a vertex shader and a fragment shader.
+    #[inline]
+    pub fn new(vertex_shader: Rc<Program>, fragment_shader: Rc<Program>) -> PipelineStages {
+        PipelineStages {
+            vertex_shader,
+            tessellation_control_shader: None,
+            tessellation_evaluation_shader: None,
+            geometry_shader: None,
+            fragment_shader,
+            outputs_srgb: true,
+            uses_point_size: false,
+        }
+    }
+}
+
+/// Error that can be triggered when creating a `ProgramPipeline`.
+#[derive(Clone, Debug)]
+pub enum PipelineCreationError {
+    /// The backend doesn't support program pipelines.
+    NotSupported,
+
+    /// One of the programs isn't a separable program of the expected stage.
+    WrongStage {
+        /// The stage of the pipeline.
+        expected: ShaderType,
+        /// The stage of the program, or `None` if it isn't separable.
+        found: Option<ShaderType>,
+    },
+
+    /// An input of a stage has no matching output in the previous stage, or the types differ.
+    InterfaceMismatch {
+        /// The stage whose input doesn't match.
+        stage: ShaderType,
+        /// Name of the input.
+        name: String,
+    },
+
+    /// A uniform or a block is declared with different types in two stages.
+    UniformMismatch {
+        /// Name of the uniform or of the block.
+        name: String,
+    },
+
+    /// The program of a stage has subroutine uniforms, which pipelines don't support.
+    SubroutineUniforms {
+        /// The stage whose program has subroutine uniforms.
+        stage: ShaderType,
+    },
+}
+
+impl fmt::Display for PipelineCreationError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::PipelineCreationError::*;
+        match *self {
+            NotSupported =>
+                write!(fmt, "Program pipelines are not supported by the backend"),
+            WrongStage { expected, found: Some(found) } =>
+                write!(fmt, "Expected a separable program for the {:?} stage, found one for \
+                             the {:?} stage", expected, found),
+            WrongStage { expected, found: None } =>
+                write!(fmt, "The program of the {:?} stage is not separable", expected),
+            InterfaceMismatch { stage, ref name } =>
+                write!(fmt, "The input `{}` of the {:?} stage doesn't match any output of the \
+                             previous stage", name, stage),
+            UniformMismatch { ref name } =>
+                write!(fmt, "The uniform `{}` has different types in two stages", name),
+            SubroutineUniforms { stage } =>
+                write!(fmt, "The program of the {:?} stage has subroutine uniforms, which are \
+                             not supported by pipelines", stage),
+        }
+    }
+}
+
+impl Error for PipelineCreationError {}
+
+/// A combination of separable programs, one for each stage.
+///
+/// Contrary to a `Program`, the stages are not linked together. Building a pipeline is cheap,
+/// which allows using many combinations of shaders without linking each of them.
+///
+/// The uniforms, uniform blocks and shader storage blocks of all the stages are merged. A uniform
+/// that is declared in several stages receives the same value in each of them. Programs with
+/// subroutine uniforms can't be used in a pipeline.
+///
+/// Pipelines are drawn with `Surface::draw_pipeline`.
+///
+/// ```no_run
+/// # use glutin::surface::{ResizeableSurface, SurfaceTypeTrait};
+/// # fn example<T>(display: glium::Display<T>) where T: SurfaceTypeTrait + ResizeableSurface {
+/// # let vertex_source = ""; let fragment_source = "";
+/// use std::rc::Rc;
+/// use glium::Program;
+/// use glium::program::{ProgramPipeline, ShaderType};
+///
+/// let vertex = Rc::new(Program::new_separable(&display, ShaderType::Vertex,
+///                                             vertex_source).unwrap());
+/// let fragment = Rc::new(Program::new_separable(&display, ShaderType::Fragment,
+///                                               fragment_source).unwrap());
+/// let pipeline = ProgramPipeline::from_programs(&display, vertex, fragment).unwrap();
+/// # }
+/// ```
+pub struct ProgramPipeline {
+    context: Rc<Context>,
+    id: gl::types::GLuint,
+
+    // the programs, in the order of the stages
+    stages: SmallVec<[(ShaderType, Rc<Program>); 5]>,
+    outputs_srgb: bool,
+    uses_point_size: bool,
+
+    // the locations and ids of the merged reflection are indices in these lists, which contain
+    // the index of the stage and the real location or id in its program
+    uniforms: HashMap<String, Uniform, BuildHasherDefault<FnvHasher>>,
+    uniform_locations: Vec<SmallVec<[(usize, gl::types::GLint); 2]>>,
+    uniform_blocks: HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>>,
+    uniform_block_ids: Vec<SmallVec<[(usize, gl::types::GLuint); 2]>>,
+    ssbos: HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>>,
+    ssbo_ids: Vec<SmallVec<[(usize, gl::types::GLuint); 2]>>,
+    atomic_counters: HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>>,
+    subroutine_data: SubroutineData,
+}
+
+impl ProgramPipeline {
+    /// Builds a pipeline from separable programs.
+    ///
+    /// The inputs of each stage are checked against the outputs of the previous stage if the
+    /// backend supports `GL_ARB_program_interface_query`.
+    pub fn new<F: ?Sized>(facade: &F, stages: PipelineStages)
+                          -> Result<ProgramPipeline, PipelineCreationError> where F: Facade
+    {
+        if !is_separable_program_supported(facade) {
+            return Err(PipelineCreationError::NotSupported);
+        }
+
+        let mut list: SmallVec<[(ShaderType, Rc<Program>); 5]> = SmallVec::new();
+        list.push((ShaderType::Vertex, stages.vertex_shader));
+        if let Some(program) = stages.tessellation_control_shader {
+            list.push((ShaderType::TesselationControl, program));
+        }
+        if let Some(program) = stages.tessellation_evaluation_shader {
+            list.push((ShaderType::TesselationEvaluation, program));
+        }
+        if let Some(program) = stages.geometry_shader {
+            list.push((ShaderType::Geometry, program));
+        }
+        list.push((ShaderType::Fragment, stages.fragment_shader));
+
+        for &(ty, ref program) in list.iter() {
+            if program.separable_stage() != Some(ty) {
+                return Err(PipelineCreationError::WrongStage {
+                    expected: ty,
+                    found: program.separable_stage(),
+                });
+            }
+
+            if !ProgramExt::get_subroutine_data(&**program).subroutine_uniforms.is_empty() {
+                return Err(PipelineCreationError::SubroutineUniforms { stage: ty });
+            }
+        }
+
+        let mut ctxt = facade.get_context().make_current();
+
+        if is_interface_query_supported(&ctxt) {
+            for pair in list.windows(2) {
+                unsafe { check_interface(&mut ctxt, &pair[0].1, pair[1].0, &pair[1].1)? };
+            }
+        }
+
+        let mut uniforms = HashMap::with_hasher(Default::default());
+        let mut uniform_locations: Vec<SmallVec<[_; 2]>> = Vec::new();
+        let mut uniform_blocks = HashMap::with_hasher(Default::default());
+        let mut uniform_block_ids = Vec::new();
+        let mut ssbos = HashMap::with_hasher(Default::default());
+        let mut ssbo_ids = Vec::new();
+        let mut atomic_counters: HashMap<String, UniformBlock, _> =
+            HashMap::with_hasher(Default::default());
+
+        for (index, (_, program)) in list.iter().enumerate() {
+            for (name, uniform) in program.uniforms() {
+                let merged = uniforms.entry(name.clone()).or_insert_with(|| {
+                    uniform_locations.push(SmallVec::new());
+                    Uniform {
+                        location: uniform_locations.len() as i32 - 1,
+                        ty: uniform.ty,
+                        size: uniform.size,
+                    }
+                });
+
+                if merged.ty != uniform.ty || merged.size != uniform.size {
+                    return Err(PipelineCreationError::UniformMismatch { name: name.clone() });
+                }
+
+                uniform_locations[merged.location as usize].push((index, uniform.location));
+            }
+
+            merge_blocks(&mut uniform_blocks, &mut uniform_block_ids, program.get_uniform_blocks(),
+                         index)?;
+            merge_blocks(&mut ssbos, &mut ssbo_ids, program.get_shader_storage_blocks(), index)?;
+
+            // atomic counters are bound to the binding point of the shader and don't need to
+            // be dispatched to the stages
+            for (name, counter) in program.get_atomic_counters() {
+                match atomic_counters.get(name) {
+                    Some(c) if c.initial_binding != counter.initial_binding =>
+                        return Err(PipelineCreationError::UniformMismatch { name: name.clone() }),
+                    Some(_) => (),
+                    None => { atomic_counters.insert(name.clone(), counter.clone()); },
+                }
+            }
+        }
+
+        let id = unsafe {
+            let mut id = 0;
+            ctxt.gl.GenProgramPipelines(1, &mut id);
+
+            for &(ty, ref program) in list.iter() {
+                let bit = match ty {
+                    ShaderType::Vertex => gl::VERTEX_SHADER_BIT,
+                    ShaderType::TesselationControl => gl::TESS_CONTROL_SHADER_BIT,
+                    ShaderType::TesselationEvaluation => gl::TESS_EVALUATION_SHADER_BIT,
+                    ShaderType::Geometry => gl::GEOMETRY_SHADER_BIT,
+                    ShaderType::Fragment => gl::FRAGMENT_SHADER_BIT,
+                    ShaderType::Compute => unreachable!(),
+                };
+
+                ctxt.gl.UseProgramStages(id, bit, program_id(program));
+            }
+
+            id
+        };
+
+        Ok(ProgramPipeline {
+            context: facade.get_context().clone(),
+            id,
+            stages: list,
+            outputs_srgb: stages.outputs_srgb,
+            uses_point_size: stages.uses_point_size,
+            uniforms,
+            uniform_locations,
+            uniform_blocks,
+            uniform_block_ids,
+            ssbos,
+            ssbo_ids,
+            atomic_counters,
+            subroutine_data: Default::default(),
+        })
+    }
+
+    /// Builds a pipeline from a vertex program and a fragment program.
+    #[inline]
+    pub fn from_programs<F: ?Sized>(facade: &F, vertex_shader: Rc<Program>,
+                                    fragment_shader: Rc<Program>)
+                                    -> Result<ProgramPipeline, PipelineCreationError>
+                                    where F: Facade
+    {
+        ProgramPipeline::new(facade, PipelineStages::new(vertex_shader, fragment_shader))
+    }
+
+    /// Returns the program of a stage, if the pipeline has one.
+    #[inline]
+    pub fn get_stage(&self, ty: ShaderType) -> Option<&Rc<Program>> {
+        self.stages.iter().find(|&&(t, _)| t == ty).map(|(_, program)| program)
+    }
+
+    /// Returns the program of the vertex shader.
+    #[inline]
+    pub fn vertex_shader(&self) -> &Rc<Program> {
+        &self.stages[0].1
+    }
+
+    /// Returns the program of the fragment shader.
+    #[inline]
+    pub fn fragment_shader(&self) -> &Rc<Program> {
+        &self.stages[self.stages.len() - 1].1
+    }
+
+    /// Returns informations about a uniform variable of any stage, if it exists.
+    ///
+    /// The location is internal to the pipeline and doesn't match the location in the programs.
+    #[inline]
+    pub fn get_uniform(&self, name: &str) -> Option<&Uniform> {
+        self.uniforms.get(name)
+    }
+
+    /// Returns an iterator to the list of uniforms of all the stages.
+    #[inline]
+    pub fn uniforms(&self) -> hash_map::Iter<'_, String, Uniform> {
+        self.uniforms.iter()
+    }
+
+    /// Returns the list of uniform blocks of all the stages.
+    #[inline]
+    pub fn get_uniform_blocks(&self)
+                              -> &HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>> {
+        &self.uniform_blocks
+    }
+
+    /// Returns the list of shader storage blocks of all the stages.
+    #[inline]
+    pub fn get_shader_storage_blocks(&self)
+            -> &HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>> {
+        &self.ssbos
+    }
+
+    /// Returns informations about an attribute of the vertex shader, if it exists.
+    #[inline]
+    pub fn get_attribute(&self, name: &str) -> Option<&Attribute> {
+        self.vertex_shader().get_attribute(name)
+    }
+
+    /// Returns an iterator to the list of attributes of the vertex shader.
+    #[inline]
+    pub fn attributes(&self) -> hash_map::Iter<'_, String, Attribute> {
+        self.vertex_shader().attributes()
+    }
+
+    /// Returns the *location* of an output fragment of the fragment shader, if it exists.
+    #[inline]
+    pub fn get_frag_data_location(&self, name: &str) -> Option<u32> {
+        self.fragment_shader().get_frag_data_location(name)
+    }
+}
+
+impl fmt::Debug for ProgramPipeline {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("ProgramPipeline")
+           .field("id", &self.id)
+           .field("stages", &self.stages)
+           .finish()
+    }
+}
+
+impl GlObject for ProgramPipeline {
+    type Id = gl::types::GLuint;
+
+    #[inline]
+    fn get_id(&self) -> gl::types::GLuint {
+        self.id
+    }
+}
+
+impl ProgramExt for ProgramPipeline {
+    fn use_program(&self, ctxt: &mut CommandContext<'_>) {
+        sync_point_size_and_srgb(ctxt, self.uses_point_size, self.outputs_srgb);
+
+        unsafe {
+            // the pipeline is only used if no program is current
+            if ctxt.state.program != Handle::Id(0) {
+                ctxt.gl.UseProgram(0);
+                ctxt.state.program = Handle::Id(0);
+            }
+
+            if ctxt.state.program_pipeline != self.id {
+                ctxt.gl.BindProgramPipeline(self.id);
+                ctxt.state.program_pipeline = self.id;
+            }
+        }
+    }
+
+    // the values are set by making the program of each stage current, so the pipeline must be
+    // used again afterwards
+    fn set_uniform(&self, ctxt: &mut CommandContext<'_>, uniform_location: gl::types::GLint,
+                   value: &RawUniformValue)
+    {
+        for &(stage, location) in self.uniform_locations[uniform_location as usize].iter() {
+            let raw = self.stages[stage].1.as_raw();
+            raw.use_program(ctxt);
+            raw.set_uniform(ctxt, location, value);
+        }
+    }
+
+    fn set_uniform_block_binding(&self, ctxt: &mut CommandContext<'_>, block_location: gl::types::GLuint,
+                                 value: gl::types::GLuint)
+    {
+        for &(stage, id) in self.uniform_block_ids[block_location as usize].iter() {
+            let raw = self.stages[stage].1.as_raw();
+            raw.use_program(ctxt);
+            raw.set_uniform_block_binding(ctxt, id, value);
+        }
+    }
+
+    fn set_shader_storage_block_binding(&self, ctxt: &mut CommandContext<'_>,
+                                        block_location: gl::types::GLuint,
+                                        value: gl::types::GLuint)
+    {
+        for &(stage, id) in self.ssbo_ids[block_location as usize].iter() {
+            let raw = self.stages[stage].1.as_raw();
+            raw.use_program(ctxt);
+            raw.set_shader_storage_block_binding(ctxt, id, value);
+        }
+    }
+
+    #[inline]
+    fn set_subroutine_uniforms_for_stage(&self, _: &mut CommandContext<'_>, _: ShaderStage,
+                                         _: &[gl::types::GLuint]) -> Result<(), DrawError>
+    {
+        Err(DrawError::SubroutinesNotSupported)
+    }
+
+    #[inline]
+    fn get_uniform(&self, name: &str) -> Option<&Uniform> {
+        self.uniforms.get(name)
+    }
+
+    #[inline]
+    fn get_uniform_blocks(&self) -> &HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>> {
+        &self.uniform_blocks
+    }
+
+    #[inline]
+    fn get_shader_storage_blocks(&self)
+                                 -> &HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>> {
+        &self.ssbos
+    }
+
+    #[inline]
+    fn get_atomic_counters(&self)
+                           -> &HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>> {
+        &self.atomic_counters
+    }
+
+    #[inline]
+    fn get_subroutine_data(&self) -> &SubroutineData {
+        &self.subroutine_data
+    }
+}
+
+impl Drop for ProgramPipeline {
+    fn drop(&mut self) {
+        let mut ctxt = self.context.make_current();
+
+        unsafe {
+            if ctxt.state.program_pipeline == self.id {
+                ctxt.gl.BindProgramPipeline(0);
+                ctxt.state.program_pipeline = 0;
+            }
+
+            ctxt.gl.DeleteProgramPipelines(1, &self.id);
+        }
+    }
+}
+
+/// A program or a program pipeline, that can be used to draw.
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum DrawProgram<'a> {
+    /// A regular program.
+    Program(&'a Program),
+    /// A pipeline of separable programs.
+    Pipeline(&'a ProgramPipeline),
+}
+
+impl<'a> DrawProgram<'a> {
+    /// Returns the program that contains the vertex attributes.
+    #[inline]
+    pub(crate) fn vertex_program(&self) -> &'a Program {
+        match *self {
+            DrawProgram::Program(program) => program,
+            DrawProgram::Pipeline(pipeline) => pipeline.vertex_shader(),
+        }
+    }
+
+    /// Returns the program that contains the fragment outputs.
+    #[inline]
+    pub(crate) fn fragment_program(&self) -> &'a Program {
+        match *self {
+            DrawProgram::Program(program) => program,
+            DrawProgram::Pipeline(pipeline) => pipeline.fragment_shader(),
+        }
+    }
+}
+
+impl<'a> From<&'a Program> for DrawProgram<'a> {
+    #[inline]
+    fn from(program: &'a Program) -> DrawProgram<'a> {
+        DrawProgram::Program(program)
+    }
+}
+
+impl<'a> From<&'a ProgramPipeline> for DrawProgram<'a> {
+    #[inline]
+    fn from(pipeline: &'a ProgramPipeline) -> DrawProgram<'a> {
+        DrawProgram::Pipeline(pipeline)
+    }
+}
+
+macro_rules! delegate {
+    ($program:expr, $p:ident => $e:expr) => {
+        match $program {
+            DrawProgram::Program($p) => $e,
+            DrawProgram::Pipeline($p) => $e,
+        }
+    };
+}
+
+impl<'a> ProgramExt for DrawProgram<'a> {
+    #[inline]
+    fn use_program(&self, ctxt: &mut CommandContext<'_>) {
+        delegate!(*self, p => p.use_program(ctxt))
+    }
+
+    #[inline]
+    fn set_uniform(&self, ctxt: &mut CommandContext<'_>, uniform_location: gl::types::GLint,
+                   value: &RawUniformValue)
+    {
+        delegate!(*self, p => p.set_uniform(ctxt, uniform_location, value))
+    }
+
+    #[inline]
+    fn set_uniform_block_binding(&self, ctxt: &mut CommandContext<'_>, block_location: gl::types::GLuint,
+                                 value: gl::types::GLuint)
+    {
+        delegate!(*self, p => p.set_uniform_block_binding(ctxt, block_location, value))
+    }
+
+    #[inline]
+    fn set_shader_storage_block_binding(&self, ctxt: &mut CommandContext<'_>,
+                                        block_location: gl::types::GLuint,
+                                        value: gl::types::GLuint)
+    {
+        delegate!(*self, p => p.set_shader_storage_block_binding(ctxt, block_location, value))
+    }
+
+    #[inline]
+    fn set_subroutine_uniforms_for_stage(&self, ctxt: &mut CommandContext<'_>,
+                                         stage: ShaderStage,
+                                         indices: &[gl::types::GLuint]) -> Result<(), DrawError>
+    {
+        delegate!(*self, p => p.set_subroutine_uniforms_for_stage(ctxt, stage, indices))
+    }
+
+    #[inline]
+    fn get_uniform(&self, name: &str) -> Option<&Uniform> {
+        delegate!(*self, p => ProgramExt::get_uniform(p, name))
+    }
+
+    #[inline]
+    fn get_uniform_blocks(&self) -> &HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>> {
+        delegate!(*self, p => ProgramExt::get_uniform_blocks(p))
+    }
+
+    #[inline]
+    fn get_shader_storage_blocks(&self)
+                                 -> &HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>> {
+        delegate!(*self, p => ProgramExt::get_shader_storage_blocks(p))
+    }
+
+    #[inline]
+    fn get_atomic_counters(&self)
+                           -> &HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>> {
+        delegate!(*self, p => ProgramExt::get_atomic_counters(p))
+    }
+
+    #[inline]
+    fn get_subroutine_data(&self) -> &SubroutineData {
+        delegate!(*self, p => ProgramExt::get_subroutine_data(p))
+    }
+}
+
+/// Merges the blocks of a stage with the blocks of the previous stages.
+fn merge_blocks(merged: &mut HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>>,
+                ids: &mut Vec<SmallVec<[(usize, gl::types::GLuint); 2]>>,
+                blocks: &HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>>,
+                stage: usize) -> Result<(), PipelineCreationError>
+{
+    for (name, block) in blocks {
+        let entry = merged.entry(name.clone()).or_insert_with(|| {
+            ids.push(SmallVec::new());
+            UniformBlock {
+                id: ids.len() as i32 - 1,
+                initial_binding: block.initial_binding,
+                size: block.size,
+                layout: block.layout.clone(),
+            }
+        });
+
+        if entry.size != block.size || entry.layout != block.layout {
+            return Err(PipelineCreationError::UniformMismatch { name: name.clone() });
+        }
+
+        ids[entry.id as usize].push((stage, block.id as gl::types::GLuint));
+    }
+
+    Ok(())
+}
+
+fn program_id(program: &Program) -> gl::types::GLuint {
+    match program.get_id() {
+        Handle::Id(id) => id,
+        Handle::Handle(_) => unreachable!(),
+    }
+}
+
+fn is_interface_query_supported(ctxt: &CommandContext<'_>) -> bool {
+    ctxt.version >= &Version(Api::Gl, 4, 3) || ctxt.version >= &Version(Api::GlEs, 3, 1) ||
+        ctxt.extensions.gl_arb_program_interface_query
+}
+
+/// Checks that all the inputs of `consumer` are written by `producer`.
+unsafe fn check_interface(ctxt: &mut CommandContext<'_>, producer: &Program, stage: ShaderType,
+                          consumer: &Program) -> Result<(), PipelineCreationError>
+{
+    let outputs = reflect_interface(ctxt, program_id(producer), gl::PROGRAM_OUTPUT);
+    let inputs = reflect_interface(ctxt, program_id(consumer), gl::PROGRAM_INPUT);
+
+    for input in inputs.iter() {
+        let output = if input.location >= 0 {
+            outputs.iter().find(|o| o.location == input.location)
+        } else {
+            outputs.iter().find(|o| o.name == input.name)
+        };
+
+        match output {
+            Some(output) if output.ty == input.ty => (),
+            _ => return Err(PipelineCreationError::InterfaceMismatch {
+                stage,
+                name: input.name.clone(),
+            }),
+        }
+    }
+
+    Ok(())
+}
+
+struct InterfaceVariable {
+    name: String,
+    ty: gl::types::GLint,
+    location: gl::types::GLint,
+}
+
+/// Returns the inputs or the outputs of a program, except the built-in variables.
+unsafe fn reflect_interface(ctxt: &mut CommandContext<'_>, program: gl::types::GLuint,
+                            interface: gl::types::GLenum) -> Vec<InterfaceVariable>
+{
+    let mut count = 0;
+    ctxt.gl.GetProgramInterfaceiv(program, interface, gl::ACTIVE_RESOURCES, &mut count);
+
+    let mut max_name_len = 0;
+    ctxt.gl.GetProgramInterfaceiv(program, interface, gl::MAX_NAME_LENGTH, &mut max_name_len);
+
+    let mut variables = Vec::with_capacity(count as usize);
+
+    for index in 0 .. count as gl::types::GLuint {
+        let mut name: Vec<u8> = vec![0; max_name_len as usize + 1];
+        let mut name_len = 0;
+        ctxt.gl.GetProgramResourceName(program, interface, index, name.len() as gl::types::GLsizei,
+                                       &mut name_len, name.as_mut_ptr() as *mut _);
+        name.truncate(name_len as usize);
+        let mut name = String::from_utf8(name).unwrap();
+
+        if name.starts_with("gl_") {
+            continue;
+        }
+
+        // the per-vertex inputs and outputs of the tessellation and geometry stages are arrays
+        if name.ends_with("[0]") {
+            let len = name.len();
+            name.truncate(len - 3);
+        }
+
+        let properties = [gl::TYPE, gl::LOCATION];
+        let mut values = [0; 2];
+        ctxt.gl.GetProgramResourceiv(program, interface, index, 2, properties.as_ptr(), 2,
+                                     ptr::null_mut(), values.as_mut_ptr());
+
+        variables.push(InterfaceVariable {
+            name,
+            ty: values[0],
+            location: values[1],
+        });
+    }
+
+    variables
+}
@@ -0,0 +1,424 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Provides the content of the files included with `#include`.
+pub trait IncludeProvider {
+    /// Returns the name and the content of the file included with `#include "path"` from the
+    /// file named `includer`.
+    ///
+    /// The returned name is the one used in the error messages, and is passed as `includer`
+    /// for the nested includes.
+    fn include(&self, path: &str, includer: &str) -> Result<(String, String), String>;
+}
+
+/// The keys are the paths used in the `#include` directives.
+impl IncludeProvider for HashMap<String, String> {
+    fn include(&self, path: &str, _: &str) -> Result<(String, String), String> {
+        match self.get(path) {
+            Some(content) => Ok((path.to_owned(), content.clone())),
+            None => Err("file not found".to_owned()),
+        }
+    }
+}
+
+/// Loads the included files from the file system.
+///
+/// Paths are first looked up relative to the directory of the including file, then relative
+/// to the root directory.
+#[derive(Debug, Clone)]
+pub struct FileSystemProvider {
+    root: PathBuf,
+}
+
+impl FileSystemProvider {
+    /// Builds a provider that loads files relative to `root`.
+    #[inline]
+    pub fn new<P>(root: P) -> FileSystemProvider where P: Into<PathBuf> {
+        FileSystemProvider { root: root.into() }
+    }
+}
+
+impl IncludeProvider for FileSystemProvider {
+    fn include(&self, path: &str, includer: &str) -> Result<(String, String), String> {
+        let relative = Path::new(includer).parent().filter(|dir| !dir.as_os_str().is_empty())
+                                              .map(|dir| dir.join(path));
+        let candidates = relative.into_iter().chain(Some(self.root.join(path)));
+
+        let mut last_error = None;
+        for candidate in candidates {
+            match fs::read_to_string(&candidate) {
+                Ok(content) => return Ok((candidate.to_string_lossy().into_owned(), content)),
+                Err(err) => last_error = Some(err),
+            }
+        }
+
+        Err(last_error.map(|err| err.to_string()).unwrap_or_default())
+    }
+}
+
+/// Preprocessor of GLSL source code.
+///
+/// The preprocessor resolves the `#include "file"` and `#include <file>` directives with an
+/// `IncludeProvider`, and injects a `#version` directive and a list of `#define`s at the start
+/// of the code. Files that contain `#pragma once` are only included once.
+///
+/// Conditional directives such as `#ifdef` are left to the GLSL compiler, which means that the
+/// `#include` directives are processed even in disabled branches.
+///
+/// The line numbers in the output don't match the original files anymore. The `SourceMap` of
+/// the `PreprocessedSource` translates them back, and `Program::new_preprocessed` uses it to
+/// rewrite the compilation errors.
+///
+/// ```no_run
+/// # use glutin::surface::{ResizeableSurface, SurfaceTypeTrait};
+/// # fn example<T>(display: glium::Display<T>) where T: SurfaceTypeTrait + ResizeableSurface {
+/// use glium::program::{FileSystemProvider, Preprocessor};
+///
+/// let preprocessor = Preprocessor::new()
+///     .include_provider(FileSystemProvider::new("shaders"))
+///     .version("330 core")
+///     .define("LIGHTS_COUNT", "4");
+///
+/// let program = glium::Program::new_preprocessed(&display, &preprocessor, glium::program::SourceCode {
+///     vertex_shader: "#include \"phong.vert\"",
+///     fragment_shader: "#include \"phong.frag\"",
+///     geometry_shader: None,
+///     tessellation_control_shader: None,
+///     tessellation_evaluation_shader: None,
+/// });
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Preprocessor {
+    provider: Option<Box<dyn IncludeProvider>>,
+    version: Option<String>,
+    defines: Vec<(String, String)>,
+}
+
+/// Maximum depth of nested includes.
+const MAX_INCLUDE_DEPTH: usize = 32;
+
+impl Preprocessor {
+    /// Builds a preprocessor without defines and without an include provider.
+    #[inline]
+    pub fn new() -> Preprocessor {
+        Preprocessor::default()
+    }
+
+    /// Sets the provider used to resolve `#include` directives.
+    ///
+    /// Without a provider, `#include` directives are errors.
+    #[inline]
+    pub fn include_provider<P>(mut self, provider: P) -> Preprocessor
+        where P: IncludeProvider + 'static
+    {
+        self.provider = Some(Box::new(provider));
+        self
+    }
+
+    /// Sets the version written in the `#version` directive, for example `"330 core"` or
+    /// `"300 es"`. It replaces the `#version` directive of the source code, if any.
+    #[inline]
+    pub fn version<S>(mut self, version: S) -> Preprocessor where S: Into<String> {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Adds a `#define name value` directive. An empty value defines a flag.
+    pub fn define<N, V>(mut self, name: N, value: V) -> Preprocessor
+        where N: Into<String>, V: Into<String>
+    {
+        let name = name.into();
+        let value = value.into();
+
+        match self.defines.iter_mut().find(|(n, _)| *n == name) {
+            Some(define) => define.1 = value,
+            None => self.defines.push((name, value)),
+        }
+
+        self
+    }
+
+    /// Processes the source code of a shader. `name` is the name of the file in the error
+    /// messages and in the source map.
+    pub fn process(&self, name: &str, source: &str)
+                   -> Result<PreprocessedSource, PreprocessorError>
+    {
+        let mut state = State {
+            code: String::new(),
+            source_map: SourceMap { files: vec![name.to_owned()], lines: Vec::new() },
+            version: self.version.clone().map(|v| (v, None)),
+            included_once: Vec::new(),
+            stack: Vec::new(),
+        };
+
+        state.stack.push(name.to_owned());
+        self.process_file(&mut state, 0, source)?;
+
+        // the `#version` directive and the defines are written before the code
+        let mut header = String::new();
+        let mut header_lines = Vec::new();
+
+        if let Some((version, origin)) = state.version {
+            header.push_str(&format!("#version {}\n", version));
+            header_lines.push(origin.unwrap_or((0, 0)));
+        }
+
+        for (name, value) in &self.defines {
+            header.push_str(&format!("#define {} {}\n", name, value));
+            header_lines.push((0, 0));
+        }
+
+        header.push_str(&state.code);
+        header_lines.extend(state.source_map.lines);
+        state.source_map.lines = header_lines;
+
+        Ok(PreprocessedSource { code: header, source_map: state.source_map })
+    }
+
+    fn process_file(&self, state: &mut State, file: usize, source: &str)
+                    -> Result<(), PreprocessorError>
+    {
+        for (index, line) in source.lines().enumerate() {
+            let line_number = index + 1;
+
+            match parse_directive(line) {
+                Some(("version", version)) => {
+                    if state.version.is_none() {
+                        state.version = Some((version.to_owned(), Some((file, line_number))));
+                    }
+                },
+
+                Some(("pragma", "once")) => {
+                    state.included_once.push(state.source_map.files[file].clone());
+                },
+
+                Some(("include", argument)) => {
+                    let path = match parse_include_path(argument) {
+                        Some(path) => path,
+                        None => return Err(PreprocessorError::InvalidDirective {
+                            file: state.source_map.files[file].clone(),
+                            line: line_number,
+                        }),
+                    };
+
+                    self.include(state, file, line_number, path)?;
+                },
+
+                _ => {
+                    state.code.push_str(line);
+                    state.code.push('\n');
+                    state.source_map.lines.push((file, line_number));
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    fn include(&self, state: &mut State, file: usize, line: usize, path: &str)
+               -> Result<(), PreprocessorError>
+    {
+        let includer = state.source_map.files[file].clone();
+        let error = |message: String| PreprocessorError::IncludeError {
+            path: path.to_owned(),
+            file: includer.clone(),
+            line,
+            message,
+        };
+
+        let provider = self.provider.as_ref()
+                                    .ok_or_else(|| error("no include provider".to_owned()))?;
+        let (name, content) = provider.include(path, &includer).map_err(&error)?;
+
+        if state.included_once.contains(&name) {
+            return Ok(());
+        }
+
+        if state.stack.contains(&name) {
+            return Err(error("recursive include".to_owned()));
+        }
+
+        if state.stack.len() >= MAX_INCLUDE_DEPTH {
+            return Err(error("too many nested includes".to_owned()));
+        }
+
+        let index = match state.source_map.files.iter().position(|f| *f == name) {
+            Some(index) => index,
+            None => {
+                state.source_map.files.push(name.clone());
+                state.source_map.files.len() - 1
+            },
+        };
+
+        state.stack.push(name);
+        self.process_file(state, index, &content)?;
+        state.stack.pop();
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Preprocessor {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Preprocessor")
+           .field("version", &self.version)
+           .field("defines", &self.defines)
+           .finish()
+    }
+}
+
+struct State {
+    code: String,
+    source_map: SourceMap,
+    /// Version of the output, and the location of the `#version` directive if it comes from
+    /// the source code.
+    version: Option<(String, Option<(usize, usize)>)>,
+    /// Files that contain `#pragma once`.
+    included_once: Vec<String>,
+    /// Files that are being processed.
+    stack: Vec<String>,
+}
+
+/// Splits a preprocessor directive into its name and its argument.
+fn parse_directive(line: &str) -> Option<(&str, &str)> {
+    let directive = line.trim_start().strip_prefix('#')?.trim_start();
+    let end = directive.find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                       .unwrap_or(directive.len());
+    let argument = directive[end ..].trim();
+    let argument = match argument.find("//") {
+        Some(comment) => argument[.. comment].trim_end(),
+        None => argument,
+    };
+
+    Some((&directive[.. end], argument))
+}
+
+fn parse_include_path(argument: &str) -> Option<&str> {
+    let path = argument.strip_prefix('"').and_then(|a| a.strip_suffix('"'))
+                       .or_else(|| argument.strip_prefix('<').and_then(|a| a.strip_suffix('>')))?;
+
+    if path.is_empty() { None } else { Some(path) }
+}
+
+/// Source code produced by the `Preprocessor`.
+#[derive(Debug, Clone)]
+pub struct PreprocessedSource {
+    /// The GLSL code to compile.
+    pub code: String,
+
+    /// Location of each line of the code in the original files.
+    pub source_map: SourceMap,
+}
+
+/// Location in the original files of the lines of preprocessed source code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceMap {
+    files: Vec<String>,
+    /// For each line of the output, the index of the file and the line in that file. The line is
+    /// 0 for the lines that were added by the preprocessor.
+    lines: Vec<(usize, usize)>,
+}
+
+impl SourceMap {
+    /// Returns the name of the file and the line in that file of a line of the preprocessed
+    /// code. Lines start at 1.
+    ///
+    /// Returns `None` if the line doesn't exist or was added by the preprocessor.
+    pub fn lookup(&self, line: usize) -> Option<(&str, usize)> {
+        match self.lines.get(line.checked_sub(1)?) {
+            Some(&(_, 0)) | None => None,
+            Some(&(file, line)) => Some((&self.files[file], line)),
+        }
+    }
+
+    /// Rewrites the locations in an info log of the GLSL compiler so that they point to the
+    /// original files.
+    ///
+    /// The locations written as `0:12` or `0(12)`, which are the formats used by the common
+    /// OpenGL implementations, become `file:line` or `file(line)`.
+    pub fn rewrite_log(&self, log: &str) -> String {
+        let mut output = String::with_capacity(log.len());
+
+        for line in log.split_inclusive('\n') {
+            output.push_str(&self.rewrite_log_line(line));
+        }
+
+        output
+    }
+
+    fn rewrite_log_line(&self, line: &str) -> String {
+        let bytes = line.as_bytes();
+
+        for (position, _) in line.match_indices('0') {
+            if position > 0 && !bytes[position - 1].is_ascii_whitespace() {
+                continue;
+            }
+
+            let separator = match bytes.get(position + 1) {
+                Some(b':') => ':',
+                Some(b'(') => '(',
+                _ => continue,
+            };
+
+            let digits_start = position + 2;
+            let digits_end = line[digits_start ..].find(|c: char| !c.is_ascii_digit())
+                                                   .map(|e| digits_start + e)
+                                                   .unwrap_or(line.len());
+            let number = match line[digits_start .. digits_end].parse() {
+                Ok(number) => number,
+                Err(_) => continue,
+            };
+
+            if separator == '(' && bytes.get(digits_end) != Some(&b')') {
+                continue;
+            }
+
+            if let Some((file, original_line)) = self.lookup(number) {
+                return format!("{}{}{}{}{}", &line[.. position], file, separator, original_line,
+                               &line[digits_end ..]);
+            }
+        }
+
+        line.to_owned()
+    }
+}
+
+/// Error that can happen while preprocessing source code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PreprocessorError {
+    /// An `#include` directive couldn't be resolved.
+    IncludeError {
+        /// Path written in the directive.
+        path: String,
+        /// File that contains the directive.
+        file: String,
+        /// Line of the directive, starting at 1.
+        line: usize,
+        /// Description of the error.
+        message: String,
+    },
+
+    /// An `#include` directive isn't followed by a path between quotes or angle brackets.
+    InvalidDirective {
+        /// File that contains the directive.
+        file: String,
+        /// Line of the directive, starting at 1.
+        line: usize,
+    },
+}
+
+impl fmt::Display for PreprocessorError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PreprocessorError::IncludeError { path, file, line, message } =>
+                write!(fmt, "{}:{}: could not include `{}`: {}", file, line, path, message),
+            PreprocessorError::InvalidDirective { file, line } =>
+                write!(fmt, "{}:{}: invalid #include directive", file, line),
+        }
+    }
+}
+
+impl Error for PreprocessorError {}
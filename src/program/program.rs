@@ -13,13 +13,15 @@ use std::hash::BuildHasherDefault;
 
 use fnv::FnvHasher;
 
+use crate::DrawError;
 use crate::GlObject;
 use crate::ProgramExt;
 use crate::Handle;
 use crate::RawUniformValue;
 
 use crate::program::{COMPILER_GLOBAL_LOCK, ProgramCreationInput, ProgramCreationError, ShaderType, Binary, SpirvProgram};
-use crate::program::GetBinaryError;
+use crate::program::{GetBinaryError, PendingProgram, PreprocessedSource, Preprocessor};
+use crate::program::is_separable_program_supported;
 
 use crate::program::reflection::{Uniform, UniformBlock, OutputPrimitives};
 use crate::program::reflection::{Attribute, TransformFeedbackBuffer};
@@ -35,6 +37,7 @@ pub struct Program {
     raw: RawProgram,
     outputs_srgb: bool,
     uses_point_size: bool,
+    separable_stage: Option<ShaderType>,
 }
 
 impl Program {
@@ -45,61 +48,9 @@ impl Program {
         let input = input.into();
 
         let (raw, outputs_srgb, uses_point_size) = match input {
-            ProgramCreationInput::SourceCode { vertex_shader, tessellation_control_shader,
-                                               tessellation_evaluation_shader, geometry_shader,
-                                               fragment_shader, transform_feedback_varyings,
-                                               outputs_srgb, uses_point_size } =>
-            {
-                let mut has_geometry_shader = false;
-                let mut has_tessellation_control_shader = false;
-                let mut has_tessellation_evaluation_shader = false;
-
-                let mut shaders = vec![
-                    (vertex_shader, ShaderType::Vertex),
-                    (fragment_shader, ShaderType::Fragment)
-                ];
-
-                if let Some(gs) = geometry_shader {
-                    shaders.push((gs, ShaderType::Geometry));
-                    has_geometry_shader = true;
-                }
-
-                if let Some(ts) = tessellation_control_shader {
-                    shaders.push((ts, ShaderType::TesselationControl));
-                    has_tessellation_control_shader = true;
-                }
-
-                if let Some(ts) = tessellation_evaluation_shader {
-                    shaders.push((ts, ShaderType::TesselationEvaluation));
-                    has_tessellation_evaluation_shader = true;
-                }
-
-                // TODO: move somewhere else
-                if transform_feedback_varyings.is_some() &&
-                    !(facade.get_context().get_version() >= &Version(Api::Gl, 3, 0)) &&
-                    !facade.get_context().get_extensions().gl_ext_transform_feedback
-                {
-                    return Err(ProgramCreationError::TransformFeedbackNotSupported);
-                }
-
-                if uses_point_size && (facade.get_context().get_version().0 == Api::Gl) && !(facade.get_context().get_version() >= &Version(Api::Gl, 2, 0)) {
-                    return Err(ProgramCreationError::PointSizeNotSupported);
-                }
-
-                let _lock = COMPILER_GLOBAL_LOCK.lock();
-
-                let shaders_store = {
-                    let mut shaders_store = Vec::new();
-                    for (src, ty) in shaders.into_iter() {
-                        shaders_store.push(build_shader(facade, ty.to_opengl_type(), src)?);
-                    }
-                    shaders_store
-                };
-
-                (RawProgram::from_shaders(facade, &shaders_store, has_geometry_shader,
-                                               has_tessellation_control_shader, has_tessellation_evaluation_shader,
-                                               transform_feedback_varyings)?,
-                 outputs_srgb, uses_point_size)
+            // the shaders are compiled and linked the same way as with `new_async`
+            input @ ProgramCreationInput::SourceCode { .. } => {
+                return PendingProgram::new(facade, input)?.finish();
             },
 
             ProgramCreationInput::Binary { data, outputs_srgb, uses_point_size } => {
@@ -163,7 +114,7 @@ impl Program {
 
                 (RawProgram::from_shaders(facade, &shaders_store, has_geometry_shader,
                                                has_tessellation_control_shader, has_tessellation_evaluation_shader,
-                                               transform_feedback_varyings)?,
+                                               transform_feedback_varyings, false)?,
                  outputs_srgb, uses_point_size)
             }
         };
@@ -171,6 +122,7 @@ impl Program {
             raw,
             outputs_srgb,
             uses_point_size,
+            separable_stage: None,
         })
     }
 
@@ -212,6 +164,179 @@ impl Program {
         })
     }
 
+    /// Builds a new program from source code that is first processed by a `Preprocessor`.
+    ///
+    /// The shaders are named after their stage in the source maps, for example `vertex`. If a
+    /// shader fails to compile, the locations in the error message are rewritten so that they
+    /// point to the original files. Errors of the preprocessor are returned as compilation
+    /// errors of the shader.
+    ///
+    /// Inputs that are not source code are passed to `Program::new` unchanged.
+    pub fn new_preprocessed<'a, F: ?Sized, I>(facade: &F, preprocessor: &Preprocessor, input: I)
+                                              -> Result<Program, ProgramCreationError>
+                                              where I: Into<ProgramCreationInput<'a>>, F: Facade
+    {
+        let (vertex_shader, tessellation_control_shader, tessellation_evaluation_shader,
+             geometry_shader, fragment_shader, transform_feedback_varyings, outputs_srgb,
+             uses_point_size) = match input.into()
+        {
+            ProgramCreationInput::SourceCode { vertex_shader, tessellation_control_shader,
+                                               tessellation_evaluation_shader, geometry_shader,
+                                               fragment_shader, transform_feedback_varyings,
+                                               outputs_srgb, uses_point_size } =>
+            {
+                (vertex_shader, tessellation_control_shader, tessellation_evaluation_shader,
+                 geometry_shader, fragment_shader, transform_feedback_varyings, outputs_srgb,
+                 uses_point_size)
+            },
+            input => return Program::new(facade, input),
+        };
+
+        let process = |source: &str, ty: ShaderType, name: &str| {
+            preprocessor.process(name, source)
+                        .map_err(|err| ProgramCreationError::CompilationError(err.to_string(), ty))
+        };
+
+        let vertex_shader = process(vertex_shader, ShaderType::Vertex, "vertex")?;
+        let fragment_shader = process(fragment_shader, ShaderType::Fragment, "fragment")?;
+        let geometry_shader = geometry_shader
+            .map(|s| process(s, ShaderType::Geometry, "geometry")).transpose()?;
+        let tessellation_control_shader = tessellation_control_shader
+            .map(|s| process(s, ShaderType::TesselationControl, "tessellation_control"))
+            .transpose()?;
+        let tessellation_evaluation_shader = tessellation_evaluation_shader
+            .map(|s| process(s, ShaderType::TesselationEvaluation, "tessellation_evaluation"))
+            .transpose()?;
+
+        let result = Program::new(facade, ProgramCreationInput::SourceCode {
+            vertex_shader: &vertex_shader.code,
+            tessellation_control_shader: tessellation_control_shader.as_ref().map(|s| &*s.code),
+            tessellation_evaluation_shader: tessellation_evaluation_shader.as_ref()
+                                                                          .map(|s| &*s.code),
+            geometry_shader: geometry_shader.as_ref().map(|s| &*s.code),
+            fragment_shader: &fragment_shader.code,
+            transform_feedback_varyings,
+            outputs_srgb,
+            uses_point_size,
+        });
+
+        match result {
+            Err(ProgramCreationError::CompilationError(log, ty)) => {
+                let source: Option<&PreprocessedSource> = match ty {
+                    ShaderType::Vertex => Some(&vertex_shader),
+                    ShaderType::Fragment => Some(&fragment_shader),
+                    ShaderType::Geometry => geometry_shader.as_ref(),
+                    ShaderType::TesselationControl => tessellation_control_shader.as_ref(),
+                    ShaderType::TesselationEvaluation => tessellation_evaluation_shader.as_ref(),
+                    ShaderType::Compute => None,
+                };
+
+                let log = match source {
+                    Some(source) => source.source_map.rewrite_log(&log),
+                    None => log,
+                };
+
+                Err(ProgramCreationError::CompilationError(log, ty))
+            },
+            result => result,
+        }
+    }
+
+    /// Builds a separable program that contains a single stage.
+    ///
+    /// Separable programs can't be used alone. Instead they are combined with the programs of the
+    /// other stages in a `ProgramPipeline`, which avoids linking every combination of shaders.
+    /// Drawing with a separable program directly returns `DrawError::SeparableProgram`.
+    ///
+    /// Returns `SeparableProgramsNotSupported` if the backend doesn't support
+    /// `GL_ARB_separate_shader_objects`, and `ShaderTypeNotSupported` for compute shaders.
+    pub fn new_separable<F: ?Sized>(facade: &F, ty: ShaderType, source: &str)
+                                    -> Result<Program, ProgramCreationError> where F: Facade
+    {
+        if !is_separable_program_supported(facade) {
+            return Err(ProgramCreationError::SeparableProgramsNotSupported);
+        }
+
+        if ty == ShaderType::Compute {
+            return Err(ProgramCreationError::ShaderTypeNotSupported);
+        }
+
+        let _lock = COMPILER_GLOBAL_LOCK.lock();
+
+        let shader = build_shader(facade, ty.to_opengl_type(), source)?;
+        let raw = RawProgram::from_shaders(facade, &[shader], ty == ShaderType::Geometry,
+                                           ty == ShaderType::TesselationControl,
+                                           ty == ShaderType::TesselationEvaluation, None, true)?;
+
+        Ok(Program {
+            raw,
+            outputs_srgb: true,
+            uses_point_size: false,
+            separable_stage: Some(ty),
+        })
+    }
+
+    /// Starts building a new program without waiting for the driver to compile and link it.
+    ///
+    /// Call `is_ready` on the returned `PendingProgram` to know whether the program can be
+    /// retrieved without blocking. Only errors that are detected before compiling are returned
+    /// here; compilation and linking errors are returned by `PendingProgram::finish`.
+    ///
+    /// The driver only compiles in the background if it supports
+    /// `GL_KHR_parallel_shader_compile`. Binaries and SPIR-V programs are always built
+    /// immediately.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use glutin::surface::{ResizeableSurface, SurfaceTypeTrait};
+    /// # fn example<T>(display: glium::Display<T>) where T: SurfaceTypeTrait + ResizeableSurface {
+    /// # let vertex_source = ""; let fragment_source = "";
+    /// let pending = glium::Program::new_async(&display, glium::program::SourceCode {
+    ///     vertex_shader: vertex_source,
+    ///     fragment_shader: fragment_source,
+    ///     geometry_shader: None,
+    ///     tessellation_control_shader: None,
+    ///     tessellation_evaluation_shader: None,
+    /// }).unwrap();
+    ///
+    /// while !pending.is_ready() {
+    ///     // draw the loading screen
+    /// }
+    ///
+    /// let program = pending.finish().unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn new_async<'a, F: ?Sized, I>(facade: &F, input: I)
+                                       -> Result<PendingProgram, ProgramCreationError>
+                                       where I: Into<ProgramCreationInput<'a>>, F: Facade
+    {
+        PendingProgram::new(facade, input.into())
+    }
+
+    /// Builds a program from a linked raw program.
+    #[inline]
+    pub(crate) fn from_raw(raw: RawProgram, outputs_srgb: bool, uses_point_size: bool) -> Program {
+        Program {
+            raw,
+            outputs_srgb,
+            uses_point_size,
+            separable_stage: None,
+        }
+    }
+
+    /// Returns the stage of the program if it has been built with `Program::new_separable`.
+    #[inline]
+    pub fn separable_stage(&self) -> Option<ShaderType> {
+        self.separable_stage
+    }
+
+    #[inline]
+    pub(crate) fn as_raw(&self) -> &RawProgram {
+        &self.raw
+    }
+
     /// Returns the program's compiled binary.
     ///
     /// You can store the result in a file, then reload it later. This avoids having to compile
@@ -432,28 +557,35 @@ impl GlObject for Program {
     }
 }
 
-impl ProgramExt for Program {
-    fn use_program(&self, ctxt: &mut CommandContext<'_>) {
-        // compatibility was checked at program creation
-        if ctxt.version.0 == Api::Gl {
-            if self.uses_point_size && !ctxt.state.enabled_program_point_size {
-                unsafe { ctxt.gl.Enable(gl::PROGRAM_POINT_SIZE); }
-            } else if !self.uses_point_size && ctxt.state.enabled_program_point_size {
-                unsafe { ctxt.gl.Disable(gl::PROGRAM_POINT_SIZE); }
-            }
+/// Enables or disables `GL_PROGRAM_POINT_SIZE` and `GL_FRAMEBUFFER_SRGB` before a program or a
+/// program pipeline is used.
+pub(crate) fn sync_point_size_and_srgb(ctxt: &mut CommandContext<'_>, uses_point_size: bool,
+                                       outputs_srgb: bool)
+{
+    // compatibility was checked at program creation
+    if ctxt.version.0 == Api::Gl {
+        if uses_point_size && !ctxt.state.enabled_program_point_size {
+            unsafe { ctxt.gl.Enable(gl::PROGRAM_POINT_SIZE); }
+        } else if !uses_point_size && ctxt.state.enabled_program_point_size {
+            unsafe { ctxt.gl.Disable(gl::PROGRAM_POINT_SIZE); }
         }
+    }
 
-        if (ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.extensions.gl_arb_framebuffer_srgb ||
-           ctxt.extensions.gl_ext_framebuffer_srgb || ctxt.extensions.gl_ext_srgb_write_control) && ctxt.state.enabled_framebuffer_srgb == self.outputs_srgb {
-            ctxt.state.enabled_framebuffer_srgb = !self.outputs_srgb;
+    if (ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.extensions.gl_arb_framebuffer_srgb ||
+       ctxt.extensions.gl_ext_framebuffer_srgb || ctxt.extensions.gl_ext_srgb_write_control) && ctxt.state.enabled_framebuffer_srgb == outputs_srgb {
+        ctxt.state.enabled_framebuffer_srgb = !outputs_srgb;
 
-            if self.outputs_srgb {
-                unsafe { ctxt.gl.Disable(gl::FRAMEBUFFER_SRGB) };
-            } else {
-                unsafe { ctxt.gl.Enable(gl::FRAMEBUFFER_SRGB) };
-            }
+        if outputs_srgb {
+            unsafe { ctxt.gl.Disable(gl::FRAMEBUFFER_SRGB) };
+        } else {
+            unsafe { ctxt.gl.Enable(gl::FRAMEBUFFER_SRGB) };
         }
+    }
+}
 
+impl ProgramExt for Program {
+    fn use_program(&self, ctxt: &mut CommandContext<'_>) {
+        sync_point_size_and_srgb(ctxt, self.uses_point_size, self.outputs_srgb);
         self.raw.use_program(ctxt)
     }
 
@@ -482,9 +614,9 @@ impl ProgramExt for Program {
     #[inline]
     fn set_subroutine_uniforms_for_stage(&self, ctxt: &mut CommandContext<'_>,
                                          stage: ShaderStage,
-                                         indices: &[gl::types::GLuint])
+                                         indices: &[gl::types::GLuint]) -> Result<(), DrawError>
     {
-        self.raw.set_subroutine_uniforms_for_stage(ctxt, stage, indices);
+        self.raw.set_subroutine_uniforms_for_stage(ctxt, stage, indices)
     }
 
     #[inline]
@@ -32,6 +32,7 @@ use crate::BufferExt;
 use crate::BufferSliceExt;
 
 use crate::program::{ProgramCreationError, Binary, GetBinaryError};
+use crate::program::is_parallel_compilation_supported;
 use crate::program::uniforms_storage::UniformsStorage;
 
 use crate::program::compute::ComputeCommand;
@@ -75,9 +76,26 @@ impl RawProgram {
     pub fn from_shaders<'a, F: ?Sized, I>(facade: &'a F, shaders: I, has_geometry_shader: bool,
                                   has_tessellation_control_shader: bool,
                                   has_tessellation_evaluation_shader: bool,
-                                  transform_feedback: Option<(Vec<String>, TransformFeedbackMode)>)
+                                  transform_feedback: Option<(Vec<String>, TransformFeedbackMode)>,
+                                  separable: bool)
                                   -> Result<RawProgram, ProgramCreationError>
                                   where F: Facade, I: IntoIterator<Item = &'a Shader>
+    {
+        RawProgram::start_linking(facade, shaders, has_geometry_shader,
+                                  has_tessellation_control_shader,
+                                  has_tessellation_evaluation_shader, transform_feedback,
+                                  separable).finish()
+    }
+
+    /// Starts linking a program from a list of shaders, without waiting for the result.
+    pub fn start_linking<'a, F: ?Sized, I>(facade: &'a F, shaders: I, has_geometry_shader: bool,
+                                           has_tessellation_control_shader: bool,
+                                           has_tessellation_evaluation_shader: bool,
+                                           transform_feedback: Option<(Vec<String>,
+                                                                       TransformFeedbackMode)>,
+                                           separable: bool)
+                                           -> LinkingProgram
+                                           where F: Facade, I: IntoIterator<Item = &'a Shader>
     {
         let mut ctxt = facade.get_context().make_current();
 
@@ -139,6 +157,16 @@ impl RawProgram {
                 }
             }
 
+            // separable programs can be combined with other programs in a pipeline
+            if separable {
+                let id = match id {
+                    Handle::Id(id) => id,
+                    Handle::Handle(_) => unreachable!()
+                };
+
+                ctxt.gl.ProgramParameteri(id, gl::PROGRAM_SEPARABLE, gl::TRUE as gl::types::GLint);
+            }
+
             // linking
             {
                 ctxt.report_debug_output_errors.set(false);
@@ -158,48 +186,16 @@ impl RawProgram {
                 ctxt.report_debug_output_errors.set(true);
             }
 
-            // checking for errors
-            check_program_link_errors(&mut ctxt, id)?;
-
             id
         };
 
-        let (uniforms, atomic_counters) = unsafe { reflect_uniforms(&mut ctxt, id) };
-        let attributes = unsafe { reflect_attributes(&mut ctxt, id) };
-        let blocks = unsafe { reflect_uniform_blocks(&mut ctxt, id) };
-        let tf_buffers = unsafe { reflect_transform_feedback(&mut ctxt, id) };
-        let ssbos = unsafe { reflect_shader_storage_blocks(&mut ctxt, id) };
-        let subroutine_data = unsafe {
-            reflect_subroutine_data(&mut ctxt, id, has_geometry_shader,
-                                    has_tessellation_control_shader,
-                                    has_tessellation_evaluation_shader)
-            };
-
-        let output_primitives = if has_geometry_shader {
-            Some(unsafe { reflect_geometry_output_type(&mut ctxt, id) })
-        } else if has_tessellation_evaluation_shader {
-            Some(unsafe { reflect_tess_eval_output_type(&mut ctxt, id) })
-        } else {
-            None
-        };
-
-        Ok(RawProgram {
+        LinkingProgram {
             context: facade.get_context().clone(),
-            id,
-            uniforms,
-            uniform_values: UniformsStorage::new(),
-            uniform_blocks: blocks,
-            subroutine_data,
-            attributes,
-            frag_data_locations: RefCell::new(HashMap::with_hasher(Default::default())),
-            tf_buffers,
-            ssbos,
-            atomic_counters,
-            output_primitives,
+            id: Some(id),
             has_geometry_shader,
             has_tessellation_control_shader,
             has_tessellation_evaluation_shader,
-        })
+        }
     }
 
     /// Creates a program from binary.
@@ -274,6 +270,12 @@ impl RawProgram {
         })
     }
 
+    /// Returns the context this program belongs to.
+    #[inline]
+    pub(crate) fn get_context(&self) -> &Rc<Context> {
+        &self.context
+    }
+
     /// Returns the program's compiled binary.
     ///
     /// You can store the result in a file, then reload it later. This avoids having to compile
@@ -577,6 +579,78 @@ impl RawProgram {
         Ok(())
     }
 
+    /// Assumes that the program contains a compute shader with a variable local size and
+    /// executes it with the given local size.
+    ///
+    /// # Safety
+    ///
+    /// The program *must* contain a compute shader that uses a variable local size, and
+    /// `ARB_compute_variable_group_size` must be supported. The parameters must have been
+    /// checked against the limits of the backend.
+    pub unsafe fn dispatch_compute_group_size<U>(&self, uniforms: U, x: u32, y: u32, z: u32,
+                                                 local_x: u32, local_y: u32, local_z: u32)
+                                                 -> Result<(), DrawError>
+                                                 where U: Uniforms
+    {
+        let mut ctxt = self.context.make_current();
+
+        assert!(ctxt.extensions.gl_arb_compute_variable_group_size);
+
+        TimeElapsedQuery::end_conditional_render(&mut ctxt);
+
+        let mut fences = Vec::with_capacity(0);
+
+        self.use_program(&mut ctxt);
+        uniforms.bind_uniforms(&mut ctxt, self, &mut fences)?;
+        ctxt.gl.DispatchComputeGroupSizeARB(x, y, z, local_x, local_y, local_z);
+
+        for fence in fences {
+            fence.insert(&mut ctxt);
+        }
+
+        ctxt.state.next_draw_call_id += 1;
+
+        Ok(())
+    }
+
+    /// Assumes that the program contains a compute shader and returns its local size, or `None`
+    /// if it uses a variable local size.
+    ///
+    /// # Safety
+    ///
+    /// The program *must* contain a compute shader.
+    pub unsafe fn get_compute_work_group_size(&self) -> Option<(u32, u32, u32)> {
+        let mut ctxt = self.context.make_current();
+
+        let id = match self.id {
+            Handle::Id(id) => id,
+            Handle::Handle(_) => unreachable!()     // compute shaders require OpenGL 4.3
+        };
+
+        // a fixed local size is at least 1 in each dimension, so zeros left untouched by the
+        // query mean that the program uses a variable local size
+        let mut values = [0; 3];
+
+        if ctxt.extensions.gl_arb_compute_variable_group_size {
+            // querying the local size of a program that uses a variable local size generates
+            // a `GL_INVALID_OPERATION` error, which is consumed here after draining the errors
+            // that were already pending so that they are not reported as ours
+            while crate::get_gl_error(&mut ctxt).is_some() {}
+            ctxt.report_debug_output_errors.set(false);
+            ctxt.gl.GetProgramiv(id, gl::COMPUTE_WORK_GROUP_SIZE, values.as_mut_ptr());
+            crate::get_gl_error(&mut ctxt);
+            ctxt.report_debug_output_errors.set(true);
+        } else {
+            ctxt.gl.GetProgramiv(id, gl::COMPUTE_WORK_GROUP_SIZE, values.as_mut_ptr());
+        }
+
+        if values.iter().any(|&v| v <= 0) {
+            return None;
+        }
+
+        Some((values[0] as u32, values[1] as u32, values[2] as u32))
+    }
+
     /// Assumes that the program contains a compute shader and executes it.
     ///
     /// # Safety
@@ -680,9 +754,10 @@ impl ProgramExt for RawProgram {
     #[inline]
     fn set_subroutine_uniforms_for_stage(&self, ctxt: &mut CommandContext<'_>,
                                          stage: ShaderStage,
-                                         indices: &[gl::types::GLuint])
+                                         indices: &[gl::types::GLuint]) -> Result<(), DrawError>
     {
         self.uniform_values.set_subroutine_uniforms_for_stage(ctxt, self.id, stage, indices);
+        Ok(())
     }
 
     #[inline]
@@ -751,6 +826,93 @@ impl Drop for RawProgram {
     }
 }
 
+/// A program whose linking has been started, but whose result hasn't been checked yet.
+pub struct LinkingProgram {
+    context: Rc<Context>,
+    id: Option<Handle>,
+    has_geometry_shader: bool,
+    has_tessellation_control_shader: bool,
+    has_tessellation_evaluation_shader: bool,
+}
+
+impl LinkingProgram {
+    /// Returns true if the driver has finished linking the program, in which case `finish`
+    /// doesn't block. Always true if the backend doesn't support
+    /// `GL_KHR_parallel_shader_compile`.
+    pub fn is_complete(&self) -> bool {
+        let ctxt = self.context.make_current();
+
+        match self.id {
+            Some(Handle::Id(id)) if is_parallel_compilation_supported(&ctxt) => unsafe {
+                let mut complete: gl::types::GLint = 0;
+                ctxt.gl.GetProgramiv(id, gl::COMPLETION_STATUS_KHR, &mut complete);
+                complete != 0
+            },
+            _ => true,
+        }
+    }
+
+    /// Waits for the linking to finish and checks its result.
+    pub fn finish(mut self) -> Result<RawProgram, ProgramCreationError> {
+        let mut ctxt = self.context.make_current();
+
+        // on error, the program is deleted when `self` is dropped
+        unsafe { check_program_link_errors(&mut ctxt, self.id.unwrap())? };
+        let id = self.id.take().unwrap();
+
+        let (uniforms, atomic_counters) = unsafe { reflect_uniforms(&mut ctxt, id) };
+        let attributes = unsafe { reflect_attributes(&mut ctxt, id) };
+        let blocks = unsafe { reflect_uniform_blocks(&mut ctxt, id) };
+        let tf_buffers = unsafe { reflect_transform_feedback(&mut ctxt, id) };
+        let ssbos = unsafe { reflect_shader_storage_blocks(&mut ctxt, id) };
+        let subroutine_data = unsafe {
+            reflect_subroutine_data(&mut ctxt, id, self.has_geometry_shader,
+                                    self.has_tessellation_control_shader,
+                                    self.has_tessellation_evaluation_shader)
+        };
+
+        let output_primitives = if self.has_geometry_shader {
+            Some(unsafe { reflect_geometry_output_type(&mut ctxt, id) })
+        } else if self.has_tessellation_evaluation_shader {
+            Some(unsafe { reflect_tess_eval_output_type(&mut ctxt, id) })
+        } else {
+            None
+        };
+
+        Ok(RawProgram {
+            context: self.context.clone(),
+            id,
+            uniforms,
+            uniform_values: UniformsStorage::new(),
+            uniform_blocks: blocks,
+            subroutine_data,
+            attributes,
+            frag_data_locations: RefCell::new(HashMap::with_hasher(Default::default())),
+            tf_buffers,
+            ssbos,
+            atomic_counters,
+            output_primitives,
+            has_geometry_shader: self.has_geometry_shader,
+            has_tessellation_control_shader: self.has_tessellation_control_shader,
+            has_tessellation_evaluation_shader: self.has_tessellation_evaluation_shader,
+        })
+    }
+}
+
+impl Drop for LinkingProgram {
+    fn drop(&mut self) {
+        let ctxt = self.context.make_current();
+
+        unsafe {
+            match self.id {
+                Some(Handle::Id(id)) => ctxt.gl.DeleteProgram(id),
+                Some(Handle::Handle(id)) => ctxt.gl.DeleteObjectARB(id),
+                None => (),
+            }
+        }
+    }
+}
+
 /// Builds an empty program from within the GL context.
 unsafe fn create_program(ctxt: &mut CommandContext<'_>) -> Handle {
     let id = if ctxt.version >= &Version(Api::Gl, 2, 0) ||
@@ -0,0 +1,280 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::SystemTime;
+
+use crate::backend::Facade;
+use crate::context::Context;
+
+use crate::program::{Program, ProgramCreationError, ProgramCreationInput, TransformFeedbackMode};
+
+/// The files and the options of a `ReloadableProgram`.
+#[derive(Clone, Debug)]
+pub struct ShaderFiles {
+    /// Path of the vertex shader.
+    pub vertex_shader: PathBuf,
+
+    /// Path of the optional tessellation control shader.
+    pub tessellation_control_shader: Option<PathBuf>,
+
+    /// Path of the optional tessellation evaluation shader.
+    pub tessellation_evaluation_shader: Option<PathBuf>,
+
+    /// Path of the optional geometry shader.
+    pub geometry_shader: Option<PathBuf>,
+
+    /// Path of the fragment shader.
+    pub fragment_shader: PathBuf,
+
+    /// See [`ProgramCreationInput::SourceCode::transform_feedback_varyings`].
+    pub transform_feedback_varyings: Option<(Vec<String>, TransformFeedbackMode)>,
+
+    /// See [`ProgramCreationInput::SourceCode::outputs_srgb`].
+    pub outputs_srgb: bool,
+
+    /// See [`ProgramCreationInput::SourceCode::uses_point_size`].
+    pub uses_point_size: bool,
+}
+
+impl ShaderFiles {
+    /// Builds the description of a program made of a vertex shader and a fragment shader, with
+    /// the same defaults as `Program::from_source`.
+    pub fn new<V, F>(vertex_shader: V, fragment_shader: F) -> ShaderFiles
+                     where V: Into<PathBuf>, F: Into<PathBuf>
+    {
+        ShaderFiles {
+            vertex_shader: vertex_shader.into(),
+            tessellation_control_shader: None,
+            tessellation_evaluation_shader: None,
+            geometry_shader: None,
+            fragment_shader: fragment_shader.into(),
+            transform_feedback_varyings: None,
+            outputs_srgb: true,
+            uses_point_size: false,
+        }
+    }
+
+    /// Returns the paths of all the shaders of the program.
+    fn paths(&self) -> impl Iterator<Item = &Path> {
+        Some(&self.vertex_shader).into_iter()
+            .chain(self.tessellation_control_shader.as_ref())
+            .chain(self.tessellation_evaluation_shader.as_ref())
+            .chain(self.geometry_shader.as_ref())
+            .chain(Some(&self.fragment_shader))
+            .map(|p| &**p)
+    }
+}
+
+/// Error while reloading a `ReloadableProgram`.
+#[derive(Debug)]
+pub enum ReloadError {
+    /// One of the shader files couldn't be read.
+    Io {
+        /// The path of the file.
+        path: PathBuf,
+        /// The error.
+        error: io::Error,
+    },
+
+    /// The program couldn't be built from the new sources.
+    ProgramCreationError(ProgramCreationError),
+}
+
+impl fmt::Display for ReloadError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ReloadError::Io { ref path, ref error } =>
+                write!(fmt, "Could not read {}: {}", path.display(), error),
+            ReloadError::ProgramCreationError(ref err) =>
+                write!(fmt, "{}", err),
+        }
+    }
+}
+
+impl Error for ReloadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            ReloadError::Io { ref error, .. } => Some(error),
+            ReloadError::ProgramCreationError(ref err) => Some(err),
+        }
+    }
+}
+
+impl From<ProgramCreationError> for ReloadError {
+    #[inline]
+    fn from(err: ProgramCreationError) -> ReloadError {
+        ReloadError::ProgramCreationError(err)
+    }
+}
+
+/// A program built from shader files, that is rebuilt when the files are modified.
+///
+/// The files are watched by polling their modification time in `reload_if_changed`, which is
+/// usually called once per frame. If the new sources fail to compile, the previous program is
+/// kept and the error is available with `last_error`.
+///
+/// This struct derefs to the current `Program`, so it can be passed to `draw` with `&*program`.
+///
+/// ```no_run
+/// # use glutin::surface::{ResizeableSurface, SurfaceTypeTrait};
+/// # fn example<T>(display: glium::Display<T>) where T: SurfaceTypeTrait + ResizeableSurface {
+/// use glium::program::ReloadableProgram;
+///
+/// let mut program = ReloadableProgram::from_files(&display, "shaders/teapot.vert",
+///                                                 "shaders/teapot.frag", None).unwrap();
+///
+/// // in the rendering loop
+/// program.reload_if_changed();
+/// if let Some(err) = program.last_error() {
+///     eprintln!("{}", err);
+/// }
+/// # }
+/// ```
+pub struct ReloadableProgram {
+    context: Rc<Context>,
+    files: ShaderFiles,
+    modification_times: Vec<Option<SystemTime>>,
+    program: Program,
+    last_error: Option<ReloadError>,
+}
+
+impl ReloadableProgram {
+    /// Reads the files and builds the program.
+    ///
+    /// Contrary to the reloads, the creation fails if the files can't be read or if the program
+    /// can't be built.
+    pub fn new<F: ?Sized>(facade: &F, files: ShaderFiles) -> Result<ReloadableProgram, ReloadError>
+                          where F: Facade
+    {
+        let context = facade.get_context().clone();
+        let modification_times = files.paths().map(modification_time).collect();
+        let program = build(&context, &files)?;
+
+        Ok(ReloadableProgram {
+            context,
+            files,
+            modification_times,
+            program,
+            last_error: None,
+        })
+    }
+
+    /// Builds a program from a vertex shader, a fragment shader and an optional geometry
+    /// shader. See `Program::from_source`.
+    pub fn from_files<F: ?Sized, P>(facade: &F, vertex_shader: P, fragment_shader: P,
+                                    geometry_shader: Option<P>)
+                                    -> Result<ReloadableProgram, ReloadError>
+                                    where F: Facade, P: Into<PathBuf>
+    {
+        let mut files = ShaderFiles::new(vertex_shader, fragment_shader);
+        files.geometry_shader = geometry_shader.map(Into::into);
+        ReloadableProgram::new(facade, files)
+    }
+
+    /// Rebuilds the program if one of the files has been modified since the last call.
+    ///
+    /// Returns true if the program has been replaced.
+    pub fn reload_if_changed(&mut self) -> bool {
+        let modification_times: Vec<_> = self.files.paths().map(modification_time).collect();
+        if modification_times == self.modification_times {
+            return false;
+        }
+
+        // the times are recorded before reading the files, so that a modification made while
+        // we are reading is noticed on the next call
+        self.modification_times = modification_times;
+        self.reload()
+    }
+
+    /// Rebuilds the program, whether the files have been modified or not.
+    ///
+    /// Returns true if the program has been replaced. On failure, the previous program is kept
+    /// and the error is available with `last_error`.
+    pub fn reload(&mut self) -> bool {
+        match build(&self.context, &self.files) {
+            Ok(program) => {
+                self.program = program;
+                self.last_error = None;
+                true
+            },
+            Err(err) => {
+                self.last_error = Some(err);
+                false
+            },
+        }
+    }
+
+    /// Returns the error of the last reload, or `None` if it succeeded.
+    #[inline]
+    pub fn last_error(&self) -> Option<&ReloadError> {
+        self.last_error.as_ref()
+    }
+
+    /// Returns the program built from the last valid sources.
+    #[inline]
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    /// Returns the files of the program.
+    #[inline]
+    pub fn files(&self) -> &ShaderFiles {
+        &self.files
+    }
+}
+
+impl Deref for ReloadableProgram {
+    type Target = Program;
+
+    #[inline]
+    fn deref(&self) -> &Program {
+        &self.program
+    }
+}
+
+impl fmt::Debug for ReloadableProgram {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("ReloadableProgram")
+           .field("files", &self.files)
+           .field("program", &self.program)
+           .field("last_error", &self.last_error)
+           .finish()
+    }
+}
+
+/// Returns `None` if the file doesn't exist, which happens for a short time with the editors
+/// that save by replacing the file.
+fn modification_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn build(context: &Rc<Context>, files: &ShaderFiles) -> Result<Program, ReloadError> {
+    let read = |path: &Path| {
+        fs::read_to_string(path).map_err(|error| ReloadError::Io { path: path.to_owned(), error })
+    };
+
+    let vertex_shader = read(&files.vertex_shader)?;
+    let tessellation_control_shader = files.tessellation_control_shader.as_deref()
+                                           .map(read).transpose()?;
+    let tessellation_evaluation_shader = files.tessellation_evaluation_shader.as_deref()
+                                              .map(read).transpose()?;
+    let geometry_shader = files.geometry_shader.as_deref().map(read).transpose()?;
+    let fragment_shader = read(&files.fragment_shader)?;
+
+    let program = Program::new(context, ProgramCreationInput::SourceCode {
+        vertex_shader: &vertex_shader,
+        tessellation_control_shader: tessellation_control_shader.as_deref(),
+        tessellation_evaluation_shader: tessellation_evaluation_shader.as_deref(),
+        geometry_shader: geometry_shader.as_deref(),
+        fragment_shader: &fragment_shader,
+        transform_feedback_varyings: files.transform_feedback_varyings.clone(),
+        outputs_srgb: files.outputs_srgb,
+        uses_point_size: files.uses_point_size,
+    })?;
+
+    Ok(program)
+}
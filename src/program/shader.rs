@@ -53,6 +53,20 @@ impl Drop for Shader {
 /// Builds an individual shader.
 pub fn build_shader<F: ?Sized>(facade: &F, shader_type: gl::types::GLenum, source_code: &str)
                        -> Result<Shader, ProgramCreationError> where F: Facade
+{
+    let shader = start_shader_compilation(facade, shader_type, source_code)?;
+    shader.check_compilation(shader_type)?;
+    Ok(shader)
+}
+
+/// Starts compiling an individual shader, without waiting for the result.
+///
+/// `Shader::check_compilation` must be called before the shader is used. With
+/// `GL_KHR_parallel_shader_compile`, the driver compiles the shader in the background until then.
+pub fn start_shader_compilation<F: ?Sized>(facade: &F, shader_type: gl::types::GLenum,
+                                           source_code: &str)
+                                           -> Result<Shader, ProgramCreationError>
+                                           where F: Facade
 {
     unsafe {
         let ctxt = facade.get_context().make_current();
@@ -112,71 +126,86 @@ pub fn build_shader<F: ?Sized>(facade: &F, shader_type: gl::types::GLenum, sourc
             ctxt.report_debug_output_errors.set(true);
         }
 
-        // checking compilation success by reading a flag on the shader
-        let compilation_success = {
-            let mut compilation_success: gl::types::GLint = 0;
-            match id {
-                Handle::Id(id) => {
-                    assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
-                            ctxt.version >= &Version(Api::GlEs, 2, 0));
-                    ctxt.gl.GetShaderiv(id, gl::COMPILE_STATUS, &mut compilation_success);
-                },
-                Handle::Handle(id) => {
-                    assert!(ctxt.extensions.gl_arb_shader_objects);
-                    ctxt.gl.GetObjectParameterivARB(id, gl::OBJECT_COMPILE_STATUS_ARB,
-                                                    &mut compilation_success);
-                }
-            }
-            compilation_success
-        };
-
-        if compilation_success == 1 {
-            Ok(Shader {
-                context: facade.get_context().clone(),
-                id
-            })
+        Ok(Shader {
+            context: facade.get_context().clone(),
+            id
+        })
+    }
+}
 
-        } else {
-            // compilation error
-            let mut error_log_size: gl::types::GLint = 0;
+impl Shader {
+    /// Waits for the compilation of the shader to finish and returns the compilation error, if
+    /// any.
+    pub fn check_compilation(&self, shader_type: gl::types::GLenum)
+                             -> Result<(), ProgramCreationError>
+    {
+        let ctxt = self.context.make_current();
+        let id = self.id;
 
-            match id {
-                Handle::Id(id) => {
-                    assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
-                            ctxt.version >= &Version(Api::GlEs, 2, 0));
-                    ctxt.gl.GetShaderiv(id, gl::INFO_LOG_LENGTH, &mut error_log_size);
-                },
-                Handle::Handle(id) => {
-                    assert!(ctxt.extensions.gl_arb_shader_objects);
-                    ctxt.gl.GetObjectParameterivARB(id, gl::OBJECT_INFO_LOG_LENGTH_ARB,
-                                                    &mut error_log_size);
+        unsafe {
+            // checking compilation success by reading a flag on the shader
+            let compilation_success = {
+                let mut compilation_success: gl::types::GLint = 0;
+                match id {
+                    Handle::Id(id) => {
+                        assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
+                                ctxt.version >= &Version(Api::GlEs, 2, 0));
+                        ctxt.gl.GetShaderiv(id, gl::COMPILE_STATUS, &mut compilation_success);
+                    },
+                    Handle::Handle(id) => {
+                        assert!(ctxt.extensions.gl_arb_shader_objects);
+                        ctxt.gl.GetObjectParameterivARB(id, gl::OBJECT_COMPILE_STATUS_ARB,
+                                                        &mut compilation_success);
+                    }
+                }
+                compilation_success
+            };
+
+            if compilation_success == 1 {
+                Ok(())
+
+            } else {
+                // compilation error
+                let mut error_log_size: gl::types::GLint = 0;
+
+                match id {
+                    Handle::Id(id) => {
+                        assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
+                                ctxt.version >= &Version(Api::GlEs, 2, 0));
+                        ctxt.gl.GetShaderiv(id, gl::INFO_LOG_LENGTH, &mut error_log_size);
+                    },
+                    Handle::Handle(id) => {
+                        assert!(ctxt.extensions.gl_arb_shader_objects);
+                        ctxt.gl.GetObjectParameterivARB(id, gl::OBJECT_INFO_LOG_LENGTH_ARB,
+                                                        &mut error_log_size);
+                    }
                 }
-            }
-
-            let mut error_log: Vec<u8> = Vec::with_capacity(error_log_size as usize);
 
-            match id {
-                Handle::Id(id) => {
-                    assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
-                            ctxt.version >= &Version(Api::GlEs, 2, 0));
-                    ctxt.gl.GetShaderInfoLog(id, error_log_size, &mut error_log_size,
-                                             error_log.as_mut_ptr() as *mut gl::types::GLchar);
-                },
-                Handle::Handle(id) => {
-                    assert!(ctxt.extensions.gl_arb_shader_objects);
-                    ctxt.gl.GetInfoLogARB(id, error_log_size, &mut error_log_size,
-                                          error_log.as_mut_ptr() as *mut gl::types::GLchar);
+                let mut error_log: Vec<u8> = Vec::with_capacity(error_log_size as usize);
+
+                match id {
+                    Handle::Id(id) => {
+                        assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
+                                ctxt.version >= &Version(Api::GlEs, 2, 0));
+                        ctxt.gl.GetShaderInfoLog(id, error_log_size, &mut error_log_size,
+                                                 error_log.as_mut_ptr() as *mut gl::types::GLchar);
+                    },
+                    Handle::Handle(id) => {
+                        assert!(ctxt.extensions.gl_arb_shader_objects);
+                        ctxt.gl.GetInfoLogARB(id, error_log_size, &mut error_log_size,
+                                              error_log.as_mut_ptr() as *mut gl::types::GLchar);
+                    }
                 }
-            }
 
-            error_log.set_len(error_log_size as usize);
+                error_log.set_len(error_log_size as usize);
 
-            match String::from_utf8(error_log) {
-                Ok(msg) => Err(ProgramCreationError::CompilationError(msg, ShaderType::from_opengl_type(shader_type))),
-                Err(_) => Err(
-                    ProgramCreationError::CompilationError("Could not convert the log \
-                                                            message to UTF-8".to_owned(), ShaderType::from_opengl_type(shader_type))
-                ),
+                match String::from_utf8(error_log) {
+                    Ok(msg) => Err(ProgramCreationError::CompilationError(msg, ShaderType::from_opengl_type(shader_type))),
+                    Err(_) => Err(
+                        ProgramCreationError::CompilationError("Could not convert the log \
+                                                                message to UTF-8".to_owned(), ShaderType::from_opengl_type(shader_type))
+                    ),
+                }
             }
         }
     }
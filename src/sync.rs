@@ -8,12 +8,29 @@ use crate::context::Context;
 use crate::ContextExt;
 use std::rc::Rc;
 
+use std::future::{Future, IntoFuture};
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
 use std::thread;
+use std::time::Duration;
 
 /// Error that happens when sync functionalities are not supported.
 #[derive(Copy, Clone, Debug)]
 pub struct SyncNotSupportedError;
 
+/// Result of waiting for a `SyncFence` with a timeout.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FenceWaitResult {
+    /// The fence has been signaled.
+    Signaled,
+
+    /// The timeout expired before the fence was signaled.
+    TimeoutExpired,
+
+    /// An error occurred while waiting for the fence.
+    Failed,
+}
+
 /// Provides a way to wait for a server-side operation to be finished.
 ///
 /// Creating a `SyncFence` injects an element in the commands queue of the backend.
@@ -56,6 +73,55 @@ impl SyncFence {
             _ => panic!("Could not wait for the fence")
         };
     }
+
+    /// Returns true if the operation has finished on the server. Never blocks.
+    ///
+    /// Contrary to `wait_timeout`, this function doesn't flush the commands queue. If nothing
+    /// else flushes it, the fence may never become signaled.
+    pub fn is_signaled(&self) -> bool {
+        let sync = self.id.unwrap();
+
+        let mut ctxt = self.context.make_current();
+        unsafe { is_signaled(&mut ctxt, sync) }
+    }
+
+    /// Blocks until the operation has finished on the server or until the timeout expires,
+    /// whichever comes first.
+    ///
+    /// The commands queue is flushed before waiting. Passing a timeout of zero doesn't block
+    /// and can be used to poll the fence. The fence can be waited on again after
+    /// a `TimeoutExpired`.
+    pub fn wait_timeout(&self, timeout: Duration) -> FenceWaitResult {
+        let sync = self.id.unwrap();
+
+        let mut ctxt = self.context.make_current();
+        let timeout = timeout.as_nanos().min(gl::types::GLuint64::MAX as u128)
+                                        as gl::types::GLuint64;
+
+        match unsafe { client_wait_timeout(&mut ctxt, sync, timeout) } {
+            gl::ALREADY_SIGNALED | gl::CONDITION_SATISFIED => FenceWaitResult::Signaled,
+            gl::TIMEOUT_EXPIRED => FenceWaitResult::TimeoutExpired,
+            _ => FenceWaitResult::Failed,
+        }
+    }
+}
+
+impl IntoFuture for SyncFence {
+    type Output = FenceWaitResult;
+    type IntoFuture = SyncFenceFuture;
+
+    /// Turns the fence into a `Future` that resolves once the operation has finished on
+    /// the server.
+    ///
+    /// The future doesn't wake itself up. Instead you must periodically call
+    /// `Context::poll_fences` (for example once per frame), which wakes up all the tasks that
+    /// are waiting for a fence of this context so that they check their fence again.
+    #[inline]
+    fn into_future(self) -> SyncFenceFuture {
+        SyncFenceFuture {
+            fence: Some(self),
+        }
+    }
 }
 
 impl Drop for SyncFence {
@@ -71,6 +137,45 @@ impl Drop for SyncFence {
     }
 }
 
+/// A `Future` that resolves when a `SyncFence` is signaled.
+///
+/// Obtained by calling `into_future` on a `SyncFence`, or by awaiting it directly. The tasks
+/// awaiting on this future are woken up by `Context::poll_fences`.
+pub struct SyncFenceFuture {
+    fence: Option<SyncFence>,
+}
+
+impl SyncFenceFuture {
+    /// Returns the fence, or `None` if the future has already resolved.
+    #[inline]
+    pub fn into_inner(self) -> Option<SyncFence> {
+        self.fence
+    }
+}
+
+impl Future for SyncFenceFuture {
+    type Output = FenceWaitResult;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<FenceWaitResult> {
+        let result = match self.fence {
+            Some(ref fence) => fence.wait_timeout(Duration::from_secs(0)),
+            None => panic!("`SyncFenceFuture` polled after completion"),
+        };
+
+        match result {
+            FenceWaitResult::TimeoutExpired => {
+                let fence = self.fence.as_ref().unwrap();
+                fence.context.register_fence_waker(cx.waker());
+                Poll::Pending
+            },
+            result => {
+                self.fence = None;
+                Poll::Ready(result)
+            },
+        }
+    }
+}
+
 /// Prototype for a `SyncFence`.
 ///
 /// The fence must be consumed with either `into_sync_fence`, otherwise
@@ -182,6 +287,48 @@ unsafe fn client_wait(ctxt: &mut CommandContext<'_>, fence: gl::types::GLsync) -
     }
 }
 
+/// Calls `glClientWaitSync` with the given timeout in nanoseconds and returns the result.
+///
+/// # Unsafety
+///
+/// The fence object must exist.
+///
+unsafe fn client_wait_timeout(ctxt: &mut CommandContext<'_>, fence: gl::types::GLsync,
+                              timeout: gl::types::GLuint64) -> gl::types::GLenum
+{
+    if ctxt.version >= &Version(Api::Gl, 3, 2) ||
+       ctxt.version >= &Version(Api::GlEs, 3, 0) || ctxt.extensions.gl_arb_sync
+    {
+        ctxt.gl.ClientWaitSync(fence, gl::SYNC_FLUSH_COMMANDS_BIT, timeout)
+    } else if ctxt.extensions.gl_apple_sync {
+        ctxt.gl.ClientWaitSyncAPPLE(fence, gl::SYNC_FLUSH_COMMANDS_BIT_APPLE, timeout)
+    } else {
+        unreachable!();
+    }
+}
+
+/// Returns true if the fence is signaled.
+///
+/// # Unsafety
+///
+/// The fence object must exist.
+///
+unsafe fn is_signaled(ctxt: &mut CommandContext<'_>, fence: gl::types::GLsync) -> bool {
+    let mut value = 0;
+
+    if ctxt.version >= &Version(Api::Gl, 3, 2) ||
+       ctxt.version >= &Version(Api::GlEs, 3, 0) || ctxt.extensions.gl_arb_sync
+    {
+        ctxt.gl.GetSynciv(fence, gl::SYNC_STATUS, 1, std::ptr::null_mut(), &mut value);
+    } else if ctxt.extensions.gl_apple_sync {
+        ctxt.gl.GetSyncivAPPLE(fence, gl::SYNC_STATUS_APPLE, 1, std::ptr::null_mut(), &mut value);
+    } else {
+        unreachable!();
+    }
+
+    value as gl::types::GLenum == gl::SIGNALED
+}
+
 /// Deletes a fence.
 ///
 /// # Unsafety
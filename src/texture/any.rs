@@ -21,15 +21,19 @@ use crate::texture::{MipmapsOption, TextureFormat, TextureCreationError, CubeLay
 use crate::texture::{get_format, InternalFormat, GetFormatError};
 use crate::texture::pixel::PixelValue;
 use crate::texture::pixel_buffer::PixelBuffer;
+use crate::texture::view::{self, TextureViewTarget, TextureViewError};
 
 use crate::fbo::ClearBufferData;
 
+use smallvec::SmallVec;
+
 use crate::buffer::BufferSlice;
 use crate::buffer::BufferAny;
 use crate::BufferExt;
 use crate::BufferSliceExt;
 
 use std::cmp;
+use std::error::Error;
 use std::fmt;
 use std::mem;
 use std::ptr;
@@ -81,8 +85,9 @@ pub struct TextureAny {
     /// If this texture was created in Vulkan for example, it may be backed by external memory.
     memory: Option<MemoryObject>,
 
-    /// ID of the draw call where the texture was last written as a texture unit.
-    latest_shader_write: Cell<u64>,
+    /// ID of the draw call where the texture was last written as a texture unit. Shared with
+    /// the views of this texture and with the texture this one is a view of, if any.
+    latest_shader_write: Rc<Cell<u64>>,
 }
 
 fn extract_dimensions(ty: Dimensions)
@@ -457,7 +462,7 @@ pub fn new_texture<'a, F: ?Sized, P>(facade: &F, format: TextureFormatRequest,
         generate_mipmaps: should_generate_mipmaps,
         owned: true,
         memory: None,
-        latest_shader_write: Cell::new(0),
+        latest_shader_write: Rc::new(Cell::new(0)),
     })
 }
 
@@ -489,7 +494,7 @@ pub unsafe fn from_id<F: Facade + ?Sized>(facade: &F,
         generate_mipmaps: should_generate_mipmaps,
         owned,
         memory: None,
-        latest_shader_write: Cell::new(0),
+        latest_shader_write: Rc::new(Cell::new(0)),
     }
 }
 
@@ -618,10 +623,219 @@ pub unsafe fn new_from_fd<F: Facade + ?Sized>(facade: &F,
         generate_mipmaps: should_generate_mipmaps,
         owned: false,
         memory: Some(memory),
-        latest_shader_write: Cell::new(0),
+        latest_shader_write: Rc::new(Cell::new(0)),
     })
 }
 
+/// Builds a new texture that shares the storage of an existing texture.
+pub fn new_view(texture: &TextureAny, ty: Dimensions, format: TextureFormat,
+                levels: Range<u32>, layers: Range<u32>)
+                -> Result<TextureAny, TextureViewError>
+{
+    let context = &texture.context;
+
+    if !(context.get_version() >= &Version(Api::Gl, 4, 3) ||
+         context.get_extensions().gl_arb_texture_view)
+    {
+        return Err(TextureViewError::NotSupported);
+    }
+
+    if levels.start >= levels.end || levels.end > texture.levels {
+        return Err(TextureViewError::LevelsOutOfRange);
+    }
+
+    let num_layers = match texture.ty {
+        Dimensions::Cubemap { .. } => 6,
+        Dimensions::CubemapArray { array_size, .. } => array_size * 6,
+        ty => extract_dimensions(ty).3.unwrap_or(1),
+    };
+
+    if layers.start >= layers.end || layers.end > num_layers {
+        return Err(TextureViewError::LayersOutOfRange);
+    }
+
+    let original_bind_point = get_bind_point(texture.ty);
+    let view_bind_point = get_bind_point(ty);
+
+    if !view::is_target_compatible(original_bind_point, view_bind_point) {
+        return Err(TextureViewError::IncompatibleType);
+    }
+
+    let layers_ok = match view_bind_point {
+        gl::TEXTURE_1D | gl::TEXTURE_2D | gl::TEXTURE_2D_MULTISAMPLE | gl::TEXTURE_3D => {
+            layers.len() == 1
+        },
+        gl::TEXTURE_CUBE_MAP => layers.len() == 6,
+        gl::TEXTURE_CUBE_MAP_ARRAY => layers.len().is_multiple_of(6),
+        _ => true,
+    };
+
+    if !layers_ok {
+        return Err(TextureViewError::IncompatibleType);
+    }
+
+    let internal_format = image_format::format_request_to_glenum(context,
+                                                                 TextureFormatRequest::Specific(format),
+                                                                 image_format::RequestType::TexStorage)?;
+
+    let mut ctxt = context.make_current();
+
+    let id = unsafe {
+        texture.bind_to_current(&mut ctxt);
+
+        let mut immutable = 0;
+        ctxt.gl.GetTexParameteriv(original_bind_point, gl::TEXTURE_IMMUTABLE_FORMAT,
+                                  &mut immutable);
+        if immutable == 0 {
+            return Err(TextureViewError::MutableStorage);
+        }
+
+        let original_format = texture.query_internal_format(&mut ctxt, 0);
+
+        if !view::is_format_compatible(original_format, internal_format) {
+            return Err(TextureViewError::IncompatibleFormat);
+        }
+
+        // the name passed to `glTextureView` must not have been bound yet
+        let mut id: gl::types::GLuint = 0;
+        ctxt.gl.GenTextures(1, &mut id);
+        ctxt.gl.TextureView(id, view_bind_point, texture.id, internal_format,
+                            levels.start, levels.len() as gl::types::GLuint,
+                            layers.start, layers.len() as gl::types::GLuint);
+
+        {
+            ctxt.gl.BindTexture(view_bind_point, id);
+            let act = ctxt.state.active_texture as usize;
+            ctxt.state.texture_units[act].texture = id;
+        }
+
+        let filtering = match format {
+            TextureFormat::UncompressedIntegral(_) => gl::NEAREST,
+            TextureFormat::UncompressedUnsigned(_) => gl::NEAREST,
+            TextureFormat::StencilFormat(_) => gl::NEAREST,
+            _ => gl::LINEAR,
+        };
+
+        if view_bind_point != gl::TEXTURE_2D_MULTISAMPLE &&
+           view_bind_point != gl::TEXTURE_2D_MULTISAMPLE_ARRAY
+        {
+            ctxt.gl.TexParameteri(view_bind_point, gl::TEXTURE_MAG_FILTER, filtering as i32);
+            ctxt.gl.TexParameteri(view_bind_point, gl::TEXTURE_MIN_FILTER, filtering as i32);
+        }
+
+        id
+    };
+
+    Ok(TextureAny {
+        context: context.clone(),
+        id,
+        requested_format: TextureFormatRequest::Specific(format),
+        actual_format: Cell::new(None),
+        ty,
+        levels: levels.len() as u32,
+        generate_mipmaps: false,
+        owned: true,
+        memory: None,
+        latest_shader_write: texture.latest_shader_write.clone(),
+    })
+}
+
+/// Builds a new sparse texture, whose pages must be committed before they can be used.
+///
+/// Returns the texture and the number of mipmap levels that can be partially committed. The
+/// levels after this one are committed and decommitted as a whole.
+pub fn new_sparse_texture<F: ?Sized>(facade: &F, format: TextureFormat, mipmaps: MipmapsOption,
+                                     ty: Dimensions, page_size_index: u32)
+                                     -> Result<(TextureAny, u32), TextureCreationError>
+                                     where F: Facade
+{
+    let (width, height, depth, array_size, _) = extract_dimensions(ty);
+
+    let bind_point = get_bind_point(ty);
+    if bind_point != gl::TEXTURE_2D && bind_point != gl::TEXTURE_2D_ARRAY &&
+       bind_point != gl::TEXTURE_3D
+    {
+        return Err(TextureCreationError::TypeNotSupported);
+    }
+
+    if !facade.get_context().get_extensions().gl_arb_sparse_texture {
+        return Err(TextureCreationError::TypeNotSupported);
+    }
+
+    let internal_format = image_format::format_request_to_glenum(facade.get_context(),
+                                                                 TextureFormatRequest::Specific(format),
+                                                                 image_format::RequestType::TexStorage)?;
+
+    let texture_levels = mipmaps.num_levels(width, height, depth) as gl::types::GLsizei;
+
+    let filtering = match format {
+        TextureFormat::UncompressedIntegral(_) => gl::NEAREST,
+        TextureFormat::UncompressedUnsigned(_) => gl::NEAREST,
+        _ => gl::LINEAR,
+    };
+
+    let mut ctxt = facade.get_context().make_current();
+
+    let (id, num_sparse_levels) = unsafe {
+        let mut id: gl::types::GLuint = 0;
+        ctxt.gl.GenTextures(1, &mut id);
+
+        {
+            ctxt.gl.BindTexture(bind_point, id);
+            let act = ctxt.state.active_texture as usize;
+            ctxt.state.texture_units[act].texture = id;
+        }
+
+        // the sparse parameters must be set before the storage is allocated
+        ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_SPARSE_ARB, gl::TRUE as i32);
+        ctxt.gl.TexParameteri(bind_point, gl::VIRTUAL_PAGE_SIZE_INDEX_ARB,
+                              page_size_index as gl::types::GLint);
+
+        ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MAG_FILTER, filtering as i32);
+        if texture_levels > 1 {
+            let mipmap_filtering = if filtering == gl::NEAREST {
+                gl::NEAREST_MIPMAP_NEAREST
+            } else {
+                gl::LINEAR_MIPMAP_LINEAR
+            };
+            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MIN_FILTER, mipmap_filtering as i32);
+        } else {
+            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MIN_FILTER, filtering as i32);
+        }
+
+        if bind_point == gl::TEXTURE_2D {
+            ctxt.gl.TexStorage2D(bind_point, texture_levels, internal_format,
+                                 width as gl::types::GLsizei,
+                                 height.unwrap() as gl::types::GLsizei);
+        } else {
+            ctxt.gl.TexStorage3D(bind_point, texture_levels, internal_format,
+                                 width as gl::types::GLsizei,
+                                 height.unwrap() as gl::types::GLsizei,
+                                 depth.or(array_size).unwrap() as gl::types::GLsizei);
+        }
+
+        let mut num_sparse_levels = 0;
+        ctxt.gl.GetTexParameteriv(bind_point, gl::NUM_SPARSE_LEVELS_ARB, &mut num_sparse_levels);
+
+        (id, num_sparse_levels as u32)
+    };
+
+    let texture = TextureAny {
+        context: facade.get_context().clone(),
+        id,
+        requested_format: TextureFormatRequest::Specific(format),
+        actual_format: Cell::new(None),
+        ty,
+        levels: texture_levels as u32,
+        generate_mipmaps: false,
+        owned: true,
+        memory: None,
+        latest_shader_write: Rc::new(Cell::new(0)),
+    };
+
+    Ok((texture, num_sparse_levels))
+}
+
 impl TextureAny {
     /// Returns the width of the texture.
     #[inline]
@@ -753,6 +967,29 @@ impl TextureAny {
         })
     }
 
+    /// Builds a texture of type `T` that shares its storage with this texture.
+    ///
+    /// `levels` and `layers` are the ranges of mipmap levels and layers of this texture that
+    /// the view contains. Cubemaps count as six layers. The dimensions of the view are the ones of
+    /// the first mipmap level in `levels`.
+    ///
+    /// `format` must be in the same compatibility class as the format of this texture. For
+    /// uncompressed formats, this means that the number of bits per texel must be the same.
+    ///
+    /// See the `view` module for more information.
+    pub fn view<T>(&self, format: T::Format, levels: Range<u32>, layers: Range<u32>)
+                   -> Result<T, TextureViewError> where T: TextureViewTarget
+    {
+        let format = T::format_to_texture_format(format);
+
+        let mipmap = self.mipmap(levels.start).ok_or(TextureViewError::LevelsOutOfRange)?;
+        let ty = T::view_dimensions(mipmap.get_width(), mipmap.get_height().unwrap_or(1),
+                                    mipmap.get_depth().unwrap_or(1), layers.len() as u32,
+                                    self.get_samples().unwrap_or(0));
+
+        new_view(self, ty, format, levels, layers).map(T::from_any)
+    }
+
     /// Returns the type of the texture (1D, 2D, 3D, etc.).
     #[inline]
     pub fn get_texture_type(&self) -> Dimensions {
@@ -824,6 +1061,39 @@ impl TextureAny {
         self.bind_to_current(&mut ctxt);
         generate_mipmaps(&ctxt, self.get_bind_point());
     }
+
+    /// Invalidates the content of all the mipmap levels of the texture, which becomes undefined.
+    ///
+    /// This is useful for transient textures whose content is not needed anymore, as it lets
+    /// the implementation skip preserving it. Nothing happens if OpenGL 4.3 or
+    /// `GL_ARB_invalidate_subdata` is not available.
+    pub fn invalidate(&self) {
+        for level in 0 .. self.levels {
+            self.mipmap(level).unwrap().invalidate();
+        }
+    }
+}
+
+impl TextureAny {
+    /// Queries the internal format of a mipmap level of the texture.
+    fn query_internal_format(&self, ctxt: &mut CommandContext<'_>, level: u32)
+                             -> gl::types::GLenum
+    {
+        let bind_point = self.bind_to_current(ctxt);
+
+        let level_target = if bind_point == gl::TEXTURE_CUBE_MAP {
+            gl::TEXTURE_CUBE_MAP_POSITIVE_X
+        } else {
+            bind_point
+        };
+
+        let mut format = 0;
+        unsafe {
+            ctxt.gl.GetTexLevelParameteriv(level_target, level as gl::types::GLint,
+                                           gl::TEXTURE_INTERNAL_FORMAT, &mut format);
+        }
+        format as gl::types::GLenum
+    }
 }
 
 impl TextureExt for TextureAny {
@@ -878,6 +1148,12 @@ impl TextureExt for TextureAny {
                     ctxt.state.latest_memory_barrier_framebuffer = ctxt.state.next_draw_call_id;
                 }
             },
+            crate::TextureAccess::TextureUpdate => {
+                if self.latest_shader_write.get() >= ctxt.state.latest_memory_barrier_texture_update {
+                    unsafe { ctxt.gl.MemoryBarrier(gl::TEXTURE_UPDATE_BARRIER_BIT); }
+                    ctxt.state.latest_memory_barrier_texture_update = ctxt.state.next_draw_call_id;
+                }
+            },
         }        
     }
 
@@ -1097,6 +1373,361 @@ impl<'a> TextureAnyMipmap<'a> {
         self.texture.get_array_size()
     }
 
+    /// Copies a region of this mipmap level to a mipmap level of this texture or of another one.
+    ///
+    /// `source_offset` and `target_offset` are the `(x, y, z)` coordinates of the region in each
+    /// texture, and `size` is its `(width, height, depth)` in texels of this texture. The `z`
+    /// coordinate is the layer for array textures, the face for cubemaps (in the order of
+    /// `CubeLayer::get_layer_index`, with six faces per cubemap for cubemap arrays) and the depth
+    /// for 3D textures. It must be 0 for other textures.
+    ///
+    /// The formats of the two textures must be in the same compatibility class, as described in
+    /// the `view` module. A compressed texture can also be copied to an uncompressed texture and
+    /// vice versa if the size of a block is the size of a texel, in which case each block of the
+    /// compressed texture corresponds to a texel of the uncompressed one. In compressed textures,
+    /// the region must be aligned to the blocks of the format (for example 4x4 texels for S3TC,
+    /// or 6x5 texels for ASTC 6x5) unless it touches the right or bottom border.
+    ///
+    /// This uses `glCopyImageSubData` if OpenGL 4.3, OpenGL ES 3.2 or `GL_ARB_copy_image` is
+    /// available. Otherwise, the region is blitted between framebuffers, which only works for
+    /// uncompressed textures of the same kind whose formats can be attached to a framebuffer.
+    pub fn copy_region_to(&self, source_offset: (u32, u32, u32), size: (u32, u32, u32),
+                          target: &TextureAnyMipmap<'_>, target_offset: (u32, u32, u32))
+                          -> Result<(), CopyImageError>
+    {
+        if self.texture.get_samples() != target.texture.get_samples() {
+            return Err(CopyImageError::SampleCountMismatch);
+        }
+
+        let mut ctxt = self.texture.context.make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 4, 3) ||
+             ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+             ctxt.extensions.gl_arb_copy_image)
+        {
+            drop(ctxt);
+            return self.blit_region_to(source_offset, size, target, target_offset);
+        }
+
+        let source_format = self.texture.query_internal_format(&mut ctxt, self.level);
+        let target_format = target.texture.query_internal_format(&mut ctxt, target.level);
+
+        if !view::is_copy_compatible(source_format, target_format) {
+            return Err(CopyImageError::IncompatibleFormats);
+        }
+
+        let source_block = view::get_block_dimensions(source_format);
+        let target_block = view::get_block_dimensions(target_format);
+
+        self.check_copy_region(source_offset, size, source_block)?;
+
+        // dimensions of the region in texels of the target
+        let target_size = match (source_block, target_block) {
+            (Some((block_width, block_height)), None) => {
+                (size.0.div_ceil(block_width), size.1.div_ceil(block_height), size.2)
+            },
+            (None, Some((block_width, block_height))) => {
+                let (width, height, _) = target.get_region_extent();
+                (cmp::min(size.0.saturating_mul(block_width),
+                          width.saturating_sub(target_offset.0)),
+                 cmp::min(size.1.saturating_mul(block_height),
+                          height.saturating_sub(target_offset.1)), size.2)
+            },
+            _ => size,
+        };
+
+        target.check_copy_region(target_offset, target_size, target_block)?;
+
+        self.texture.prepare_for_access(&mut ctxt, crate::TextureAccess::TextureUpdate);
+        target.texture.prepare_for_access(&mut ctxt, crate::TextureAccess::TextureUpdate);
+
+        let (src_x, src_y, src_z) = self.to_copy_coordinates(source_offset);
+        let (dst_x, dst_y, dst_z) = target.to_copy_coordinates(target_offset);
+        let (width, height, depth) = self.to_copy_coordinates(size);
+
+        unsafe {
+            ctxt.gl.CopyImageSubData(self.texture.id, get_bind_point(self.texture.ty),
+                                     self.level as gl::types::GLint,
+                                     src_x as gl::types::GLint, src_y as gl::types::GLint,
+                                     src_z as gl::types::GLint,
+                                     target.texture.id, get_bind_point(target.texture.ty),
+                                     target.level as gl::types::GLint,
+                                     dst_x as gl::types::GLint, dst_y as gl::types::GLint,
+                                     dst_z as gl::types::GLint,
+                                     width as gl::types::GLsizei, height as gl::types::GLsizei,
+                                     depth as gl::types::GLsizei);
+        }
+
+        Ok(())
+    }
+
+    /// Implementation of `copy_region_to` when `glCopyImageSubData` is not available.
+    fn blit_region_to(&self, source_offset: (u32, u32, u32), size: (u32, u32, u32),
+                      target: &TextureAnyMipmap<'_>, target_offset: (u32, u32, u32))
+                      -> Result<(), CopyImageError>
+    {
+        let kind = self.texture.kind();
+
+        if kind != target.texture.kind() || is_compressed_request(self.texture.requested_format) ||
+           is_compressed_request(target.texture.requested_format)
+        {
+            return Err(CopyImageError::NotSupported);
+        }
+
+        self.check_copy_region(source_offset, size, None)?;
+        target.check_copy_region(target_offset, size, None)?;
+
+        let mask = match kind {
+            TextureKind::Float | TextureKind::Integral | TextureKind::Unsigned => {
+                gl::COLOR_BUFFER_BIT
+            },
+            TextureKind::Depth => gl::DEPTH_BUFFER_BIT,
+            TextureKind::Stencil => gl::STENCIL_BUFFER_BIT,
+            TextureKind::DepthStencil => gl::DEPTH_BUFFER_BIT | gl::STENCIL_BUFFER_BIT,
+        };
+
+        let src_rect = Rect {
+            left: source_offset.0,
+            bottom: source_offset.1,
+            width: size.0,
+            height: size.1,
+        };
+
+        let target_rect = crate::BlitTarget {
+            left: target_offset.0,
+            bottom: target_offset.1,
+            width: size.0 as i32,
+            height: size.1 as i32,
+        };
+
+        for layer in 0 .. size.2 {
+            let source = self.get_copy_image(source_offset.2 + layer).unwrap();
+            let destination = target.get_copy_image(target_offset.2 + layer).unwrap();
+
+            let source = image_attachments(source, kind).validate(&*self.texture.context)
+                                            .map_err(|_| CopyImageError::NotSupported)?;
+            let destination = image_attachments(destination, kind).validate(&*self.texture.context)
+                                                      .map_err(|_| CopyImageError::NotSupported)?;
+
+            ops::blit(&self.texture.context, Some(&source), Some(&destination), mask, &src_rect,
+                      &target_rect, gl::NEAREST);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the dimensions of this mipmap level in the coordinates used by `copy_region_to`
+    /// and `raw_clear_region`.
+    pub(crate) fn get_region_extent(&self) -> (u32, u32, u32) {
+        let layers = match self.texture.ty {
+            Dimensions::Cubemap { .. } => 6,
+            Dimensions::CubemapArray { array_size, .. } => array_size * 6,
+            _ => self.texture.get_array_size().or(self.depth).unwrap_or(1),
+        };
+
+        (self.width, self.height.unwrap_or(1), layers)
+    }
+
+    /// Checks that a region is within the boundaries of this mipmap level, and that it is
+    /// aligned to the blocks of the format if the texture is compressed.
+    fn check_copy_region(&self, offset: (u32, u32, u32), size: (u32, u32, u32),
+                         block: Option<(u32, u32)>) -> Result<(), CopyImageError>
+    {
+        let (width, height, depth) = self.get_region_extent();
+
+        let in_range = |offset: u32, size: u32, max: u32| {
+            offset.checked_add(size).map(|end| end <= max).unwrap_or(false)
+        };
+
+        if !in_range(offset.0, size.0, width) || !in_range(offset.1, size.1, height) ||
+           !in_range(offset.2, size.2, depth)
+        {
+            return Err(CopyImageError::OutOfRange);
+        }
+
+        if let Some((block_width, block_height)) = block {
+            let aligned = |offset: u32, size: u32, max: u32, block: u32| {
+                offset.is_multiple_of(block) && (size.is_multiple_of(block) || offset + size == max)
+            };
+
+            if !aligned(offset.0, size.0, width, block_width) ||
+               !aligned(offset.1, size.1, height, block_height)
+            {
+                return Err(CopyImageError::UnalignedCompressedRegion);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Converts coordinates used by `copy_region_to` to the ones of `glCopyImageSubData`, which
+    /// uses the `y` coordinate for the layers of 1D array textures.
+    #[inline]
+    fn to_copy_coordinates(&self, (x, y, z): (u32, u32, u32)) -> (u32, u32, u32) {
+        match self.texture.ty {
+            Dimensions::Texture1dArray { .. } => (x, z, y),
+            _ => (x, y, z),
+        }
+    }
+
+    /// Returns the image at the given `z` coordinate used by `copy_region_to`.
+    fn get_copy_image(&self, z: u32) -> Option<TextureAnyImage<'a>> {
+        const CUBE_LAYERS: [CubeLayer; 6] = [
+            CubeLayer::PositiveX, CubeLayer::NegativeX, CubeLayer::PositiveY,
+            CubeLayer::NegativeY, CubeLayer::PositiveZ, CubeLayer::NegativeZ,
+        ];
+
+        match self.texture.ty {
+            Dimensions::Cubemap { .. } | Dimensions::CubemapArray { .. } => {
+                self.layer(z / 6)?.into_image(Some(CUBE_LAYERS[(z % 6) as usize]))
+            },
+            _ => self.layer(z)?.into_image(None),
+        }
+    }
+
+    /// Invalidates the content of this mipmap level, which becomes undefined.
+    ///
+    /// Nothing happens if OpenGL 4.3 or `GL_ARB_invalidate_subdata` is not available.
+    pub fn invalidate(&self) {
+        let ctxt = self.texture.context.make_current();
+
+        if ctxt.version >= &Version(Api::Gl, 4, 3) || ctxt.extensions.gl_arb_invalidate_subdata {
+            unsafe {
+                ctxt.gl.InvalidateTexImage(self.texture.id, self.level as gl::types::GLint);
+            }
+        }
+    }
+
+    /// Clears the whole mipmap level, including all its layers, to a value.
+    ///
+    /// See `raw_clear_region`.
+    #[inline]
+    pub fn raw_clear<D>(&self, data: D) -> Result<(), ClearTextureError>
+        where D: Into<ClearBufferData>
+    {
+        self.raw_clear_region((0, 0, 0), self.get_region_extent(), data)
+    }
+
+    /// Clears a region of this mipmap level to a value.
+    ///
+    /// The offset and the size are in the same coordinates as in `copy_region_to`: the third
+    /// coordinate is the layer for array textures, the face for cubemaps and the depth for 3D
+    /// textures. The kind of value must match the kind of texture, for example
+    /// `ClearBufferData::Unsigned` for an unsigned texture.
+    ///
+    /// This uses `glClearTexSubImage` if OpenGL 4.4 or `GL_ARB_clear_texture` is available.
+    /// Otherwise, each layer is attached to a framebuffer and cleared with `glClearBuffer`, which
+    /// requires OpenGL 3.0 or OpenGL ES 3.0 and a format that can be rendered to. Compressed
+    /// textures can't be cleared.
+    pub fn raw_clear_region<D>(&self, offset: (u32, u32, u32), size: (u32, u32, u32), data: D)
+                               -> Result<(), ClearTextureError>
+        where D: Into<ClearBufferData>
+    {
+        let data = data.into();
+
+        let kind_matches = matches!((self.texture.kind(), data),
+            (TextureKind::Float, ClearBufferData::Float(_)) |
+            (TextureKind::Integral, ClearBufferData::Integral(_)) |
+            (TextureKind::Unsigned, ClearBufferData::Unsigned(_)) |
+            (TextureKind::Depth, ClearBufferData::Depth(_)) |
+            (TextureKind::Stencil, ClearBufferData::Stencil(_)) |
+            (TextureKind::DepthStencil, ClearBufferData::DepthStencil(_, _)));
+
+        if !kind_matches {
+            return Err(ClearTextureError::ValueKindMismatch);
+        }
+
+        if is_compressed_request(self.texture.requested_format) {
+            return Err(ClearTextureError::NotSupported);
+        }
+
+        self.check_copy_region(offset, size, None).map_err(|_| ClearTextureError::OutOfRange)?;
+
+        if size.0 == 0 || size.1 == 0 || size.2 == 0 {
+            return Ok(());
+        }
+
+        let mut ctxt = self.texture.context.make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 4, 4) || ctxt.extensions.gl_arb_clear_texture) {
+            drop(ctxt);
+            return self.clear_region_with_framebuffer(offset, size, data);
+        }
+
+        self.texture.prepare_for_access(&mut ctxt, crate::TextureAccess::TextureUpdate);
+
+        let (x, y, z) = self.to_copy_coordinates(offset);
+        let (width, height, depth) = self.to_copy_coordinates(size);
+
+        // `FLOAT_32_UNSIGNED_INT_24_8_REV` data for depth-stencil textures
+        let depth_stencil;
+
+        let (format, ty, ptr): (_, _, *const c_void) = match data {
+            ClearBufferData::Float(ref value) => {
+                (gl::RGBA, gl::FLOAT, value.as_ptr() as *const _)
+            },
+            ClearBufferData::Integral(ref value) => {
+                (gl::RGBA_INTEGER, gl::INT, value.as_ptr() as *const _)
+            },
+            ClearBufferData::Unsigned(ref value) => {
+                (gl::RGBA_INTEGER, gl::UNSIGNED_INT, value.as_ptr() as *const _)
+            },
+            ClearBufferData::Depth(ref depth) => {
+                (gl::DEPTH_COMPONENT, gl::FLOAT, depth as *const f32 as *const _)
+            },
+            ClearBufferData::Stencil(ref stencil) => {
+                (gl::STENCIL_INDEX, gl::INT, stencil as *const i32 as *const _)
+            },
+            ClearBufferData::DepthStencil(depth, stencil) => {
+                depth_stencil = [depth.to_bits(), stencil as u32 & 0xff];
+                (gl::DEPTH_STENCIL, gl::FLOAT_32_UNSIGNED_INT_24_8_REV,
+                 depth_stencil.as_ptr() as *const _)
+            },
+        };
+
+        unsafe {
+            ctxt.gl.ClearTexSubImage(self.texture.id, self.level as gl::types::GLint,
+                                     x as gl::types::GLint, y as gl::types::GLint,
+                                     z as gl::types::GLint, width as gl::types::GLsizei,
+                                     height as gl::types::GLsizei, depth as gl::types::GLsizei,
+                                     format, ty, ptr);
+        }
+
+        Ok(())
+    }
+
+    /// Implementation of `raw_clear_region` when `glClearTexSubImage` is not available.
+    fn clear_region_with_framebuffer(&self, offset: (u32, u32, u32), size: (u32, u32, u32),
+                                     data: ClearBufferData) -> Result<(), ClearTextureError>
+    {
+        {
+            let ctxt = self.texture.context.make_current();
+            if !(ctxt.version >= &Version(Api::Gl, 3, 0) ||
+                 ctxt.version >= &Version(Api::GlEs, 3, 0))
+            {
+                return Err(ClearTextureError::NotSupported);
+            }
+        }
+
+        let rect = Rect {
+            left: offset.0,
+            bottom: offset.1,
+            width: size.0,
+            height: size.1,
+        };
+
+        for layer in offset.2 .. offset.2 + size.2 {
+            let image = self.get_copy_image(layer).unwrap();
+            let attachments = image_attachments(image, self.texture.kind())
+                                                .validate(&*self.texture.context)
+                                                .map_err(|_| ClearTextureError::NotSupported)?;
+
+            ops::clear_buffer(&self.texture.context, &attachments, &rect, data);
+        }
+
+        Ok(())
+    }
+
     /// Uploads data to the texture from a buffer.
     ///
     /// # Panic
@@ -1151,6 +1782,7 @@ impl<'a> TextureAnyMipmap<'a> {
                                                   self.texture.requested_format, inverted).unwrap();
 
         let mut ctxt = self.texture.context.make_current();
+        self.texture.prepare_for_access(&mut ctxt, crate::TextureAccess::TextureUpdate);
 
         // binds the pixel buffer
         source.prepare_and_bind_for_pixel_unpack(&mut ctxt);
@@ -1315,10 +1947,19 @@ impl<'t> TextureMipmapExt for TextureAnyMipmap<'t> {
         assert!(!regen_mipmaps || level == 0);  // when regen_mipmaps is true, level must be 0!
         assert!(x_offset <= self.width);
         assert!(y_offset <= self.height.unwrap_or(1));
-        assert!(z_offset <= self.depth.unwrap_or(1));
+        // the layers of array textures and the faces of cubemaps are uploaded like the depth of
+        // 3D textures
+        let max_z = match self.texture.ty {
+            Dimensions::Texture2dArray { array_size, .. } => array_size,
+            Dimensions::Cubemap { .. } => 6,
+            Dimensions::CubemapArray { array_size, .. } => array_size * 6,
+            _ => self.depth.unwrap_or(1),
+        };
+
+        assert!(z_offset <= max_z);
         assert!(x_offset + width <= self.width);
         assert!(y_offset + height.unwrap_or(1) <= self.height.unwrap_or(1));
-        assert!(z_offset + depth.unwrap_or(1) <= self.depth.unwrap_or(1));
+        assert!(z_offset + depth.unwrap_or(1) <= max_z);
 
         if data.len() * mem::size_of::<P>() != data_bufsize
         {
@@ -1331,6 +1972,7 @@ impl<'t> TextureMipmapExt for TextureAnyMipmap<'t> {
                                                                                  .map_err(|_| ())?;
 
         let mut ctxt = self.texture.context.make_current();
+        self.texture.prepare_for_access(&mut ctxt, crate::TextureAccess::TextureUpdate);
 
         unsafe {
             if ctxt.state.pixel_store_unpack_alignment != 1 {
@@ -1341,8 +1983,55 @@ impl<'t> TextureMipmapExt for TextureAnyMipmap<'t> {
             BufferAny::unbind_pixel_unpack(&mut ctxt);
             let bind_point = self.texture.bind_to_current(&mut ctxt);
 
-            if bind_point == gl::TEXTURE_3D || bind_point == gl::TEXTURE_2D_ARRAY {
-                unimplemented!();
+            if bind_point == gl::TEXTURE_3D || bind_point == gl::TEXTURE_2D_ARRAY ||
+               bind_point == gl::TEXTURE_CUBE_MAP_ARRAY
+            {
+                if is_client_compressed {
+                    ctxt.gl.CompressedTexSubImage3D(bind_point, level as gl::types::GLint,
+                                                    x_offset as gl::types::GLint,
+                                                    y_offset as gl::types::GLint,
+                                                    z_offset as gl::types::GLint,
+                                                    width as gl::types::GLsizei,
+                                                    height.unwrap_or(1) as gl::types::GLsizei,
+                                                    depth.unwrap_or(1) as gl::types::GLsizei,
+                                                    client_format,
+                                                    data_bufsize as gl::types::GLsizei,
+                                                    data.as_ptr() as *const _);
+                } else {
+                    ctxt.gl.TexSubImage3D(bind_point, level as gl::types::GLint,
+                                          x_offset as gl::types::GLint,
+                                          y_offset as gl::types::GLint,
+                                          z_offset as gl::types::GLint,
+                                          width as gl::types::GLsizei,
+                                          height.unwrap_or(1) as gl::types::GLsizei,
+                                          depth.unwrap_or(1) as gl::types::GLsizei,
+                                          client_format, client_type,
+                                          data.as_ptr() as *const _);
+                }
+
+            } else if bind_point == gl::TEXTURE_CUBE_MAP {
+                // faces are uploaded one by one
+                assert!(depth.unwrap_or(1) == 1);
+                let face = gl::TEXTURE_CUBE_MAP_POSITIVE_X + z_offset;
+
+                if is_client_compressed {
+                    ctxt.gl.CompressedTexSubImage2D(face, level as gl::types::GLint,
+                                                    x_offset as gl::types::GLint,
+                                                    y_offset as gl::types::GLint,
+                                                    width as gl::types::GLsizei,
+                                                    height.unwrap_or(1) as gl::types::GLsizei,
+                                                    client_format,
+                                                    data_bufsize as gl::types::GLsizei,
+                                                    data.as_ptr() as *const _);
+                } else {
+                    ctxt.gl.TexSubImage2D(face, level as gl::types::GLint,
+                                          x_offset as gl::types::GLint,
+                                          y_offset as gl::types::GLint,
+                                          width as gl::types::GLsizei,
+                                          height.unwrap_or(1) as gl::types::GLsizei,
+                                          client_format, client_type,
+                                          data.as_ptr() as *const _);
+                }
 
             } else if bind_point == gl::TEXTURE_2D || bind_point == gl::TEXTURE_1D_ARRAY {
                 assert!(z_offset == 0);
@@ -1391,6 +2080,7 @@ impl<'t> TextureMipmapExt for TextureAnyMipmap<'t> {
         let level = self.level as i32;
 
         let mut ctxt = texture.context.make_current();
+        texture.prepare_for_access(&mut ctxt, crate::TextureAccess::TextureUpdate);
 
         unsafe {
             let bind_point = texture.bind_to_current(&mut ctxt);
@@ -1640,3 +2330,108 @@ impl<'a> TextureAnyImage<'a> {
         }
     }
 }
+
+/// Builds framebuffer attachments containing a single image, as a color, depth and/or stencil
+/// attachment depending on `kind`.
+fn image_attachments(image: TextureAnyImage<'_>, kind: TextureKind)
+                     -> fbo::FramebufferAttachments<'_>
+{
+    let image = fbo::RegularAttachment::Texture(image);
+    let mut colors = SmallVec::new();
+
+    let depth_stencil = match kind {
+        TextureKind::Depth => fbo::DepthStencilAttachments::DepthAttachment(image),
+        TextureKind::Stencil => fbo::DepthStencilAttachments::StencilAttachment(image),
+        TextureKind::DepthStencil => fbo::DepthStencilAttachments::DepthStencilAttachment(image),
+        _ => {
+            colors.push((0, image));
+            fbo::DepthStencilAttachments::None
+        },
+    };
+
+    fbo::FramebufferAttachments::Regular(fbo::FramebufferSpecificAttachments {
+        colors,
+        depth_stencil,
+    })
+}
+
+/// Returns true if the format request corresponds to a compressed format.
+fn is_compressed_request(format: TextureFormatRequest) -> bool {
+    matches!(format, TextureFormatRequest::AnyCompressed |
+                     TextureFormatRequest::AnyCompressedSrgb |
+                     TextureFormatRequest::Specific(TextureFormat::CompressedFormat(_)) |
+                     TextureFormatRequest::Specific(TextureFormat::CompressedSrgbFormat(_)))
+}
+
+/// Error that can happen when clearing a texture.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ClearTextureError {
+    /// The texture can't be cleared with this backend. This happens for compressed textures, or
+    /// if `glClearTexSubImage` is not available and the format can't be attached to a
+    /// framebuffer.
+    NotSupported,
+
+    /// The region is outside of the boundaries of the texture.
+    OutOfRange,
+
+    /// The kind of value doesn't match the kind of texture.
+    ValueKindMismatch,
+}
+
+impl fmt::Display for ClearTextureError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        use self::ClearTextureError::*;
+        let desc = match *self {
+            NotSupported =>
+                "Clearing this texture is not supported by the backend",
+            OutOfRange =>
+                "The region is outside of the boundaries of the texture",
+            ValueKindMismatch =>
+                "The kind of value doesn't match the kind of texture",
+        };
+        fmt.write_str(desc)
+    }
+}
+
+impl Error for ClearTextureError {}
+
+/// Error that can happen when copying a region between two textures.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CopyImageError {
+    /// The copy can't be done with this backend. This happens if `glCopyImageSubData` is not
+    /// available and the textures can't be blitted.
+    NotSupported,
+
+    /// The region is outside of the boundaries of one of the textures.
+    OutOfRange,
+
+    /// The formats of the two textures are not compatible.
+    IncompatibleFormats,
+
+    /// The region is not aligned to the blocks of a compressed texture.
+    UnalignedCompressedRegion,
+
+    /// The two textures don't have the same number of samples.
+    SampleCountMismatch,
+}
+
+impl fmt::Display for CopyImageError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        use self::CopyImageError::*;
+        let desc = match *self {
+            NotSupported =>
+                "Copying between these textures is not supported by the backend",
+            OutOfRange =>
+                "The region is outside of the boundaries of one of the textures",
+            IncompatibleFormats =>
+                "The formats of the two textures are not compatible",
+            UnalignedCompressedRegion =>
+                "The region is not aligned to the blocks of a compressed texture",
+            SampleCountMismatch =>
+                "The two textures don't have the same number of samples",
+        };
+        fmt.write_str(desc)
+    }
+}
+
+impl Error for CopyImageError {}
@@ -0,0 +1,777 @@
+/*!
+Compression of textures on the CPU.
+
+By default, creating a compressed texture from uncompressed data asks the OpenGL driver to
+compress the data. This is slow, often produces poor results, and isn't supported at all by many
+OpenGL ES drivers.
+
+When the `block_compression` feature is enabled, glium instead compresses the data on the CPU
+for the following formats:
+
+ - `S3tcDxt1NoAlpha` and `S3tcDxt1Alpha` (BC1).
+ - `S3tcDxt5Alpha` (BC3).
+ - `RgtcFormatU` (BC4) and `RgtcFormatUU` (BC5).
+ - `BptcUnorm4` (BC7).
+
+The sRGB equivalents of these formats are supported as well. The data must use one of the
+`U8`, `U8U8`, `U8U8U8` or `U8U8U8U8` client formats, otherwise the driver is used as usual.
+
+If the texture has mipmaps, they are generated from the main level with a box filter and
+encoded along with it. Blocks are encoded in parallel on all the available cores.
+
+```no_run
+# use glutin::surface::{ResizeableSurface, SurfaceTypeTrait};
+# fn example<T>(display: glium::Display<T>) where T: SurfaceTypeTrait + ResizeableSurface {
+use glium::texture::{CompressedFormat, CompressedMipmapsOption, CompressedTexture2d, RawImage2d};
+use glium::texture::compress::CompressionQuality;
+
+# let data = vec![0u8; 256 * 256 * 4];
+let image = RawImage2d::from_raw_rgba(data, (256, 256));
+let texture = CompressedTexture2d::with_format_and_quality(&display, image,
+                                                           CompressedFormat::BptcUnorm4,
+                                                           CompressedMipmapsOption::EmptyMipmaps,
+                                                           CompressionQuality::High).unwrap();
+# }
+```
+
+*/
+#![cfg(feature = "block_compression")]
+
+use std::borrow::Cow;
+use std::mem;
+use std::slice;
+use std::sync::Mutex;
+use std::thread;
+
+use smallvec::SmallVec;
+
+use crate::backend::Facade;
+use crate::image_format::{ClientFormat, ClientFormatAny, TextureFormat, TextureFormatRequest};
+use crate::image_format::{CompressedFormat, CompressedSrgbFormat};
+use crate::texture::any::{self, TextureAny};
+use crate::texture::{Dimensions, MipmapsOption, RawImage2d, TextureCreationError};
+use crate::TextureMipmapExt;
+
+/// Trade-off between the speed of the encoder and the quality of the result.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum CompressionQuality {
+    /// Uses the bounding box of the colors of each block.
+    Fast,
+    /// Uses the principal axis of the colors of each block.
+    #[default]
+    Normal,
+    /// Like `Normal`, but also refines the end points of each block.
+    High,
+}
+
+/// Returns true if the format can be compressed on the CPU.
+pub fn is_format_supported(format: TextureFormat) -> bool {
+    Encoder::from_format(format).is_some()
+}
+
+/// Compresses an image on the CPU.
+///
+/// The data is read as RGBA, missing components being replaced with `0` for green and blue and
+/// `255` for alpha. Returns `None` if the format or the client format of the image is not
+/// supported.
+///
+/// ## Panic
+///
+/// Panics if the length of the data doesn't match the dimensions of the image.
+pub fn compress(image: &RawImage2d<'_, u8>, format: TextureFormat,
+                quality: CompressionQuality) -> Option<Vec<u8>>
+{
+    let encoder = Encoder::from_format(format)?;
+    let image = Image::from_raw(&image.data, image.format, image.width, image.height)?;
+    encode_levels(&[image], encoder, quality).pop()
+}
+
+/// Creates a texture from uncompressed data, compressing it on the CPU.
+///
+/// Returns `Ok(None)` if the format can't be compressed on the CPU, in which case the caller
+/// must fall back to the driver.
+pub(crate) fn new_texture_2d<F: ?Sized, T>(facade: &F, format: TextureFormatRequest,
+                                           client_format: ClientFormat, data: &[T],
+                                           width: u32, height: u32, mipmaps: MipmapsOption,
+                                           quality: CompressionQuality)
+                                           -> Result<Option<TextureAny>, TextureCreationError>
+                                           where F: Facade
+{
+    let format = match format {
+        TextureFormatRequest::Specific(format) => format,
+        _ => return Ok(None),
+    };
+
+    let encoder = match Encoder::from_format(format) {
+        Some(encoder) => encoder,
+        None => return Ok(None),
+    };
+
+    // the driver is responsible for reporting unsupported formats
+    let (supported, compressed_format) = match format {
+        TextureFormat::CompressedFormat(format) => {
+            (format.is_supported(facade.get_context()), ClientFormatAny::CompressedFormat(format))
+        },
+        TextureFormat::CompressedSrgbFormat(format) => {
+            (format.is_supported(facade.get_context()),
+             ClientFormatAny::CompressedSrgbFormat(format))
+        },
+        _ => unreachable!(),
+    };
+
+    if !supported {
+        return Ok(None);
+    }
+
+    let data = unsafe { slice::from_raw_parts(data.as_ptr() as *const u8, mem::size_of_val(data)) };
+    let image = match Image::from_raw(data, client_format, width, height) {
+        Some(image) => image,
+        None => return Ok(None),
+    };
+
+    let num_levels = mipmaps.num_levels(width, Some(height), None);
+    let mut levels = vec![image];
+    while (levels.len() as u32) < num_levels {
+        let next = levels.last().unwrap().downsample();
+        levels.push(next);
+    }
+
+    let mipmaps = match mipmaps {
+        MipmapsOption::NoMipmap => MipmapsOption::NoMipmap,
+        _ => MipmapsOption::EmptyMipmapsMax(num_levels - 1),
+    };
+
+    let texture = any::new_texture::<_, u8>(facade, TextureFormatRequest::Specific(format), None,
+                                            mipmaps, Dimensions::Texture2d { width, height })?;
+
+    for (level, data) in encode_levels(&levels, encoder, quality).into_iter().enumerate() {
+        let image = &levels[level];
+        texture.mipmap(level as u32).unwrap()
+               .upload_texture(0, 0, 0, (compressed_format, Cow::Owned(data)), image.width,
+                               Some(image.height), None, false)
+               .map_err(|_| TextureCreationError::FormatNotSupported)?;
+    }
+
+    Ok(Some(texture))
+}
+
+/// An RGBA image with eight bits per component.
+struct Image {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl Image {
+    fn from_raw(data: &[u8], format: ClientFormat, width: u32, height: u32) -> Option<Image> {
+        let components = match format {
+            ClientFormat::U8 => 1,
+            ClientFormat::U8U8 => 2,
+            ClientFormat::U8U8U8 => 3,
+            ClientFormat::U8U8U8U8 => 4,
+            _ => return None,
+        };
+
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        assert_eq!(data.len(), width as usize * height as usize * components);
+
+        let data = data.chunks(components).flat_map(|texel| {
+            let mut rgba = [0, 0, 0, 255];
+            rgba[.. components].copy_from_slice(texel);
+            rgba
+        }).collect();
+
+        Some(Image { width, height, data })
+    }
+
+    #[inline]
+    fn get(&self, x: u32, y: u32) -> [u8; 4] {
+        let x = x.min(self.width - 1) as usize;
+        let y = y.min(self.height - 1) as usize;
+        let offset = (y * self.width as usize + x) * 4;
+        [self.data[offset], self.data[offset + 1], self.data[offset + 2], self.data[offset + 3]]
+    }
+
+    /// Returns the next mipmap level, using a box filter.
+    fn downsample(&self) -> Image {
+        let width = (self.width / 2).max(1);
+        let height = (self.height / 2).max(1);
+
+        let mut data = Vec::with_capacity(width as usize * height as usize * 4);
+        for y in 0 .. height {
+            for x in 0 .. width {
+                let texels = [self.get(x * 2, y * 2), self.get(x * 2 + 1, y * 2),
+                              self.get(x * 2, y * 2 + 1), self.get(x * 2 + 1, y * 2 + 1)];
+                for c in 0 .. 4 {
+                    let sum: u32 = texels.iter().map(|t| t[c] as u32).sum();
+                    data.push(((sum + 2) / 4) as u8);
+                }
+            }
+        }
+
+        Image { width, height, data }
+    }
+
+    /// Returns the 4x4 block at the given block coordinates. Texels outside of the image are
+    /// replaced with the nearest texel.
+    fn get_block(&self, block_x: u32, block_y: u32) -> [[u8; 4]; 16] {
+        let mut block = [[0; 4]; 16];
+        for (i, texel) in block.iter_mut().enumerate() {
+            *texel = self.get(block_x * 4 + i as u32 % 4, block_y * 4 + i as u32 / 4);
+        }
+        block
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Encoder {
+    Bc1,
+    Bc1Alpha,
+    Bc3,
+    Bc4,
+    Bc5,
+    Bc7,
+}
+
+impl Encoder {
+    fn from_format(format: TextureFormat) -> Option<Encoder> {
+        Some(match format {
+            TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt1NoAlpha) => Encoder::Bc1,
+            TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt1Alpha) => Encoder::Bc1Alpha,
+            TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt5Alpha) => Encoder::Bc3,
+            TextureFormat::CompressedFormat(CompressedFormat::RgtcFormatU) => Encoder::Bc4,
+            TextureFormat::CompressedFormat(CompressedFormat::RgtcFormatUU) => Encoder::Bc5,
+            TextureFormat::CompressedFormat(CompressedFormat::BptcUnorm4) => Encoder::Bc7,
+            TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt1NoAlpha) => Encoder::Bc1,
+            TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt1Alpha) => Encoder::Bc1Alpha,
+            TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt5Alpha) => Encoder::Bc3,
+            TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::Bptc) => Encoder::Bc7,
+            _ => return None,
+        })
+    }
+
+    /// Size in bytes of a block.
+    fn block_size(self) -> usize {
+        match self {
+            Encoder::Bc1 | Encoder::Bc1Alpha | Encoder::Bc4 => 8,
+            Encoder::Bc3 | Encoder::Bc5 | Encoder::Bc7 => 16,
+        }
+    }
+
+    fn encode(self, block: &[[u8; 4]; 16], quality: CompressionQuality, out: &mut [u8]) {
+        match self {
+            Encoder::Bc1 => out.copy_from_slice(&encode_bc1(block, quality, false)),
+            Encoder::Bc1Alpha => out.copy_from_slice(&encode_bc1(block, quality, true)),
+            Encoder::Bc3 => {
+                out[.. 8].copy_from_slice(&encode_bc4(&channel(block, 3), quality));
+                out[8 ..].copy_from_slice(&encode_bc1(block, quality, false));
+            },
+            Encoder::Bc4 => out.copy_from_slice(&encode_bc4(&channel(block, 0), quality)),
+            Encoder::Bc5 => {
+                out[.. 8].copy_from_slice(&encode_bc4(&channel(block, 0), quality));
+                out[8 ..].copy_from_slice(&encode_bc4(&channel(block, 1), quality));
+            },
+            Encoder::Bc7 => out.copy_from_slice(&encode_bc7(block, quality)),
+        }
+    }
+}
+
+/// Encodes all the images, spreading the rows of blocks of all the levels over all the cores.
+fn encode_levels(levels: &[Image], encoder: Encoder, quality: CompressionQuality) -> Vec<Vec<u8>> {
+    let block_size = encoder.block_size();
+
+    let mut outputs: Vec<Vec<u8>> = levels.iter().map(|image| {
+        let blocks = image.width.div_ceil(4) as usize * image.height.div_ceil(4) as usize;
+        vec![0; blocks * block_size]
+    }).collect();
+
+    let mut jobs = Vec::new();
+    for (image, output) in levels.iter().zip(outputs.iter_mut()) {
+        let row_size = image.width.div_ceil(4) as usize * block_size;
+        for (block_y, row) in output.chunks_mut(row_size).enumerate() {
+            jobs.push((image, block_y as u32, row));
+        }
+    }
+
+    let num_threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+                                                     .min(jobs.len());
+    let jobs = Mutex::new(jobs);
+
+    thread::scope(|scope| {
+        for _ in 0 .. num_threads {
+            scope.spawn(|| {
+                loop {
+                    let job = jobs.lock().unwrap().pop();
+                    let (image, block_y, row) = match job {
+                        Some(job) => job,
+                        None => break,
+                    };
+
+                    for (block_x, out) in row.chunks_mut(block_size).enumerate() {
+                        let block = image.get_block(block_x as u32, block_y);
+                        encoder.encode(&block, quality, out);
+                    }
+                }
+            });
+        }
+    });
+
+    outputs
+}
+
+/// Extracts one component of a block.
+#[inline]
+fn channel(block: &[[u8; 4]; 16], component: usize) -> [u8; 16] {
+    let mut values = [0; 16];
+    for (value, texel) in values.iter_mut().zip(block.iter()) {
+        *value = texel[component];
+    }
+    values
+}
+
+type Color = [f32; 4];
+
+#[inline]
+fn to_color(texel: &[u8; 4]) -> Color {
+    [texel[0] as f32, texel[1] as f32, texel[2] as f32, texel[3] as f32]
+}
+
+#[inline]
+fn distance(a: &Color, b: &Color, channels: usize) -> f32 {
+    (0 .. channels).map(|c| (a[c] - b[c]) * (a[c] - b[c])).sum()
+}
+
+#[inline]
+fn lerp(a: &Color, b: &Color, weight: f32) -> Color {
+    let mut result = [0.0; 4];
+    for c in 0 .. 4 {
+        result[c] = a[c] + (b[c] - a[c]) * weight;
+    }
+    result
+}
+
+/// Returns the index of the nearest color of the palette and the squared distance to it.
+#[inline]
+fn nearest(palette: &[Color], color: &Color, channels: usize) -> (usize, f32) {
+    let mut best = (0, f32::MAX);
+    for (index, entry) in palette.iter().enumerate() {
+        let error = distance(entry, color, channels);
+        if error < best.1 {
+            best = (index, error);
+        }
+    }
+    best
+}
+
+/// Computes the initial end points of a set of colors.
+fn initial_end_points(colors: &[Color], channels: usize, quality: CompressionQuality)
+                      -> (Color, Color)
+{
+    if quality == CompressionQuality::Fast {
+        let mut min = [255.0f32; 4];
+        let mut max = [0.0f32; 4];
+        for color in colors {
+            for c in 0 .. channels {
+                min[c] = min[c].min(color[c]);
+                max[c] = max[c].max(color[c]);
+            }
+        }
+
+        // moving the end points slightly inside the box reduces the average error
+        for c in 0 .. channels {
+            let inset = (max[c] - min[c]) / 16.0;
+            min[c] += inset;
+            max[c] -= inset;
+        }
+
+        return (max, min);
+    }
+
+    let count = colors.len() as f32;
+    let mut mean = [0.0f32; 4];
+    for color in colors {
+        for c in 0 .. channels {
+            mean[c] += color[c] / count;
+        }
+    }
+
+    let mut covariance = [[0.0f32; 4]; 4];
+    for color in colors {
+        for i in 0 .. channels {
+            for j in 0 .. channels {
+                covariance[i][j] += (color[i] - mean[i]) * (color[j] - mean[j]);
+            }
+        }
+    }
+
+    // the principal axis is found with a few steps of power iteration
+    let mut axis = [0.0f32; 4];
+    for value in axis.iter_mut().take(channels) {
+        *value = 1.0 / (channels as f32).sqrt();
+    }
+    for _ in 0 .. 8 {
+        let mut next = [0.0f32; 4];
+        for i in 0 .. channels {
+            for j in 0 .. channels {
+                next[i] += covariance[i][j] * axis[j];
+            }
+        }
+
+        let length = next.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if length < 1e-6 {
+            break;
+        }
+        for c in 0 .. channels {
+            axis[c] = next[c] / length;
+        }
+    }
+
+    let mut min = f32::MAX;
+    let mut max = f32::MIN;
+    for color in colors {
+        let projection: f32 = (0 .. channels).map(|c| (color[c] - mean[c]) * axis[c]).sum();
+        min = min.min(projection);
+        max = max.max(projection);
+    }
+
+    let mut start = [0.0; 4];
+    let mut end = [0.0; 4];
+    for c in 0 .. channels {
+        start[c] = (mean[c] + axis[c] * max).clamp(0.0, 255.0);
+        end[c] = (mean[c] + axis[c] * min).clamp(0.0, 255.0);
+    }
+    (start, end)
+}
+
+/// Finds the end points minimizing the squared error for the given palette indices, where
+/// `weights[index]` is the position of the palette entry between the two end points.
+fn refine_end_points(colors: &[Color], indices: &[usize], weights: &[f32], channels: usize)
+                     -> Option<(Color, Color)>
+{
+    let (mut aa, mut bb, mut ab) = (0.0f32, 0.0f32, 0.0f32);
+    let mut ax = [0.0f32; 4];
+    let mut bx = [0.0f32; 4];
+
+    for (color, &index) in colors.iter().zip(indices.iter()) {
+        let b = weights[index];
+        let a = 1.0 - b;
+        aa += a * a;
+        bb += b * b;
+        ab += a * b;
+        for c in 0 .. channels {
+            ax[c] += a * color[c];
+            bx[c] += b * color[c];
+        }
+    }
+
+    let determinant = aa * bb - ab * ab;
+    if determinant.abs() < 1e-6 {
+        return None;
+    }
+
+    let mut start = [0.0; 4];
+    let mut end = [0.0; 4];
+    for c in 0 .. channels {
+        start[c] = ((ax[c] * bb - bx[c] * ab) / determinant).clamp(0.0, 255.0);
+        end[c] = ((bx[c] * aa - ax[c] * ab) / determinant).clamp(0.0, 255.0);
+    }
+    Some((start, end))
+}
+
+#[inline]
+fn to_565(color: &Color) -> u16 {
+    let r = (color[0] * 31.0 / 255.0).round() as u16;
+    let g = (color[1] * 63.0 / 255.0).round() as u16;
+    let b = (color[2] * 31.0 / 255.0).round() as u16;
+    (r << 11) | (g << 5) | b
+}
+
+#[inline]
+fn from_565(value: u16) -> Color {
+    let r = (value >> 11) & 0x1f;
+    let g = (value >> 5) & 0x3f;
+    let b = value & 0x1f;
+    [((r << 3) | (r >> 2)) as f32, ((g << 2) | (g >> 4)) as f32, ((b << 3) | (b >> 2)) as f32, 255.0]
+}
+
+/// Result of encoding a BC1 block with some end points.
+struct Bc1Block {
+    bytes: [u8; 8],
+    indices: [usize; 16],
+    weights: &'static [f32],
+    error: f32,
+}
+
+fn encode_bc1(block: &[[u8; 4]; 16], quality: CompressionQuality, punch_through: bool) -> [u8; 8] {
+    let transparent: [bool; 16] = {
+        let mut transparent = [false; 16];
+        for (t, texel) in transparent.iter_mut().zip(block.iter()) {
+            *t = punch_through && texel[3] < 128;
+        }
+        transparent
+    };
+
+    let colors: SmallVec<[Color; 16]> = block.iter().zip(transparent.iter())
+                                             .filter(|&(_, &t)| !t)
+                                             .map(|(texel, _)| to_color(texel)).collect();
+
+    if colors.is_empty() {
+        // three-color mode with all the texels set to transparent black
+        return [0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff];
+    }
+
+    let three_colors = colors.len() < 16;
+    let (start, end) = initial_end_points(&colors, 3, quality);
+    let mut best = try_bc1(block, &transparent, &start, &end, three_colors);
+
+    if quality == CompressionQuality::High {
+        for _ in 0 .. 2 {
+            let indices: SmallVec<[usize; 16]> = best.indices.iter().zip(transparent.iter())
+                                                     .filter(|&(_, &t)| !t)
+                                                     .map(|(&i, _)| i).collect();
+            let (start, end) = match refine_end_points(&colors, &indices, best.weights, 3) {
+                Some(end_points) => end_points,
+                None => break,
+            };
+
+            let candidate = try_bc1(block, &transparent, &start, &end, three_colors);
+            if candidate.error >= best.error {
+                break;
+            }
+            best = candidate;
+        }
+    }
+
+    best.bytes
+}
+
+fn try_bc1(block: &[[u8; 4]; 16], transparent: &[bool; 16], start: &Color, end: &Color,
+           three_colors: bool) -> Bc1Block
+{
+    const FOUR_COLORS_WEIGHTS: [f32; 4] = [0.0, 1.0, 1.0 / 3.0, 2.0 / 3.0];
+    const THREE_COLORS_WEIGHTS: [f32; 3] = [0.0, 1.0, 0.5];
+
+    let mut color0 = to_565(start);
+    let mut color1 = to_565(end);
+
+    // the order of the end points selects the mode of the block
+    if (three_colors && color0 > color1) || (!three_colors && color0 < color1) {
+        mem::swap(&mut color0, &mut color1);
+    }
+
+    let weights: &'static [f32] = if color0 > color1 {
+        &FOUR_COLORS_WEIGHTS
+    } else {
+        &THREE_COLORS_WEIGHTS
+    };
+
+    let (start, end) = (from_565(color0), from_565(color1));
+    let palette: SmallVec<[Color; 4]> = weights.iter().map(|&w| lerp(&start, &end, w)).collect();
+
+    let mut indices = [0; 16];
+    let mut error = 0.0;
+    for (i, texel) in block.iter().enumerate() {
+        if transparent[i] {
+            indices[i] = 3;
+        } else {
+            let (index, e) = nearest(&palette, &to_color(texel), 3);
+            indices[i] = index;
+            error += e;
+        }
+    }
+
+    let mut bits = 0u32;
+    for (i, &index) in indices.iter().enumerate() {
+        bits |= (index as u32) << (i * 2);
+    }
+
+    let mut bytes = [0; 8];
+    bytes[0 .. 2].copy_from_slice(&color0.to_le_bytes());
+    bytes[2 .. 4].copy_from_slice(&color1.to_le_bytes());
+    bytes[4 .. 8].copy_from_slice(&bits.to_le_bytes());
+
+    Bc1Block { bytes, indices, weights, error }
+}
+
+fn encode_bc4(values: &[u8; 16], quality: CompressionQuality) -> [u8; 8] {
+    let min = *values.iter().min().unwrap();
+    let max = *values.iter().max().unwrap();
+
+    if min == max {
+        return [max, min, 0, 0, 0, 0, 0, 0];
+    }
+
+    let mut best = try_bc4(values, max, min);
+
+    if quality != CompressionQuality::Fast {
+        // the six-values mode has explicit 0 and 255 entries, which helps with blocks that
+        // contain these values alongside others
+        let inner = values.iter().filter(|&&v| v != 0 && v != 255);
+        if let (Some(&min), Some(&max)) = (inner.clone().min(), inner.max()) {
+            let candidate = try_bc4(values, min, max);
+            if candidate.1 < best.1 {
+                best = candidate;
+            }
+        }
+    }
+
+    if quality == CompressionQuality::High {
+        for inset_max in 0 .. 4u8 {
+            for inset_min in 0 .. 4u8 {
+                let (start, end) = (max.saturating_sub(inset_max), min.saturating_add(inset_min));
+                if start <= end {
+                    continue;
+                }
+
+                let candidate = try_bc4(values, start, end);
+                if candidate.1 < best.1 {
+                    best = candidate;
+                }
+            }
+        }
+    }
+
+    best.0
+}
+
+fn try_bc4(values: &[u8; 16], red0: u8, red1: u8) -> ([u8; 8], u32) {
+    let (r0, r1) = (red0 as u32, red1 as u32);
+
+    let palette: [u32; 8] = if red0 > red1 {
+        [r0, r1, (6 * r0 + r1 + 3) / 7, (5 * r0 + 2 * r1 + 3) / 7, (4 * r0 + 3 * r1 + 3) / 7,
+         (3 * r0 + 4 * r1 + 3) / 7, (2 * r0 + 5 * r1 + 3) / 7, (r0 + 6 * r1 + 3) / 7]
+    } else {
+        [r0, r1, (4 * r0 + r1 + 2) / 5, (3 * r0 + 2 * r1 + 2) / 5, (2 * r0 + 3 * r1 + 2) / 5,
+         (r0 + 4 * r1 + 2) / 5, 0, 255]
+    };
+
+    let mut bits = 0u64;
+    let mut error = 0;
+    for (i, &value) in values.iter().enumerate() {
+        let (index, e) = palette.iter().enumerate()
+                                .map(|(index, &entry)| (index, entry.abs_diff(value as u32)))
+                                .min_by_key(|&(_, e)| e).unwrap();
+        bits |= (index as u64) << (i * 3);
+        error += e * e;
+    }
+
+    let mut bytes = [0; 8];
+    bytes[0] = red0;
+    bytes[1] = red1;
+    bytes[2 ..].copy_from_slice(&bits.to_le_bytes()[.. 6]);
+    (bytes, error)
+}
+
+/// Encodes a BC7 block using mode 6, which has a single subset with RGBA end points of seven
+/// bits plus a p-bit, and four-bit indices.
+fn encode_bc7(block: &[[u8; 4]; 16], quality: CompressionQuality) -> [u8; 16] {
+    const WEIGHTS: [f32; 16] = [0.0, 4.0 / 64.0, 9.0 / 64.0, 13.0 / 64.0, 17.0 / 64.0,
+                                21.0 / 64.0, 26.0 / 64.0, 30.0 / 64.0, 34.0 / 64.0, 38.0 / 64.0,
+                                43.0 / 64.0, 47.0 / 64.0, 51.0 / 64.0, 55.0 / 64.0, 60.0 / 64.0,
+                                1.0];
+
+    let colors: SmallVec<[Color; 16]> = block.iter().map(to_color).collect();
+    let (start, end) = initial_end_points(&colors, 4, quality);
+    let mut best = try_bc7(&colors, &start, &end);
+
+    if quality == CompressionQuality::High {
+        for _ in 0 .. 2 {
+            let (start, end) = match refine_end_points(&colors, &best.1, &WEIGHTS, 4) {
+                Some(end_points) => end_points,
+                None => break,
+            };
+
+            let candidate = try_bc7(&colors, &start, &end);
+            if candidate.2 >= best.2 {
+                break;
+            }
+            best = candidate;
+        }
+    }
+
+    best.0
+}
+
+/// Quantizes an end point to seven bits per component plus a shared p-bit.
+fn quantize_bc7(color: &Color) -> ([u8; 4], u8) {
+    let mut best = ([0; 4], 0, f32::MAX);
+    for p_bit in 0 .. 2u8 {
+        let mut quantized = [0u8; 4];
+        let mut error = 0.0;
+        for c in 0 .. 4 {
+            let value = ((color[c] - p_bit as f32) / 2.0).round().clamp(0.0, 127.0) as u8;
+            let expanded = (value << 1 | p_bit) as f32;
+            quantized[c] = value;
+            error += (expanded - color[c]) * (expanded - color[c]);
+        }
+
+        if error < best.2 {
+            best = (quantized, p_bit, error);
+        }
+    }
+    (best.0, best.1)
+}
+
+fn try_bc7(colors: &[Color], start: &Color, end: &Color) -> ([u8; 16], [usize; 16], f32) {
+    const WEIGHTS: [u32; 16] = [0, 4, 9, 13, 17, 21, 26, 30, 34, 38, 43, 47, 51, 55, 60, 64];
+
+    let (mut end0, mut p0) = quantize_bc7(start);
+    let (mut end1, mut p1) = quantize_bc7(end);
+
+    let expand = |end: &[u8; 4], p: u8| -> [u32; 4] {
+        let mut result = [0; 4];
+        for c in 0 .. 4 {
+            result[c] = (end[c] << 1 | p) as u32;
+        }
+        result
+    };
+
+    let (e0, e1) = (expand(&end0, p0), expand(&end1, p1));
+    let palette: SmallVec<[Color; 16]> = WEIGHTS.iter().map(|&w| {
+        let mut color = [0.0; 4];
+        for c in 0 .. 4 {
+            color[c] = (((64 - w) * e0[c] + w * e1[c] + 32) >> 6) as f32;
+        }
+        color
+    }).collect();
+
+    let mut indices = [0; 16];
+    let mut error = 0.0;
+    for (i, color) in colors.iter().enumerate() {
+        let (index, e) = nearest(&palette, color, 4);
+        indices[i] = index;
+        error += e;
+    }
+
+    // the most significant bit of the index of the first texel is implicitly zero
+    if indices[0] >= 8 {
+        mem::swap(&mut end0, &mut end1);
+        mem::swap(&mut p0, &mut p1);
+        for index in indices.iter_mut() {
+            *index = 15 - *index;
+        }
+    }
+
+    let mut bits = 1u128 << 6;
+    let mut position = 7;
+    let mut write = |value: u128, count: u32| {
+        bits |= value << position;
+        position += count;
+    };
+
+    for c in 0 .. 4 {
+        write(end0[c] as u128, 7);
+        write(end1[c] as u128, 7);
+    }
+    write(p0 as u128, 1);
+    write(p1 as u128, 1);
+    write(indices[0] as u128, 3);
+    for &index in &indices[1 ..] {
+        write(index as u128, 4);
+    }
+
+    (bits.to_le_bytes(), indices, error)
+}
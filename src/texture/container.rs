@@ -0,0 +1,749 @@
+/*!
+Loading textures from KTX2 and DDS container files.
+
+Container files store the data of a texture, usually pre-compressed, with all its mipmap levels,
+array layers and cubemap faces. This module parses these files and uploads their content to
+a new texture.
+
+```no_run
+# use glutin::surface::{ResizeableSurface, SurfaceTypeTrait};
+# fn example<T>(display: glium::Display<T>) where T: SurfaceTypeTrait + ResizeableSurface {
+use glium::texture::container::{Container, ContainerTexture};
+
+let data = std::fs::read("texture.ktx2").unwrap();
+let container = Container::parse(&data).unwrap();
+
+let texture = match container.create_texture(&display).unwrap() {
+    ContainerTexture::CompressedSrgbTexture2d(texture) => texture,
+    _ => panic!("expected a compressed 2D texture"),
+};
+# }
+```
+
+Two-dimensional textures, 2D array textures, cubemaps and cubemap arrays are supported. The
+KTX2 files must not use supercompression, and the DDS files must either use a FourCC code or
+a `DX10` header for block-compressed formats.
+
+The images are uploaded as they are stored in the file, without flipping them. KTX2 and DDS
+files usually store the rows from top to bottom, while OpenGL expects the first row to be the
+bottom one, so the texture coordinates must be flipped vertically when sampling these textures.
+
+A KTX2 file with a level count of 0 asks the application to generate the mipmaps. Only the base
+level of these files is loaded, and the texture has no other mipmap level.
+
+This module is only available if the `texture_container` feature is enabled.
+
+*/
+#![cfg(feature = "texture_container")]
+
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+
+use crate::backend::Facade;
+use crate::image_format::{ClientFormat, ClientFormatAny, TextureFormatRequest};
+use crate::texture::any::{self, TextureAny};
+use crate::texture::{CompressedFormat, CompressedSrgbFormat, SrgbFormat, UncompressedFloatFormat};
+use crate::texture::{Dimensions, MipmapsOption, TextureCreationError, TextureViewTarget};
+use crate::texture::{Texture2d, SrgbTexture2d, CompressedTexture2d, CompressedSrgbTexture2d};
+use crate::texture::{Texture2dArray, SrgbTexture2dArray, CompressedTexture2dArray};
+use crate::texture::{CompressedSrgbTexture2dArray, Cubemap, SrgbCubemap, CompressedCubemap};
+use crate::texture::{CompressedSrgbCubemap, CubemapArray, SrgbCubemapArray};
+use crate::texture::{CompressedCubemapArray, CompressedSrgbCubemapArray};
+use crate::TextureMipmapExt;
+
+const KTX2_IDENTIFIER: [u8; 12] = [0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB,
+                                   0x0D, 0x0A, 0x1A, 0x0A];
+const DDS_MAGIC: [u8; 4] = *b"DDS ";
+
+/// Error that can happen when parsing a container file or loading it into a texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerError {
+    /// The data doesn't start with the identifier of a KTX2 or DDS file.
+    UnknownContainer,
+
+    /// The file is truncated or its header is invalid.
+    InvalidData,
+
+    /// The format of the data has no equivalent in glium.
+    ///
+    /// The code is the `VkFormat` for KTX2 files, and the DXGI format or the FourCC code for DDS
+    /// files.
+    UnsupportedFormat(u32),
+
+    /// The KTX2 file uses a supercompression scheme.
+    UnsupportedSupercompression(u32),
+
+    /// The file contains a one-dimensional or a three-dimensional texture.
+    UnsupportedDimensions,
+
+    /// Error while creating the texture.
+    TextureCreationError(TextureCreationError),
+}
+
+impl fmt::Display for ContainerError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::ContainerError::*;
+        let desc = match *self {
+            UnknownContainer =>
+                "The data is not a KTX2 or DDS file",
+            InvalidData =>
+                "The file is truncated or its header is invalid",
+            UnsupportedFormat(_) =>
+                "The format of the data is not supported",
+            UnsupportedSupercompression(_) =>
+                "The supercompression scheme of the file is not supported",
+            UnsupportedDimensions =>
+                "The file contains a one-dimensional or three-dimensional texture",
+            TextureCreationError(_) =>
+                "Error while creating the texture",
+        };
+        match *self {
+            UnsupportedFormat(code) | UnsupportedSupercompression(code) =>
+                write!(fmt, "{}: {}", desc, code),
+            _ =>
+                fmt.write_str(desc),
+        }
+    }
+}
+
+impl Error for ContainerError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            ContainerError::TextureCreationError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<TextureCreationError> for ContainerError {
+    #[inline]
+    fn from(err: TextureCreationError) -> ContainerError {
+        ContainerError::TextureCreationError(err)
+    }
+}
+
+/// Format of the data of a container file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerFormat {
+    /// Uncompressed data. The client format describes the layout of a texel in the file.
+    Uncompressed(UncompressedFloatFormat, ClientFormat),
+    /// Uncompressed sRGB data.
+    Srgb(SrgbFormat, ClientFormat),
+    /// Block-compressed data.
+    Compressed(CompressedFormat),
+    /// Block-compressed sRGB data.
+    CompressedSrgb(CompressedSrgbFormat),
+}
+
+impl ContainerFormat {
+    /// Returns the size in bytes of an image of this format, or `None` if it overflows.
+    fn get_image_size(&self, width: u32, height: u32) -> Option<usize> {
+        match self.to_client_format() {
+            ClientFormatAny::ClientFormat(format) => {
+                format.get_size().checked_mul(width as usize)?.checked_mul(height as usize)
+            },
+            format => {
+                let (block_width, block_height, block_size) = format.get_block_layout()?;
+                let blocks = (width.div_ceil(block_width) as usize)
+                                        .checked_mul(height.div_ceil(block_height) as usize)?;
+                blocks.checked_mul(block_size)
+            },
+        }
+    }
+
+    /// Returns the total size in bytes of all the images of a file, or `None` if it overflows.
+    fn get_payload_size(&self, width: u32, height: u32, levels: u32, images_per_level: u32)
+                        -> Option<usize>
+    {
+        (0 .. levels).try_fold(0usize, |total, level| {
+            let (level_width, level_height) = level_dimensions(width, height, level);
+            let size = self.get_image_size(level_width, level_height)?
+                           .checked_mul(images_per_level as usize)?;
+            total.checked_add(size)
+        })
+    }
+
+    fn to_client_format(self) -> ClientFormatAny {
+        match self {
+            ContainerFormat::Uncompressed(_, client) => ClientFormatAny::ClientFormat(client),
+            ContainerFormat::Srgb(_, client) => ClientFormatAny::ClientFormat(client),
+            ContainerFormat::Compressed(format) => ClientFormatAny::CompressedFormat(format),
+            ContainerFormat::CompressedSrgb(format) => ClientFormatAny::CompressedSrgbFormat(format),
+        }
+    }
+
+    fn to_format_request(self) -> TextureFormatRequest {
+        TextureFormatRequest::Specific(match self {
+            ContainerFormat::Uncompressed(format, _) => format.to_texture_format(),
+            ContainerFormat::Srgb(format, _) => format.to_texture_format(),
+            ContainerFormat::Compressed(format) => format.to_texture_format(),
+            ContainerFormat::CompressedSrgb(format) => format.to_texture_format(),
+        })
+    }
+}
+
+/// A texture loaded from a container file, whose type depends on the content of the file.
+#[derive(Debug)]
+pub enum ContainerTexture {
+    /// An uncompressed two-dimensional texture.
+    Texture2d(Texture2d),
+    /// An uncompressed two-dimensional sRGB texture.
+    SrgbTexture2d(SrgbTexture2d),
+    /// A compressed two-dimensional texture.
+    CompressedTexture2d(CompressedTexture2d),
+    /// A compressed two-dimensional sRGB texture.
+    CompressedSrgbTexture2d(CompressedSrgbTexture2d),
+    /// An uncompressed array of two-dimensional textures.
+    Texture2dArray(Texture2dArray),
+    /// An uncompressed array of two-dimensional sRGB textures.
+    SrgbTexture2dArray(SrgbTexture2dArray),
+    /// A compressed array of two-dimensional textures.
+    CompressedTexture2dArray(CompressedTexture2dArray),
+    /// A compressed array of two-dimensional sRGB textures.
+    CompressedSrgbTexture2dArray(CompressedSrgbTexture2dArray),
+    /// An uncompressed cubemap.
+    Cubemap(Cubemap),
+    /// An uncompressed sRGB cubemap.
+    SrgbCubemap(SrgbCubemap),
+    /// A compressed cubemap.
+    CompressedCubemap(CompressedCubemap),
+    /// A compressed sRGB cubemap.
+    CompressedSrgbCubemap(CompressedSrgbCubemap),
+    /// An uncompressed array of cubemaps.
+    CubemapArray(CubemapArray),
+    /// An uncompressed array of sRGB cubemaps.
+    SrgbCubemapArray(SrgbCubemapArray),
+    /// A compressed array of cubemaps.
+    CompressedCubemapArray(CompressedCubemapArray),
+    /// A compressed array of sRGB cubemaps.
+    CompressedSrgbCubemapArray(CompressedSrgbCubemapArray),
+}
+
+impl ContainerTexture {
+    /// Returns the texture, whatever its type.
+    pub fn get_texture(&self) -> &TextureAny {
+        match *self {
+            ContainerTexture::Texture2d(ref t) => t,
+            ContainerTexture::SrgbTexture2d(ref t) => t,
+            ContainerTexture::CompressedTexture2d(ref t) => t,
+            ContainerTexture::CompressedSrgbTexture2d(ref t) => t,
+            ContainerTexture::Texture2dArray(ref t) => t,
+            ContainerTexture::SrgbTexture2dArray(ref t) => t,
+            ContainerTexture::CompressedTexture2dArray(ref t) => t,
+            ContainerTexture::CompressedSrgbTexture2dArray(ref t) => t,
+            ContainerTexture::Cubemap(ref t) => t,
+            ContainerTexture::SrgbCubemap(ref t) => t,
+            ContainerTexture::CompressedCubemap(ref t) => t,
+            ContainerTexture::CompressedSrgbCubemap(ref t) => t,
+            ContainerTexture::CubemapArray(ref t) => t,
+            ContainerTexture::SrgbCubemapArray(ref t) => t,
+            ContainerTexture::CompressedCubemapArray(ref t) => t,
+            ContainerTexture::CompressedSrgbCubemapArray(ref t) => t,
+        }
+    }
+}
+
+/// A parsed KTX2 or DDS file.
+///
+/// The images are borrowed from the data of the file.
+#[derive(Debug, Clone)]
+pub struct Container<'a> {
+    format: ContainerFormat,
+    dimensions: Dimensions,
+    levels: u32,
+    // number of layers times number of faces
+    images_per_level: u32,
+    // images ordered by level, then layer, then face
+    images: Vec<&'a [u8]>,
+}
+
+impl<'a> Container<'a> {
+    /// Parses a KTX2 or DDS file, depending on its identifier.
+    pub fn parse(data: &'a [u8]) -> Result<Container<'a>, ContainerError> {
+        if data.starts_with(&KTX2_IDENTIFIER) {
+            Container::parse_ktx2(data)
+        } else if data.starts_with(&DDS_MAGIC) {
+            Container::parse_dds(data)
+        } else {
+            Err(ContainerError::UnknownContainer)
+        }
+    }
+
+    /// Parses a KTX2 file.
+    pub fn parse_ktx2(data: &'a [u8]) -> Result<Container<'a>, ContainerError> {
+        if !data.starts_with(&KTX2_IDENTIFIER) {
+            return Err(ContainerError::UnknownContainer);
+        }
+
+        let vk_format = read_u32(data, 12)?;
+        let width = read_u32(data, 20)?;
+        let height = read_u32(data, 24)?;
+        let depth = read_u32(data, 28)?;
+        let layer_count = read_u32(data, 32)?;
+        let face_count = read_u32(data, 36)?;
+        let level_count = read_u32(data, 40)?;
+        let supercompression = read_u32(data, 44)?;
+
+        if supercompression != 0 {
+            return Err(ContainerError::UnsupportedSupercompression(supercompression));
+        }
+
+        let format = ktx2_format(vk_format).ok_or(ContainerError::UnsupportedFormat(vk_format))?;
+
+        if height == 0 || depth != 0 {
+            return Err(ContainerError::UnsupportedDimensions);
+        }
+
+        if face_count != 1 && face_count != 6 {
+            return Err(ContainerError::InvalidData);
+        }
+
+        let layers = if layer_count == 0 { None } else { Some(layer_count) };
+        let dimensions = get_dimensions(width, height, layers, face_count == 6)?;
+
+        // a level count of 0 means that the mipmaps should be generated by the application,
+        // in which case only the base level is loaded
+        let levels = level_count.max(1);
+        check_levels(width, height, levels)?;
+
+        let images_per_level = layer_count.max(1).checked_mul(face_count)
+                                                 .ok_or(ContainerError::InvalidData)?;
+        let image_count = check_payload(format, width, height, levels, images_per_level,
+                                        data.len())?;
+
+        let mut images = Vec::with_capacity(image_count);
+
+        for level in 0 .. levels {
+            let index = 80 + level as usize * 24;
+            let offset = read_u64(data, index)?;
+            let length = read_u64(data, index + 8)?;
+
+            let (level_width, level_height) = level_dimensions(width, height, level);
+            let image_size = format.get_image_size(level_width, level_height)
+                                   .ok_or(ContainerError::InvalidData)?;
+
+            if Some(length) != image_size.checked_mul(images_per_level as usize) {
+                return Err(ContainerError::InvalidData);
+            }
+
+            images.extend(slice(data, offset, length)?.chunks_exact(image_size));
+        }
+
+        Container::new(format, dimensions, levels, images_per_level, images)
+    }
+
+    /// Parses a DDS file.
+    pub fn parse_dds(data: &'a [u8]) -> Result<Container<'a>, ContainerError> {
+        const DDSD_MIPMAPCOUNT: u32 = 0x20000;
+        const DDPF_ALPHAPIXELS: u32 = 0x1;
+        const DDPF_FOURCC: u32 = 0x4;
+        const DDPF_RGB: u32 = 0x40;
+        const DDSCAPS2_CUBEMAP: u32 = 0x200;
+        const DDSCAPS2_VOLUME: u32 = 0x200000;
+        const DDS_RESOURCE_MISC_TEXTURECUBE: u32 = 0x4;
+        const DDS_DIMENSION_TEXTURE2D: u32 = 3;
+
+        if !data.starts_with(&DDS_MAGIC) {
+            return Err(ContainerError::UnknownContainer);
+        }
+
+        if read_u32(data, 4)? != 124 {
+            return Err(ContainerError::InvalidData);
+        }
+
+        let flags = read_u32(data, 8)?;
+        let height = read_u32(data, 12)?;
+        let width = read_u32(data, 16)?;
+        let mipmap_count = read_u32(data, 28)?;
+        let pixel_flags = read_u32(data, 80)?;
+        let fourcc = read_u32(data, 84)?;
+        let caps2 = read_u32(data, 112)?;
+
+        if caps2 & DDSCAPS2_VOLUME != 0 {
+            return Err(ContainerError::UnsupportedDimensions);
+        }
+
+        let levels = if flags & DDSD_MIPMAPCOUNT != 0 { mipmap_count.max(1) } else { 1 };
+
+        let (format, layers, cubemap, data_offset) = if pixel_flags & DDPF_FOURCC != 0 &&
+                                                        fourcc == u32::from_le_bytes(*b"DX10")
+        {
+            let dxgi_format = read_u32(data, 128)?;
+            let dimension = read_u32(data, 132)?;
+            let misc_flags = read_u32(data, 136)?;
+            let array_size = read_u32(data, 140)?;
+
+            if dimension != DDS_DIMENSION_TEXTURE2D {
+                return Err(ContainerError::UnsupportedDimensions);
+            }
+
+            let format = dxgi_format_to_format(dxgi_format)
+                                        .ok_or(ContainerError::UnsupportedFormat(dxgi_format))?;
+            let layers = if array_size > 1 { Some(array_size) } else { None };
+            (format, layers, misc_flags & DDS_RESOURCE_MISC_TEXTURECUBE != 0, 148)
+
+        } else if pixel_flags & DDPF_FOURCC != 0 {
+            let alpha = pixel_flags & DDPF_ALPHAPIXELS != 0;
+            let format = fourcc_to_format(fourcc, alpha)
+                                        .ok_or(ContainerError::UnsupportedFormat(fourcc))?;
+            (format, None, caps2 & DDSCAPS2_CUBEMAP != 0, 128)
+
+        } else if pixel_flags & DDPF_RGB != 0 && read_u32(data, 88)? == 32 &&
+                  read_u32(data, 92)? == 0xff && read_u32(data, 96)? == 0xff00 &&
+                  read_u32(data, 100)? == 0xff0000 && read_u32(data, 104)? == 0xff000000
+        {
+            let format = ContainerFormat::Uncompressed(UncompressedFloatFormat::U8U8U8U8,
+                                                       ClientFormat::U8U8U8U8);
+            (format, None, caps2 & DDSCAPS2_CUBEMAP != 0, 128)
+
+        } else {
+            return Err(ContainerError::UnsupportedFormat(fourcc));
+        };
+
+        let dimensions = get_dimensions(width, height, layers, cubemap)?;
+        let faces = if cubemap { 6 } else { 1 };
+        let images_per_level = layers.unwrap_or(1).checked_mul(faces)
+                                                   .ok_or(ContainerError::InvalidData)?;
+
+        check_levels(width, height, levels)?;
+        let image_count = check_payload(format, width, height, levels, images_per_level,
+                                        data.len().saturating_sub(data_offset))?;
+
+        // DDS files store all the mipmap levels of a face one after the other
+        let mut images = vec![&data[0 .. 0]; image_count];
+        let mut offset = data_offset;
+
+        for image in 0 .. images_per_level {
+            for level in 0 .. levels {
+                let (level_width, level_height) = level_dimensions(width, height, level);
+                let image_size = format.get_image_size(level_width, level_height)
+                                       .ok_or(ContainerError::InvalidData)?;
+
+                images[(level * images_per_level + image) as usize] =
+                                                            slice(data, offset, image_size)?;
+                offset += image_size;
+            }
+        }
+
+        Container::new(format, dimensions, levels, images_per_level, images)
+    }
+
+    fn new(format: ContainerFormat, dimensions: Dimensions, levels: u32, images_per_level: u32,
+           images: Vec<&'a [u8]>) -> Result<Container<'a>, ContainerError>
+    {
+        Ok(Container {
+            format,
+            dimensions,
+            levels,
+            images_per_level,
+            images,
+        })
+    }
+
+    /// Returns the format of the data.
+    #[inline]
+    pub fn get_format(&self) -> ContainerFormat {
+        self.format
+    }
+
+    /// Returns the type and dimensions of the texture.
+    #[inline]
+    pub fn get_dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    /// Returns the number of mipmap levels stored in the file.
+    #[inline]
+    pub fn get_mipmap_levels(&self) -> u32 {
+        self.levels
+    }
+
+    /// Returns the data of an image.
+    ///
+    /// For cubemaps, `layer` is the index of the face, with six faces per cubemap for
+    /// cubemap arrays. Returns `None` if out of range.
+    #[inline]
+    pub fn get_image(&self, level: u32, layer: u32) -> Option<&'a [u8]> {
+        if level >= self.levels || layer >= self.images_per_level {
+            return None;
+        }
+
+        Some(self.images[(level * self.images_per_level + layer) as usize])
+    }
+
+    /// Creates a texture and uploads all the mipmap levels, layers and faces of the file.
+    ///
+    /// The rows of the images are uploaded in the order of the file, which is usually from top
+    /// to bottom. See the module documentation.
+    pub fn create_texture<F: ?Sized>(&self, facade: &F) -> Result<ContainerTexture, ContainerError>
+                                   where F: Facade
+    {
+        let mipmaps = MipmapsOption::EmptyMipmapsMax(self.levels - 1);
+        let texture = any::new_texture::<_, u8>(facade, self.format.to_format_request(), None,
+                                                mipmaps, self.dimensions)?;
+
+        let client_format = self.format.to_client_format();
+
+        for level in 0 .. self.levels {
+            let mipmap = texture.mipmap(level).unwrap();
+            let width = mipmap.get_width();
+            let height = mipmap.get_height().unwrap_or(1);
+
+            for layer in 0 .. self.images_per_level {
+                let data = self.get_image(level, layer).unwrap();
+                mipmap.upload_texture(0, 0, layer, (client_format, Cow::Borrowed(data)), width,
+                                      Some(height), None, false)
+                      .map_err(|_| TextureCreationError::FormatNotSupported)?;
+            }
+        }
+
+        Ok(match (self.format, self.dimensions) {
+            (ContainerFormat::Uncompressed(..), Dimensions::Texture2d { .. }) => {
+                ContainerTexture::Texture2d(Texture2d::from_any(texture))
+            },
+            (ContainerFormat::Srgb(..), Dimensions::Texture2d { .. }) => {
+                ContainerTexture::SrgbTexture2d(SrgbTexture2d::from_any(texture))
+            },
+            (ContainerFormat::Compressed(_), Dimensions::Texture2d { .. }) => {
+                ContainerTexture::CompressedTexture2d(CompressedTexture2d::from_any(texture))
+            },
+            (ContainerFormat::CompressedSrgb(_), Dimensions::Texture2d { .. }) => {
+                ContainerTexture::CompressedSrgbTexture2d(CompressedSrgbTexture2d::from_any(texture))
+            },
+            (ContainerFormat::Uncompressed(..), Dimensions::Texture2dArray { .. }) => {
+                ContainerTexture::Texture2dArray(Texture2dArray::from_any(texture))
+            },
+            (ContainerFormat::Srgb(..), Dimensions::Texture2dArray { .. }) => {
+                ContainerTexture::SrgbTexture2dArray(SrgbTexture2dArray::from_any(texture))
+            },
+            (ContainerFormat::Compressed(_), Dimensions::Texture2dArray { .. }) => {
+                ContainerTexture::CompressedTexture2dArray(CompressedTexture2dArray::from_any(texture))
+            },
+            (ContainerFormat::CompressedSrgb(_), Dimensions::Texture2dArray { .. }) => {
+                ContainerTexture::CompressedSrgbTexture2dArray(
+                                                CompressedSrgbTexture2dArray::from_any(texture))
+            },
+            (ContainerFormat::Uncompressed(..), Dimensions::Cubemap { .. }) => {
+                ContainerTexture::Cubemap(Cubemap::from_any(texture))
+            },
+            (ContainerFormat::Srgb(..), Dimensions::Cubemap { .. }) => {
+                ContainerTexture::SrgbCubemap(SrgbCubemap::from_any(texture))
+            },
+            (ContainerFormat::Compressed(_), Dimensions::Cubemap { .. }) => {
+                ContainerTexture::CompressedCubemap(CompressedCubemap::from_any(texture))
+            },
+            (ContainerFormat::CompressedSrgb(_), Dimensions::Cubemap { .. }) => {
+                ContainerTexture::CompressedSrgbCubemap(CompressedSrgbCubemap::from_any(texture))
+            },
+            (ContainerFormat::Uncompressed(..), Dimensions::CubemapArray { .. }) => {
+                ContainerTexture::CubemapArray(CubemapArray::from_any(texture))
+            },
+            (ContainerFormat::Srgb(..), Dimensions::CubemapArray { .. }) => {
+                ContainerTexture::SrgbCubemapArray(SrgbCubemapArray::from_any(texture))
+            },
+            (ContainerFormat::Compressed(_), Dimensions::CubemapArray { .. }) => {
+                ContainerTexture::CompressedCubemapArray(CompressedCubemapArray::from_any(texture))
+            },
+            (ContainerFormat::CompressedSrgb(_), Dimensions::CubemapArray { .. }) => {
+                ContainerTexture::CompressedSrgbCubemapArray(
+                                                CompressedSrgbCubemapArray::from_any(texture))
+            },
+            _ => unreachable!(),
+        })
+    }
+}
+
+/// Builds the dimensions of a texture, checking that cubemaps are square.
+fn get_dimensions(width: u32, height: u32, layers: Option<u32>, cubemap: bool)
+                  -> Result<Dimensions, ContainerError>
+{
+    if width == 0 || height == 0 || layers == Some(0) {
+        return Err(ContainerError::InvalidData);
+    }
+
+    if cubemap && width != height {
+        return Err(ContainerError::InvalidData);
+    }
+
+    Ok(match (layers, cubemap) {
+        (None, false) => Dimensions::Texture2d { width, height },
+        (Some(array_size), false) => Dimensions::Texture2dArray { width, height, array_size },
+        (None, true) => Dimensions::Cubemap { dimension: width },
+        (Some(array_size), true) => Dimensions::CubemapArray { dimension: width, array_size },
+    })
+}
+
+/// Checks that the number of levels doesn't exceed what the dimensions allow.
+fn check_levels(width: u32, height: u32, levels: u32) -> Result<(), ContainerError> {
+    let max_levels = 32 - width.max(height).leading_zeros();
+    if levels > max_levels {
+        return Err(ContainerError::InvalidData);
+    }
+
+    Ok(())
+}
+
+/// Checks that the images of a file fit in the available data, and returns their number.
+fn check_payload(format: ContainerFormat, width: u32, height: u32, levels: u32,
+                 images_per_level: u32, available: usize) -> Result<usize, ContainerError>
+{
+    let image_count = levels.checked_mul(images_per_level).ok_or(ContainerError::InvalidData)?;
+
+    match format.get_payload_size(width, height, levels, images_per_level) {
+        Some(size) if size <= available => Ok(image_count as usize),
+        _ => Err(ContainerError::InvalidData),
+    }
+}
+
+/// Returns the dimensions of a mipmap level.
+#[inline]
+fn level_dimensions(width: u32, height: u32, level: u32) -> (u32, u32) {
+    ((width >> level).max(1), (height >> level).max(1))
+}
+
+/// Maps a `VkFormat` to a format.
+fn ktx2_format(vk_format: u32) -> Option<ContainerFormat> {
+    use self::ContainerFormat::*;
+
+    Some(match vk_format {
+        9 => Uncompressed(UncompressedFloatFormat::U8, ClientFormat::U8),
+        16 => Uncompressed(UncompressedFloatFormat::U8U8, ClientFormat::U8U8),
+        23 => Uncompressed(UncompressedFloatFormat::U8U8U8, ClientFormat::U8U8U8),
+        29 => Srgb(SrgbFormat::U8U8U8, ClientFormat::U8U8U8),
+        37 => Uncompressed(UncompressedFloatFormat::U8U8U8U8, ClientFormat::U8U8U8U8),
+        43 => Srgb(SrgbFormat::U8U8U8U8, ClientFormat::U8U8U8U8),
+        76 => Uncompressed(UncompressedFloatFormat::F16, ClientFormat::F16),
+        83 => Uncompressed(UncompressedFloatFormat::F16F16, ClientFormat::F16F16),
+        97 => Uncompressed(UncompressedFloatFormat::F16F16F16F16, ClientFormat::F16F16F16F16),
+        100 => Uncompressed(UncompressedFloatFormat::F32, ClientFormat::F32),
+        103 => Uncompressed(UncompressedFloatFormat::F32F32, ClientFormat::F32F32),
+        109 => Uncompressed(UncompressedFloatFormat::F32F32F32F32, ClientFormat::F32F32F32F32),
+        131 => Compressed(CompressedFormat::S3tcDxt1NoAlpha),
+        132 => CompressedSrgb(CompressedSrgbFormat::S3tcDxt1NoAlpha),
+        133 => Compressed(CompressedFormat::S3tcDxt1Alpha),
+        134 => CompressedSrgb(CompressedSrgbFormat::S3tcDxt1Alpha),
+        135 => Compressed(CompressedFormat::S3tcDxt3Alpha),
+        136 => CompressedSrgb(CompressedSrgbFormat::S3tcDxt3Alpha),
+        137 => Compressed(CompressedFormat::S3tcDxt5Alpha),
+        138 => CompressedSrgb(CompressedSrgbFormat::S3tcDxt5Alpha),
+        139 => Compressed(CompressedFormat::RgtcFormatU),
+        140 => Compressed(CompressedFormat::RgtcFormatI),
+        141 => Compressed(CompressedFormat::RgtcFormatUU),
+        142 => Compressed(CompressedFormat::RgtcFormatII),
+        143 => Compressed(CompressedFormat::BptcUnsignedFloat3),
+        144 => Compressed(CompressedFormat::BptcSignedFloat3),
+        145 => Compressed(CompressedFormat::BptcUnorm4),
+        146 => CompressedSrgb(CompressedSrgbFormat::Bptc),
+        147 => Compressed(CompressedFormat::Etc2Rgb8),
+        148 => CompressedSrgb(CompressedSrgbFormat::Etc2Rgb8),
+        149 => Compressed(CompressedFormat::Etc2Rgb8A1),
+        150 => CompressedSrgb(CompressedSrgbFormat::Etc2Rgb8A1),
+        151 => Compressed(CompressedFormat::Etc2Rgba8),
+        152 => CompressedSrgb(CompressedSrgbFormat::Etc2Rgba8),
+        153 => Compressed(CompressedFormat::EacR11U),
+        154 => Compressed(CompressedFormat::EacR11I),
+        155 => Compressed(CompressedFormat::EacRg11U),
+        156 => Compressed(CompressedFormat::EacRg11I),
+        157 => Compressed(CompressedFormat::Astc4x4),
+        158 => CompressedSrgb(CompressedSrgbFormat::Astc4x4),
+        159 => Compressed(CompressedFormat::Astc5x4),
+        160 => CompressedSrgb(CompressedSrgbFormat::Astc5x4),
+        161 => Compressed(CompressedFormat::Astc5x5),
+        162 => CompressedSrgb(CompressedSrgbFormat::Astc5x5),
+        163 => Compressed(CompressedFormat::Astc6x5),
+        164 => CompressedSrgb(CompressedSrgbFormat::Astc6x5),
+        165 => Compressed(CompressedFormat::Astc6x6),
+        166 => CompressedSrgb(CompressedSrgbFormat::Astc6x6),
+        167 => Compressed(CompressedFormat::Astc8x5),
+        168 => CompressedSrgb(CompressedSrgbFormat::Astc8x5),
+        169 => Compressed(CompressedFormat::Astc8x6),
+        170 => CompressedSrgb(CompressedSrgbFormat::Astc8x6),
+        171 => Compressed(CompressedFormat::Astc8x8),
+        172 => CompressedSrgb(CompressedSrgbFormat::Astc8x8),
+        173 => Compressed(CompressedFormat::Astc10x5),
+        174 => CompressedSrgb(CompressedSrgbFormat::Astc10x5),
+        175 => Compressed(CompressedFormat::Astc10x6),
+        176 => CompressedSrgb(CompressedSrgbFormat::Astc10x6),
+        177 => Compressed(CompressedFormat::Astc10x8),
+        178 => CompressedSrgb(CompressedSrgbFormat::Astc10x8),
+        179 => Compressed(CompressedFormat::Astc10x10),
+        180 => CompressedSrgb(CompressedSrgbFormat::Astc10x10),
+        181 => Compressed(CompressedFormat::Astc12x10),
+        182 => CompressedSrgb(CompressedSrgbFormat::Astc12x10),
+        183 => Compressed(CompressedFormat::Astc12x12),
+        184 => CompressedSrgb(CompressedSrgbFormat::Astc12x12),
+        _ => return None,
+    })
+}
+
+/// Maps a `DXGI_FORMAT` to a format.
+fn dxgi_format_to_format(dxgi_format: u32) -> Option<ContainerFormat> {
+    use self::ContainerFormat::*;
+
+    Some(match dxgi_format {
+        2 => Uncompressed(UncompressedFloatFormat::F32F32F32F32, ClientFormat::F32F32F32F32),
+        10 => Uncompressed(UncompressedFloatFormat::F16F16F16F16, ClientFormat::F16F16F16F16),
+        16 => Uncompressed(UncompressedFloatFormat::F32F32, ClientFormat::F32F32),
+        28 => Uncompressed(UncompressedFloatFormat::U8U8U8U8, ClientFormat::U8U8U8U8),
+        29 => Srgb(SrgbFormat::U8U8U8U8, ClientFormat::U8U8U8U8),
+        34 => Uncompressed(UncompressedFloatFormat::F16F16, ClientFormat::F16F16),
+        41 => Uncompressed(UncompressedFloatFormat::F32, ClientFormat::F32),
+        49 => Uncompressed(UncompressedFloatFormat::U8U8, ClientFormat::U8U8),
+        54 => Uncompressed(UncompressedFloatFormat::F16, ClientFormat::F16),
+        61 => Uncompressed(UncompressedFloatFormat::U8, ClientFormat::U8),
+        71 => Compressed(CompressedFormat::S3tcDxt1Alpha),
+        72 => CompressedSrgb(CompressedSrgbFormat::S3tcDxt1Alpha),
+        74 => Compressed(CompressedFormat::S3tcDxt3Alpha),
+        75 => CompressedSrgb(CompressedSrgbFormat::S3tcDxt3Alpha),
+        77 => Compressed(CompressedFormat::S3tcDxt5Alpha),
+        78 => CompressedSrgb(CompressedSrgbFormat::S3tcDxt5Alpha),
+        80 => Compressed(CompressedFormat::RgtcFormatU),
+        81 => Compressed(CompressedFormat::RgtcFormatI),
+        83 => Compressed(CompressedFormat::RgtcFormatUU),
+        84 => Compressed(CompressedFormat::RgtcFormatII),
+        95 => Compressed(CompressedFormat::BptcUnsignedFloat3),
+        96 => Compressed(CompressedFormat::BptcSignedFloat3),
+        98 => Compressed(CompressedFormat::BptcUnorm4),
+        99 => CompressedSrgb(CompressedSrgbFormat::Bptc),
+        _ => return None,
+    })
+}
+
+/// Maps the FourCC code of a legacy DDS file to a format.
+fn fourcc_to_format(fourcc: u32, alpha: bool) -> Option<ContainerFormat> {
+    use self::ContainerFormat::*;
+
+    Some(match &fourcc.to_le_bytes() {
+        b"DXT1" if alpha => Compressed(CompressedFormat::S3tcDxt1Alpha),
+        b"DXT1" => Compressed(CompressedFormat::S3tcDxt1NoAlpha),
+        b"DXT2" | b"DXT3" => Compressed(CompressedFormat::S3tcDxt3Alpha),
+        b"DXT4" | b"DXT5" => Compressed(CompressedFormat::S3tcDxt5Alpha),
+        b"ATI1" | b"BC4U" => Compressed(CompressedFormat::RgtcFormatU),
+        b"BC4S" => Compressed(CompressedFormat::RgtcFormatI),
+        b"ATI2" | b"BC5U" => Compressed(CompressedFormat::RgtcFormatUU),
+        b"BC5S" => Compressed(CompressedFormat::RgtcFormatII),
+        _ => return None,
+    })
+}
+
+#[inline]
+fn slice(data: &[u8], offset: usize, length: usize) -> Result<&[u8], ContainerError> {
+    offset.checked_add(length).and_then(|end| data.get(offset .. end))
+                              .ok_or(ContainerError::InvalidData)
+}
+
+#[inline]
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, ContainerError> {
+    let bytes = slice(data, offset, 4)?;
+    Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+#[inline]
+fn read_u64(data: &[u8], offset: usize) -> Result<usize, ContainerError> {
+    let bytes = slice(data, offset, 8)?;
+    let mut value = [0; 8];
+    value.copy_from_slice(bytes);
+    usize::try_from(u64::from_le_bytes(value)).map_err(|_| ContainerError::InvalidData)
+}
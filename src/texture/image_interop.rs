@@ -0,0 +1,228 @@
+/*!
+Conversions between textures and the images of the `image` crate.
+
+`ImageBuffer`s and `DynamicImage`s can be used directly as the source of the data of
+a two-dimensional texture, and textures can be read back into them. The images of the `image`
+crate start with the top row while textures start with the bottom row, so the rows are flipped
+in both directions.
+
+```no_run
+# use glutin::surface::{ResizeableSurface, SurfaceTypeTrait};
+# fn example<T>(display: glium::Display<T>) where T: SurfaceTypeTrait + ResizeableSurface {
+let image = image::open("texture.png").unwrap().to_rgba8();
+let texture = glium::texture::SrgbTexture2d::new(&display, image).unwrap();
+
+let readback: image::RgbaImage = texture.read();
+texture.save_png("debug.png").unwrap();
+# }
+```
+
+Images with eight bits per component are usually in the sRGB color space, while images with
+floating-point components are linear. `new_texture_2d` picks the right kind of texture.
+
+This module is only available if the `image_interop` feature is enabled.
+
+*/
+#![cfg(feature = "image_interop")]
+
+use std::borrow::Cow;
+use std::path::Path;
+
+use image::{DynamicImage, GenericImageView, ImageBuffer, ImageFormat, ImageResult};
+use image::{Luma, LumaA, Rgb, Rgba, RgbaImage};
+
+use crate::backend::Facade;
+use crate::texture::{ClientFormat, MipmapsOption, RawImage2d, SrgbFormat, SrgbTexture2d};
+use crate::texture::{Texture2d, Texture2dDataSink, Texture2dDataSource, TextureCreationError};
+
+/// A texture created from a `DynamicImage` with `new_texture_2d`.
+#[derive(Debug)]
+pub enum ImageTexture {
+    /// The image has eight bits per component and at least three components, and is stored in
+    /// the sRGB color space.
+    Srgb(SrgbTexture2d),
+
+    /// The image has one or two components, or more than eight bits per component.
+    Linear(Texture2d),
+}
+
+impl ImageTexture {
+    /// Returns the dimensions of the texture.
+    #[inline]
+    pub fn dimensions(&self) -> (u32, u32) {
+        match *self {
+            ImageTexture::Srgb(ref texture) => texture.dimensions(),
+            ImageTexture::Linear(ref texture) => texture.dimensions(),
+        }
+    }
+}
+
+/// Builds a texture from an image, choosing an sRGB texture for images with eight bits per
+/// component and three or four components, and a regular texture otherwise.
+pub fn new_texture_2d<F: ?Sized>(facade: &F, image: DynamicImage, mipmaps: MipmapsOption)
+                                 -> Result<ImageTexture, TextureCreationError> where F: Facade
+{
+    let srgb_format = match image {
+        DynamicImage::ImageRgb8(_) => Some(SrgbFormat::U8U8U8),
+        DynamicImage::ImageRgba8(_) => Some(SrgbFormat::U8U8U8U8),
+        _ => None,
+    };
+
+    match srgb_format {
+        Some(format) => {
+            SrgbTexture2d::with_format(facade, image, format, mipmaps).map(ImageTexture::Srgb)
+        },
+        None => {
+            Texture2d::with_mipmaps(facade, image, mipmaps).map(ImageTexture::Linear)
+        },
+    }
+}
+
+/// Flips the rows of an image.
+fn flip_rows<T>(data: &[T], row_length: usize) -> Vec<T> where T: Clone {
+    if row_length == 0 {
+        return Vec::new();
+    }
+
+    data.chunks(row_length).rev().flat_map(|row| row.iter()).cloned().collect()
+}
+
+macro_rules! impl_image_buffer {
+    ($pixel:ident, $subpixel:ty, $format:ident, $pixel_ty:ty, $pattern:pat, [$($component:ident),+]) => (
+        impl<'a> Texture2dDataSource<'a> for ImageBuffer<$pixel<$subpixel>, Vec<$subpixel>> {
+            type Data = $subpixel;
+
+            #[inline]
+            fn into_raw(self) -> RawImage2d<'a, $subpixel> {
+                (&self).into_raw()
+            }
+        }
+
+        impl<'a> Texture2dDataSource<'a> for &ImageBuffer<$pixel<$subpixel>, Vec<$subpixel>> {
+            type Data = $subpixel;
+
+            fn into_raw(self) -> RawImage2d<'a, $subpixel> {
+                let (width, height) = self.dimensions();
+                let format = ClientFormat::$format;
+                let row_length = width as usize * format.get_num_components() as usize;
+
+                RawImage2d {
+                    data: Cow::Owned(flip_rows(self.as_raw(), row_length)),
+                    width,
+                    height,
+                    format,
+                }
+            }
+        }
+
+        impl Texture2dDataSink<$pixel_ty> for ImageBuffer<$pixel<$subpixel>, Vec<$subpixel>> {
+            fn from_raw(data: Cow<'_, [$pixel_ty]>, width: u32, height: u32) -> Self {
+                let mut buffer = Vec::with_capacity(data.len() * ClientFormat::$format.get_num_components() as usize);
+
+                if width != 0 {
+                    for row in data.chunks(width as usize).rev() {
+                        for &$pattern in row {
+                            $(buffer.push($component);)+
+                        }
+                    }
+                }
+
+                ImageBuffer::from_raw(width, height, buffer).unwrap()
+            }
+        }
+    );
+}
+
+impl_image_buffer!(Luma, u8, U8, u8, l, [l]);
+impl_image_buffer!(LumaA, u8, U8U8, (u8, u8), (l, a), [l, a]);
+impl_image_buffer!(Rgb, u8, U8U8U8, (u8, u8, u8), (r, g, b), [r, g, b]);
+impl_image_buffer!(Rgba, u8, U8U8U8U8, (u8, u8, u8, u8), (r, g, b, a), [r, g, b, a]);
+impl_image_buffer!(Luma, u16, U16, u16, l, [l]);
+impl_image_buffer!(LumaA, u16, U16U16, (u16, u16), (l, a), [l, a]);
+impl_image_buffer!(Rgb, u16, U16U16U16, (u16, u16, u16), (r, g, b), [r, g, b]);
+impl_image_buffer!(Rgba, u16, U16U16U16U16, (u16, u16, u16, u16), (r, g, b, a), [r, g, b, a]);
+impl_image_buffer!(Luma, f32, F32, f32, l, [l]);
+impl_image_buffer!(LumaA, f32, F32F32, (f32, f32), (l, a), [l, a]);
+impl_image_buffer!(Rgb, f32, F32F32F32, (f32, f32, f32), (r, g, b), [r, g, b]);
+impl_image_buffer!(Rgba, f32, F32F32F32F32, (f32, f32, f32, f32), (r, g, b, a), [r, g, b, a]);
+
+impl<'a> Texture2dDataSource<'a> for DynamicImage {
+    type Data = u8;
+
+    #[inline]
+    fn into_raw(self) -> RawImage2d<'a, u8> {
+        (&self).into_raw()
+    }
+}
+
+impl<'a> Texture2dDataSource<'a> for &DynamicImage {
+    type Data = u8;
+
+    fn into_raw(self) -> RawImage2d<'a, u8> {
+        let format = match *self {
+            DynamicImage::ImageLuma8(_) => ClientFormat::U8,
+            DynamicImage::ImageLumaA8(_) => ClientFormat::U8U8,
+            DynamicImage::ImageRgb8(_) => ClientFormat::U8U8U8,
+            DynamicImage::ImageRgba8(_) => ClientFormat::U8U8U8U8,
+            DynamicImage::ImageLuma16(_) => ClientFormat::U16,
+            DynamicImage::ImageLumaA16(_) => ClientFormat::U16U16,
+            DynamicImage::ImageRgb16(_) => ClientFormat::U16U16U16,
+            DynamicImage::ImageRgba16(_) => ClientFormat::U16U16U16U16,
+            DynamicImage::ImageRgb32F(_) => ClientFormat::F32F32F32,
+            DynamicImage::ImageRgba32F(_) => ClientFormat::F32F32F32F32,
+            _ => return DynamicImage::ImageRgba8(self.to_rgba8()).into_raw(),
+        };
+
+        // the components are stored as native-endian bytes, which is what OpenGL expects
+        let (width, height) = self.dimensions();
+        let row_length = width as usize * format.get_size();
+
+        RawImage2d {
+            data: Cow::Owned(flip_rows(self.as_bytes(), row_length)),
+            width,
+            height,
+            format,
+        }
+    }
+}
+
+impl Texture2dDataSink<(u8, u8, u8, u8)> for DynamicImage {
+    #[inline]
+    fn from_raw(data: Cow<'_, [(u8, u8, u8, u8)]>, width: u32, height: u32) -> Self {
+        DynamicImage::ImageRgba8(Texture2dDataSink::from_raw(data, width, height))
+    }
+}
+
+impl Texture2dDataSink<(u16, u16, u16, u16)> for DynamicImage {
+    #[inline]
+    fn from_raw(data: Cow<'_, [(u16, u16, u16, u16)]>, width: u32, height: u32) -> Self {
+        DynamicImage::ImageRgba16(Texture2dDataSink::from_raw(data, width, height))
+    }
+}
+
+impl Texture2dDataSink<(f32, f32, f32, f32)> for DynamicImage {
+    #[inline]
+    fn from_raw(data: Cow<'_, [(f32, f32, f32, f32)]>, width: u32, height: u32) -> Self {
+        DynamicImage::ImageRgba32F(Texture2dDataSink::from_raw(data, width, height))
+    }
+}
+
+impl Texture2d {
+    /// Reads the content of the texture and saves it to a PNG file.
+    ///
+    /// This is mostly useful for debugging.
+    pub fn save_png<P>(&self, path: P) -> ImageResult<()> where P: AsRef<Path> {
+        let image: RgbaImage = self.read();
+        image.save_with_format(path, ImageFormat::Png)
+    }
+}
+
+impl SrgbTexture2d {
+    /// Reads the content of the texture and saves it to a PNG file.
+    ///
+    /// This is mostly useful for debugging.
+    pub fn save_png<P>(&self, path: P) -> ImageResult<()> where P: AsRef<Path> {
+        let image: RgbaImage = self.read();
+        image.save_with_format(path, ImageFormat::Png)
+    }
+}
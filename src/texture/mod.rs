@@ -122,8 +122,9 @@ pub use crate::image_format::{ClientFormat, TextureFormat};
 pub use crate::image_format::{UncompressedFloatFormat, UncompressedIntFormat, UncompressedUintFormat};
 pub use crate::image_format::{CompressedFormat, DepthFormat, DepthStencilFormat, StencilFormat};
 pub use crate::image_format::{CompressedSrgbFormat, SrgbFormat};
+pub use crate::fbo::ClearBufferData;
 pub use self::any::{TextureAny, TextureAnyMipmap, TextureAnyLayer, TextureAnyLayerMipmap};
-pub use self::any::{TextureAnyImage, Dimensions};
+pub use self::any::{TextureAnyImage, Dimensions, CopyImageError, ClearTextureError};
 pub use self::bindless::{ResidentTexture, TextureHandle, BindlessTexturesNotSupportedError};
 pub use self::get_format::{InternalFormat, InternalFormatType, GetFormatError};
 pub use self::pixel::PixelValue;
@@ -135,10 +136,21 @@ pub use self::ty_support::is_cubemap_arrays_supported;
 pub use self::texture_import::ExternalTilingMode;
 pub use self::texture_import::ImportParameters;
 pub use self::texture_import::TextureImportError;
+pub use self::sparse::{SparseTexture2d, SparseTexture2dArray, SparseTextureError};
+pub use self::view::{TextureViewTarget, TextureViewError};
 
 pub mod bindless;
 pub mod buffer_texture;
+#[cfg(feature = "block_compression")]
+pub mod compress;
+#[cfg(feature = "image_interop")]
+pub mod image_interop;
+#[cfg(feature = "texture_container")]
+pub mod container;
 pub mod pixel_buffer;
+pub mod sparse;
+pub mod streamer;
+pub mod view;
 
 mod any;
 mod get_format;
@@ -0,0 +1,563 @@
+/*!
+Sparse textures, whose storage is only partially backed by memory.
+
+A sparse texture is divided into pages of a size that depends on the format of the texture.
+When the texture is created, none of its pages are backed by memory. Regions of the texture must
+be committed with `commit` before they can be written, and can be decommitted later with
+`decommit` in order to free the memory. This makes it possible to create textures that are much
+larger than the available video memory, for example for virtual texturing.
+
+Reading from a page that is not committed returns undefined values, and writing to it has no
+effect. The `write` functions of the sparse textures check that the whole region is committed
+before uploading data.
+
+```no_run
+# use glutin::surface::{ResizeableSurface, SurfaceTypeTrait};
+# fn example<T>(display: glium::Display<T>) where T: SurfaceTypeTrait + ResizeableSurface {
+use glium::texture::{MipmapsOption, UncompressedFloatFormat};
+use glium::texture::sparse::SparseTexture2d;
+
+let format = UncompressedFloatFormat::U8U8U8U8;
+let page_sizes = SparseTexture2d::get_page_sizes(&display, format);
+let (page_width, page_height, _) = page_sizes[0];
+
+let texture = SparseTexture2d::new(&display, format, MipmapsOption::NoMipmap, 0,
+                                   16384, 16384).unwrap();
+
+let rect = glium::Rect { left: 0, bottom: 0, width: page_width, height: page_height };
+texture.commit(&rect, 0).unwrap();
+# }
+```
+
+Sparse textures require the `GL_ARB_sparse_texture` extension.
+
+*/
+use crate::gl;
+
+use crate::backend::Facade;
+use crate::context::Context;
+use crate::CapabilitiesSource;
+use crate::ContextExt;
+use crate::GlObject;
+use crate::Rect;
+use crate::TextureExt;
+use crate::TextureMipmapExt;
+
+use crate::image_format::{self, ClientFormatAny, TextureFormatRequest};
+use crate::texture::any::{self, TextureAny};
+use crate::texture::{Dimensions, MipmapsOption, RawImage2d, Texture2dDataSource};
+use crate::texture::{Texture2d, Texture2dArray, TextureCreationError, TextureViewTarget};
+use crate::texture::UncompressedFloatFormat;
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+/// Value of the level in the keys of the mipmap tail, which is committed as a whole.
+const MIP_TAIL: u32 = u32::MAX;
+
+/// Error that can happen when committing, decommitting or writing to a sparse texture.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SparseTextureError {
+    /// The mipmap level, the layers or the region are outside of the boundaries of the texture.
+    OutOfRange,
+
+    /// The region is not aligned to the pages of the texture. The offsets must be multiples of the
+    /// page size, and the dimensions must be multiples of the page size unless the region
+    /// touches the border of the mipmap level. Levels that are in the mipmap tail must be
+    /// committed as a whole.
+    UnalignedRegion,
+
+    /// Some of the pages of the region are not committed.
+    NotCommitted,
+}
+
+impl fmt::Display for SparseTextureError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        use self::SparseTextureError::*;
+        let desc = match *self {
+            OutOfRange =>
+                "The region is outside of the boundaries of the texture",
+            UnalignedRegion =>
+                "The region is not aligned to the pages of the texture",
+            NotCommitted =>
+                "Some of the pages of the region are not committed",
+        };
+        fmt.write_str(desc)
+    }
+}
+
+impl Error for SparseTextureError {}
+
+/// A two-dimensional sparse texture containing floating-point data.
+pub struct SparseTexture2d {
+    texture: Texture2d,
+    pages: PageTable,
+}
+
+impl SparseTexture2d {
+    /// Returns true if sparse textures are supported by the backend.
+    #[inline]
+    pub fn is_supported<C: ?Sized>(context: &C) -> bool where C: CapabilitiesSource {
+        context.get_extensions().gl_arb_sparse_texture
+    }
+
+    /// Returns the list of `(width, height, depth)` page sizes that are available for this
+    /// format. The index of a page size in this list is the value to pass to `new`.
+    ///
+    /// Returns an empty list if the format can't be used for sparse textures.
+    #[inline]
+    pub fn get_page_sizes<F: ?Sized>(facade: &F, format: UncompressedFloatFormat)
+                                     -> Vec<(u32, u32, u32)> where F: Facade
+    {
+        get_page_sizes(facade.get_context(), gl::TEXTURE_2D, format)
+    }
+
+    /// Creates a sparse texture with none of its pages committed.
+    ///
+    /// `page_size_index` is the index of the page size in the list returned by
+    /// `get_page_sizes`. The dimensions must be multiples of this page size.
+    pub fn new<F: ?Sized>(facade: &F, format: UncompressedFloatFormat, mipmaps: MipmapsOption,
+                          page_size_index: u32, width: u32, height: u32)
+                          -> Result<SparseTexture2d, TextureCreationError> where F: Facade
+    {
+        let ty = Dimensions::Texture2d { width, height };
+        let (texture, pages) = new_sparse(facade, gl::TEXTURE_2D, format, mipmaps, ty,
+                                          page_size_index)?;
+
+        Ok(SparseTexture2d {
+            texture: Texture2d::from_any(texture),
+            pages,
+        })
+    }
+
+    /// Returns the texture, which can be used like any other `Texture2d`.
+    ///
+    /// Writing to the texture directly doesn't check whether the pages are committed.
+    #[inline]
+    pub fn get_texture(&self) -> &Texture2d {
+        &self.texture
+    }
+
+    /// Returns the `(width, height, depth)` of a page of this texture.
+    #[inline]
+    pub fn get_page_size(&self) -> (u32, u32, u32) {
+        self.pages.page_size
+    }
+
+    /// Returns the number of mipmap levels that can be partially committed. The levels after
+    /// this one form the mipmap tail, which is committed and decommitted as a whole.
+    #[inline]
+    pub fn get_num_sparse_levels(&self) -> u32 {
+        self.pages.num_sparse_levels
+    }
+
+    /// Commits the pages of a region of a mipmap level, so that they are backed by memory.
+    ///
+    /// The content of newly-committed pages is undefined.
+    #[inline]
+    pub fn commit(&self, rect: &Rect, level: u32) -> Result<(), SparseTextureError> {
+        self.pages.set_commitment(&self.texture, level, rect, 0 .. 1, true)
+    }
+
+    /// Decommits the pages of a region of a mipmap level, and frees the memory that backs them.
+    #[inline]
+    pub fn decommit(&self, rect: &Rect, level: u32) -> Result<(), SparseTextureError> {
+        self.pages.set_commitment(&self.texture, level, rect, 0 .. 1, false)
+    }
+
+    /// Returns true if all the pages of a region of a mipmap level are committed.
+    #[inline]
+    pub fn is_committed(&self, rect: &Rect, level: u32) -> bool {
+        self.pages.check_committed(&self.texture, level, rect, 0 .. 1).is_ok()
+    }
+
+    /// Uploads some data in a region of a mipmap level, after checking that all the pages
+    /// of the region are committed.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the the dimensions of `data` don't match the `Rect`.
+    pub fn write<'a, T>(&self, level: u32, rect: Rect, data: T) -> Result<(), SparseTextureError>
+                        where T: Texture2dDataSource<'a>
+    {
+        self.pages.check_committed(&self.texture, level, &rect, 0 .. 1)?;
+        upload(&self.texture, level, 0, rect, data);
+        Ok(())
+    }
+}
+
+impl fmt::Debug for SparseTexture2d {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(fmt, "Sparse{:?}", *self.texture)
+    }
+}
+
+impl GlObject for SparseTexture2d {
+    type Id = gl::types::GLuint;
+
+    #[inline]
+    fn get_id(&self) -> gl::types::GLuint {
+        self.texture.get_id()
+    }
+}
+
+/// An array of two-dimensional sparse textures containing floating-point data.
+pub struct SparseTexture2dArray {
+    texture: Texture2dArray,
+    pages: PageTable,
+}
+
+impl SparseTexture2dArray {
+    /// Returns true if sparse array textures are supported by the backend.
+    #[inline]
+    pub fn is_supported<C: ?Sized>(context: &C) -> bool where C: CapabilitiesSource {
+        context.get_extensions().gl_arb_sparse_texture
+    }
+
+    /// Returns the list of `(width, height, depth)` page sizes that are available for this
+    /// format. The index of a page size in this list is the value to pass to `new`.
+    ///
+    /// Returns an empty list if the format can't be used for sparse textures.
+    #[inline]
+    pub fn get_page_sizes<F: ?Sized>(facade: &F, format: UncompressedFloatFormat)
+                                     -> Vec<(u32, u32, u32)> where F: Facade
+    {
+        get_page_sizes(facade.get_context(), gl::TEXTURE_2D_ARRAY, format)
+    }
+
+    /// Creates a sparse array texture with none of its pages committed.
+    ///
+    /// `page_size_index` is the index of the page size in the list returned by
+    /// `get_page_sizes`. The width and height must be multiples of this page size.
+    pub fn new<F: ?Sized>(facade: &F, format: UncompressedFloatFormat, mipmaps: MipmapsOption,
+                          page_size_index: u32, width: u32, height: u32, array_size: u32)
+                          -> Result<SparseTexture2dArray, TextureCreationError> where F: Facade
+    {
+        let ty = Dimensions::Texture2dArray { width, height, array_size };
+        let (texture, pages) = new_sparse(facade, gl::TEXTURE_2D_ARRAY, format, mipmaps, ty,
+                                          page_size_index)?;
+
+        Ok(SparseTexture2dArray {
+            texture: Texture2dArray::from_any(texture),
+            pages,
+        })
+    }
+
+    /// Returns the texture, which can be used like any other `Texture2dArray`.
+    ///
+    /// Writing to the texture directly doesn't check whether the pages are committed.
+    #[inline]
+    pub fn get_texture(&self) -> &Texture2dArray {
+        &self.texture
+    }
+
+    /// Returns the `(width, height, depth)` of a page of this texture.
+    #[inline]
+    pub fn get_page_size(&self) -> (u32, u32, u32) {
+        self.pages.page_size
+    }
+
+    /// Returns the number of mipmap levels that can be partially committed. The levels after
+    /// this one form the mipmap tail, which is committed and decommitted as a whole.
+    #[inline]
+    pub fn get_num_sparse_levels(&self) -> u32 {
+        self.pages.num_sparse_levels
+    }
+
+    /// Commits the pages of a region of a mipmap level in a range of layers, so that they are
+    /// backed by memory.
+    ///
+    /// The content of newly-committed pages is undefined.
+    #[inline]
+    pub fn commit(&self, rect: &Rect, layers: Range<u32>, level: u32)
+                  -> Result<(), SparseTextureError>
+    {
+        self.pages.set_commitment(&self.texture, level, rect, layers, true)
+    }
+
+    /// Decommits the pages of a region of a mipmap level in a range of layers, and frees the
+    /// memory that backs them.
+    #[inline]
+    pub fn decommit(&self, rect: &Rect, layers: Range<u32>, level: u32)
+                    -> Result<(), SparseTextureError>
+    {
+        self.pages.set_commitment(&self.texture, level, rect, layers, false)
+    }
+
+    /// Returns true if all the pages of a region of a mipmap level in a range of layers
+    /// are committed.
+    #[inline]
+    pub fn is_committed(&self, rect: &Rect, layers: Range<u32>, level: u32) -> bool {
+        self.pages.check_committed(&self.texture, level, rect, layers).is_ok()
+    }
+
+    /// Uploads some data in a region of a layer of a mipmap level, after checking that all the
+    /// pages of the region are committed.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the the dimensions of `data` don't match the `Rect`.
+    pub fn write<'a, T>(&self, level: u32, layer: u32, rect: Rect, data: T)
+                        -> Result<(), SparseTextureError> where T: Texture2dDataSource<'a>
+    {
+        self.pages.check_committed(&self.texture, level, &rect, layer .. layer + 1)?;
+        upload(&self.texture, level, layer, rect, data);
+        Ok(())
+    }
+}
+
+impl fmt::Debug for SparseTexture2dArray {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(fmt, "Sparse{:?}", *self.texture)
+    }
+}
+
+impl GlObject for SparseTexture2dArray {
+    type Id = gl::types::GLuint;
+
+    #[inline]
+    fn get_id(&self) -> gl::types::GLuint {
+        self.texture.get_id()
+    }
+}
+
+/// Keeps track of the pages of a sparse texture that are committed.
+struct PageTable {
+    page_size: (u32, u32, u32),
+    num_sparse_levels: u32,
+
+    // `(level, layer, page_x, page_y)` of the committed pages, with `level` set to `MIP_TAIL`
+    // for the mipmap tail
+    committed: RefCell<HashSet<(u32, u32, u32, u32)>>,
+}
+
+impl PageTable {
+    /// Checks that a region is within the texture and aligned to its pages, and returns the
+    /// keys of the pages that it covers.
+    fn get_pages(&self, texture: &TextureAny, level: u32, rect: &Rect, layers: Range<u32>)
+                 -> Result<Vec<(u32, u32, u32, u32)>, SparseTextureError>
+    {
+        let mipmap = texture.mipmap(level).ok_or(SparseTextureError::OutOfRange)?;
+        let width = mipmap.get_width();
+        let height = mipmap.get_height().unwrap_or(1);
+
+        if rect.left.checked_add(rect.width).map(|r| r > width).unwrap_or(true) ||
+           rect.bottom.checked_add(rect.height).map(|b| b > height).unwrap_or(true) ||
+           layers.start >= layers.end || layers.end > texture.get_array_size().unwrap_or(1)
+        {
+            return Err(SparseTextureError::OutOfRange);
+        }
+
+        if level >= self.num_sparse_levels {
+            if rect.left != 0 || rect.bottom != 0 || rect.width != width || rect.height != height {
+                return Err(SparseTextureError::UnalignedRegion);
+            }
+
+            return Ok(layers.map(|layer| (MIP_TAIL, layer, 0, 0)).collect());
+        }
+
+        let (page_width, page_height, _) = self.page_size;
+
+        let aligned = |offset: u32, size: u32, page: u32, max: u32| {
+            offset.is_multiple_of(page) && (size.is_multiple_of(page) || offset + size == max)
+        };
+
+        if !aligned(rect.left, rect.width, page_width, width) ||
+           !aligned(rect.bottom, rect.height, page_height, height)
+        {
+            return Err(SparseTextureError::UnalignedRegion);
+        }
+
+        let pages_x = rect.left / page_width .. (rect.left + rect.width).div_ceil(page_width);
+        let pages_y = rect.bottom / page_height .. (rect.bottom + rect.height).div_ceil(page_height);
+
+        let mut pages = Vec::new();
+        for layer in layers {
+            for y in pages_y.clone() {
+                for x in pages_x.clone() {
+                    pages.push((level, layer, x, y));
+                }
+            }
+        }
+
+        Ok(pages)
+    }
+
+    /// Commits or decommits a region of the texture.
+    fn set_commitment(&self, texture: &TextureAny, level: u32, rect: &Rect, layers: Range<u32>,
+                      commit: bool) -> Result<(), SparseTextureError>
+    {
+        let pages = self.get_pages(texture, level, rect, layers.clone())?;
+
+        let mut ctxt = texture.get_context().make_current();
+        let bind_point = texture.bind_to_current(&mut ctxt);
+
+        unsafe {
+            ctxt.gl.TexPageCommitmentARB(bind_point, level as gl::types::GLint,
+                                         rect.left as gl::types::GLint,
+                                         rect.bottom as gl::types::GLint,
+                                         layers.start as gl::types::GLint,
+                                         rect.width as gl::types::GLsizei,
+                                         rect.height as gl::types::GLsizei,
+                                         layers.len() as gl::types::GLsizei,
+                                         if commit { gl::TRUE } else { gl::FALSE });
+        }
+
+        let mut committed = self.committed.borrow_mut();
+        for page in pages {
+            if commit {
+                committed.insert(page);
+            } else {
+                committed.remove(&page);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that all the pages of a region are committed.
+    fn check_committed(&self, texture: &TextureAny, level: u32, rect: &Rect, layers: Range<u32>)
+                       -> Result<(), SparseTextureError>
+    {
+        let mipmap = texture.mipmap(level).ok_or(SparseTextureError::OutOfRange)?;
+        let width = mipmap.get_width();
+        let height = mipmap.get_height().unwrap_or(1);
+
+        // the region itself must be checked, as extending it to the pages could clamp it
+        if rect.left.checked_add(rect.width).map(|r| r > width).unwrap_or(true) ||
+           rect.bottom.checked_add(rect.height).map(|b| b > height).unwrap_or(true)
+        {
+            return Err(SparseTextureError::OutOfRange);
+        }
+
+        // any region is accepted when checking, so the region is extended to the pages
+        // that contain it
+        let (page_width, page_height, _) = self.page_size;
+        let rect = if level >= self.num_sparse_levels {
+            Rect {
+                left: 0,
+                bottom: 0,
+                width,
+                height,
+            }
+        } else {
+            let left = rect.left - rect.left % page_width;
+            let bottom = rect.bottom - rect.bottom % page_height;
+            let right = (rect.left + rect.width).div_ceil(page_width) * page_width;
+            let top = (rect.bottom + rect.height).div_ceil(page_height) * page_height;
+
+            Rect {
+                left,
+                bottom,
+                width: right.min(width) - left,
+                height: top.min(height) - bottom,
+            }
+        };
+
+        let pages = self.get_pages(texture, level, &rect, layers)?;
+
+        let committed = self.committed.borrow();
+        if pages.iter().all(|page| committed.contains(page)) {
+            Ok(())
+        } else {
+            Err(SparseTextureError::NotCommitted)
+        }
+    }
+}
+
+/// Creates a sparse texture and its page table.
+fn new_sparse<F: ?Sized>(facade: &F, bind_point: gl::types::GLenum,
+                         format: UncompressedFloatFormat, mipmaps: MipmapsOption, ty: Dimensions,
+                         page_size_index: u32)
+                         -> Result<(TextureAny, PageTable), TextureCreationError> where F: Facade
+{
+    let page_sizes = get_page_sizes(facade.get_context(), bind_point, format);
+    let page_size = match page_sizes.get(page_size_index as usize) {
+        Some(size) => *size,
+        None => return Err(TextureCreationError::FormatNotSupported),
+    };
+
+    let (width, height) = match ty {
+        Dimensions::Texture2d { width, height } => (width, height),
+        Dimensions::Texture2dArray { width, height, .. } => (width, height),
+        _ => unreachable!(),
+    };
+
+    if !width.is_multiple_of(page_size.0) || !height.is_multiple_of(page_size.1) {
+        return Err(TextureCreationError::DimensionsNotSupported);
+    }
+
+    let (texture, num_sparse_levels) = any::new_sparse_texture(facade, format.to_texture_format(),
+                                                               mipmaps, ty, page_size_index)?;
+
+    let pages = PageTable {
+        page_size,
+        num_sparse_levels,
+        committed: RefCell::new(HashSet::new()),
+    };
+
+    Ok((texture, pages))
+}
+
+/// Queries the page sizes available for a format.
+fn get_page_sizes(context: &Context, bind_point: gl::types::GLenum,
+                  format: UncompressedFloatFormat) -> Vec<(u32, u32, u32)>
+{
+    if !context.get_extensions().gl_arb_sparse_texture {
+        return Vec::new();
+    }
+
+    let format = TextureFormatRequest::Specific(format.to_texture_format());
+    let internal_format = match image_format::format_request_to_glenum(context, format,
+                                                                       image_format::RequestType::TexStorage)
+    {
+        Ok(f) => f,
+        Err(_) => return Vec::new(),
+    };
+
+    let ctxt = context.make_current();
+
+    unsafe {
+        let mut num = 0;
+        ctxt.gl.GetInternalformativ(bind_point, internal_format, gl::NUM_VIRTUAL_PAGE_SIZES_ARB,
+                                    1, &mut num);
+
+        if num <= 0 {
+            return Vec::new();
+        }
+
+        let mut x = vec![0; num as usize];
+        let mut y = vec![0; num as usize];
+        let mut z = vec![0; num as usize];
+        ctxt.gl.GetInternalformativ(bind_point, internal_format, gl::VIRTUAL_PAGE_SIZE_X_ARB,
+                                    num, x.as_mut_ptr());
+        ctxt.gl.GetInternalformativ(bind_point, internal_format, gl::VIRTUAL_PAGE_SIZE_Y_ARB,
+                                    num, y.as_mut_ptr());
+        ctxt.gl.GetInternalformativ(bind_point, internal_format, gl::VIRTUAL_PAGE_SIZE_Z_ARB,
+                                    num, z.as_mut_ptr());
+
+        x.into_iter().zip(y).zip(z).map(|((x, y), z)| (x as u32, y as u32, z as u32)).collect()
+    }
+}
+
+/// Uploads data to a layer of a mipmap level of a 2D or 2D array texture.
+fn upload<'a, T>(texture: &TextureAny, level: u32, layer: u32, rect: Rect, data: T)
+                 where T: Texture2dDataSource<'a>
+{
+    let RawImage2d { data, width, height, format: client_format } = data.into_raw();
+
+    assert_eq!(width, rect.width);
+    assert_eq!(height, rect.height);
+
+    let client_format = ClientFormatAny::ClientFormat(client_format);
+    let depth = texture.get_array_size().map(|_| 1);
+
+    texture.mipmap(level).unwrap()
+           .upload_texture(rect.left, rect.bottom, layer, (client_format, data), width,
+                           Some(height), depth, false).unwrap()
+}
@@ -0,0 +1,239 @@
+/*!
+Streaming of frames to a texture through a ring of pixel buffers.
+
+Uploading data to a texture with `write` blocks until the driver has copied the data. When
+a new image has to be uploaded at each frame, for example when playing a video, it is better
+to write the data to a pixel buffer and let the GPU copy it to the texture asynchronously.
+
+A `TextureStreamer` owns a ring of persistent-mapped pixel buffers, each guarded by a fence.
+Frames are pushed to the next free buffer without blocking, and uploaded to the texture later
+with `upload`. If all the buffers are in use, the frame is dropped.
+
+The streamer relies on sync fences to know when the GPU has finished reading a buffer. If they
+aren't supported by the backend, `push` instead blocks until the GPU has finished reading the
+buffer it writes to, and frames are never dropped.
+
+```no_run
+# use glutin::surface::{ResizeableSurface, SurfaceTypeTrait};
+# fn example<T>(display: glium::Display<T>) where T: SurfaceTypeTrait + ResizeableSurface {
+# fn decode_next_frame() -> Vec<(u8, u8, u8, u8)> { unimplemented!() }
+use glium::texture::streamer::TextureStreamer;
+
+let texture = glium::texture::Texture2d::empty(&display, 1280, 720).unwrap();
+let mut streamer = TextureStreamer::new(&display, 1280, 720, 3).unwrap();
+
+// in the decoding loop
+let frame = decode_next_frame();
+if streamer.push(&frame).is_err() {
+    // the GPU is lagging behind, the frame has been dropped
+}
+
+// before drawing
+streamer.upload(&texture.main_level());
+# }
+```
+
+*/
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::backend::Facade;
+use crate::buffer::{Buffer, BufferCreationError, BufferMode, BufferType};
+use crate::context::Context;
+use crate::sync::{FenceWaitResult, SyncFence};
+use crate::texture::{PixelValue, TextureAnyMipmap};
+
+/// Error returned by `TextureStreamer::push` when all the pixel buffers are in use.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FrameDroppedError;
+
+impl fmt::Display for FrameDroppedError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("All the pixel buffers of the streamer are in use, the frame has been dropped")
+    }
+}
+
+impl Error for FrameDroppedError {}
+
+/// A pixel buffer of the ring.
+struct Slot<P> where P: PixelValue {
+    buffer: Buffer<[P]>,
+    // signaled when the GPU has finished reading the buffer
+    fence: Option<SyncFence>,
+}
+
+/// Ring of pixel buffers used to upload frames to a texture asynchronously.
+///
+/// The type parameter is the type of the pixels of the frames.
+pub struct TextureStreamer<P> where P: PixelValue {
+    context: Rc<Context>,
+    slots: Vec<Slot<P>>,
+    width: u32,
+    height: u32,
+    // index of the next buffer to write to
+    next: usize,
+    // indices of the buffers that contain a frame waiting to be uploaded, oldest first
+    pending: VecDeque<usize>,
+    dropped_frames: u64,
+}
+
+impl<P> TextureStreamer<P> where P: PixelValue {
+    /// Builds a new streamer for frames of the given dimensions, with `ring_size` pixel buffers.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `ring_size` is zero.
+    pub fn new<F: ?Sized>(facade: &F, width: u32, height: u32, ring_size: usize)
+                          -> Result<TextureStreamer<P>, BufferCreationError> where F: Facade
+    {
+        assert!(ring_size >= 1, "The ring must contain at least one pixel buffer");
+
+        let len = width as usize * height as usize;
+        let slots = (0 .. ring_size).map(|_| {
+            Ok(Slot {
+                buffer: Buffer::empty_array(facade, BufferType::PixelUnpackBuffer, len,
+                                            BufferMode::Persistent)?,
+                fence: None,
+            })
+        }).collect::<Result<Vec<_>, BufferCreationError>>()?;
+
+        Ok(TextureStreamer {
+            context: facade.get_context().clone(),
+            slots,
+            width,
+            height,
+            next: 0,
+            pending: VecDeque::with_capacity(ring_size),
+            dropped_frames: 0,
+        })
+    }
+
+    /// Returns the dimensions of the frames.
+    #[inline]
+    pub fn get_dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Returns the number of pixel buffers of the ring.
+    #[inline]
+    pub fn get_ring_size(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the number of frames waiting to be uploaded.
+    #[inline]
+    pub fn get_pending_frames(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns the number of frames that have been dropped since the creation of the streamer.
+    #[inline]
+    pub fn get_dropped_frames(&self) -> u64 {
+        self.dropped_frames
+    }
+
+    /// Copies a frame to the next free pixel buffer.
+    ///
+    /// The data must start by the bottom-left hand corner pixel and progress left-to-right and
+    /// bottom-to-top. Returns an error and drops the frame if all the pixel buffers contain
+    /// a frame that hasn't been uploaded yet or are still being read by the GPU.
+    ///
+    /// Doesn't block, unless sync fences aren't supported by the backend. In that case, this
+    /// function waits until the GPU has finished reading the pixel buffer.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the length of the data doesn't match the dimensions of the frames.
+    pub fn push(&mut self, data: &[P]) -> Result<(), FrameDroppedError> {
+        assert_eq!(data.len(), self.width as usize * self.height as usize);
+
+        let index = self.next;
+        if self.pending.contains(&index) || !self.is_slot_available(index) {
+            self.dropped_frames += 1;
+            return Err(FrameDroppedError);
+        }
+
+        let slot = &mut self.slots[index];
+        slot.fence = None;
+        slot.buffer.write(data);
+
+        self.pending.push_back(index);
+        self.next = (index + 1) % self.slots.len();
+        Ok(())
+    }
+
+    /// Schedules the upload of the oldest pending frame to the mipmap. Never blocks.
+    ///
+    /// Returns `false` if there was no frame to upload.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the mipmap is smaller than the frames.
+    pub fn upload(&mut self, target: &TextureAnyMipmap<'_>) -> bool {
+        let index = match self.pending.pop_front() {
+            Some(index) => index,
+            None => return false,
+        };
+
+        let slot = &mut self.slots[index];
+        target.raw_upload_from_pixel_buffer(slot.buffer.as_slice(), 0 .. self.width,
+                                            0 .. self.height, 0 .. 1);
+
+        // without sync fences, glium synchronizes the buffer itself at the next write
+        slot.fence = SyncFence::new(&self.context).ok();
+        true
+    }
+
+    /// Schedules the upload of the most recent pending frame, and drops the older ones.
+    ///
+    /// Returns `false` if there was no frame to upload.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the mipmap is smaller than the frames.
+    pub fn upload_latest(&mut self, target: &TextureAnyMipmap<'_>) -> bool {
+        while self.pending.len() > 1 {
+            self.pending.pop_front();
+            self.dropped_frames += 1;
+        }
+
+        self.upload(target)
+    }
+
+    /// Returns true if the GPU has finished reading the pixel buffer.
+    fn is_slot_available(&mut self, index: usize) -> bool {
+        let slot = &mut self.slots[index];
+
+        let result = match slot.fence {
+            None => return true,
+            // a timeout of zero flushes the commands queue without blocking
+            Some(ref fence) => fence.wait_timeout(Duration::from_secs(0)),
+        };
+
+        match result {
+            FenceWaitResult::Signaled => (),
+            FenceWaitResult::TimeoutExpired => return false,
+            // the state of the fence is unknown, so we wait until all the commands are
+            // finished instead of keeping the buffer out of the ring forever
+            FenceWaitResult::Failed => self.context.finish(),
+        }
+
+        slot.fence = None;
+        true
+    }
+}
+
+impl<P> fmt::Debug for TextureStreamer<P> where P: PixelValue {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("TextureStreamer")
+           .field("width", &self.width)
+           .field("height", &self.height)
+           .field("ring_size", &self.slots.len())
+           .field("pending_frames", &self.pending.len())
+           .field("dropped_frames", &self.dropped_frames)
+           .finish()
+    }
+}
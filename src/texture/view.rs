@@ -0,0 +1,265 @@
+/*!
+Texture views, which reinterpret the storage of an existing texture.
+
+A texture view is a texture object that shares its storage with another texture. It can expose
+a subset of the mipmap levels and layers of the original texture, use a different but
+compatible texture type (for example a single layer of a `Texture2dArray` as a `Texture2d`),
+and reinterpret the data with a different format of the same compatibility class (for example
+an `SrgbTexture2d` as a linear `Texture2d`).
+
+Views are created with `TextureAny::view`. Writing to a view modifies the original texture and
+vice versa. The storage is kept alive for as long as either the original or one of its views
+exists.
+
+```no_run
+# fn example(array: glium::texture::Texture2dArray) {
+use glium::texture::{Texture2d, UncompressedFloatFormat};
+
+// the third layer of the array, with all its mipmaps
+let layer: Texture2d = array.view(UncompressedFloatFormat::U8U8U8U8,
+                                  0 .. array.get_mipmap_levels(), 2 .. 3).unwrap();
+# }
+```
+
+Creating a view requires OpenGL 4.3 or `GL_ARB_texture_view`, and the original texture must
+have been created with immutable storage, which is the case for all the textures created by
+glium if OpenGL 4.2 or `GL_ARB_texture_storage` is available.
+
+*/
+use crate::gl;
+
+use std::error::Error;
+use std::fmt;
+
+use crate::image_format::{ClientFormatAny, FormatNotSupportedError};
+use crate::texture::{Dimensions, TextureAny, TextureFormat};
+
+/// A texture type that can be created as a view of another texture.
+///
+/// This trait is implemented on all the texture types except buffer textures.
+pub trait TextureViewTarget: Sized {
+    /// The format enum corresponding to this texture type.
+    type Format: Copy;
+
+    #[doc(hidden)]
+    fn format_to_texture_format(format: Self::Format) -> TextureFormat;
+
+    #[doc(hidden)]
+    fn view_dimensions(width: u32, height: u32, depth: u32, layers: u32, samples: u32)
+                       -> Dimensions;
+
+    #[doc(hidden)]
+    fn from_any(texture: TextureAny) -> Self;
+}
+
+/// Error that can happen when creating a texture view.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TextureViewError {
+    /// Texture views are not supported by the backend.
+    NotSupported,
+
+    /// The original texture doesn't have immutable storage.
+    MutableStorage,
+
+    /// The requested format is not supported by the backend.
+    FormatNotSupported,
+
+    /// The requested format is not in the same compatibility class as the format of the
+    /// original texture.
+    IncompatibleFormat,
+
+    /// The type of the view is not compatible with the type of the original texture, or the
+    /// number of layers doesn't match what the type of the view requires.
+    IncompatibleType,
+
+    /// The range of mipmap levels is empty or out of the range of the original texture.
+    LevelsOutOfRange,
+
+    /// The range of layers is empty or out of the range of the original texture.
+    LayersOutOfRange,
+}
+
+impl fmt::Display for TextureViewError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        use self::TextureViewError::*;
+        let desc = match *self {
+            NotSupported =>
+                "Texture views are not supported by the backend",
+            MutableStorage =>
+                "The original texture doesn't have immutable storage",
+            FormatNotSupported =>
+                "The requested format is not supported by the backend",
+            IncompatibleFormat =>
+                "The requested format is not compatible with the format of the original texture",
+            IncompatibleType =>
+                "The type of the view is not compatible with the type of the original texture",
+            LevelsOutOfRange =>
+                "The range of mipmap levels is out of the range of the original texture",
+            LayersOutOfRange =>
+                "The range of layers is out of the range of the original texture",
+        };
+        fmt.write_str(desc)
+    }
+}
+
+impl Error for TextureViewError {}
+
+impl From<FormatNotSupportedError> for TextureViewError {
+    #[inline]
+    fn from(_: FormatNotSupportedError) -> TextureViewError {
+        TextureViewError::FormatNotSupported
+    }
+}
+
+/// Returns true if a texture with the bind point `original` can be viewed as a texture with
+/// the bind point `view`.
+pub(crate) fn is_target_compatible(original: gl::types::GLenum, view: gl::types::GLenum) -> bool {
+    match original {
+        gl::TEXTURE_1D | gl::TEXTURE_1D_ARRAY => {
+            view == gl::TEXTURE_1D || view == gl::TEXTURE_1D_ARRAY
+        },
+        gl::TEXTURE_2D | gl::TEXTURE_2D_ARRAY => {
+            view == gl::TEXTURE_2D || view == gl::TEXTURE_2D_ARRAY
+        },
+        gl::TEXTURE_CUBE_MAP | gl::TEXTURE_CUBE_MAP_ARRAY => {
+            view == gl::TEXTURE_2D || view == gl::TEXTURE_2D_ARRAY ||
+            view == gl::TEXTURE_CUBE_MAP || view == gl::TEXTURE_CUBE_MAP_ARRAY
+        },
+        gl::TEXTURE_3D => view == gl::TEXTURE_3D,
+        gl::TEXTURE_2D_MULTISAMPLE | gl::TEXTURE_2D_MULTISAMPLE_ARRAY => {
+            view == gl::TEXTURE_2D_MULTISAMPLE || view == gl::TEXTURE_2D_MULTISAMPLE_ARRAY
+        },
+        _ => false,
+    }
+}
+
+/// Returns true if the two internal formats are in the same view compatibility class.
+pub(crate) fn is_format_compatible(a: gl::types::GLenum, b: gl::types::GLenum) -> bool {
+    if a == b {
+        return true;
+    }
+
+    match (compatibility_class(a), compatibility_class(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum CompatibilityClass {
+    Bits128,
+    Bits96,
+    Bits64,
+    Bits48,
+    Bits32,
+    Bits24,
+    Bits16,
+    Bits8,
+    Rgtc1Red,
+    Rgtc2Rg,
+    BptcUnorm,
+    BptcFloat,
+}
+
+/// Returns the view compatibility class of an internal format. Formats that aren't in any class
+/// are only compatible with themselves.
+fn compatibility_class(format: gl::types::GLenum) -> Option<CompatibilityClass> {
+    Some(match format {
+        gl::RGBA32F | gl::RGBA32UI | gl::RGBA32I => CompatibilityClass::Bits128,
+
+        gl::RGB32F | gl::RGB32UI | gl::RGB32I => CompatibilityClass::Bits96,
+
+        gl::RGBA16F | gl::RG32F | gl::RGBA16UI | gl::RG32UI | gl::RGBA16I | gl::RG32I |
+        gl::RGBA16 | gl::RGBA16_SNORM => CompatibilityClass::Bits64,
+
+        gl::RGB16 | gl::RGB16_SNORM | gl::RGB16F | gl::RGB16UI | gl::RGB16I => {
+            CompatibilityClass::Bits48
+        },
+
+        gl::RG16F | gl::R11F_G11F_B10F | gl::R32F | gl::RGB10_A2UI | gl::RGBA8UI | gl::RG16UI |
+        gl::R32UI | gl::RGBA8I | gl::RG16I | gl::R32I | gl::RGB10_A2 | gl::RGBA8 | gl::RG16 |
+        gl::RGBA8_SNORM | gl::RG16_SNORM | gl::SRGB8_ALPHA8 | gl::RGB9_E5 => {
+            CompatibilityClass::Bits32
+        },
+
+        gl::RGB8 | gl::RGB8_SNORM | gl::SRGB8 | gl::RGB8UI | gl::RGB8I => {
+            CompatibilityClass::Bits24
+        },
+
+        gl::R16F | gl::RG8UI | gl::R16UI | gl::RG8I | gl::R16I | gl::RG8 | gl::R16 |
+        gl::RG8_SNORM | gl::R16_SNORM => CompatibilityClass::Bits16,
+
+        gl::R8UI | gl::R8I | gl::R8 | gl::R8_SNORM => CompatibilityClass::Bits8,
+
+        gl::COMPRESSED_RED_RGTC1 | gl::COMPRESSED_SIGNED_RED_RGTC1 => {
+            CompatibilityClass::Rgtc1Red
+        },
+
+        gl::COMPRESSED_RG_RGTC2 | gl::COMPRESSED_SIGNED_RG_RGTC2 => {
+            CompatibilityClass::Rgtc2Rg
+        },
+
+        gl::COMPRESSED_RGBA_BPTC_UNORM | gl::COMPRESSED_SRGB_ALPHA_BPTC_UNORM => {
+            CompatibilityClass::BptcUnorm
+        },
+
+        gl::COMPRESSED_RGB_BPTC_SIGNED_FLOAT | gl::COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT => {
+            CompatibilityClass::BptcFloat
+        },
+
+        _ => return None,
+    })
+}
+
+/// Returns true if data can be copied between two textures of these internal formats with
+/// `glCopyImageSubData`.
+///
+/// This is the case if the formats are in the same view compatibility class, or if one is
+/// compressed and the other one is uncompressed and the size of a block matches the size of
+/// a texel.
+pub(crate) fn is_copy_compatible(a: gl::types::GLenum, b: gl::types::GLenum) -> bool {
+    if is_format_compatible(a, b) {
+        return true;
+    }
+
+    match (texel_size(a), texel_size(b)) {
+        (Some((size_a, compressed_a)), Some((size_b, compressed_b))) => {
+            compressed_a != compressed_b && size_a == size_b
+        },
+        _ => false,
+    }
+}
+
+/// Returns the width and height in texels of a block if the internal format is a
+/// block-compressed format.
+#[inline]
+pub(crate) fn get_block_dimensions(format: gl::types::GLenum) -> Option<(u32, u32)> {
+    let (width, height, _) = ClientFormatAny::from_internal_compressed_format(format)?
+                                .get_block_layout()?;
+    Some((width, height))
+}
+
+/// Returns the size in bytes of a texel of an uncompressed format or of a block of a compressed
+/// format, and whether the format is compressed.
+fn texel_size(format: gl::types::GLenum) -> Option<(usize, bool)> {
+    let block_layout = ClientFormatAny::from_internal_compressed_format(format)
+                            .and_then(|f| f.get_block_layout());
+
+    if let Some((_, _, size)) = block_layout {
+        return Some((size, true));
+    }
+
+    let size = match compatibility_class(format)? {
+        CompatibilityClass::Bits128 => 16,
+        CompatibilityClass::Bits96 => 12,
+        CompatibilityClass::Bits64 => 8,
+        CompatibilityClass::Bits48 => 6,
+        CompatibilityClass::Bits32 => 4,
+        CompatibilityClass::Bits24 => 3,
+        CompatibilityClass::Bits16 => 2,
+        CompatibilityClass::Bits8 => 1,
+        _ => return None,
+    };
+
+    Some((size, false))
+}
@@ -180,7 +180,7 @@ fn bind_subroutine_uniforms<P>(ctxt: &mut context::CommandContext<'_>, program:
 
             indices[uniform.location as usize] = subroutine.index;
         }
-        program.set_subroutine_uniforms_for_stage(ctxt, *stage, &indices);
+        program.set_subroutine_uniforms_for_stage(ctxt, *stage, &indices)?;
     }
     Ok(())
 }
@@ -0,0 +1,578 @@
+/*!
+Compile-time checked `std140` and `std430` layouts.
+
+The data of a uniform buffer or of a shader storage buffer must follow the packing rules of
+the `std140` or `std430` layout of GLSL. The `UniformBlock` trait only checks at draw time that
+a Rust struct matches the layout of a block reflected by the program.
+
+The `Std140Layout` and `Std430Layout` traits describe how a type is laid out in GLSL. They are
+implemented for scalars, vectors and matrices, and can be derived for `#[repr(C)]` structs with
+the `derive` feature. The derived implementation checks at compile time that the offset of each
+field of the Rust struct matches the offset required by GLSL.
+
+Since Rust and GLSL disagree in some situations, this module also provides a few wrappers:
+
+ - `Padded<T>` aligns a value to 16 bytes. The elements of `std140` arrays and the `vec3`
+   elements of arrays must be padded.
+ - `Array<T, N>` is a GLSL array, whose elements must have the same stride in Rust and in GLSL.
+ - `Mat2` and `Mat3` are matrices whose columns are padded to 16 bytes.
+
+```ignore
+#[derive(Copy, Clone, glium::UniformBlock, glium::Std140Layout)]
+#[repr(C)]
+struct Light {
+    position: [f32; 3],
+    intensity: f32,
+    color: Padded<[f32; 3]>,
+    weights: Array<Padded<f32>, 4>,
+}
+
+let buffer = glium::uniforms::UniformBuffer::new(&display, Std140::new(light)).unwrap();
+```
+
+When the layout doesn't match the program anyway, for example because the GLSL declaration
+was modified, drawing returns a `LayoutMismatchError` for the first mismatch that is found.
+`diff_block` and `diff_layouts` then list all the members that differ.
+
+*/
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+use crate::program::{self, BlockLayout};
+use crate::uniforms::{AsUniformValue, LayoutMismatchError, UniformBlock, UniformType, UniformValue};
+
+/// Types that can be stored in a block with the `std140` layout.
+///
+/// # Safety
+///
+/// The constants must be the base alignment and the size of the type in the `std140` layout,
+/// and the Rust representation of the type must follow these rules.
+pub unsafe trait Std140Layout: Copy {
+    /// Base alignment of the type in bytes.
+    const STD140_ALIGNMENT: usize;
+
+    /// Size of the type in bytes.
+    const STD140_SIZE: usize;
+}
+
+/// Types that can be stored in a block with the `std430` layout.
+///
+/// # Safety
+///
+/// The constants must be the base alignment and the size of the type in the `std430` layout,
+/// and the Rust representation of the type must follow these rules.
+pub unsafe trait Std430Layout: Copy {
+    /// Base alignment of the type in bytes.
+    const STD430_ALIGNMENT: usize;
+
+    /// Size of the type in bytes.
+    const STD430_SIZE: usize;
+}
+
+/// Rounds `offset` up to the next multiple of `alignment`.
+#[inline]
+pub const fn align_offset(offset: usize, alignment: usize) -> usize {
+    offset.div_ceil(alignment) * alignment
+}
+
+/// Returns the base alignment of a struct in the `std140` layout, given the alignments of its
+/// members.
+pub const fn std140_struct_alignment(members: &[usize]) -> usize {
+    align_offset(std430_struct_alignment(members), 16)
+}
+
+/// Returns the base alignment of a struct in the `std430` layout, given the alignments of its
+/// members.
+pub const fn std430_struct_alignment(members: &[usize]) -> usize {
+    let mut alignment = 1;
+    let mut i = 0;
+    while i < members.len() {
+        if members[i] > alignment {
+            alignment = members[i];
+        }
+        i += 1;
+    }
+    alignment
+}
+
+macro_rules! impl_layout {
+    ($ty:ty, $alignment:expr, $size:expr) => (
+        unsafe impl Std140Layout for $ty {
+            const STD140_ALIGNMENT: usize = $alignment;
+            const STD140_SIZE: usize = $size;
+        }
+
+        unsafe impl Std430Layout for $ty {
+            const STD430_ALIGNMENT: usize = $alignment;
+            const STD430_SIZE: usize = $size;
+        }
+    );
+}
+
+macro_rules! impl_scalar_and_vectors {
+    ($scalar:ty, $size:expr) => (
+        impl_layout!($scalar, $size, $size);
+        impl_layout!([$scalar; 2], 2 * $size, 2 * $size);
+        impl_layout!([$scalar; 3], 4 * $size, 3 * $size);
+        impl_layout!([$scalar; 4], 4 * $size, 4 * $size);
+    );
+}
+
+impl_scalar_and_vectors!(f32, 4);
+impl_scalar_and_vectors!(i32, 4);
+impl_scalar_and_vectors!(u32, 4);
+impl_scalar_and_vectors!(f64, 8);
+impl_scalar_and_vectors!(i64, 8);
+impl_scalar_and_vectors!(u64, 8);
+
+// the columns of the other matrices are padded in at least one of the layouts
+impl_layout!([[f32; 4]; 4], 16, 64);
+impl_layout!([[f64; 2]; 2], 16, 32);
+impl_layout!([[f64; 4]; 4], 32, 128);
+
+unsafe impl Std430Layout for [[f32; 2]; 2] {
+    const STD430_ALIGNMENT: usize = 8;
+    const STD430_SIZE: usize = 16;
+}
+
+/// A value aligned to 16 bytes.
+///
+/// This doesn't change the layout of the value in GLSL, but changes its size and alignment in
+/// Rust. This is required for the elements of arrays whose stride is larger than the size of
+/// the elements.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(C, align(16))]
+pub struct Padded<T>(pub T);
+
+impl<T> From<T> for Padded<T> {
+    #[inline]
+    fn from(value: T) -> Padded<T> {
+        Padded(value)
+    }
+}
+
+impl<T> Deref for Padded<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Padded<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+unsafe impl<T> Std140Layout for Padded<T> where T: Std140Layout {
+    const STD140_ALIGNMENT: usize = T::STD140_ALIGNMENT;
+    const STD140_SIZE: usize = T::STD140_SIZE;
+}
+
+unsafe impl<T> Std430Layout for Padded<T> where T: Std430Layout {
+    const STD430_ALIGNMENT: usize = T::STD430_ALIGNMENT;
+    const STD430_SIZE: usize = T::STD430_SIZE;
+}
+
+impl<T> UniformBlock for Padded<T> where T: UniformBlock {
+    #[inline]
+    fn matches(layout: &BlockLayout, base_offset: usize) -> Result<(), LayoutMismatchError> {
+        T::matches(layout, base_offset)
+    }
+
+    #[inline]
+    fn build_layout(base_offset: usize) -> BlockLayout {
+        T::build_layout(base_offset)
+    }
+}
+
+/// A GLSL array of `N` elements.
+///
+/// Contrary to Rust arrays, the stride of the elements of an array in GLSL can be larger than
+/// their size. Elements that are smaller than their stride must be wrapped in a `Padded`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Array<T, const N: usize>(pub [T; N]);
+
+impl<T, const N: usize> Array<T, N> {
+    /// Returns the stride of the elements in the given layout, and panics if the elements don't
+    /// have the same stride in Rust.
+    const fn checked_stride(alignment: usize, size: usize) -> usize {
+        let stride = align_offset(size, alignment);
+        if stride != std::mem::size_of::<T>() {
+            panic!("The size of the elements of the array doesn't match their stride in GLSL, \
+                    they must be wrapped in a `Padded`");
+        }
+        stride
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Array<T, N> {
+    #[inline]
+    fn from(value: [T; N]) -> Array<T, N> {
+        Array(value)
+    }
+}
+
+impl<T, const N: usize> Deref for Array<T, N> {
+    type Target = [T; N];
+
+    #[inline]
+    fn deref(&self) -> &[T; N] {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> DerefMut for Array<T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T; N] {
+        &mut self.0
+    }
+}
+
+unsafe impl<T, const N: usize> Std140Layout for Array<T, N> where T: Std140Layout {
+    // the alignment of the elements is rounded up to the alignment of a `vec4`
+    const STD140_ALIGNMENT: usize = align_offset(T::STD140_ALIGNMENT, 16);
+    const STD140_SIZE: usize = N * Self::checked_stride(Self::STD140_ALIGNMENT, T::STD140_SIZE);
+}
+
+unsafe impl<T, const N: usize> Std430Layout for Array<T, N> where T: Std430Layout {
+    const STD430_ALIGNMENT: usize = T::STD430_ALIGNMENT;
+    const STD430_SIZE: usize = N * Self::checked_stride(Self::STD430_ALIGNMENT, T::STD430_SIZE);
+}
+
+impl<T, const N: usize> UniformBlock for Array<T, N> where T: UniformBlock {
+    fn matches(layout: &BlockLayout, base_offset: usize) -> Result<(), LayoutMismatchError> {
+        if let BlockLayout::Array { content, length } = layout {
+            if let Err(err) = T::matches(content, base_offset) {
+                return Err(LayoutMismatchError::MemberMismatch {
+                    member: "<array content>".to_owned(),
+                    err: Box::new(err),
+                });
+            }
+
+            if *length == N {
+                return Ok(());
+            }
+        }
+
+        Err(LayoutMismatchError::LayoutMismatch {
+            expected: layout.clone(),
+            obtained: Self::build_layout(base_offset),
+        })
+    }
+
+    #[inline]
+    fn build_layout(base_offset: usize) -> BlockLayout {
+        BlockLayout::Array {
+            content: Box::new(T::build_layout(base_offset)),
+            length: N,
+        }
+    }
+}
+
+macro_rules! padded_matrix {
+    ($(#[$attr:meta])* $name:ident, $columns:expr, $value:ident, $uniform_ty:ident) => (
+        $(#[$attr])*
+        #[derive(Copy, Clone, Debug, Default, PartialEq)]
+        #[repr(C)]
+        pub struct $name(pub [Padded<[f32; $columns]>; $columns]);
+
+        impl From<[[f32; $columns]; $columns]> for $name {
+            #[inline]
+            fn from(value: [[f32; $columns]; $columns]) -> $name {
+                $name(value.map(Padded))
+            }
+        }
+
+        impl From<$name> for [[f32; $columns]; $columns] {
+            #[inline]
+            fn from(value: $name) -> [[f32; $columns]; $columns] {
+                value.0.map(|column| column.0)
+            }
+        }
+
+        impl AsUniformValue for $name {
+            #[inline]
+            fn as_uniform_value(&self) -> UniformValue<'_> {
+                UniformValue::$value((*self).into())
+            }
+        }
+
+        impl UniformBlock for $name {
+            #[inline]
+            fn matches(layout: &BlockLayout, base_offset: usize)
+                       -> Result<(), LayoutMismatchError>
+            {
+                // the layout only describes the type and the offset of the matrix
+                <[[f32; $columns]; $columns] as UniformBlock>::matches(layout, base_offset)
+            }
+
+            #[inline]
+            fn build_layout(base_offset: usize) -> BlockLayout {
+                BlockLayout::BasicType {
+                    ty: UniformType::$uniform_ty,
+                    offset_in_buffer: base_offset,
+                }
+            }
+        }
+
+        unsafe impl Std140Layout for $name {
+            const STD140_ALIGNMENT: usize = 16;
+            const STD140_SIZE: usize = $columns * 16;
+        }
+    );
+}
+
+padded_matrix!(
+    /// A `mat2` whose columns are padded to 16 bytes, as required by the `std140` layout.
+    ///
+    /// In the `std430` layout, use `[[f32; 2]; 2]` instead.
+    Mat2, 2, Mat2, FloatMat2
+);
+
+padded_matrix!(
+    /// A `mat3` whose columns are padded to 16 bytes, as required by both layouts.
+    Mat3, 3, Mat3, FloatMat3
+);
+
+unsafe impl Std430Layout for Mat3 {
+    const STD430_ALIGNMENT: usize = 16;
+    const STD430_SIZE: usize = 48;
+}
+
+macro_rules! layout_wrapper {
+    ($(#[$attr:meta])* $name:ident, $layout:ident, $size:ident) => (
+        $(#[$attr])*
+        #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+        #[repr(transparent)]
+        pub struct $name<T>(T);
+
+        impl<T> $name<T> where T: $layout {
+            /// Wraps a value. Fails to compile if the layout of `T` doesn't match GLSL.
+            #[inline]
+            pub const fn new(value: T) -> $name<T> {
+                const { T::$size };
+                $name(value)
+            }
+
+            /// Returns the wrapped value.
+            #[inline]
+            pub fn into_inner(self) -> T {
+                self.0
+            }
+        }
+
+        impl<T> From<T> for $name<T> where T: $layout {
+            #[inline]
+            fn from(value: T) -> $name<T> {
+                $name::new(value)
+            }
+        }
+
+        impl<T> Deref for $name<T> {
+            type Target = T;
+
+            #[inline]
+            fn deref(&self) -> &T {
+                &self.0
+            }
+        }
+
+        impl<T> DerefMut for $name<T> {
+            #[inline]
+            fn deref_mut(&mut self) -> &mut T {
+                &mut self.0
+            }
+        }
+
+        impl<T> UniformBlock for $name<T> where T: UniformBlock {
+            #[inline]
+            fn matches(layout: &BlockLayout, base_offset: usize)
+                       -> Result<(), LayoutMismatchError>
+            {
+                T::matches(layout, base_offset)
+            }
+
+            #[inline]
+            fn build_layout(base_offset: usize) -> BlockLayout {
+                T::build_layout(base_offset)
+            }
+        }
+    );
+}
+
+layout_wrapper!(
+    /// The content of a uniform buffer whose layout is checked against the `std140` rules at
+    /// compile time.
+    Std140, Std140Layout, STD140_SIZE
+);
+
+layout_wrapper!(
+    /// The content of a buffer whose layout is checked against the `std430` rules at compile
+    /// time.
+    Std430, Std430Layout, STD430_SIZE
+);
+
+/// A difference between two block layouts, returned by `diff_layouts`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LayoutDifference {
+    /// Path of the member, for example `lights[].color`. Empty for the block itself.
+    pub path: String,
+
+    /// What differs.
+    pub kind: LayoutDifferenceKind,
+}
+
+/// Kind of a `LayoutDifference`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LayoutDifferenceKind {
+    /// The members have a different type.
+    Type {
+        /// Type in the expected layout.
+        expected: UniformType,
+        /// Type in the obtained layout.
+        obtained: UniformType,
+    },
+
+    /// The members are at a different offset.
+    Offset {
+        /// Offset in the expected layout.
+        expected: usize,
+        /// Offset in the obtained layout.
+        obtained: usize,
+    },
+
+    /// The arrays have a different length.
+    Length {
+        /// Length in the expected layout.
+        expected: usize,
+        /// Length in the obtained layout.
+        obtained: usize,
+    },
+
+    /// The member only exists in the expected layout.
+    Missing,
+
+    /// The member only exists in the obtained layout.
+    Unexpected,
+
+    /// The members are of a different kind, for example a struct and an array.
+    Structure,
+}
+
+impl fmt::Display for LayoutDifference {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() { "<block>" } else { &self.path };
+
+        match self.kind {
+            LayoutDifferenceKind::Type { expected, obtained } =>
+                write!(fmt, "{}: expected type {:?}, got {:?}", path, expected, obtained),
+            LayoutDifferenceKind::Offset { expected, obtained } =>
+                write!(fmt, "{}: expected offset {}, got {}", path, expected, obtained),
+            LayoutDifferenceKind::Length { expected, obtained } =>
+                write!(fmt, "{}: expected {} elements, got {}", path, expected, obtained),
+            LayoutDifferenceKind::Missing =>
+                write!(fmt, "{}: missing member", path),
+            LayoutDifferenceKind::Unexpected =>
+                write!(fmt, "{}: unexpected member", path),
+            LayoutDifferenceKind::Structure =>
+                write!(fmt, "{}: the layouts are totally different", path),
+        }
+    }
+}
+
+/// Compares two layouts member by member.
+///
+/// `expected` is usually the layout reflected by the program, and `obtained` the layout of
+/// the Rust type returned by `UniformBlock::build_layout`. Returns an empty list if they match.
+pub fn diff_layouts(expected: &BlockLayout, obtained: &BlockLayout) -> Vec<LayoutDifference> {
+    let mut differences = Vec::new();
+    diff_layouts_impl(String::new(), expected, obtained, &mut differences);
+    differences
+}
+
+/// Compares the layout of a block reflected by the program with the layout of `T`.
+///
+/// This is the comparison made when drawing, except that all the members that differ are
+/// returned instead of the first one. Returns an empty list if they match.
+#[inline]
+pub fn diff_block<T: ?Sized + UniformBlock>(block: &program::UniformBlock) -> Vec<LayoutDifference> {
+    diff_layouts(&block.layout, &T::build_layout(0))
+}
+
+fn diff_layouts_impl(path: String, expected: &BlockLayout, obtained: &BlockLayout,
+                     output: &mut Vec<LayoutDifference>)
+{
+    let member_path = |name: &str| {
+        if path.is_empty() { name.to_owned() } else { format!("{}.{}", path, name) }
+    };
+
+    match (expected, obtained) {
+        (BlockLayout::Struct { members: expected }, BlockLayout::Struct { members: obtained }) => {
+            for (name, expected) in expected {
+                match obtained.iter().find(|member| member.0 == *name) {
+                    Some((_, obtained)) => diff_layouts_impl(member_path(name), expected,
+                                                             obtained, output),
+                    None => output.push(LayoutDifference {
+                        path: member_path(name),
+                        kind: LayoutDifferenceKind::Missing,
+                    }),
+                }
+            }
+
+            for (name, _) in obtained {
+                if !expected.iter().any(|member| member.0 == *name) {
+                    output.push(LayoutDifference {
+                        path: member_path(name),
+                        kind: LayoutDifferenceKind::Unexpected,
+                    });
+                }
+            }
+        },
+
+        (&BlockLayout::BasicType { ty: expected_ty, offset_in_buffer: expected_offset },
+         &BlockLayout::BasicType { ty: obtained_ty, offset_in_buffer: obtained_offset }) =>
+        {
+            if expected_ty != obtained_ty {
+                output.push(LayoutDifference {
+                    path: path.clone(),
+                    kind: LayoutDifferenceKind::Type { expected: expected_ty, obtained: obtained_ty },
+                });
+            }
+
+            if expected_offset != obtained_offset {
+                output.push(LayoutDifference {
+                    path,
+                    kind: LayoutDifferenceKind::Offset { expected: expected_offset,
+                                                         obtained: obtained_offset },
+                });
+            }
+        },
+
+        (BlockLayout::Array { content: expected, length: expected_length },
+         BlockLayout::Array { content: obtained, length: obtained_length }) =>
+        {
+            if expected_length != obtained_length {
+                output.push(LayoutDifference {
+                    path: path.clone(),
+                    kind: LayoutDifferenceKind::Length { expected: *expected_length,
+                                                         obtained: *obtained_length },
+                });
+            }
+
+            diff_layouts_impl(format!("{}[]", path), expected, obtained, output);
+        },
+
+        (BlockLayout::DynamicSizedArray { content: expected },
+         BlockLayout::DynamicSizedArray { content: obtained }) =>
+        {
+            diff_layouts_impl(format!("{}[]", path), expected, obtained, output);
+        },
+
+        _ => output.push(LayoutDifference { path, kind: LayoutDifferenceKind::Structure }),
+    }
+}
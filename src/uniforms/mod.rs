@@ -145,6 +145,8 @@ use crate::buffer::Buffer;
 use crate::program;
 use crate::program::BlockLayout;
 
+pub mod layout;
+
 mod bind;
 mod buffer;
 mod image_unit;
@@ -978,3 +978,323 @@ impl<'a> AsUniformValue for (&'a str, ShaderStage) {
         UniformValue::Subroutine(self.1, self.0)
     }
 }
+
+// Implementations for the types of the `mint` crate. Row matrices are transposed when used as
+// uniform values, and can't be used in uniform blocks since their layout doesn't match.
+#[cfg(feature = "mint")]
+mod mint_impls {
+    use crate::program;
+    use crate::program::BlockLayout;
+    use crate::uniforms::{AsUniformValue, LayoutMismatchError, UniformBlock};
+    use super::{UniformType, UniformValue};
+
+    macro_rules! impl_mint_vectors {
+        ($scalar:ty, [$vec2:ident, $vec3:ident, $vec4:ident], [$ty2:ident, $ty3:ident, $ty4:ident]) => (
+            impl AsUniformValue for mint::Vector2<$scalar> {
+                #[inline]
+                fn as_uniform_value(&self) -> UniformValue<'_> {
+                    UniformValue::$vec2((*self).into())
+                }
+            }
+
+            impl_uniform_block_basic!(mint::Vector2<$scalar>, UniformType::$ty2);
+
+            impl AsUniformValue for mint::Point2<$scalar> {
+                #[inline]
+                fn as_uniform_value(&self) -> UniformValue<'_> {
+                    UniformValue::$vec2((*self).into())
+                }
+            }
+
+            impl_uniform_block_basic!(mint::Point2<$scalar>, UniformType::$ty2);
+
+            impl AsUniformValue for mint::Vector3<$scalar> {
+                #[inline]
+                fn as_uniform_value(&self) -> UniformValue<'_> {
+                    UniformValue::$vec3((*self).into())
+                }
+            }
+
+            impl_uniform_block_basic!(mint::Vector3<$scalar>, UniformType::$ty3);
+
+            impl AsUniformValue for mint::Point3<$scalar> {
+                #[inline]
+                fn as_uniform_value(&self) -> UniformValue<'_> {
+                    UniformValue::$vec3((*self).into())
+                }
+            }
+
+            impl_uniform_block_basic!(mint::Point3<$scalar>, UniformType::$ty3);
+
+            impl AsUniformValue for mint::Vector4<$scalar> {
+                #[inline]
+                fn as_uniform_value(&self) -> UniformValue<'_> {
+                    UniformValue::$vec4((*self).into())
+                }
+            }
+
+            impl_uniform_block_basic!(mint::Vector4<$scalar>, UniformType::$ty4);
+        );
+    }
+
+    impl_mint_vectors!(f32, [Vec2, Vec3, Vec4], [FloatVec2, FloatVec3, FloatVec4]);
+    impl_mint_vectors!(f64, [DoubleVec2, DoubleVec3, DoubleVec4], [DoubleVec2, DoubleVec3, DoubleVec4]);
+    impl_mint_vectors!(i32, [IntVec2, IntVec3, IntVec4], [IntVec2, IntVec3, IntVec4]);
+    impl_mint_vectors!(u32, [UnsignedIntVec2, UnsignedIntVec3, UnsignedIntVec4],
+                       [UnsignedIntVec2, UnsignedIntVec3, UnsignedIntVec4]);
+    impl_mint_vectors!(i64, [Int64Vec2, Int64Vec3, Int64Vec4], [Int64Vec2, Int64Vec3, Int64Vec4]);
+    impl_mint_vectors!(u64, [UnsignedInt64Vec2, UnsignedInt64Vec3, UnsignedInt64Vec4],
+                       [UnsignedInt64Vec2, UnsignedInt64Vec3, UnsignedInt64Vec4]);
+
+    macro_rules! impl_mint_matrix {
+        ($scalar:ty, $column:ident, $row:ident, $value:ident, $uniform_ty:ident) => (
+            impl AsUniformValue for mint::$column<$scalar> {
+                #[inline]
+                fn as_uniform_value(&self) -> UniformValue<'_> {
+                    UniformValue::$value((*self).into())
+                }
+            }
+
+            impl_uniform_block_basic!(mint::$column<$scalar>, UniformType::$uniform_ty);
+
+            impl AsUniformValue for mint::$row<$scalar> {
+                #[inline]
+                fn as_uniform_value(&self) -> UniformValue<'_> {
+                    UniformValue::$value(mint::$column::from(*self).into())
+                }
+            }
+        );
+    }
+
+    impl_mint_matrix!(f32, ColumnMatrix2, RowMatrix2, Mat2, FloatMat2);
+    impl_mint_matrix!(f32, ColumnMatrix3, RowMatrix3, Mat3, FloatMat3);
+    impl_mint_matrix!(f32, ColumnMatrix4, RowMatrix4, Mat4, FloatMat4);
+    impl_mint_matrix!(f64, ColumnMatrix2, RowMatrix2, DoubleMat2, DoubleMat2);
+    impl_mint_matrix!(f64, ColumnMatrix3, RowMatrix3, DoubleMat3, DoubleMat3);
+    impl_mint_matrix!(f64, ColumnMatrix4, RowMatrix4, DoubleMat4, DoubleMat4);
+
+    impl AsUniformValue for mint::Quaternion<f32> {
+        #[inline]
+        fn as_uniform_value(&self) -> UniformValue<'_> {
+            UniformValue::Vec4((*self).into())
+        }
+    }
+
+    impl_uniform_block_basic!(mint::Quaternion<f32>, UniformType::FloatVec4);
+
+    impl AsUniformValue for mint::Quaternion<f64> {
+        #[inline]
+        fn as_uniform_value(&self) -> UniformValue<'_> {
+            UniformValue::DoubleVec4((*self).into())
+        }
+    }
+
+    impl_uniform_block_basic!(mint::Quaternion<f64>, UniformType::DoubleVec4);
+}
+
+// Implementations for the types of the `glam` crate. Matrices are sent column by column.
+#[cfg(feature = "glam")]
+mod glam_impls {
+    use crate::program;
+    use crate::program::BlockLayout;
+    use crate::uniforms::{AsUniformValue, LayoutMismatchError, UniformBlock};
+    use super::{UniformType, UniformValue};
+
+    macro_rules! impl_glam_vectors {
+        ([$vec2:ident, $vec3:ident, $vec4:ident], [$value2:ident, $value3:ident, $value4:ident],
+         [$ty2:ident, $ty3:ident, $ty4:ident]) => (
+            impl AsUniformValue for glam::$vec2 {
+                #[inline]
+                fn as_uniform_value(&self) -> UniformValue<'_> {
+                    UniformValue::$value2(self.to_array())
+                }
+            }
+
+            impl_uniform_block_basic!(glam::$vec2, UniformType::$ty2);
+
+            impl AsUniformValue for glam::$vec3 {
+                #[inline]
+                fn as_uniform_value(&self) -> UniformValue<'_> {
+                    UniformValue::$value3(self.to_array())
+                }
+            }
+
+            impl_uniform_block_basic!(glam::$vec3, UniformType::$ty3);
+
+            impl AsUniformValue for glam::$vec4 {
+                #[inline]
+                fn as_uniform_value(&self) -> UniformValue<'_> {
+                    UniformValue::$value4(self.to_array())
+                }
+            }
+
+            impl_uniform_block_basic!(glam::$vec4, UniformType::$ty4);
+        );
+    }
+
+    impl_glam_vectors!([Vec2, Vec3, Vec4], [Vec2, Vec3, Vec4], [FloatVec2, FloatVec3, FloatVec4]);
+    impl_glam_vectors!([DVec2, DVec3, DVec4], [DoubleVec2, DoubleVec3, DoubleVec4],
+                       [DoubleVec2, DoubleVec3, DoubleVec4]);
+    impl_glam_vectors!([IVec2, IVec3, IVec4], [IntVec2, IntVec3, IntVec4],
+                       [IntVec2, IntVec3, IntVec4]);
+    impl_glam_vectors!([UVec2, UVec3, UVec4], [UnsignedIntVec2, UnsignedIntVec3, UnsignedIntVec4],
+                       [UnsignedIntVec2, UnsignedIntVec3, UnsignedIntVec4]);
+    impl_glam_vectors!([I64Vec2, I64Vec3, I64Vec4], [Int64Vec2, Int64Vec3, Int64Vec4],
+                       [Int64Vec2, Int64Vec3, Int64Vec4]);
+    impl_glam_vectors!([U64Vec2, U64Vec3, U64Vec4],
+                       [UnsignedInt64Vec2, UnsignedInt64Vec3, UnsignedInt64Vec4],
+                       [UnsignedInt64Vec2, UnsignedInt64Vec3, UnsignedInt64Vec4]);
+
+    macro_rules! impl_glam_matrix {
+        ($matrix:ident, $value:ident, $uniform_ty:ident) => (
+            impl AsUniformValue for glam::$matrix {
+                #[inline]
+                fn as_uniform_value(&self) -> UniformValue<'_> {
+                    UniformValue::$value(self.to_cols_array_2d())
+                }
+            }
+
+            impl_uniform_block_basic!(glam::$matrix, UniformType::$uniform_ty);
+        );
+    }
+
+    impl_glam_matrix!(Mat2, Mat2, FloatMat2);
+    impl_glam_matrix!(Mat3, Mat3, FloatMat3);
+    impl_glam_matrix!(Mat4, Mat4, FloatMat4);
+    impl_glam_matrix!(DMat2, DoubleMat2, DoubleMat2);
+    impl_glam_matrix!(DMat3, DoubleMat3, DoubleMat3);
+    impl_glam_matrix!(DMat4, DoubleMat4, DoubleMat4);
+
+    impl AsUniformValue for glam::Quat {
+        #[inline]
+        fn as_uniform_value(&self) -> UniformValue<'_> {
+            UniformValue::Vec4(self.to_array())
+        }
+    }
+
+    impl_uniform_block_basic!(glam::Quat, UniformType::FloatVec4);
+
+    impl AsUniformValue for glam::DQuat {
+        #[inline]
+        fn as_uniform_value(&self) -> UniformValue<'_> {
+            UniformValue::DoubleVec4(self.to_array())
+        }
+    }
+
+    impl_uniform_block_basic!(glam::DQuat, UniformType::DoubleVec4);
+}
+
+// Implementations for the types of the `nalgebra` crate. Matrices are sent column by column, and
+// quaternions as `(i, j, k, w)`.
+#[cfg(feature = "nalgebra")]
+mod nalgebra_impls {
+    use crate::program;
+    use crate::program::BlockLayout;
+    use crate::uniforms::{AsUniformValue, LayoutMismatchError, UniformBlock};
+    use super::{UniformType, UniformValue};
+
+    macro_rules! impl_nalgebra_vectors {
+        ($scalar:ty, [$vec2:ident, $vec3:ident, $vec4:ident], [$ty2:ident, $ty3:ident, $ty4:ident]) => (
+            impl AsUniformValue for nalgebra::Vector2<$scalar> {
+                #[inline]
+                fn as_uniform_value(&self) -> UniformValue<'_> {
+                    UniformValue::$vec2((*self).into())
+                }
+            }
+
+            impl_uniform_block_basic!(nalgebra::Vector2<$scalar>, UniformType::$ty2);
+
+            impl AsUniformValue for nalgebra::Point2<$scalar> {
+                #[inline]
+                fn as_uniform_value(&self) -> UniformValue<'_> {
+                    UniformValue::$vec2(self.coords.into())
+                }
+            }
+
+            impl_uniform_block_basic!(nalgebra::Point2<$scalar>, UniformType::$ty2);
+
+            impl AsUniformValue for nalgebra::Vector3<$scalar> {
+                #[inline]
+                fn as_uniform_value(&self) -> UniformValue<'_> {
+                    UniformValue::$vec3((*self).into())
+                }
+            }
+
+            impl_uniform_block_basic!(nalgebra::Vector3<$scalar>, UniformType::$ty3);
+
+            impl AsUniformValue for nalgebra::Point3<$scalar> {
+                #[inline]
+                fn as_uniform_value(&self) -> UniformValue<'_> {
+                    UniformValue::$vec3(self.coords.into())
+                }
+            }
+
+            impl_uniform_block_basic!(nalgebra::Point3<$scalar>, UniformType::$ty3);
+
+            impl AsUniformValue for nalgebra::Vector4<$scalar> {
+                #[inline]
+                fn as_uniform_value(&self) -> UniformValue<'_> {
+                    UniformValue::$vec4((*self).into())
+                }
+            }
+
+            impl_uniform_block_basic!(nalgebra::Vector4<$scalar>, UniformType::$ty4);
+
+            impl AsUniformValue for nalgebra::Point4<$scalar> {
+                #[inline]
+                fn as_uniform_value(&self) -> UniformValue<'_> {
+                    UniformValue::$vec4(self.coords.into())
+                }
+            }
+
+            impl_uniform_block_basic!(nalgebra::Point4<$scalar>, UniformType::$ty4);
+        );
+    }
+
+    impl_nalgebra_vectors!(f32, [Vec2, Vec3, Vec4], [FloatVec2, FloatVec3, FloatVec4]);
+    impl_nalgebra_vectors!(f64, [DoubleVec2, DoubleVec3, DoubleVec4], [DoubleVec2, DoubleVec3, DoubleVec4]);
+    impl_nalgebra_vectors!(i32, [IntVec2, IntVec3, IntVec4], [IntVec2, IntVec3, IntVec4]);
+    impl_nalgebra_vectors!(u32, [UnsignedIntVec2, UnsignedIntVec3, UnsignedIntVec4],
+                           [UnsignedIntVec2, UnsignedIntVec3, UnsignedIntVec4]);
+    impl_nalgebra_vectors!(i64, [Int64Vec2, Int64Vec3, Int64Vec4], [Int64Vec2, Int64Vec3, Int64Vec4]);
+    impl_nalgebra_vectors!(u64, [UnsignedInt64Vec2, UnsignedInt64Vec3, UnsignedInt64Vec4],
+                           [UnsignedInt64Vec2, UnsignedInt64Vec3, UnsignedInt64Vec4]);
+
+    macro_rules! impl_nalgebra_matrix {
+        ($scalar:ty, $matrix:ident, $value:ident, $uniform_ty:ident) => (
+            impl AsUniformValue for nalgebra::$matrix<$scalar> {
+                #[inline]
+                fn as_uniform_value(&self) -> UniformValue<'_> {
+                    UniformValue::$value((*self).into())
+                }
+            }
+
+            impl_uniform_block_basic!(nalgebra::$matrix<$scalar>, UniformType::$uniform_ty);
+        );
+    }
+
+    impl_nalgebra_matrix!(f32, Matrix2, Mat2, FloatMat2);
+    impl_nalgebra_matrix!(f32, Matrix3, Mat3, FloatMat3);
+    impl_nalgebra_matrix!(f32, Matrix4, Mat4, FloatMat4);
+    impl_nalgebra_matrix!(f64, Matrix2, DoubleMat2, DoubleMat2);
+    impl_nalgebra_matrix!(f64, Matrix3, DoubleMat3, DoubleMat3);
+    impl_nalgebra_matrix!(f64, Matrix4, DoubleMat4, DoubleMat4);
+
+    impl AsUniformValue for nalgebra::Quaternion<f32> {
+        #[inline]
+        fn as_uniform_value(&self) -> UniformValue<'_> {
+            UniformValue::Vec4(self.coords.into())
+        }
+    }
+
+    impl_uniform_block_basic!(nalgebra::Quaternion<f32>, UniformType::FloatVec4);
+
+    impl AsUniformValue for nalgebra::Quaternion<f64> {
+        #[inline]
+        fn as_uniform_value(&self) -> UniformValue<'_> {
+            UniformValue::DoubleVec4(self.coords.into())
+        }
+    }
+
+    impl_uniform_block_basic!(nalgebra::Quaternion<f64>, UniformType::DoubleVec4);
+}
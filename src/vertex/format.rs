@@ -711,3 +711,224 @@ unsafe impl Attribute for [[f64; 3]; 3] {
 unsafe impl Attribute for [[f64; 4]; 4] {
     const TYPE: AttributeType = AttributeType::F64x4x4;
 }
+
+// Implementations for the types of the `mint` crate. The vectors, points, column matrices and
+// quaternions of `mint` have the same memory layout as the corresponding arrays.
+#[cfg(feature = "mint")]
+mod mint_impls {
+    use super::AttributeType;
+    use crate::vertex::Attribute;
+
+    macro_rules! impl_mint_vectors {
+        ($scalar:ty, $vec2:ident, $vec3:ident, $vec4:ident) => (
+            unsafe impl Attribute for mint::Vector2<$scalar> {
+                const TYPE: AttributeType = AttributeType::$vec2;
+            }
+
+            unsafe impl Attribute for mint::Point2<$scalar> {
+                const TYPE: AttributeType = AttributeType::$vec2;
+            }
+
+            unsafe impl Attribute for mint::Vector3<$scalar> {
+                const TYPE: AttributeType = AttributeType::$vec3;
+            }
+
+            unsafe impl Attribute for mint::Point3<$scalar> {
+                const TYPE: AttributeType = AttributeType::$vec3;
+            }
+
+            unsafe impl Attribute for mint::Vector4<$scalar> {
+                const TYPE: AttributeType = AttributeType::$vec4;
+            }
+        );
+    }
+
+    impl_mint_vectors!(i8, I8I8, I8I8I8, I8I8I8I8);
+    impl_mint_vectors!(u8, U8U8, U8U8U8, U8U8U8U8);
+    impl_mint_vectors!(i16, I16I16, I16I16I16, I16I16I16I16);
+    impl_mint_vectors!(u16, U16U16, U16U16U16, U16U16U16U16);
+    impl_mint_vectors!(i32, I32I32, I32I32I32, I32I32I32I32);
+    impl_mint_vectors!(u32, U32U32, U32U32U32, U32U32U32U32);
+    impl_mint_vectors!(i64, I64I64, I64I64I64, I64I64I64I64);
+    impl_mint_vectors!(u64, U64U64, U64U64U64, U64U64U64U64);
+    impl_mint_vectors!(f32, F32F32, F32F32F32, F32F32F32F32);
+    impl_mint_vectors!(f64, F64F64, F64F64F64, F64F64F64F64);
+
+    unsafe impl Attribute for mint::Quaternion<f32> {
+        const TYPE: AttributeType = AttributeType::F32F32F32F32;
+    }
+
+    unsafe impl Attribute for mint::Quaternion<f64> {
+        const TYPE: AttributeType = AttributeType::F64F64F64F64;
+    }
+
+    unsafe impl Attribute for mint::ColumnMatrix2<f32> {
+        const TYPE: AttributeType = AttributeType::F32x2x2;
+    }
+
+    unsafe impl Attribute for mint::ColumnMatrix3<f32> {
+        const TYPE: AttributeType = AttributeType::F32x3x3;
+    }
+
+    unsafe impl Attribute for mint::ColumnMatrix4<f32> {
+        const TYPE: AttributeType = AttributeType::F32x4x4;
+    }
+
+    unsafe impl Attribute for mint::ColumnMatrix2<f64> {
+        const TYPE: AttributeType = AttributeType::F64x2x2;
+    }
+
+    unsafe impl Attribute for mint::ColumnMatrix3<f64> {
+        const TYPE: AttributeType = AttributeType::F64x3x3;
+    }
+
+    unsafe impl Attribute for mint::ColumnMatrix4<f64> {
+        const TYPE: AttributeType = AttributeType::F64x4x4;
+    }
+}
+
+// Implementations for the types of the `glam` crate. `Vec3A` and `Mat3A` are left out since they
+// are padded to 16 bytes.
+#[cfg(feature = "glam")]
+mod glam_impls {
+    use super::AttributeType;
+    use crate::vertex::Attribute;
+
+    macro_rules! impl_glam_vectors {
+        ($vec2:ident, $vec3:ident, $vec4:ident, $ty2:ident, $ty3:ident, $ty4:ident) => (
+            unsafe impl Attribute for glam::$vec2 {
+                const TYPE: AttributeType = AttributeType::$ty2;
+            }
+
+            unsafe impl Attribute for glam::$vec3 {
+                const TYPE: AttributeType = AttributeType::$ty3;
+            }
+
+            unsafe impl Attribute for glam::$vec4 {
+                const TYPE: AttributeType = AttributeType::$ty4;
+            }
+        );
+    }
+
+    impl_glam_vectors!(I8Vec2, I8Vec3, I8Vec4, I8I8, I8I8I8, I8I8I8I8);
+    impl_glam_vectors!(U8Vec2, U8Vec3, U8Vec4, U8U8, U8U8U8, U8U8U8U8);
+    impl_glam_vectors!(I16Vec2, I16Vec3, I16Vec4, I16I16, I16I16I16, I16I16I16I16);
+    impl_glam_vectors!(U16Vec2, U16Vec3, U16Vec4, U16U16, U16U16U16, U16U16U16U16);
+    impl_glam_vectors!(IVec2, IVec3, IVec4, I32I32, I32I32I32, I32I32I32I32);
+    impl_glam_vectors!(UVec2, UVec3, UVec4, U32U32, U32U32U32, U32U32U32U32);
+    impl_glam_vectors!(I64Vec2, I64Vec3, I64Vec4, I64I64, I64I64I64, I64I64I64I64);
+    impl_glam_vectors!(U64Vec2, U64Vec3, U64Vec4, U64U64, U64U64U64, U64U64U64U64);
+    impl_glam_vectors!(Vec2, Vec3, Vec4, F32F32, F32F32F32, F32F32F32F32);
+    impl_glam_vectors!(DVec2, DVec3, DVec4, F64F64, F64F64F64, F64F64F64F64);
+
+    unsafe impl Attribute for glam::Quat {
+        const TYPE: AttributeType = AttributeType::F32F32F32F32;
+    }
+
+    unsafe impl Attribute for glam::DQuat {
+        const TYPE: AttributeType = AttributeType::F64F64F64F64;
+    }
+
+    unsafe impl Attribute for glam::Mat2 {
+        const TYPE: AttributeType = AttributeType::F32x2x2;
+    }
+
+    unsafe impl Attribute for glam::Mat3 {
+        const TYPE: AttributeType = AttributeType::F32x3x3;
+    }
+
+    unsafe impl Attribute for glam::Mat4 {
+        const TYPE: AttributeType = AttributeType::F32x4x4;
+    }
+
+    unsafe impl Attribute for glam::DMat2 {
+        const TYPE: AttributeType = AttributeType::F64x2x2;
+    }
+
+    unsafe impl Attribute for glam::DMat3 {
+        const TYPE: AttributeType = AttributeType::F64x3x3;
+    }
+
+    unsafe impl Attribute for glam::DMat4 {
+        const TYPE: AttributeType = AttributeType::F64x4x4;
+    }
+}
+
+// Implementations for the types of the `nalgebra` crate. Its vectors, points, matrices and
+// quaternions are stored column by column, like the corresponding arrays.
+#[cfg(feature = "nalgebra")]
+mod nalgebra_impls {
+    use super::AttributeType;
+    use crate::vertex::Attribute;
+
+    macro_rules! impl_nalgebra_vectors {
+        ($scalar:ty, $vec2:ident, $vec3:ident, $vec4:ident) => (
+            unsafe impl Attribute for nalgebra::Vector2<$scalar> {
+                const TYPE: AttributeType = AttributeType::$vec2;
+            }
+
+            unsafe impl Attribute for nalgebra::Point2<$scalar> {
+                const TYPE: AttributeType = AttributeType::$vec2;
+            }
+
+            unsafe impl Attribute for nalgebra::Vector3<$scalar> {
+                const TYPE: AttributeType = AttributeType::$vec3;
+            }
+
+            unsafe impl Attribute for nalgebra::Point3<$scalar> {
+                const TYPE: AttributeType = AttributeType::$vec3;
+            }
+
+            unsafe impl Attribute for nalgebra::Vector4<$scalar> {
+                const TYPE: AttributeType = AttributeType::$vec4;
+            }
+
+            unsafe impl Attribute for nalgebra::Point4<$scalar> {
+                const TYPE: AttributeType = AttributeType::$vec4;
+            }
+        );
+    }
+
+    impl_nalgebra_vectors!(i8, I8I8, I8I8I8, I8I8I8I8);
+    impl_nalgebra_vectors!(u8, U8U8, U8U8U8, U8U8U8U8);
+    impl_nalgebra_vectors!(i16, I16I16, I16I16I16, I16I16I16I16);
+    impl_nalgebra_vectors!(u16, U16U16, U16U16U16, U16U16U16U16);
+    impl_nalgebra_vectors!(i32, I32I32, I32I32I32, I32I32I32I32);
+    impl_nalgebra_vectors!(u32, U32U32, U32U32U32, U32U32U32U32);
+    impl_nalgebra_vectors!(i64, I64I64, I64I64I64, I64I64I64I64);
+    impl_nalgebra_vectors!(u64, U64U64, U64U64U64, U64U64U64U64);
+    impl_nalgebra_vectors!(f32, F32F32, F32F32F32, F32F32F32F32);
+    impl_nalgebra_vectors!(f64, F64F64, F64F64F64, F64F64F64F64);
+
+    unsafe impl Attribute for nalgebra::Quaternion<f32> {
+        const TYPE: AttributeType = AttributeType::F32F32F32F32;
+    }
+
+    unsafe impl Attribute for nalgebra::Quaternion<f64> {
+        const TYPE: AttributeType = AttributeType::F64F64F64F64;
+    }
+
+    unsafe impl Attribute for nalgebra::Matrix2<f32> {
+        const TYPE: AttributeType = AttributeType::F32x2x2;
+    }
+
+    unsafe impl Attribute for nalgebra::Matrix3<f32> {
+        const TYPE: AttributeType = AttributeType::F32x3x3;
+    }
+
+    unsafe impl Attribute for nalgebra::Matrix4<f32> {
+        const TYPE: AttributeType = AttributeType::F32x4x4;
+    }
+
+    unsafe impl Attribute for nalgebra::Matrix2<f64> {
+        const TYPE: AttributeType = AttributeType::F64x2x2;
+    }
+
+    unsafe impl Attribute for nalgebra::Matrix3<f64> {
+        const TYPE: AttributeType = AttributeType::F64x3x3;
+    }
+
+    unsafe impl Attribute for nalgebra::Matrix4<f64> {
+        const TYPE: AttributeType = AttributeType::F64x4x4;
+    }
+}
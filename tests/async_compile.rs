@@ -0,0 +1,132 @@
+#[macro_use]
+extern crate glium;
+
+use glium::Program;
+use glium::program::{self, ProgramCreationError, ShaderType};
+
+mod support;
+
+const VERTEX_SHADER: &str = "
+    #version 110
+
+    attribute vec2 position;
+
+    void main() {
+        gl_Position = vec4(position, 0.0, 1.0);
+    }
+";
+
+const FRAGMENT_SHADER: &str = "
+    #version 110
+
+    uniform vec4 color;
+
+    void main() {
+        gl_FragColor = color;
+    }
+";
+
+fn source<'a>(vertex_shader: &'a str, fragment_shader: &'a str) -> program::SourceCode<'a> {
+    program::SourceCode {
+        vertex_shader,
+        fragment_shader,
+        geometry_shader: None,
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+    }
+}
+
+#[test]
+fn new_async() {
+    let display = support::build_display();
+
+    let pending = match Program::new_async(&display, source(VERTEX_SHADER, FRAGMENT_SHADER)) {
+        Err(ProgramCreationError::CompilationNotSupported) => return,
+        result => result.unwrap(),
+    };
+
+    while !pending.is_ready() {
+        std::thread::yield_now();
+    }
+
+    let program = pending.finish().unwrap();
+    assert!(program.get_uniform("color").is_some());
+    assert!(program.get_attribute("position").is_some());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn finish_without_polling() {
+    let display = support::build_display();
+
+    let pending = match Program::new_async(&display, source(VERTEX_SHADER, FRAGMENT_SHADER)) {
+        Err(ProgramCreationError::CompilationNotSupported) => return,
+        result => result.unwrap(),
+    };
+
+    assert!(pending.finish().unwrap().get_uniform("color").is_some());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn compilation_error() {
+    let display = support::build_display();
+
+    let pending = match Program::new_async(&display, source(VERTEX_SHADER, "invalid glsl code")) {
+        Err(ProgramCreationError::CompilationNotSupported) => return,
+        result => result.unwrap(),
+    };
+
+    match pending.finish() {
+        Err(ProgramCreationError::CompilationError(_, ShaderType::Fragment)) => (),
+        result => panic!("{:?}", result),
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn drop_pending() {
+    let display = support::build_display();
+
+    match Program::new_async(&display, source(VERTEX_SHADER, FRAGMENT_SHADER)) {
+        Err(ProgramCreationError::CompilationNotSupported) => return,
+        result => drop(result.unwrap()),
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn binary() {
+    let display = support::build_display();
+
+    let program = match Program::new(&display, source(VERTEX_SHADER, FRAGMENT_SHADER)) {
+        Err(ProgramCreationError::CompilationNotSupported) => return,
+        result => result.unwrap(),
+    };
+
+    let binary = match program.get_binary() {
+        Ok(binary) => binary,
+        Err(_) => return,
+    };
+
+    let pending = Program::new_async(&display, binary).unwrap();
+    assert!(pending.is_ready());
+    assert!(pending.finish().unwrap().get_uniform("color").is_some());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn max_shader_compiler_threads() {
+    let display = support::build_display();
+
+    let supported = program::is_parallel_compilation_supported(&display);
+    assert_eq!(program::set_max_shader_compiler_threads(&display, 2), supported);
+    assert_eq!(program::set_max_shader_compiler_threads(&display, u32::MAX), supported);
+
+    display.assert_no_error(None);
+}
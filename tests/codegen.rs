@@ -0,0 +1,171 @@
+#[macro_use]
+extern crate glium;
+
+use glium::program::codegen::{CodegenError, Generator};
+
+mod support;
+
+const VERTEX_SHADER: &str = "
+    #version 330
+
+    layout(location = 0) in vec3 position;
+    layout(location = 1) in vec2 tex_coords;
+
+    out vec2 v_tex_coords;
+
+    void main() {
+        v_tex_coords = tex_coords;
+        gl_Position = vec4(position, 1.0);
+    }
+";
+
+const FRAGMENT_SHADER: &str = "
+    #version 330
+
+    struct Light {
+        vec3 position;
+        float intensity;
+        vec4 color;
+    };
+
+    layout(std140) uniform Scene {
+        mat3 rotation;
+        float weights[2];
+        Light light;
+        float ambient;
+        vec2 offset;
+    };
+
+    in vec2 v_tex_coords;
+    out vec4 f_color;
+
+    void main() {
+        f_color = light.color * light.intensity * weights[1] + ambient * rotation[0].x
+                  + vec4(offset + v_tex_coords, 0.0, 0.0);
+    }
+";
+
+mod generated {
+    include!("fixture/codegen.rs");
+}
+
+fn without_header(code: &str) -> &str {
+    code.split_once('\n').unwrap().1
+}
+
+fn generate_from_source() -> Generator {
+    let mut generator = Generator::new();
+    generator.add_vertex_shader_source("Vertex", VERTEX_SHADER).unwrap();
+    generator.add_shader_source(FRAGMENT_SHADER).unwrap();
+    generator
+}
+
+#[test]
+fn source_codegen() {
+    let code = generate_from_source().generate().unwrap();
+    assert_eq!(code, include_str!("fixture/codegen.rs"));
+}
+
+#[test]
+fn generated_layouts() {
+    use glium::uniforms::UniformBlock;
+    use glium::uniforms::layout::Mat3;
+
+    assert_eq!(std::mem::size_of::<generated::Scene>(), 128);
+
+    let scene = generated::Scene::new(
+        Mat3::from([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]),
+        [0.5.into(), 1.0.into()].into(),
+        generated::SceneLight::new([0.0; 3], 1.0, [1.0; 4]),
+        0.25,
+        [0.0, 0.0],
+    );
+    assert_eq!(scene.ambient, 0.25);
+
+    let vertex = generated::Vertex { position: [0.0, 1.0, 0.0], tex_coords: [0.5, 0.5] };
+    assert_eq!(vertex.tex_coords, [0.5, 0.5]);
+    let bindings = <generated::Vertex as glium::Vertex>::build_bindings();
+    assert_eq!(bindings[0].3, glium::vertex::AttributeType::F32F32F32);
+    assert_eq!(bindings[1].3, glium::vertex::AttributeType::F32F32);
+
+    let mut generator = Generator::new();
+    generator.add_block("Scene", &<generated::Scene as UniformBlock>::build_layout(0),
+                        glium::program::codegen::Packing::Std140);
+    let code = generator.generate().unwrap();
+    assert!(include_str!("fixture/codegen.rs").ends_with(without_header(&code)));
+}
+
+#[test]
+fn non_ascii_comments() {
+    let mut generator = Generator::new();
+    generator.add_shader_source("
+        /* é */
+        uniform Block {
+            float value; // é
+            /* première */ vec2 offset;
+        };
+    ").unwrap();
+    assert!(generator.generate().is_ok());
+
+    let error = Generator::new().add_shader_source("
+        uniform float value; /* é").unwrap_err();
+    assert_eq!(error, CodegenError::ParseError {
+        line: 2,
+        message: "unterminated comment".to_owned(),
+    });
+}
+
+#[test]
+fn parse_errors() {
+    let error = Generator::new().add_shader_source("
+        #version 330
+
+        uniform Block {
+            mat2x3 matrix;
+            unknown value;
+        };
+    ").unwrap_err();
+
+    assert_eq!(error, CodegenError::ParseError {
+        line: 6,
+        message: "unknown type `unknown`".to_owned(),
+    });
+
+    let mut generator = Generator::new();
+    generator.add_shader_source("
+        layout(std140) uniform Block {
+            mat2x3 matrix;
+        };
+    ").unwrap();
+
+    assert_eq!(generator.generate().unwrap_err(), CodegenError::UnsupportedType {
+        path: "Block.matrix".to_owned(),
+        ty: "FloatMat2x3".to_owned(),
+    });
+
+    let mut generator = Generator::new();
+    generator.add_shader_source("uniform A { float value; };").unwrap();
+    generator.add_shader_source("uniform A { int value; };").unwrap();
+    assert_eq!(generator.generate().unwrap_err(),
+               CodegenError::ConflictingDefinitions("A".to_owned()));
+}
+
+#[test]
+fn reflection_codegen() {
+    let display = support::build_display();
+
+    let program = match glium::Program::from_source(&display, VERTEX_SHADER, FRAGMENT_SHADER, None) {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let code = Generator::new().add_program("Vertex", &program).generate().unwrap();
+    assert!(code.contains("glium::implement_vertex!(Vertex, position, tex_coords);"));
+
+    // the attributes don't have explicit locations in the reflection
+    let expected = include_str!("fixture/codegen.rs").replace(" location(0)", "")
+                                                      .replace(" location(1)", "");
+    assert_eq!(code, expected);
+
+    display.assert_no_error(None);
+}
@@ -0,0 +1,250 @@
+#[macro_use]
+extern crate glium;
+
+mod support;
+
+#[test]
+fn memory_barrier() {
+    let display = support::build_display();
+
+    display.memory_barrier(glium::MemoryBarrier::all());
+    display.memory_barrier(glium::MemoryBarrier {
+        shader_image_access: true,
+        texture_fetch: true,
+        .. Default::default()
+    });
+    display.memory_barrier(Default::default());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn image_store_then_read() {
+    let display = support::build_display();
+
+    let program = glium::program::ComputeShader::from_source(&display, r#"
+            #version 430
+            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+            uniform layout(binding = 0, rgba8) writeonly image2D tex;
+
+            void main() {
+                imageStore(tex, ivec2(gl_GlobalInvocationID.xy), vec4(1.0, 0.0, 0.0, 1.0));
+            }
+        "#);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    let texture = glium::Texture2d::empty_with_format(&display,
+                                                      glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                                      glium::texture::MipmapsOption::NoMipmap,
+                                                      16, 16).unwrap();
+
+    let image_unit = texture.image_unit(glium::uniforms::ImageUnitFormat::RGBA8).unwrap()
+                            .set_access(glium::uniforms::ImageUnitAccess::Write);
+
+    program.execute(uniform! { tex: image_unit }, 16, 16, 1);
+
+    // the barrier before the read is inserted automatically
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn ssbo_write_then_read() {
+    let display = support::build_display();
+
+    let program = glium::program::ComputeShader::from_source(&display, r#"
+            #version 430
+            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+            layout(std140) buffer MyBlock {
+                uvec4 values;
+            };
+
+            void main() {
+                values[gl_GlobalInvocationID.x] = gl_GlobalInvocationID.x + 1;
+            }
+        "#);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    #[derive(Copy, Clone)]
+    struct Data {
+        values: [u32; 4],
+    }
+
+    implement_uniform_block!(Data, values);
+
+    let buffer = match glium::uniforms::UniformBuffer::new(&display, Data { values: [0; 4] }) {
+        Err(_) => return,
+        Ok(b) => b
+    };
+
+    program.execute(uniform! { MyBlock: &buffer }, 4, 1, 1);
+
+    let data = buffer.read().unwrap();
+    assert_eq!(data.values, [1, 2, 3, 4]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn work_group_size() {
+    let display = support::build_display();
+
+    let program = glium::program::ComputeShader::from_source(&display, r#"
+            #version 430
+            layout(local_size_x = 8, local_size_y = 4, local_size_z = 2) in;
+
+            void main() {
+            }
+        "#);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    assert_eq!(program.get_work_group_size(), Some((8, 4, 2)));
+
+    match program.execute_with_group_size(glium::uniforms::EmptyUniforms, (1, 1, 1), (1, 1, 1)) {
+        Err(glium::program::ComputeDispatchError::FixedWorkGroupSize) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn execute_for_invocations() {
+    let display = support::build_display();
+
+    let program = glium::program::ComputeShader::from_source(&display, r#"
+            #version 430
+            layout(local_size_x = 8, local_size_y = 8, local_size_z = 1) in;
+
+            uniform layout(binding = 0, rgba8) writeonly image2D tex;
+
+            void main() {
+                ivec2 size = imageSize(tex);
+                ivec2 coords = ivec2(gl_GlobalInvocationID.xy);
+                if (coords.x >= size.x || coords.y >= size.y) {
+                    return;
+                }
+
+                imageStore(tex, coords, vec4(0.0, 1.0, 0.0, 1.0));
+            }
+        "#);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    // the dimensions are not multiples of the local size
+    let texture = glium::Texture2d::empty_with_format(&display,
+                                                      glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                                      glium::texture::MipmapsOption::NoMipmap,
+                                                      13, 21).unwrap();
+
+    let image_unit = texture.image_unit(glium::uniforms::ImageUnitFormat::RGBA8).unwrap()
+                            .set_access(glium::uniforms::ImageUnitAccess::Write);
+
+    program.execute_for_invocations(uniform! { tex: image_unit }, 13, 21, 1).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0, 255, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn execute_for_invocations_too_large() {
+    let display = support::build_display();
+
+    let program = glium::program::ComputeShader::from_source(&display, r#"
+            #version 430
+            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+            void main() {
+            }
+        "#);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    match program.execute_for_invocations(glium::uniforms::EmptyUniforms, u32::MAX, 1, 1) {
+        Err(glium::program::ComputeDispatchError::WorkGroupCountTooLarge { .. }) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn variable_group_size() {
+    let display = support::build_display();
+
+    let program = glium::program::ComputeShader::from_source(&display, r#"
+            #version 430
+            #extension GL_ARB_compute_variable_group_size : require
+            layout(local_size_variable) in;
+
+            uniform layout(binding = 0, rgba8) writeonly image2D tex;
+
+            void main() {
+                imageStore(tex, ivec2(gl_GlobalInvocationID.xy), vec4(0.0, 0.0, 1.0, 1.0));
+            }
+        "#);
+
+    // ignoring test in case of compilation error (extension may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    assert_eq!(program.get_work_group_size(), None);
+
+    let texture = glium::Texture2d::empty_with_format(&display,
+                                                      glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                                      glium::texture::MipmapsOption::NoMipmap,
+                                                      16, 16).unwrap();
+
+    let image_unit = texture.image_unit(glium::uniforms::ImageUnitFormat::RGBA8).unwrap()
+                            .set_access(glium::uniforms::ImageUnitAccess::Write);
+
+    program.execute_with_group_size(uniform! { tex: image_unit }, (4, 4, 1), (4, 4, 1)).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0, 0, 255, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
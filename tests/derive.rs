@@ -0,0 +1,225 @@
+#![cfg(feature = "derive")]
+
+#[macro_use]
+extern crate glium;
+
+use std::borrow::Cow;
+
+use glium::Surface;
+use glium::program::BlockLayout;
+use glium::uniforms::{AsUniformValue, UniformBlock, UniformType, UniformValue, Uniforms};
+use glium::vertex::{Attribute, AttributeType};
+
+mod support;
+
+#[derive(Copy, Clone, glium::Vertex)]
+struct Vertex {
+    position: [f32; 2],
+    #[glium(name = "color", normalize)]
+    rgba: [u8; 4],
+}
+
+#[derive(Copy, Clone, glium::Vertex)]
+#[glium(crate = "glium")]
+struct LocatedVertex {
+    #[glium(location = 1)]
+    position: [f32; 3],
+    #[glium(location = 0)]
+    tex_coords: [f32; 2],
+}
+
+#[derive(Copy, Clone, glium::Vertex)]
+struct GenericVertex<T> where T: Attribute + Copy {
+    position: [f32; 2],
+    value: T,
+}
+
+#[test]
+fn vertex_bindings() {
+    let bindings = <Vertex as glium::Vertex>::build_bindings();
+
+    assert_eq!(bindings.len(), 2);
+    assert_eq!(bindings[0], (Cow::Borrowed("position"), 0, -1, AttributeType::F32F32, false));
+    assert_eq!(bindings[1], (Cow::Borrowed("color"), 8, -1, AttributeType::U8U8U8U8, true));
+}
+
+#[test]
+fn vertex_locations() {
+    let bindings = <LocatedVertex as glium::Vertex>::build_bindings();
+
+    assert_eq!(bindings[0].2, 1);
+    assert_eq!(bindings[1].2, 0);
+}
+
+#[test]
+fn generic_vertex() {
+    let bindings = <GenericVertex<(i32, i32)> as glium::Vertex>::build_bindings();
+    assert_eq!(bindings[1].3, AttributeType::I32I32);
+
+    let bindings = <GenericVertex<f32> as glium::Vertex>::build_bindings();
+    assert_eq!(bindings[1].3, AttributeType::F32);
+}
+
+#[derive(glium::Uniforms)]
+struct Light {
+    #[glium(name = "light_color")]
+    color: [f32; 3],
+    intensity: f32,
+}
+
+#[derive(glium::Uniforms)]
+struct SceneUniforms<T> where T: AsUniformValue {
+    matrix: [[f32; 4]; 4],
+    value: T,
+    #[glium(flatten)]
+    light: Light,
+}
+
+#[test]
+fn uniforms_visit() {
+    let uniforms = SceneUniforms {
+        matrix: [[0.0; 4]; 4],
+        value: 5i32,
+        light: Light { color: [1.0, 0.5, 0.0], intensity: 2.0 },
+    };
+
+    let mut names = Vec::new();
+    uniforms.visit_values(|name, value| {
+        match (name, value) {
+            ("matrix", UniformValue::Mat4(_)) => (),
+            ("value", UniformValue::SignedInt(5)) => (),
+            ("light_color", UniformValue::Vec3([1.0, 0.5, 0.0])) => (),
+            ("intensity", UniformValue::Float(value)) => assert_eq!(value, 2.0),
+            _ => panic!("unexpected uniform {}", name),
+        }
+
+        names.push(name.to_owned());
+    });
+
+    assert_eq!(names, vec!["matrix", "value", "light_color", "intensity"]);
+}
+
+#[derive(Copy, Clone, glium::UniformBlock)]
+struct Material {
+    diffuse: [f32; 4],
+    #[glium(name = "shininess")]
+    specular_power: f32,
+}
+
+#[derive(Copy, Clone, glium::UniformBlock)]
+struct Block {
+    offset: [f32; 4],
+    material: Material,
+}
+
+#[test]
+fn uniform_block_layout() {
+    let layout = <Block as UniformBlock>::build_layout(0);
+
+    let members = match layout {
+        BlockLayout::Struct { ref members } => members,
+        _ => panic!(),
+    };
+
+    assert_eq!(members[0].0, "offset");
+    assert_eq!(members[1].0, "material");
+
+    match members[1].1 {
+        BlockLayout::Struct { ref members } => {
+            assert_eq!(members[1].0, "shininess");
+            match members[1].1 {
+                BlockLayout::BasicType { ty, offset_in_buffer } => {
+                    assert_eq!(ty, UniformType::Float);
+                    assert_eq!(offset_in_buffer, 32);
+                },
+                _ => panic!(),
+            }
+        },
+        _ => panic!(),
+    }
+
+    assert!(<Block as UniformBlock>::matches(&layout, 0).is_ok());
+    assert!(<Material as UniformBlock>::matches(&layout, 0).is_err());
+}
+
+#[test]
+fn draw_with_derived_uniforms() {
+    let display = support::build_display();
+
+    #[derive(glium::Uniforms)]
+    struct Color {
+        #[glium(name = "tint")]
+        value: [f32; 4],
+    }
+
+    let vb = glium::VertexBuffer::new(&display, &[
+        Vertex { position: [-1.0, 1.0], rgba: [255, 0, 0, 255] },
+        Vertex { position: [1.0, 1.0], rgba: [255, 0, 0, 255] },
+        Vertex { position: [-1.0, -1.0], rgba: [255, 0, 0, 255] },
+        Vertex { position: [1.0, -1.0], rgba: [255, 0, 0, 255] },
+    ]).unwrap();
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+                attribute vec4 color;
+
+                varying vec4 v_color;
+
+                void main() {
+                    v_color = color;
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                uniform vec4 tint;
+                varying vec4 v_color;
+
+                void main() {
+                    gl_FragColor = v_color * tint;
+                }
+            ",
+        },
+        100 => {
+            vertex: "
+                #version 100
+
+                attribute lowp vec2 position;
+                attribute lowp vec4 color;
+
+                varying lowp vec4 v_color;
+
+                void main() {
+                    v_color = color;
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 100
+
+                uniform lowp vec4 tint;
+                varying lowp vec4 v_color;
+
+                void main() {
+                    gl_FragColor = v_color * tint;
+                }
+            ",
+        },
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &glium::index::NoIndices(glium::index::PrimitiveType::TriangleStrip),
+                              &program, &Color { value: [1.0, 1.0, 1.0, 1.0] },
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
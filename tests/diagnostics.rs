@@ -0,0 +1,127 @@
+#[macro_use]
+extern crate glium;
+
+use glium::program::{ProgramCreationError, Severity, ShaderDiagnostic, ShaderType};
+
+mod support;
+
+fn diagnostic(severity: Severity, file: Option<&str>, line: Option<u32>, column: Option<u32>,
+              message: &str) -> ShaderDiagnostic
+{
+    ShaderDiagnostic {
+        severity,
+        stage: None,
+        file: file.map(|f| f.to_owned()),
+        line,
+        column,
+        message: message.to_owned(),
+    }
+}
+
+#[test]
+fn mesa_log() {
+    let log = "0:3(12): error: `undefined_variable' undeclared\n\
+               0:5(2): warning: `unused' declared but not used\n\
+               \n\
+               error: linking failed\n";
+
+    assert_eq!(ShaderDiagnostic::parse_log(log), vec![
+        diagnostic(Severity::Error, Some("0"), Some(3), Some(12), "`undefined_variable' undeclared"),
+        diagnostic(Severity::Warning, Some("0"), Some(5), Some(2), "`unused' declared but not used"),
+        diagnostic(Severity::Error, None, None, None, "linking failed"),
+    ]);
+}
+
+#[test]
+fn nvidia_log() {
+    let log = "0(3) : error C1008: undefined variable \"undefined_variable\"\n\
+               0(7) : warning C7050: \"color\" might be used before being initialized\n";
+
+    assert_eq!(ShaderDiagnostic::parse_log(log), vec![
+        diagnostic(Severity::Error, Some("0"), Some(3), None, "C1008: undefined variable \"undefined_variable\""),
+        diagnostic(Severity::Warning, Some("0"), Some(7), None, "C7050: \"color\" might be used before being initialized"),
+    ]);
+}
+
+#[test]
+fn amd_and_angle_log() {
+    let log = "Fragment shader failed to compile with the following errors:\n\
+               ERROR: 0:3: error(#143) Undeclared identifier: undefined_variable\n\
+               WARNING: 0:9: 'foo' : extension is not supported\n\
+               ERROR: error(#273) 1 compilation errors.  No code generated\n\
+               ERROR: 1 compilation errors.  No code generated.\n";
+
+    assert_eq!(ShaderDiagnostic::parse_log(log), vec![
+        diagnostic(Severity::Info, None, None, None, "Fragment shader failed to compile with the following errors:"),
+        diagnostic(Severity::Error, Some("0"), Some(3), None, "error(#143) Undeclared identifier: undefined_variable"),
+        diagnostic(Severity::Warning, Some("0"), Some(9), None, "'foo' : extension is not supported"),
+    ]);
+}
+
+#[test]
+fn rewritten_log() {
+    // logs rewritten with `SourceMap::rewrite_log`
+    let log = "shaders/common.glsl:12(5): error: syntax error\n\
+               \x20   unexpected `}'\n\
+               lighting.glsl(4) : warning C7022: unrecognized profile specifier\n";
+
+    let diagnostics = ShaderDiagnostic::parse_log(log);
+    assert_eq!(diagnostics, vec![
+        diagnostic(Severity::Error, Some("shaders/common.glsl"), Some(12), Some(5), "syntax error\nunexpected `}'"),
+        diagnostic(Severity::Warning, Some("lighting.glsl"), Some(4), None, "C7022: unrecognized profile specifier"),
+    ]);
+
+    assert_eq!(diagnostics[0].to_string(), "shaders/common.glsl:12:5: error: syntax error\nunexpected `}'");
+}
+
+#[test]
+fn error_diagnostics() {
+    let err = ProgramCreationError::CompilationError("0:1(1): warning: foo\n0:2(1): error: bar\n".to_owned(),
+                                                     ShaderType::Vertex);
+    let diagnostics = err.diagnostics();
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics.iter().all(|d| d.stage == Some(ShaderType::Vertex)));
+    assert_eq!(diagnostics.iter().filter(|d| d.is_error()).count(), 1);
+
+    assert!(ProgramCreationError::CompilationNotSupported.diagnostics().is_empty());
+}
+
+#[test]
+fn driver_diagnostics() {
+    let display = support::build_display();
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(undefined_variable);
+                }
+            ",
+        },
+    );
+
+    let diagnostics = match program {
+        Err(glium::program::ProgramChooserCreationError::ProgramCreationError(
+            err @ ProgramCreationError::CompilationError(..))) => err.diagnostics(),
+        Err(_) => return,
+        Ok(_) => panic!(),
+    };
+
+    // the exact content of the log depends on the implementation, but all of them report at
+    // least one error for the fragment shader
+    let error = diagnostics.iter().find(|d| d.is_error()).unwrap();
+    assert_eq!(error.stage, Some(ShaderType::Fragment));
+
+    display.assert_no_error(None);
+}
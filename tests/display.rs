@@ -94,6 +94,55 @@ fn sync() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn sync_wait_timeout() {
+    let display = support::build_display();
+
+    let fence = match glium::SyncFence::new(&display) {
+        Ok(fence) => fence,
+        Err(_) => return
+    };
+
+    loop {
+        match fence.wait_timeout(std::time::Duration::from_millis(10)) {
+            glium::FenceWaitResult::Signaled => break,
+            glium::FenceWaitResult::TimeoutExpired => continue,
+            glium::FenceWaitResult::Failed => panic!(),
+        }
+    }
+
+    assert!(fence.is_signaled());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn sync_future() {
+    use std::future::{Future, IntoFuture};
+    use std::task::{Context, Poll, Waker};
+
+    let display = support::build_display();
+
+    let fence = match glium::SyncFence::new(&display) {
+        Ok(fence) => fence,
+        Err(_) => return
+    };
+
+    let mut future = Box::pin(fence.into_future());
+    let mut cx = Context::from_waker(Waker::noop());
+
+    let result = loop {
+        if let Poll::Ready(result) = future.as_mut().poll(&mut cx) {
+            break result;
+        }
+        display.poll_fences();
+    };
+
+    assert_eq!(result, glium::FenceWaitResult::Signaled);
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn multiple_displays() {
     let display1 = support::build_display();
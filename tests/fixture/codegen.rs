@@ -0,0 +1,71 @@
+// Automatically generated by glium. Do not edit.
+
+#[derive(Copy, Clone, Debug)]
+pub struct Vertex {
+    pub position: [f32; 3],
+    pub tex_coords: [f32; 2],
+}
+
+glium::implement_vertex!(Vertex, position location(0), tex_coords location(1));
+
+/// Generated from `Scene.light`.
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct SceneLight {
+    pub position: [f32; 3],
+    pub intensity: f32,
+    pub color: [f32; 4],
+}
+
+impl SceneLight {
+    /// Builds a `SceneLight`, with its padding set to zero.
+    pub const fn new(
+        position: [f32; 3],
+        intensity: f32,
+        color: [f32; 4],
+    ) -> SceneLight {
+        SceneLight {
+            position,
+            intensity,
+            color,
+        }
+    }
+}
+
+const _: () = assert!(::std::mem::size_of::<SceneLight>() == 32);
+glium::implement_uniform_block!(SceneLight, position, intensity, color);
+
+/// Generated from `Scene`.
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct Scene {
+    pub rotation: glium::uniforms::layout::Mat3,
+    pub weights: glium::uniforms::layout::Array<glium::uniforms::layout::Padded<f32>, 2>,
+    pub light: SceneLight,
+    pub ambient: f32,
+    _padding0: [u8; 4],
+    pub offset: [f32; 2],
+}
+
+impl Scene {
+    /// Builds a `Scene`, with its padding set to zero.
+    pub const fn new(
+        rotation: glium::uniforms::layout::Mat3,
+        weights: glium::uniforms::layout::Array<glium::uniforms::layout::Padded<f32>, 2>,
+        light: SceneLight,
+        ambient: f32,
+        offset: [f32; 2],
+    ) -> Scene {
+        Scene {
+            rotation,
+            weights,
+            light,
+            ambient,
+            _padding0: [0; 4],
+            offset,
+        }
+    }
+}
+
+const _: () = assert!(::std::mem::size_of::<Scene>() == 128);
+glium::implement_uniform_block!(Scene, rotation, weights, light, ambient, offset);
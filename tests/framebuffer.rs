@@ -447,3 +447,91 @@ fn empty_framebuffer_simple_draw() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn invalidate_depth_attachment() {
+    let display = support::build_display();
+
+    let color = glium::texture::Texture2d::empty_with_format(&display,
+                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                            glium::texture::MipmapsOption::NoMipmap, 128, 128).unwrap();
+    let depth = glium::framebuffer::DepthRenderBuffer::new(&display,
+                            glium::texture::DepthFormat::I24, 128, 128).unwrap();
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display,
+                                                                 &color, &depth).unwrap();
+    framebuffer.clear_color_and_depth((1.0, 0.0, 0.0, 1.0), 1.0);
+    framebuffer.invalidate_attachments(glium::BlitMask::depth());
+
+    // the color attachment is left untouched
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = color.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn invalidate_region() {
+    let display = support::build_display();
+
+    let color = glium::texture::Texture2d::empty_with_format(&display,
+                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                            glium::texture::MipmapsOption::NoMipmap, 128, 128).unwrap();
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &color).unwrap();
+    framebuffer.clear_color(0.0, 0.0, 0.0, 1.0);
+
+    let rect = glium::Rect { left: 0, bottom: 0, width: 64, height: 64 };
+    framebuffer.invalidate_attachments_region(glium::BlitMask::color_and_depth(), &rect);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn invalidate_multioutput() {
+    let display = support::build_display();
+
+    let color1 = glium::texture::Texture2d::empty_with_format(&display,
+                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                            glium::texture::MipmapsOption::NoMipmap, 128, 128).unwrap();
+    let color2 = glium::texture::Texture2d::empty_with_format(&display,
+                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                            glium::texture::MipmapsOption::NoMipmap, 128, 128).unwrap();
+
+    let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
+                               [("color1", &color1), ("color2", &color2)].iter().cloned()).unwrap();
+
+    framebuffer.invalidate_attachments(glium::BlitMask::color_and_depth_and_stencil());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn invalidate_frame() {
+    let display = support::build_display();
+
+    let mut target = display.draw();
+    target.clear_color_and_depth((0.0, 0.0, 0.0, 1.0), 1.0);
+    target.invalidate_attachments(glium::BlitMask::depth_and_stencil());
+    target.finish().unwrap();
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn invalidate_texture() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty_with_format(&display,
+                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                            glium::texture::MipmapsOption::EmptyMipmaps, 128, 128).unwrap();
+
+    texture.invalidate();
+    texture.mipmap(1).unwrap().invalidate();
+
+    display.assert_no_error(None);
+}
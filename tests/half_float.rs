@@ -0,0 +1,147 @@
+#![cfg(feature = "half")]
+
+#[macro_use]
+extern crate glium;
+
+use glium::Surface;
+use glium::uniforms::{AsUniformValue, UniformValue};
+use glium::vertex::AttributeType;
+use half::f16;
+
+mod support;
+
+#[derive(Copy, Clone)]
+struct Vertex {
+    position: [f16; 2],
+    color: (f16, f16, f16, f16),
+}
+
+implement_vertex!(Vertex, position, color);
+
+#[test]
+fn vertex_format() {
+    let format = <Vertex as glium::Vertex>::build_bindings();
+
+    assert_eq!(format[0].3, AttributeType::F16F16);
+    assert_eq!(format[1].3, AttributeType::F16F16F16F16);
+    assert_eq!(std::mem::size_of::<Vertex>(), 12);
+}
+
+#[test]
+fn uniform_value() {
+    match f16::from_f32(0.5).as_uniform_value() {
+        UniformValue::Float(value) => assert_eq!(value, 0.5),
+        _ => panic!(),
+    }
+
+    match [f16::ONE, f16::ZERO, f16::ONE].as_uniform_value() {
+        UniformValue::Vec3(value) => assert_eq!(value, [1.0, 0.0, 1.0]),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn draw_half_vertices() {
+    let display = support::build_display();
+
+    if !<Vertex as glium::Vertex>::is_supported(&display) {
+        return;
+    }
+
+    let one = f16::ONE;
+    let vertex = |x: f32, y: f32| Vertex {
+        position: [f16::from_f32(x), f16::from_f32(y)],
+        color: (one, f16::ZERO, one, one),
+    };
+
+    let vb = glium::VertexBuffer::new(&display, &[
+        vertex(-1.0, 1.0), vertex(1.0, 1.0), vertex(-1.0, -1.0), vertex(1.0, -1.0),
+    ]).unwrap();
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+                attribute vec4 color;
+
+                varying vec4 v_color;
+
+                void main() {
+                    v_color = color;
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                varying vec4 v_color;
+
+                void main() {
+                    gl_FragColor = v_color;
+                }
+            ",
+        },
+        100 => {
+            vertex: "
+                #version 100
+
+                attribute lowp vec2 position;
+                attribute lowp vec4 color;
+
+                varying lowp vec4 v_color;
+
+                void main() {
+                    v_color = color;
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 100
+
+                varying lowp vec4 v_color;
+
+                void main() {
+                    gl_FragColor = v_color;
+                }
+            ",
+        },
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &glium::index::NoIndices(glium::index::PrimitiveType::TriangleStrip),
+                              &program, &glium::uniforms::EmptyUniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 255, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn half_texture_upload() {
+    let display = support::build_display();
+
+    let format = glium::texture::UncompressedFloatFormat::F16F16F16F16;
+    if !format.to_texture_format().is_supported_for_textures(&display) {
+        return;
+    }
+
+    let half = f16::from_f32(0.5);
+    let texture = glium::texture::Texture2d::with_format(&display, vec![vec![(half, half, half, f16::ONE); 2]; 2],
+                                                         format, glium::texture::MipmapsOption::NoMipmap)
+                                                         .unwrap();
+
+    let rect = glium::Rect { left: 0, bottom: 0, width: 2, height: 2 };
+    let data: Vec<Vec<(f32, f32, f32, f32)>> = texture.main_level().first_layer().into_image(None).unwrap()
+                                                      .raw_read(&rect);
+    assert_eq!(data[1][1], (0.5, 0.5, 0.5, 1.0));
+
+    display.assert_no_error(None);
+}
@@ -0,0 +1,247 @@
+#![cfg(any(feature = "mint", feature = "glam", feature = "nalgebra"))]
+
+#[macro_use]
+extern crate glium;
+
+use glium::Surface;
+use glium::uniforms::AsUniformValue;
+
+mod support;
+
+/// Draws a rectangle with the given `matrix` and `color` uniforms, and checks that the whole
+/// texture has been filled in red. `matrix` must be an identity matrix.
+fn draw_matrix_uniform<M, C>(matrix: M, color: C) where M: AsUniformValue, C: AsUniformValue {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                uniform mat4 matrix;
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = matrix * vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                uniform vec4 color;
+
+                void main() {
+                    gl_FragColor = color;
+                }
+            ",
+        },
+        100 => {
+            vertex: "
+                #version 100
+
+                uniform lowp mat4 matrix;
+                attribute lowp vec2 position;
+
+                void main() {
+                    gl_Position = matrix * vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 100
+
+                uniform lowp vec4 color;
+
+                void main() {
+                    gl_FragColor = color;
+                }
+            ",
+        },
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniform! { matrix: matrix, color: color },
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+macro_rules! math_types_test {
+    ($name:ident, $feature:literal,
+     attributes: [$($field:ident: $ty:ty => $attr_ty:ident),+],
+     uniforms: [$($value:expr => $variant:ident($expected:expr)),+],
+     identity: $identity:expr, red: $red:expr) => (
+        #[cfg(feature = $feature)]
+        mod $name {
+            use glium::uniforms::{AsUniformValue, UniformValue};
+            use glium::vertex::AttributeType;
+
+            #[test]
+            fn vertex_format() {
+                #[derive(Copy, Clone)]
+                #[allow(dead_code)]
+                struct Vertex {
+                    $($field: $ty),+
+                }
+
+                implement_vertex!(Vertex, $($field),+);
+
+                let format = <Vertex as glium::Vertex>::build_bindings();
+                $(
+                    let binding = format.iter().find(|b| b.0 == stringify!($field)).unwrap();
+                    assert_eq!(binding.3, AttributeType::$attr_ty);
+                )+
+            }
+
+            #[test]
+            fn uniform_values() {
+                $(
+                    match $value.as_uniform_value() {
+                        UniformValue::$variant(value) => assert_eq!(value, $expected),
+                        _ => panic!("wrong uniform value for {}", stringify!($value)),
+                    }
+                )+
+            }
+
+            #[test]
+            fn matrix_uniform() {
+                super::draw_matrix_uniform($identity, $red);
+            }
+        }
+    )
+}
+
+math_types_test!(mint_types, "mint",
+    attributes: [
+        position: mint::Point2<f32> => F32F32,
+        normal: mint::Vector3<f64> => F64F64F64,
+        index: mint::Vector4<u8> => U8U8U8U8,
+        rotation: mint::Quaternion<f32> => F32F32F32F32
+    ],
+    uniforms: [
+        mint::Vector3::from([1i32, 2, 3]) => IntVec3([1, 2, 3]),
+        // quaternions are sent as (x, y, z, w)
+        mint::Quaternion { v: mint::Vector3::from([1.0f32, 2.0, 3.0]), s: 4.0 }
+            => Vec4([1.0, 2.0, 3.0, 4.0]),
+        // row matrices are transposed
+        mint::RowMatrix2::from([[1.0f32, 2.0], [3.0, 4.0]]) => Mat2([[1.0, 3.0], [2.0, 4.0]])
+    ],
+    identity: mint::ColumnMatrix4::from([
+        [1.0f32, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]),
+    red: mint::Vector4::from([1.0f32, 0.0, 0.0, 1.0])
+);
+
+math_types_test!(glam_types, "glam",
+    attributes: [
+        position: glam::Vec2 => F32F32,
+        normal: glam::DVec3 => F64F64F64,
+        index: glam::U8Vec4 => U8U8U8U8,
+        rotation: glam::Quat => F32F32F32F32,
+        transform: glam::Mat3 => F32x3x3
+    ],
+    uniforms: [
+        glam::IVec3::new(1, 2, 3) => IntVec3([1, 2, 3]),
+        // quaternions are sent as (x, y, z, w)
+        glam::Quat::from_xyzw(1.0, 2.0, 3.0, 4.0) => Vec4([1.0, 2.0, 3.0, 4.0]),
+        // matrices are sent column by column
+        glam::Mat2::from_cols(glam::Vec2::new(1.0, 2.0), glam::Vec2::new(3.0, 4.0))
+            => Mat2([[1.0, 2.0], [3.0, 4.0]])
+    ],
+    identity: glam::Mat4::IDENTITY,
+    red: glam::Vec4::new(1.0, 0.0, 0.0, 1.0)
+);
+
+math_types_test!(nalgebra_types, "nalgebra",
+    attributes: [
+        position: nalgebra::Point2<f32> => F32F32,
+        normal: nalgebra::Vector3<f64> => F64F64F64,
+        index: nalgebra::Vector4<u8> => U8U8U8U8,
+        rotation: nalgebra::Quaternion<f32> => F32F32F32F32,
+        transform: nalgebra::Matrix3<f32> => F32x3x3
+    ],
+    uniforms: [
+        nalgebra::Vector3::new(1i32, 2, 3) => IntVec3([1, 2, 3]),
+        // quaternions are sent as (i, j, k, w)
+        nalgebra::Quaternion::new(4.0f32, 1.0, 2.0, 3.0) => Vec4([1.0, 2.0, 3.0, 4.0]),
+        // matrices are sent column by column
+        nalgebra::Matrix2::new(1.0f32, 2.0, 3.0, 4.0) => Mat2([[1.0, 3.0], [2.0, 4.0]])
+    ],
+    identity: nalgebra::Matrix4::<f32>::identity(),
+    red: nalgebra::Vector4::new(1.0f32, 0.0, 0.0, 1.0)
+);
+
+#[cfg(feature = "mint")]
+#[test]
+fn mint_uniform_block() {
+    let display = support::build_display();
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 330
+
+            uniform Block {
+                vec4 color;
+            };
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+
+            uniform Block {
+                vec4 color;
+            };
+
+            out vec4 f_color;
+
+            void main() {
+                f_color = color;
+            }
+        ",
+        None)
+    {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    #[derive(Copy, Clone)]
+    struct Block {
+        color: mint::Vector4<f32>,
+    }
+
+    implement_uniform_block!(Block, color);
+
+    let buffer = match glium::uniforms::UniformBuffer::new(&display, Block {
+        color: mint::Vector4::from([0.0, 1.0, 0.0, 1.0]),
+    }) {
+        Ok(b) => b,
+        Err(_) => return,
+    };
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniform! { Block: &buffer },
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 255, 0, 255));
+
+    display.assert_no_error(None);
+}
@@ -0,0 +1,131 @@
+#[macro_use]
+extern crate glium;
+
+use std::collections::HashMap;
+
+use glium::program::{Preprocessor, PreprocessorError, ProgramCreationError, ShaderType};
+
+mod support;
+
+fn includes() -> HashMap<String, String> {
+    let mut files = HashMap::new();
+    files.insert("common.glsl".to_owned(), "#pragma once\nfloat scale(float x) {\n    return x * SCALE;\n}\n".to_owned());
+    files.insert("lighting.glsl".to_owned(), "#include \"common.glsl\"\nvec4 light() {\n    return vec4(scale(1.0));\n}\n".to_owned());
+    files.insert("broken.glsl".to_owned(), "// a comment\nfloat broken() {\n    return undefined_variable;\n}\n".to_owned());
+    files.insert("recursive.glsl".to_owned(), "#include \"recursive.glsl\"\n".to_owned());
+    files
+}
+
+#[test]
+fn includes_and_defines() {
+    let preprocessor = Preprocessor::new().include_provider(includes()).define("SCALE", "2.0");
+
+    let source = preprocessor.process("main.frag", "#version 330\n\
+                                                    #include \"common.glsl\"\n\
+                                                    #include <lighting.glsl>\n\
+                                                    void main() {}\n").unwrap();
+
+    assert_eq!(source.code, "#version 330\n\
+                             #define SCALE 2.0\n\
+                             float scale(float x) {\n    return x * SCALE;\n}\n\
+                             vec4 light() {\n    return vec4(scale(1.0));\n}\n\
+                             void main() {}\n");
+
+    assert_eq!(source.source_map.lookup(1), Some(("main.frag", 1)));
+    assert_eq!(source.source_map.lookup(2), None);
+    assert_eq!(source.source_map.lookup(4), Some(("common.glsl", 3)));
+    assert_eq!(source.source_map.lookup(7), Some(("lighting.glsl", 3)));
+    assert_eq!(source.source_map.lookup(9), Some(("main.frag", 4)));
+    assert_eq!(source.source_map.lookup(10), None);
+}
+
+#[test]
+fn version_override() {
+    let preprocessor = Preprocessor::new().version("300 es");
+    let source = preprocessor.process("main.vert", "// header\n#version 100\nvoid main() {}\n").unwrap();
+
+    assert_eq!(source.code, "#version 300 es\n// header\nvoid main() {}\n");
+    assert_eq!(source.source_map.lookup(1), None);
+    assert_eq!(source.source_map.lookup(3), Some(("main.vert", 3)));
+}
+
+#[test]
+fn include_errors() {
+    let error = Preprocessor::new().process("main.frag", "#include \"common.glsl\"\n").unwrap_err();
+    assert_eq!(error.to_string(), "main.frag:1: could not include `common.glsl`: no include provider");
+
+    let preprocessor = Preprocessor::new().include_provider(includes());
+
+    let error = preprocessor.process("main.frag", "\n#include \"missing.glsl\"\n").unwrap_err();
+    assert_eq!(error, PreprocessorError::IncludeError {
+        path: "missing.glsl".to_owned(),
+        file: "main.frag".to_owned(),
+        line: 2,
+        message: "file not found".to_owned(),
+    });
+
+    match preprocessor.process("main.frag", "#include \"recursive.glsl\"\n").unwrap_err() {
+        PreprocessorError::IncludeError { file, message, .. } => {
+            assert_eq!(file, "recursive.glsl");
+            assert_eq!(message, "recursive include");
+        },
+        err => panic!("{:?}", err),
+    }
+
+    assert_eq!(preprocessor.process("main.frag", "#include common.glsl\n").unwrap_err(),
+               PreprocessorError::InvalidDirective { file: "main.frag".to_owned(), line: 1 });
+}
+
+#[test]
+fn rewrite_log() {
+    let preprocessor = Preprocessor::new().include_provider(includes()).define("SCALE", "2.0");
+    let source = preprocessor.process("main.frag", "#version 330\n#include \"broken.glsl\"\n").unwrap();
+
+    let map = &source.source_map;
+    assert_eq!(map.rewrite_log("0:5(12): error: `undefined_variable' undeclared\n"),
+               "broken.glsl:3(12): error: `undefined_variable' undeclared\n");
+    assert_eq!(map.rewrite_log("0(5) : error C1008: undefined variable \"undefined_variable\""),
+               "broken.glsl(3) : error C1008: undefined variable \"undefined_variable\"");
+    assert_eq!(map.rewrite_log("ERROR: 0:5: 'undefined_variable' : undeclared identifier\n\
+                                ERROR: 1 compilation errors.  No code generated.\n"),
+               "ERROR: broken.glsl:3: 'undefined_variable' : undeclared identifier\n\
+                ERROR: 1 compilation errors.  No code generated.\n");
+}
+
+#[test]
+fn compilation_error_locations() {
+    let display = support::build_display();
+
+    let preprocessor = Preprocessor::new().include_provider(includes()).version("110");
+
+    let program = glium::Program::new_preprocessed(&display, &preprocessor, glium::program::SourceCode {
+        vertex_shader: "
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #include \"broken.glsl\"
+
+            void main() {
+                gl_FragColor = vec4(broken());
+            }
+        ",
+        geometry_shader: None,
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+    });
+
+    match program {
+        Err(ProgramCreationError::CompilationError(log, ShaderType::Fragment)) => {
+            assert!(log.contains("broken.glsl"), "{}", log);
+        },
+        Err(ProgramCreationError::CompilationNotSupported) => return,
+        Err(err) => panic!("{}", err),
+        Ok(_) => panic!(),
+    }
+
+    display.assert_no_error(None);
+}
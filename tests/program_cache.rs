@@ -0,0 +1,142 @@
+#[macro_use]
+extern crate glium;
+
+use std::fs;
+use std::path::PathBuf;
+
+use glium::program::{Preprocessor, ProgramCache};
+
+mod support;
+
+const VERTEX_SHADER: &str = "
+    #version 110
+
+    attribute vec2 position;
+
+    void main() {
+        gl_Position = vec4(position, 0.0, 1.0);
+    }
+";
+
+const FRAGMENT_SHADER: &str = "
+    #version 110
+
+    uniform vec4 color;
+
+    void main() {
+        gl_FragColor = color * SCALE;
+    }
+";
+
+fn cache_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("glium_program_cache_{}", name));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn cached_files(dir: &PathBuf) -> Vec<PathBuf> {
+    match fs::read_dir(dir) {
+        Ok(entries) => entries.map(|e| e.unwrap().path()).collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn source(fragment_shader: &str) -> glium::program::SourceCode<'_> {
+    glium::program::SourceCode {
+        vertex_shader: VERTEX_SHADER,
+        fragment_shader,
+        geometry_shader: None,
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+    }
+}
+
+#[test]
+fn clear_missing_directory() {
+    let cache = ProgramCache::new(cache_dir("clear_missing_directory"));
+    cache.clear().unwrap();
+}
+
+#[test]
+fn store_and_load() {
+    let display = support::build_display();
+    let dir = cache_dir("store_and_load");
+    let cache = ProgramCache::new(&dir);
+
+    let fragment_shader = FRAGMENT_SHADER.replace("SCALE", "1.0");
+    let program = cache.program(&display, source(&fragment_shader)).unwrap();
+    assert!(program.get_uniform("color").is_some());
+
+    if program.get_binary().is_err() {
+        // the backend doesn't support binaries, so nothing is cached
+        assert!(cached_files(&dir).is_empty());
+        return;
+    }
+
+    let files = cached_files(&dir);
+    assert_eq!(files.len(), 1);
+    let content = fs::read(&files[0]).unwrap();
+
+    // loaded from the cache
+    let program = cache.program(&display, source(&fragment_shader)).unwrap();
+    assert!(program.get_uniform("color").is_some());
+    assert_eq!(fs::read(&files[0]).unwrap(), content);
+
+    // another source is another entry
+    let fragment_shader = FRAGMENT_SHADER.replace("SCALE", "2.0");
+    cache.program(&display, source(&fragment_shader)).unwrap();
+    assert_eq!(cached_files(&dir).len(), 2);
+
+    cache.clear().unwrap();
+    assert!(cached_files(&dir).is_empty());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn rejected_binary() {
+    let display = support::build_display();
+    let dir = cache_dir("rejected_binary");
+    let cache = ProgramCache::new(&dir);
+
+    let fragment_shader = FRAGMENT_SHADER.replace("SCALE", "1.0");
+    let program = cache.program(&display, source(&fragment_shader)).unwrap();
+    if program.get_binary().is_err() {
+        return;
+    }
+
+    // corrupts the binary while keeping its format
+    let path = cached_files(&dir).remove(0);
+    let mut content = fs::read(&path).unwrap();
+    let len = content.len();
+    for byte in &mut content[5 .. len] {
+        *byte = !*byte;
+    }
+    fs::write(&path, &content).unwrap();
+
+    let program = cache.program(&display, source(&fragment_shader)).unwrap();
+    assert!(program.get_uniform("color").is_some());
+    assert_ne!(fs::read(&path).unwrap(), content);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn defines() {
+    let display = support::build_display();
+    let dir = cache_dir("defines");
+    let cache = ProgramCache::new(&dir);
+
+    let supported = cache.program_preprocessed(&display, &Preprocessor::new().define("SCALE", "1.0"),
+                                               source(FRAGMENT_SHADER)).unwrap()
+                         .get_binary().is_ok();
+
+    cache.program_preprocessed(&display, &Preprocessor::new().define("SCALE", "2.0"),
+                               source(FRAGMENT_SHADER)).unwrap();
+
+    if supported {
+        assert_eq!(cached_files(&dir).len(), 2);
+    }
+
+    display.assert_no_error(None);
+}
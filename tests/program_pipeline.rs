@@ -0,0 +1,192 @@
+#[macro_use]
+extern crate glium;
+
+use std::rc::Rc;
+
+use glium::{Program, Surface};
+use glium::program::{PipelineCreationError, PipelineStages, ProgramCreationError};
+use glium::program::{ProgramPipeline, ShaderType};
+
+mod support;
+
+const VERTEX_SHADER: &str = "
+    #version 410
+
+    in vec2 position;
+    out vec2 v_position;
+    out gl_PerVertex { vec4 gl_Position; };
+
+    uniform float scale;
+
+    void main() {
+        v_position = position;
+        gl_Position = vec4(position * scale, 0.0, 1.0);
+    }
+";
+
+const FRAGMENT_SHADER: &str = "
+    #version 410
+
+    in vec2 v_position;
+    out vec4 color;
+
+    uniform vec4 base_color;
+    uniform float scale;
+
+    void main() {
+        color = base_color * scale;
+    }
+";
+
+fn separable(display: &support::WindowDisplay, ty: ShaderType, source: &str)
+             -> Option<Rc<Program>>
+{
+    match Program::new_separable(display, ty, source) {
+        Ok(program) => Some(Rc::new(program)),
+        Err(ProgramCreationError::SeparableProgramsNotSupported) => None,
+        Err(ProgramCreationError::CompilationError(..)) => None,    // GLSL 4.10 not supported
+        Err(err) => panic!("{}", err),
+    }
+}
+
+#[test]
+fn draw_with_pipeline() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let (vertex, fragment) = match (separable(&display, ShaderType::Vertex, VERTEX_SHADER),
+                                    separable(&display, ShaderType::Fragment, FRAGMENT_SHADER))
+    {
+        (Some(v), Some(f)) => (v, f),
+        _ => return,
+    };
+
+    let pipeline = ProgramPipeline::from_programs(&display, vertex, fragment).unwrap();
+
+    // `scale` is declared in both stages and receives the same value
+    assert!(pipeline.get_uniform("base_color").is_some());
+    assert!(pipeline.get_uniform("scale").is_some());
+    assert!(pipeline.get_attribute("position").is_some());
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw_pipeline(&vb, &ib, &pipeline, &uniform! {
+        base_color: [1.0, 0.0, 0.0, 1.0f32],
+        scale: 1.0f32,
+    }, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(*data.last().unwrap().last().unwrap(), (255, 0, 0, 255));
+
+    // drawing with a regular program after a pipeline
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniform!{}, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn interface_mismatch() {
+    let display = support::build_display();
+
+    let fragment_shader = FRAGMENT_SHADER.replace("in vec2 v_position;", "in vec3 v_normal;")
+                                         .replace("base_color * scale", "vec4(v_normal, 1.0)");
+
+    let (vertex, fragment) = match (separable(&display, ShaderType::Vertex, VERTEX_SHADER),
+                                    separable(&display, ShaderType::Fragment, &fragment_shader))
+    {
+        (Some(v), Some(f)) => (v, f),
+        _ => return,
+    };
+
+    match ProgramPipeline::from_programs(&display, vertex, fragment) {
+        Err(PipelineCreationError::InterfaceMismatch { stage, name }) => {
+            assert_eq!(stage, ShaderType::Fragment);
+            assert_eq!(name, "v_normal");
+        },
+        Ok(_) => (),    // program interface query not supported
+        Err(err) => panic!("{}", err),
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn wrong_stage() {
+    let display = support::build_display();
+
+    let (vertex, fragment) = match (separable(&display, ShaderType::Vertex, VERTEX_SHADER),
+                                    separable(&display, ShaderType::Fragment, FRAGMENT_SHADER))
+    {
+        (Some(v), Some(f)) => (v, f),
+        _ => return,
+    };
+
+    match ProgramPipeline::from_programs(&display, fragment.clone(), vertex.clone()) {
+        Err(PipelineCreationError::WrongStage { expected: ShaderType::Vertex,
+                                                found: Some(ShaderType::Fragment) }) => (),
+        result => panic!("{:?}", result.map(|_| ())),
+    }
+
+    let (_, _, program) = support::build_fullscreen_red_pipeline(&display);
+    let mut stages = PipelineStages::new(vertex, fragment);
+    stages.geometry_shader = Some(Rc::new(program));
+
+    match ProgramPipeline::new(&display, stages) {
+        Err(PipelineCreationError::WrongStage { expected: ShaderType::Geometry, found: None }) => (),
+        result => panic!("{:?}", result.map(|_| ())),
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn subroutine_uniforms() {
+    let display = support::build_display();
+
+    let vertex_shader = VERTEX_SHADER.replace("uniform float scale;", "
+        subroutine float Scale();
+        subroutine(Scale) float unit_scale() { return 1.0; }
+        subroutine uniform Scale scale;
+    ").replace("position * scale", "position * scale()");
+
+    let (vertex, fragment) = match (separable(&display, ShaderType::Vertex, &vertex_shader),
+                                    separable(&display, ShaderType::Fragment, FRAGMENT_SHADER))
+    {
+        (Some(v), Some(f)) => (v, f),
+        _ => return,
+    };
+
+    match ProgramPipeline::from_programs(&display, vertex, fragment) {
+        Err(PipelineCreationError::SubroutineUniforms { stage: ShaderType::Vertex }) => (),
+        result => panic!("{:?}", result.map(|_| ())),
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn draw_separable_program() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let vertex = match separable(&display, ShaderType::Vertex, VERTEX_SHADER) {
+        Some(v) => v,
+        None => return,
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &ib, &vertex, &uniform! { scale: 1.0f32 },
+                                    &Default::default())
+    {
+        Err(glium::DrawError::SeparableProgram) => (),
+        result => panic!("{:?}", result),
+    }
+
+    display.assert_no_error(None);
+}
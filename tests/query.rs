@@ -309,3 +309,125 @@ fn query_to_buffer() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn query_pool_recycle() {
+    let display = support::build_display();
+
+    let mut pool = glium::draw_parameters::QueryPool::<glium::draw_parameters::SamplesPassedQuery>::new(&display);
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    for frame in 0 .. 3 {
+        let query = match pool.acquire() {
+            Err(_) => return,
+            Ok(q) => q
+        };
+
+        {
+            let params = glium::DrawParameters {
+                samples_passed_query: Some((&query).into()),
+                .. Default::default()
+            };
+
+            texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params)
+                   .unwrap();
+        }
+
+        assert_eq!(pool.submit(query), frame);
+
+        let results = pool.collect_all();
+        assert_eq!(results, vec![(frame, 1024 * 1024)]);     // texture dimensions
+        assert_eq!(pool.num_pending(), 0);
+        assert_eq!(pool.num_free(), 1);
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn query_pool_collect_ready() {
+    let display = support::build_display();
+
+    let mut pool = glium::draw_parameters::QueryPool::<glium::draw_parameters::SamplesPassedQuery>::new(&display);
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    for _ in 0 .. 4 {
+        let query = match pool.acquire() {
+            Err(_) => return,
+            Ok(q) => q
+        };
+
+        {
+            let params = glium::DrawParameters {
+                samples_passed_query: Some((&query).into()),
+                .. Default::default()
+            };
+
+            texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params)
+                   .unwrap();
+        }
+
+        pool.submit(query);
+    }
+
+    let mut results = Vec::new();
+    while results.len() < 4 {
+        results.extend(pool.collect_ready());
+    }
+
+    assert_eq!(results, vec![(0, 1024 * 1024), (1, 1024 * 1024),
+                             (2, 1024 * 1024), (3, 1024 * 1024)]);
+    assert_eq!(pool.num_free(), 4);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn query_pool_to_buffer() {
+    let display = support::build_display();
+
+    let mut pool = glium::draw_parameters::QueryPool::<glium::draw_parameters::SamplesPassedQuery>::new(&display);
+
+    let query = match pool.acquire() {
+        Err(_) => return,
+        Ok(q) => q
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    {
+        let params = glium::DrawParameters {
+            samples_passed_query: Some((&query).into()),
+            .. Default::default()
+        };
+
+        texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params)
+               .unwrap();
+    }
+
+    let mut buffer = glium::buffer::BufferView::empty(&display,
+                                                      glium::buffer::BufferType::ArrayBuffer,
+                                                      glium::buffer::BufferMode::Default).unwrap();
+    if pool.submit_to_buffer(query, buffer.as_slice()).is_err() {
+        return;
+    }
+
+    assert_eq!(pool.num_pending(), 0);
+    assert_eq!(pool.num_free(), 1);
+
+    let mapping = buffer.map();
+    assert!(*mapping == 1024 * 1024); // texture dimensions
+
+    display.assert_no_error(None);
+}
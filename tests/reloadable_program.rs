@@ -0,0 +1,120 @@
+#[macro_use]
+extern crate glium;
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use glium::program::{ProgramCreationError, ReloadError, ReloadableProgram};
+
+mod support;
+
+const VERTEX_SHADER: &str = "
+    #version 110
+
+    attribute vec2 position;
+
+    void main() {
+        gl_Position = vec4(position, 0.0, 1.0);
+    }
+";
+
+const FRAGMENT_SHADER: &str = "
+    #version 110
+
+    uniform float value;
+
+    void main() {
+        gl_FragColor = vec4(value);
+    }
+";
+
+fn shader_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("glium_reloadable_program_{}", name));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("shader.vert"), VERTEX_SHADER).unwrap();
+    fs::write(dir.join("shader.frag"), FRAGMENT_SHADER).unwrap();
+    dir
+}
+
+/// Writes the file with a modification time in the future, so that the modification is noticed
+/// even with filesystems that have a coarse time resolution.
+fn modify(path: &PathBuf, content: &str) {
+    fs::write(path, content).unwrap();
+    let file = fs::File::options().write(true).open(path).unwrap();
+    file.set_modified(SystemTime::now() + Duration::from_secs(10)).unwrap();
+}
+
+#[test]
+fn reload_on_change() {
+    let display = support::build_display();
+    let dir = shader_dir("reload_on_change");
+
+    let mut program = match ReloadableProgram::from_files(&display, dir.join("shader.vert"),
+                                                          dir.join("shader.frag"), None)
+    {
+        Err(ReloadError::ProgramCreationError(ProgramCreationError::CompilationNotSupported)) => return,
+        result => result.unwrap(),
+    };
+
+    assert!(!program.reload_if_changed());
+    assert!(program.get_uniform("value").is_some());
+
+    modify(&dir.join("shader.frag"), &FRAGMENT_SHADER.replace("value", "other"));
+    assert!(program.reload_if_changed());
+    assert!(program.last_error().is_none());
+    assert!(program.get_uniform("value").is_none());
+    assert!(program.get_uniform("other").is_some());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn keep_program_on_error() {
+    let display = support::build_display();
+    let dir = shader_dir("keep_program_on_error");
+
+    let mut program = match ReloadableProgram::from_files(&display, dir.join("shader.vert"),
+                                                          dir.join("shader.frag"), None)
+    {
+        Err(ReloadError::ProgramCreationError(ProgramCreationError::CompilationNotSupported)) => return,
+        result => result.unwrap(),
+    };
+
+    modify(&dir.join("shader.frag"), "#version 110\nvoid main() { undefined_variable; }\n");
+    assert!(!program.reload_if_changed());
+    match program.last_error() {
+        Some(ReloadError::ProgramCreationError(ProgramCreationError::CompilationError(..))) => (),
+        err => panic!("{:?}", err),
+    }
+    assert!(program.get_uniform("value").is_some());
+
+    // not retried until the file changes again
+    assert!(!program.reload_if_changed());
+    assert!(program.last_error().is_some());
+
+    fs::remove_file(dir.join("shader.frag")).unwrap();
+    assert!(!program.reload_if_changed());
+    match program.last_error() {
+        Some(ReloadError::Io { path, .. }) => assert_eq!(path, &dir.join("shader.frag")),
+        err => panic!("{:?}", err),
+    }
+
+    modify(&dir.join("shader.frag"), FRAGMENT_SHADER);
+    assert!(program.reload_if_changed());
+    assert!(program.last_error().is_none());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn missing_file() {
+    let display = support::build_display();
+
+    match ReloadableProgram::from_files(&display, "does/not/exist.vert", "does/not/exist.frag", None) {
+        Err(ReloadError::Io { path, .. }) => assert_eq!(path, PathBuf::from("does/not/exist.vert")),
+        result => panic!("{:?}", result),
+    }
+
+    display.assert_no_error(None);
+}
@@ -0,0 +1,162 @@
+#[macro_use]
+extern crate glium;
+
+use glium::Rect;
+use glium::texture::{MipmapsOption, RawImage2d, UncompressedFloatFormat};
+use glium::texture::{SparseTexture2d, SparseTexture2dArray, SparseTextureError};
+
+mod support;
+
+fn build_texture<F: ?Sized>(facade: &F) -> Option<SparseTexture2d> where F: glium::backend::Facade {
+    if !SparseTexture2d::is_supported(facade.get_context()) {
+        return None;
+    }
+
+    let page_sizes = SparseTexture2d::get_page_sizes(facade, UncompressedFloatFormat::U8U8U8U8);
+    let (page_width, page_height, _) = *page_sizes.first()?;
+
+    Some(SparseTexture2d::new(facade, UncompressedFloatFormat::U8U8U8U8, MipmapsOption::NoMipmap,
+                              0, page_width * 4, page_height * 4).unwrap())
+}
+
+#[test]
+fn commit_and_write() {
+    let display = support::build_display();
+
+    let texture = match build_texture(&display) {
+        Some(t) => t,
+        None => return
+    };
+
+    let (page_width, page_height, _) = texture.get_page_size();
+    let rect = Rect { left: page_width, bottom: 0, width: page_width, height: page_height };
+
+    assert!(!texture.is_committed(&rect, 0));
+    texture.commit(&rect, 0).unwrap();
+    assert!(texture.is_committed(&rect, 0));
+
+    let data = vec![255u8; (page_width * page_height * 4) as usize];
+    let image = RawImage2d::from_raw_rgba(data, (page_width, page_height));
+    texture.write(0, rect, image).unwrap();
+
+    texture.decommit(&rect, 0).unwrap();
+    assert!(!texture.is_committed(&rect, 0));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn write_not_committed() {
+    let display = support::build_display();
+
+    let texture = match build_texture(&display) {
+        Some(t) => t,
+        None => return
+    };
+
+    let rect = Rect { left: 0, bottom: 0, width: 1, height: 1 };
+    let image = RawImage2d::from_raw_rgba(vec![0u8; 4], (1, 1));
+
+    match texture.write(0, rect, image) {
+        Err(SparseTextureError::NotCommitted) => (),
+        r => panic!("{:?}", r)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn write_out_of_range() {
+    let display = support::build_display();
+
+    let texture = match build_texture(&display) {
+        Some(t) => t,
+        None => return
+    };
+
+    let (page_width, page_height, _) = texture.get_page_size();
+    let rect = Rect { left: 0, bottom: 0, width: page_width * 4, height: page_height * 4 };
+    texture.commit(&rect, 0).unwrap();
+
+    // the region is inside the committed pages once clamped, but not the texture
+    let rect = Rect { left: page_width * 4 - 1, bottom: 0, width: 2, height: 1 };
+    let image = RawImage2d::from_raw_rgba(vec![0u8; 8], (2, 1));
+    match texture.write(0, rect, image) {
+        Err(SparseTextureError::OutOfRange) => (),
+        r => panic!("{:?}", r)
+    };
+
+    let rect = Rect { left: u32::MAX, bottom: 0, width: 2, height: 1 };
+    let image = RawImage2d::from_raw_rgba(vec![0u8; 8], (2, 1));
+    match texture.write(0, rect, image) {
+        Err(SparseTextureError::OutOfRange) => (),
+        r => panic!("{:?}", r)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn unaligned_commit() {
+    let display = support::build_display();
+
+    let texture = match build_texture(&display) {
+        Some(t) => t,
+        None => return
+    };
+
+    let (page_width, page_height, _) = texture.get_page_size();
+
+    let rect = Rect { left: 1, bottom: 0, width: page_width, height: page_height };
+    match texture.commit(&rect, 0) {
+        Err(SparseTextureError::UnalignedRegion) => (),
+        r => panic!("{:?}", r)
+    };
+
+    let rect = Rect { left: 0, bottom: 0, width: page_width * 5, height: page_height };
+    match texture.commit(&rect, 0) {
+        Err(SparseTextureError::OutOfRange) => (),
+        r => panic!("{:?}", r)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn array_layers() {
+    let display = support::build_display();
+
+    if !SparseTexture2dArray::is_supported(&display) {
+        return;
+    }
+
+    let page_sizes = SparseTexture2dArray::get_page_sizes(&display, UncompressedFloatFormat::U8U8U8U8);
+    let (page_width, page_height, _) = match page_sizes.first() {
+        Some(s) => *s,
+        None => return
+    };
+
+    let texture = SparseTexture2dArray::new(&display, UncompressedFloatFormat::U8U8U8U8,
+                                            MipmapsOption::NoMipmap, 0, page_width, page_height,
+                                            3).unwrap();
+
+    let rect = Rect { left: 0, bottom: 0, width: page_width, height: page_height };
+    texture.commit(&rect, 1 .. 2, 0).unwrap();
+
+    assert!(!texture.is_committed(&rect, 0 .. 1, 0));
+    assert!(texture.is_committed(&rect, 1 .. 2, 0));
+    assert!(!texture.is_committed(&rect, 0 .. 3, 0));
+
+    let data = vec![255u8; (page_width * page_height * 4) as usize];
+    let image = RawImage2d::from_raw_rgba(data, (page_width, page_height));
+    texture.write(0, 1, rect, image).unwrap();
+
+    let image = RawImage2d::from_raw_rgba(vec![0u8; 4], (1, 1));
+    let pixel = Rect { left: 0, bottom: 0, width: 1, height: 1 };
+    match texture.write(0, 0, pixel, image) {
+        Err(SparseTextureError::NotCommitted) => (),
+        r => panic!("{:?}", r)
+    };
+
+    display.assert_no_error(None);
+}
@@ -0,0 +1,116 @@
+#[macro_use]
+extern crate glium;
+
+use glium::Rect;
+use glium::texture::{self, ClearBufferData, ClearTextureError, MipmapsOption};
+use glium::texture::{UncompressedFloatFormat, UncompressedUintFormat};
+
+mod support;
+
+#[test]
+fn clear_texture_2d() {
+    let display = support::build_display();
+
+    let texture = texture::Texture2d::empty_with_format(&display, UncompressedFloatFormat::U8U8U8U8,
+                                                         MipmapsOption::NoMipmap, 4, 4).unwrap();
+
+    match texture.clear((1.0, 0.0, 1.0, 1.0)) {
+        Ok(()) => (),
+        Err(ClearTextureError::NotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data, vec![vec![(255, 0, 255, 255); 4]; 4]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn clear_region_texture_2d() {
+    let display = support::build_display();
+
+    let texture = texture::Texture2d::empty_with_format(&display, UncompressedFloatFormat::U8U8U8U8,
+                                                         MipmapsOption::NoMipmap, 4, 4).unwrap();
+
+    match texture.clear((0.0, 0.0, 0.0, 0.0)) {
+        Ok(()) => (),
+        Err(ClearTextureError::NotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let rect = Rect { left: 2, bottom: 0, width: 2, height: 4 };
+    texture.clear_region(rect, (0.0, 1.0, 0.0, 1.0)).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for (col, pixel) in row.iter().enumerate() {
+            if col >= 2 {
+                assert_eq!(pixel, &(0, 255, 0, 255));
+            } else {
+                assert_eq!(pixel, &(0, 0, 0, 0));
+            }
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn clear_unsigned_texture() {
+    let display = support::build_display();
+
+    let texture = match texture::UnsignedTexture2d::empty_with_format(&display,
+                                                                      UncompressedUintFormat::U32,
+                                                                      MipmapsOption::NoMipmap,
+                                                                      4, 4)
+    {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    match texture.clear((7, 0, 0, 0)) {
+        Ok(()) | Err(ClearTextureError::NotSupported) => (),
+        Err(e) => panic!("{:?}", e)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn clear_single_mipmap() {
+    let display = support::build_display();
+
+    let texture = match texture::DepthTexture2dArray::empty(&display, 4, 4, 2) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    match texture.main_level().clear(0.5) {
+        Ok(()) | Err(ClearTextureError::NotSupported) => (),
+        Err(e) => panic!("{:?}", e)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn clear_errors() {
+    let display = support::build_display();
+
+    let texture = texture::Texture2d::empty_with_format(&display, UncompressedFloatFormat::U8U8U8U8,
+                                                         MipmapsOption::NoMipmap, 4, 4).unwrap();
+
+    match texture.main_level().raw_clear(ClearBufferData::Unsigned([0, 0, 0, 0])) {
+        Err(ClearTextureError::ValueKindMismatch) => (),
+        r => panic!("{:?}", r)
+    };
+
+    let rect = Rect { left: 2, bottom: 0, width: 4, height: 4 };
+    match texture.clear_region(rect, (0.0, 0.0, 0.0, 0.0)) {
+        Err(ClearTextureError::OutOfRange) => (),
+        r => panic!("{:?}", r)
+    };
+
+    display.assert_no_error(None);
+}
@@ -0,0 +1,149 @@
+#![cfg(feature = "block_compression")]
+
+#[macro_use]
+extern crate glium;
+
+use glium::texture::{CompressedFormat, CompressedMipmapsOption, CompressedTexture2d, RawImage2d};
+use glium::texture::compress::{self, CompressionQuality};
+
+mod support;
+
+const QUALITIES: [CompressionQuality; 3] = [CompressionQuality::Fast, CompressionQuality::Normal,
+                                            CompressionQuality::High];
+
+/// Decodes the end points of a BC1 block.
+fn bc1_end_points(block: &[u8]) -> ((u8, u8, u8), (u8, u8, u8)) {
+    let expand = |value: u16| {
+        let (r, g, b) = ((value >> 11) as u8, ((value >> 5) & 0x3f) as u8, (value & 0x1f) as u8);
+        ((r << 3) | (r >> 2), (g << 2) | (g >> 4), (b << 3) | (b >> 2))
+    };
+
+    (expand(u16::from_le_bytes([block[0], block[1]])),
+     expand(u16::from_le_bytes([block[2], block[3]])))
+}
+
+/// Decodes a BC4 block.
+fn decode_bc4(block: &[u8]) -> Vec<u8> {
+    let (r0, r1) = (block[0] as u32, block[1] as u32);
+    let palette: Vec<u32> = if r0 > r1 {
+        (0 .. 8).map(|i| match i {
+            0 => r0,
+            1 => r1,
+            i => ((8 - i) * r0 + (i - 1) * r1 + 3) / 7,
+        }).collect()
+    } else {
+        (0 .. 8).map(|i| match i {
+            0 => r0,
+            1 => r1,
+            6 => 0,
+            7 => 255,
+            i => ((6 - i) * r0 + (i - 1) * r1 + 2) / 5,
+        }).collect()
+    };
+
+    let mut bits = [0u8; 8];
+    bits[.. 6].copy_from_slice(&block[2 .. 8]);
+    let bits = u64::from_le_bytes(bits);
+
+    (0 .. 16).map(|i| palette[((bits >> (i * 3)) & 0x7) as usize] as u8).collect()
+}
+
+#[test]
+fn bc1_solid_color() {
+    for &quality in &QUALITIES {
+        let image = RawImage2d::from_raw_rgb(vec![255u8, 0, 0].repeat(16), (4, 4));
+        let data = compress::compress(&image, CompressedFormat::S3tcDxt1NoAlpha.to_texture_format(),
+                                      quality).unwrap();
+
+        assert_eq!(data.len(), 8);
+        assert_eq!(bc1_end_points(&data).0, (255, 0, 0));
+    }
+}
+
+#[test]
+fn bc1_transparent_block() {
+    let image = RawImage2d::from_raw_rgba(vec![0u8; 64], (4, 4));
+    let data = compress::compress(&image, CompressedFormat::S3tcDxt1Alpha.to_texture_format(),
+                                  CompressionQuality::Normal).unwrap();
+
+    // three-color mode with all the indices set to transparent
+    assert_eq!(data, vec![0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff]);
+}
+
+#[test]
+fn bc4_gradient() {
+    let values: Vec<u8> = (0 .. 16).map(|i| i * 16).collect();
+
+    for &quality in &QUALITIES {
+        let image = RawImage2d { data: values.clone().into(), width: 4, height: 4,
+                                 format: glium::texture::ClientFormat::U8 };
+        let data = compress::compress(&image, CompressedFormat::RgtcFormatU.to_texture_format(),
+                                      quality).unwrap();
+
+        for (decoded, original) in decode_bc4(&data).into_iter().zip(values.iter()) {
+            assert!((decoded as i32 - *original as i32).abs() <= 24, "{:?}", quality);
+        }
+    }
+}
+
+#[test]
+fn bc5_and_bc3_sizes() {
+    // partial blocks at the border are stored as whole blocks
+    let image = RawImage2d::from_raw_rgba(vec![128u8; 6 * 5 * 4], (6, 5));
+
+    let data = compress::compress(&image, CompressedFormat::RgtcFormatUU.to_texture_format(),
+                                  CompressionQuality::Fast).unwrap();
+    assert_eq!(data.len(), 4 * 16);
+
+    let data = compress::compress(&image, CompressedFormat::S3tcDxt5Alpha.to_texture_format(),
+                                  CompressionQuality::Fast).unwrap();
+    assert_eq!(data.len(), 4 * 16);
+    assert_eq!(decode_bc4(&data[.. 8]), vec![128; 16]);
+}
+
+#[test]
+fn bc7_solid_color() {
+    for &quality in &QUALITIES {
+        let image = RawImage2d::from_raw_rgba(vec![10u8, 20, 30, 40].repeat(16), (4, 4));
+        let data = compress::compress(&image, CompressedFormat::BptcUnorm4.to_texture_format(),
+                                      quality).unwrap();
+
+        assert_eq!(data.len(), 16);
+
+        // mode 6, with the seven-bit red end points followed by the green ones
+        let bits = u128::from_le_bytes(data[..].try_into().unwrap());
+        assert_eq!(bits & 0x7f, 0x40);
+        assert_eq!((bits >> 7) & 0x7f, 5);
+        assert_eq!((bits >> 21) & 0x7f, 10);
+    }
+}
+
+#[test]
+fn unsupported_formats() {
+    let image = RawImage2d::from_raw_rgba(vec![0u8; 64], (4, 4));
+    assert!(compress::compress(&image, CompressedFormat::BptcSignedFloat3.to_texture_format(),
+                               CompressionQuality::Normal).is_none());
+    assert!(!compress::is_format_supported(CompressedFormat::S3tcDxt3Alpha.to_texture_format()));
+    assert!(compress::is_format_supported(CompressedFormat::S3tcDxt5Alpha.to_texture_format()));
+}
+
+#[test]
+fn texture_with_mipmaps() {
+    let display = support::build_display();
+
+    let format = CompressedFormat::S3tcDxt5Alpha;
+    if !format.is_supported(&display) {
+        return;
+    }
+
+    let image = RawImage2d::from_raw_rgba(vec![64u8; 16 * 12 * 4], (16, 12));
+    let texture = CompressedTexture2d::with_format_and_quality(&display, image, format,
+                                                               CompressedMipmapsOption::EmptyMipmaps,
+                                                               CompressionQuality::High).unwrap();
+
+    assert_eq!(texture.get_width(), 16);
+    assert_eq!(texture.get_height(), Some(12));
+    assert_eq!(texture.get_mipmap_levels(), 5);
+
+    display.assert_no_error(None);
+}
@@ -0,0 +1,227 @@
+#![cfg(feature = "texture_container")]
+
+#[macro_use]
+extern crate glium;
+
+use glium::texture::{ClientFormat, CompressedFormat, Dimensions, UncompressedFloatFormat};
+use glium::texture::container::{Container, ContainerError, ContainerFormat, ContainerTexture};
+
+mod support;
+
+const KTX2_IDENTIFIER: [u8; 12] = [0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB,
+                                   0x0D, 0x0A, 0x1A, 0x0A];
+
+/// Builds a KTX2 file whose levels contain the given data.
+fn build_ktx2(vk_format: u32, width: u32, height: u32, layers: u32, faces: u32,
+              levels: &[Vec<u8>]) -> Vec<u8>
+{
+    let mut data = KTX2_IDENTIFIER.to_vec();
+    for value in &[vk_format, 1, width, height, 0, layers, faces, levels.len() as u32, 0] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.resize(80, 0);
+
+    let mut offset = 80 + levels.len() * 24;
+    for level in levels {
+        data.extend_from_slice(&(offset as u64).to_le_bytes());
+        data.extend_from_slice(&(level.len() as u64).to_le_bytes());
+        data.extend_from_slice(&(level.len() as u64).to_le_bytes());
+        offset += level.len();
+    }
+
+    for level in levels {
+        data.extend_from_slice(level);
+    }
+
+    data
+}
+
+/// Builds the header of a DDS file.
+fn build_dds_header(fourcc: &[u8; 4], width: u32, height: u32, mipmaps: u32, caps2: u32) -> Vec<u8> {
+    let mut data = b"DDS ".to_vec();
+    data.resize(128, 0);
+    data[4 .. 8].copy_from_slice(&124u32.to_le_bytes());
+    data[8 .. 12].copy_from_slice(&(0x1007u32 | 0x20000).to_le_bytes());
+    data[12 .. 16].copy_from_slice(&height.to_le_bytes());
+    data[16 .. 20].copy_from_slice(&width.to_le_bytes());
+    data[28 .. 32].copy_from_slice(&mipmaps.to_le_bytes());
+    data[76 .. 80].copy_from_slice(&32u32.to_le_bytes());
+    data[80 .. 84].copy_from_slice(&4u32.to_le_bytes());
+    data[84 .. 88].copy_from_slice(fourcc);
+    data[112 .. 116].copy_from_slice(&caps2.to_le_bytes());
+    data
+}
+
+#[test]
+fn parse_ktx2_mipmaps() {
+    let levels = vec![vec![1u8; 4 * 4 * 4], vec![2u8; 2 * 2 * 4], vec![3u8; 4]];
+    let data = build_ktx2(37, 4, 4, 0, 1, &levels);
+
+    let container = Container::parse(&data).unwrap();
+    assert_eq!(container.get_format(),
+               ContainerFormat::Uncompressed(UncompressedFloatFormat::U8U8U8U8,
+                                             ClientFormat::U8U8U8U8));
+    assert_eq!(container.get_dimensions(), Dimensions::Texture2d { width: 4, height: 4 });
+    assert_eq!(container.get_mipmap_levels(), 3);
+    assert_eq!(container.get_image(1, 0), Some(&levels[1][..]));
+    assert_eq!(container.get_image(2, 0), Some(&levels[2][..]));
+    assert_eq!(container.get_image(3, 0), None);
+    assert_eq!(container.get_image(0, 1), None);
+}
+
+#[test]
+fn parse_ktx2_generated_mipmaps() {
+    // a level count of 0 only stores the base level
+    let mut data = build_ktx2(37, 4, 4, 0, 1, &[vec![1u8; 4 * 4 * 4]]);
+    data[40 .. 44].copy_from_slice(&0u32.to_le_bytes());
+
+    let container = Container::parse(&data).unwrap();
+    assert_eq!(container.get_mipmap_levels(), 1);
+    assert_eq!(container.get_image(0, 0), Some(&[1u8; 64][..]));
+    assert_eq!(container.get_image(1, 0), None);
+}
+
+#[test]
+fn parse_ktx2_compressed_array() {
+    // BC1 with two layers, each 8x8 texel layer is four blocks of 8 bytes
+    let mut level = vec![0u8; 4 * 8];
+    level.extend(vec![1u8; 4 * 8]);
+    let data = build_ktx2(131, 8, 8, 2, 1, &[level]);
+
+    let container = Container::parse(&data).unwrap();
+    assert_eq!(container.get_format(), ContainerFormat::Compressed(CompressedFormat::S3tcDxt1NoAlpha));
+    assert_eq!(container.get_dimensions(),
+               Dimensions::Texture2dArray { width: 8, height: 8, array_size: 2 });
+    assert_eq!(container.get_image(0, 0), Some(&[0u8; 32][..]));
+    assert_eq!(container.get_image(0, 1), Some(&[1u8; 32][..]));
+}
+
+#[test]
+fn parse_ktx2_errors() {
+    let data = build_ktx2(1000, 4, 4, 0, 1, &[vec![0; 64]]);
+    assert_eq!(Container::parse(&data).unwrap_err(), ContainerError::UnsupportedFormat(1000));
+
+    let data = build_ktx2(37, 4, 4, 0, 1, &[vec![0; 60]]);
+    assert_eq!(Container::parse(&data).unwrap_err(), ContainerError::InvalidData);
+
+    let data = build_ktx2(37, 4, 0, 0, 1, &[vec![0; 16]]);
+    assert_eq!(Container::parse(&data).unwrap_err(), ContainerError::UnsupportedDimensions);
+
+    let mut data = build_ktx2(37, 4, 4, 0, 1, &[vec![0; 64]]);
+    data[44] = 2;
+    assert_eq!(Container::parse(&data).unwrap_err(), ContainerError::UnsupportedSupercompression(2));
+
+    assert_eq!(Container::parse(&data[.. 30]).unwrap_err(), ContainerError::InvalidData);
+    assert_eq!(Container::parse(b"not a texture").unwrap_err(), ContainerError::UnknownContainer);
+}
+
+#[test]
+fn parse_ktx2_invalid_counts() {
+    // only 1 or 6 faces are allowed
+    let data = build_ktx2(37, 4, 4, 0, 2, &[vec![0; 128]]);
+    assert_eq!(Container::parse(&data).unwrap_err(), ContainerError::InvalidData);
+
+    // a 4x4 texture has at most 3 levels
+    let levels = vec![vec![0u8; 64], vec![0u8; 16], vec![0u8; 4], vec![0u8; 4]];
+    let data = build_ktx2(37, 4, 4, 0, 1, &levels);
+    assert_eq!(Container::parse(&data).unwrap_err(), ContainerError::InvalidData);
+
+    // the layer count overflows once multiplied by the number of faces
+    let data = build_ktx2(37, 4, 4, u32::MAX, 6, &[vec![0; 64]]);
+    assert_eq!(Container::parse(&data).unwrap_err(), ContainerError::InvalidData);
+
+    // the images are larger than the file
+    let mut data = build_ktx2(37, 4, 4, 0, 1, &[vec![0; 64]]);
+    data[20 .. 24].copy_from_slice(&0x10000u32.to_le_bytes());
+    data[24 .. 28].copy_from_slice(&0x10000u32.to_le_bytes());
+    assert_eq!(Container::parse(&data).unwrap_err(), ContainerError::InvalidData);
+}
+
+#[test]
+fn parse_dds_cubemap() {
+    // DXT5 cubemap of 4x4 texels with two mipmaps, one block per image
+    let mut data = build_dds_header(b"DXT5", 4, 4, 2, 0x200 | 0xfc00);
+    for face in 0 .. 6u8 {
+        data.extend(vec![face; 16]);
+        data.extend(vec![face + 100; 16]);
+    }
+
+    let container = Container::parse(&data).unwrap();
+    assert_eq!(container.get_format(), ContainerFormat::Compressed(CompressedFormat::S3tcDxt5Alpha));
+    assert_eq!(container.get_dimensions(), Dimensions::Cubemap { dimension: 4 });
+    assert_eq!(container.get_mipmap_levels(), 2);
+    assert_eq!(container.get_image(0, 3), Some(&[3u8; 16][..]));
+    assert_eq!(container.get_image(1, 3), Some(&[103u8; 16][..]));
+}
+
+#[test]
+fn parse_dds_dx10_array() {
+    let mut data = build_dds_header(b"DX10", 4, 4, 1, 0);
+    for value in &[98u32, 3, 0, 3, 0] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend(vec![0u8; 16 * 3]);
+
+    let container = Container::parse(&data).unwrap();
+    assert_eq!(container.get_format(), ContainerFormat::Compressed(CompressedFormat::BptcUnorm4));
+    assert_eq!(container.get_dimensions(),
+               Dimensions::Texture2dArray { width: 4, height: 4, array_size: 3 });
+
+    data[128] = 200;
+    assert_eq!(Container::parse(&data).unwrap_err(), ContainerError::UnsupportedFormat(200));
+}
+
+#[test]
+fn parse_dds_truncated() {
+    let mut data = build_dds_header(b"DXT1", 8, 8, 1, 0);
+    data.extend(vec![0u8; 8 * 3]);
+    assert_eq!(Container::parse(&data).unwrap_err(), ContainerError::InvalidData);
+
+    // a huge array whose images don't fit in the file
+    let mut data = build_dds_header(b"DX10", 4, 4, 1, 0);
+    for value in &[98u32, 3, 0x4, u32::MAX, 0] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend(vec![0u8; 16 * 6]);
+    assert_eq!(Container::parse(&data).unwrap_err(), ContainerError::InvalidData);
+}
+
+#[test]
+fn load_ktx2_mipmaps() {
+    let display = support::build_display();
+
+    let levels = vec![vec![255u8; 4 * 4 * 4], vec![128u8; 2 * 2 * 4], vec![0u8; 4]];
+    let data = build_ktx2(37, 4, 4, 0, 1, &levels);
+    let container = Container::parse(&data).unwrap();
+
+    let texture = match container.create_texture(&display).unwrap() {
+        ContainerTexture::Texture2d(texture) => texture,
+        t => panic!("{:?}", t)
+    };
+
+    assert_eq!(texture.get_mipmap_levels(), 3);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data, vec![vec![(255, 255, 255, 255); 4]; 4]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn load_dds_compressed_cubemap() {
+    let display = support::build_display();
+
+    let mut data = build_dds_header(b"DXT1", 4, 4, 1, 0x200 | 0xfc00);
+    data.extend(vec![0u8; 8 * 6]);
+    let container = Container::parse(&data).unwrap();
+
+    let texture = match container.create_texture(&display) {
+        Ok(ContainerTexture::CompressedCubemap(texture)) => texture,
+        Err(ContainerError::TextureCreationError(_)) => return,
+        t => panic!("{:?}", t)
+    };
+
+    assert_eq!(texture.get_width(), 4);
+
+    display.assert_no_error(None);
+}
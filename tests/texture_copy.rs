@@ -0,0 +1,122 @@
+#[macro_use]
+extern crate glium;
+
+use glium::Surface;
+use glium::texture::{self, CopyImageError, UncompressedFloatFormat, MipmapsOption};
+
+mod support;
+
+#[test]
+fn copy_region_2d() {
+    let display = support::build_display();
+
+    let source = texture::Texture2d::empty_with_format(&display, UncompressedFloatFormat::U8U8U8U8,
+                                                        MipmapsOption::NoMipmap, 8, 8).unwrap();
+    source.as_surface().clear_color(1.0, 0.0, 0.0, 1.0);
+
+    let target = texture::Texture2d::empty_with_format(&display, UncompressedFloatFormat::U8U8U8U8,
+                                                        MipmapsOption::NoMipmap, 8, 8).unwrap();
+    target.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    source.main_level().copy_region_to((0, 0, 0), (4, 8, 1), &target.main_level(), (4, 0, 0))
+          .unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = target.read();
+    for row in data.iter() {
+        for (col, pixel) in row.iter().enumerate() {
+            if col >= 4 {
+                assert_eq!(pixel, &(255, 0, 0, 255));
+            } else {
+                assert_eq!(pixel, &(0, 0, 0, 0));
+            }
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn copy_region_typed() {
+    let display = support::build_display();
+
+    let source = texture::Texture2d::empty_with_format(&display, UncompressedFloatFormat::U8U8U8U8,
+                                                        MipmapsOption::NoMipmap, 8, 8).unwrap();
+    source.as_surface().clear_color(0.0, 1.0, 0.0, 1.0);
+
+    let target = texture::Texture2d::empty_with_format(&display, UncompressedFloatFormat::U8U8U8U8,
+                                                        MipmapsOption::NoMipmap, 8, 8).unwrap();
+    target.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let rect = glium::Rect { left: 0, bottom: 0, width: 8, height: 4 };
+    source.copy_region_to(rect, &target, 0, 4).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = target.read();
+    for (row_index, row) in data.iter().enumerate() {
+        for pixel in row.iter() {
+            if row_index >= 4 {
+                assert_eq!(pixel, &(0, 255, 0, 255));
+            } else {
+                assert_eq!(pixel, &(0, 0, 0, 0));
+            }
+        }
+    }
+
+    let rect = glium::Rect { left: 4, bottom: 4, width: 8, height: 4 };
+    assert_eq!(source.copy_region_to(rect, &target, 0, 0), Err(CopyImageError::OutOfRange));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn copy_array_layer() {
+    let display = support::build_display();
+
+    let layers = vec![
+        vec![vec![(255u8, 0u8, 0u8, 255u8); 2]; 2],
+        vec![vec![(0u8, 255u8, 0u8, 255u8); 2]; 2],
+    ];
+
+    let source = match texture::Texture2dArray::with_format(&display, layers,
+                                                            UncompressedFloatFormat::U8U8U8U8,
+                                                            MipmapsOption::NoMipmap)
+    {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let target = texture::Texture2d::empty_with_format(&display, UncompressedFloatFormat::U8U8U8U8,
+                                                        MipmapsOption::NoMipmap, 2, 2).unwrap();
+
+    match source.main_level().copy_region_to((0, 0, 1), (2, 2, 1), &target.main_level(), (0, 0, 0)) {
+        Ok(()) => (),
+        Err(CopyImageError::NotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = target.read();
+    assert_eq!(data, vec![vec![(0, 255, 0, 255); 2]; 2]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn copy_out_of_range() {
+    let display = support::build_display();
+
+    let source = texture::Texture2d::empty_with_format(&display, UncompressedFloatFormat::U8U8U8U8,
+                                                        MipmapsOption::NoMipmap, 8, 8).unwrap();
+    let target = texture::Texture2d::empty_with_format(&display, UncompressedFloatFormat::U8U8U8U8,
+                                                        MipmapsOption::NoMipmap, 4, 4).unwrap();
+
+    match source.main_level().copy_region_to((0, 0, 0), (8, 8, 1), &target.main_level(), (0, 0, 0)) {
+        Err(CopyImageError::OutOfRange) => (),
+        r => panic!("{:?}", r)
+    };
+
+    match source.main_level().copy_region_to((0, 0, 1), (1, 1, 1), &target.main_level(), (0, 0, 0)) {
+        Err(CopyImageError::OutOfRange) => (),
+        r => panic!("{:?}", r)
+    };
+
+    display.assert_no_error(None);
+}
@@ -159,6 +159,25 @@ fn empty_depth_texture2d_f32() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn s3tc_texture_2d_creation() {
+    let display = support::build_display();
+
+    let format = glium::texture::CompressedFormat::S3tcDxt1NoAlpha;
+    if !format.is_supported(&display) {
+        return;
+    }
+
+    // 6x6 texels are stored in 2x2 blocks of 8 bytes, the blocks at the border being partial
+    let texture = glium::texture::CompressedTexture2d::with_compressed_data(&display, &[0; 32], 6, 6,
+                                    format, glium::texture::CompressedMipmapsOption::NoMipmap).unwrap();
+
+    assert_eq!(texture.get_width(), 6);
+    assert_eq!(texture.get_height(), Some(6));
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn compressed_texture_2d_creation() {
     let display = support::build_display();
@@ -177,6 +196,45 @@ fn compressed_texture_2d_creation() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn etc2_texture_2d_creation() {
+    let display = support::build_display();
+
+    let format = glium::texture::CompressedFormat::Etc2Rgba8;
+    if !format.is_supported(&display) {
+        return;
+    }
+
+    // 6x6 texels are stored in 2x2 blocks of 16 bytes
+    let texture = glium::texture::CompressedTexture2d::with_compressed_data(&display, &[0; 64], 6, 6,
+                                    format, glium::texture::CompressedMipmapsOption::NoMipmap).unwrap();
+
+    assert_eq!(texture.get_width(), 6);
+    assert_eq!(texture.get_height(), Some(6));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn astc_texture_2d_creation() {
+    let display = support::build_display();
+
+    let format = glium::texture::CompressedSrgbFormat::Astc8x5;
+    if !format.is_supported(&display) {
+        return;
+    }
+
+    // 16x16 texels are stored in 2x4 blocks of 16 bytes
+    let texture = glium::texture::CompressedSrgbTexture2d::with_compressed_data(&display, &[0; 128],
+                                    16, 16, format,
+                                    glium::texture::CompressedMipmapsOption::NoMipmap).unwrap();
+
+    assert_eq!(texture.get_width(), 16);
+    assert_eq!(texture.get_height(), Some(16));
+
+    display.assert_no_error(None);
+}
+
 macro_rules! empty_texture_test {
     ($test_name:ident, $tex_ty:ident, [$($dims:expr),+],
      $w:expr, $h:expr, $d:expr, $s:expr) =>
@@ -0,0 +1,81 @@
+#![cfg(feature = "image_interop")]
+
+#[macro_use]
+extern crate glium;
+
+use glium::texture::{MipmapsOption, RawImage2d, Texture2d, Texture2dDataSource};
+use glium::texture::image_interop::{self, ImageTexture};
+
+mod support;
+
+#[test]
+fn image_buffer_rows_are_flipped() {
+    let image = image::RgbaImage::from_fn(1, 2, |_, y| image::Rgba([y as u8, 0, 0, 255]));
+    let raw: RawImage2d<'_, u8> = Texture2dDataSource::into_raw(image);
+
+    // the first row of the raw image is the bottom row of the image
+    assert_eq!(raw.data.into_owned(), vec![1, 0, 0, 255, 0, 0, 0, 255]);
+}
+
+#[test]
+fn dynamic_image_16bit() {
+    let image = image::DynamicImage::ImageLuma16(image::ImageBuffer::from_raw(1, 2, vec![1u16, 2]).unwrap());
+    let raw = image.into_raw();
+
+    assert_eq!(raw.format, glium::texture::ClientFormat::U16);
+    assert_eq!(raw.data.len(), 4);
+    assert_eq!(&raw.data[.. 2], &2u16.to_ne_bytes());
+}
+
+#[test]
+fn image_round_trip() {
+    let display = support::build_display();
+
+    let image = image::RgbaImage::from_fn(2, 2, |x, y| image::Rgba([x as u8, y as u8, 0, 255]));
+    let texture = Texture2d::new(&display, image.clone()).unwrap();
+
+    let read_back: image::RgbaImage = texture.read();
+    assert_eq!(read_back, image);
+
+    // the bottom-left pixel of the texture is the bottom-left pixel of the image
+    let raw: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(raw[0][0], (0, 1, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn new_texture_2d_srgb_selection() {
+    let display = support::build_display();
+
+    let image = image::DynamicImage::new_rgba8(4, 4);
+    match image_interop::new_texture_2d(&display, image, MipmapsOption::NoMipmap).unwrap() {
+        ImageTexture::Srgb(texture) => assert_eq!(texture.dimensions(), (4, 4)),
+        ImageTexture::Linear(_) => panic!(),
+    }
+
+    let image = image::DynamicImage::new_rgba32f(4, 4);
+    match image_interop::new_texture_2d(&display, image, MipmapsOption::NoMipmap).unwrap() {
+        ImageTexture::Linear(texture) => assert_eq!(texture.dimensions(), (4, 4)),
+        ImageTexture::Srgb(_) => panic!(),
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn save_png() {
+    let display = support::build_display();
+
+    let image = image::RgbaImage::from_pixel(3, 2, image::Rgba([10, 20, 30, 255]));
+    let texture = Texture2d::new(&display, image.clone()).unwrap();
+
+    let path = std::env::temp_dir().join("glium_texture_image_save_png.png");
+    texture.save_png(&path).unwrap();
+
+    let loaded = image::open(&path).unwrap().to_rgba8();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(loaded, image);
+
+    display.assert_no_error(None);
+}
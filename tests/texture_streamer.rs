@@ -0,0 +1,63 @@
+#[macro_use]
+extern crate glium;
+
+use glium::texture::Texture2d;
+use glium::texture::streamer::{FrameDroppedError, TextureStreamer};
+
+mod support;
+
+#[test]
+fn push_and_upload() {
+    let display = support::build_display();
+
+    let texture = Texture2d::empty(&display, 2, 2).unwrap();
+    let mut streamer = TextureStreamer::new(&display, 2, 2, 2).unwrap();
+
+    assert!(!streamer.upload(&texture.main_level()));
+
+    streamer.push(&[(255u8, 0u8, 0u8, 255u8); 4]).unwrap();
+    assert_eq!(streamer.get_pending_frames(), 1);
+    assert!(streamer.upload(&texture.main_level()));
+    assert_eq!(streamer.get_pending_frames(), 0);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data, vec![vec![(255, 0, 0, 255); 2]; 2]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn ring_full() {
+    let display = support::build_display();
+
+    let mut streamer = TextureStreamer::new(&display, 2, 2, 2).unwrap();
+
+    streamer.push(&[(0u8, 0u8, 0u8, 0u8); 4]).unwrap();
+    streamer.push(&[(0u8, 0u8, 0u8, 0u8); 4]).unwrap();
+    assert_eq!(streamer.push(&[(0u8, 0u8, 0u8, 0u8); 4]), Err(FrameDroppedError));
+    assert_eq!(streamer.get_dropped_frames(), 1);
+    assert_eq!(streamer.get_pending_frames(), 2);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn upload_latest() {
+    let display = support::build_display();
+
+    let texture = Texture2d::empty(&display, 1, 1).unwrap();
+    let mut streamer = TextureStreamer::new(&display, 1, 1, 3).unwrap();
+
+    streamer.push(&[(1u8, 1u8, 1u8, 1u8)]).unwrap();
+    streamer.push(&[(2u8, 2u8, 2u8, 2u8)]).unwrap();
+    streamer.push(&[(3u8, 3u8, 3u8, 3u8)]).unwrap();
+
+    assert!(streamer.upload_latest(&texture.main_level()));
+    assert_eq!(streamer.get_dropped_frames(), 2);
+    assert_eq!(streamer.get_pending_frames(), 0);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data, vec![vec![(3, 3, 3, 3)]]);
+
+    display.assert_no_error(None);
+}
@@ -0,0 +1,114 @@
+#[macro_use]
+extern crate glium;
+
+use glium::Surface;
+use glium::texture::{self, TextureViewError, UncompressedFloatFormat, MipmapsOption};
+
+mod support;
+
+#[test]
+fn texture_2d_array_layer_view() {
+    let display = support::build_display();
+
+    let layers = vec![
+        vec![vec![(255u8, 0u8, 0u8, 255u8); 2]; 2],
+        vec![vec![(0u8, 255u8, 0u8, 255u8); 2]; 2],
+    ];
+
+    let array = match texture::Texture2dArray::with_format(&display, layers,
+                                                           UncompressedFloatFormat::U8U8U8U8,
+                                                           MipmapsOption::NoMipmap)
+    {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let view: texture::Texture2d = match array.view(UncompressedFloatFormat::U8U8U8U8, 0 .. 1, 1 .. 2) {
+        Ok(v) => v,
+        Err(TextureViewError::NotSupported) | Err(TextureViewError::MutableStorage) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    assert_eq!(view.dimensions(), (2, 2));
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = view.read();
+    assert_eq!(data, vec![vec![(0, 255, 0, 255); 2]; 2]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn view_shares_storage() {
+    let display = support::build_display();
+
+    let original = texture::Texture2d::empty_with_format(&display, UncompressedFloatFormat::U8U8U8U8,
+                                                          MipmapsOption::NoMipmap, 4, 4).unwrap();
+
+    let view: texture::Texture2d = match original.view(UncompressedFloatFormat::U8U8U8U8, 0 .. 1, 0 .. 1) {
+        Ok(v) => v,
+        Err(TextureViewError::NotSupported) | Err(TextureViewError::MutableStorage) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    view.as_surface().clear_color(0.0, 0.0, 1.0, 1.0);
+
+    // the view stays valid after the original is destroyed
+    drop(original);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = view.read();
+    assert_eq!(data, vec![vec![(0, 0, 255, 255); 4]; 4]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn srgb_as_linear_view() {
+    let display = support::build_display();
+
+    let original = texture::SrgbTexture2d::empty_with_format(&display, texture::SrgbFormat::U8U8U8U8,
+                                                              MipmapsOption::NoMipmap, 4, 4).unwrap();
+
+    match original.view::<texture::Texture2d>(UncompressedFloatFormat::U8U8U8U8, 0 .. 1, 0 .. 1) {
+        Ok(_) => (),
+        Err(TextureViewError::NotSupported) | Err(TextureViewError::MutableStorage) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn incompatible_format() {
+    let display = support::build_display();
+
+    let original = texture::Texture2d::empty_with_format(&display, UncompressedFloatFormat::U8U8U8U8,
+                                                          MipmapsOption::NoMipmap, 4, 4).unwrap();
+
+    match original.view::<texture::Texture2d>(UncompressedFloatFormat::U16U16U16U16, 0 .. 1, 0 .. 1) {
+        Err(TextureViewError::IncompatibleFormat) => (),
+        Err(TextureViewError::NotSupported) | Err(TextureViewError::MutableStorage) => return,
+        r => panic!("{:?}", r)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn out_of_range() {
+    let display = support::build_display();
+
+    let original = texture::Texture2d::empty_with_format(&display, UncompressedFloatFormat::U8U8U8U8,
+                                                          MipmapsOption::NoMipmap, 4, 4).unwrap();
+
+    match original.view::<texture::Texture2d>(UncompressedFloatFormat::U8U8U8U8, 1 .. 2, 0 .. 1) {
+        Err(TextureViewError::LevelsOutOfRange) => (),
+        r => panic!("{:?}", r)
+    };
+
+    match original.view::<texture::Texture2dArray>(UncompressedFloatFormat::U8U8U8U8, 0 .. 1, 0 .. 2) {
+        Err(TextureViewError::LayersOutOfRange) | Err(TextureViewError::NotSupported) => (),
+        r => panic!("{:?}", r)
+    };
+
+    display.assert_no_error(None);
+}
@@ -0,0 +1,247 @@
+#[macro_use]
+extern crate glium;
+
+use glium::program::BlockLayout;
+use glium::uniforms::UniformType;
+use glium::uniforms::layout::{self, Array, LayoutDifference, LayoutDifferenceKind, Mat3, Padded};
+use glium::uniforms::layout::{Std140Layout, Std430Layout};
+
+mod support;
+
+#[test]
+fn basic_types() {
+    assert_eq!(<[f32; 3] as Std140Layout>::STD140_ALIGNMENT, 16);
+    assert_eq!(<[f32; 3] as Std140Layout>::STD140_SIZE, 12);
+    assert_eq!(<[f64; 2] as Std430Layout>::STD430_ALIGNMENT, 16);
+    assert_eq!(<Mat3 as Std140Layout>::STD140_SIZE, 48);
+    assert_eq!(std::mem::size_of::<Mat3>(), 48);
+}
+
+#[test]
+fn arrays() {
+    // the elements of std140 arrays are aligned to 16 bytes
+    assert_eq!(<Array<Padded<f32>, 4> as Std140Layout>::STD140_SIZE, 64);
+    assert_eq!(<Array<Padded<[f32; 3]>, 2> as Std430Layout>::STD430_SIZE, 32);
+    assert_eq!(<Array<f32, 4> as Std430Layout>::STD430_SIZE, 16);
+    assert_eq!(<Array<[f32; 2], 3> as Std430Layout>::STD430_ALIGNMENT, 8);
+}
+
+#[test]
+fn diff() {
+    let expected = BlockLayout::Struct {
+        members: vec![
+            ("color".to_owned(), BlockLayout::BasicType { ty: UniformType::FloatVec3, offset_in_buffer: 0 }),
+            ("intensity".to_owned(), BlockLayout::BasicType { ty: UniformType::Float, offset_in_buffer: 12 }),
+            ("weights".to_owned(), BlockLayout::Array {
+                content: Box::new(BlockLayout::BasicType { ty: UniformType::Float, offset_in_buffer: 16 }),
+                length: 4,
+            }),
+        ],
+    };
+
+    let obtained = BlockLayout::Struct {
+        members: vec![
+            ("color".to_owned(), BlockLayout::BasicType { ty: UniformType::FloatVec4, offset_in_buffer: 0 }),
+            ("intensity".to_owned(), BlockLayout::BasicType { ty: UniformType::Float, offset_in_buffer: 16 }),
+            ("extra".to_owned(), BlockLayout::BasicType { ty: UniformType::Float, offset_in_buffer: 20 }),
+        ],
+    };
+
+    assert!(layout::diff_layouts(&expected, &expected).is_empty());
+
+    let differences = layout::diff_layouts(&expected, &obtained);
+    assert_eq!(differences, vec![
+        LayoutDifference {
+            path: "color".to_owned(),
+            kind: LayoutDifferenceKind::Type { expected: UniformType::FloatVec3,
+                                               obtained: UniformType::FloatVec4 },
+        },
+        LayoutDifference {
+            path: "intensity".to_owned(),
+            kind: LayoutDifferenceKind::Offset { expected: 12, obtained: 16 },
+        },
+        LayoutDifference { path: "weights".to_owned(), kind: LayoutDifferenceKind::Missing },
+        LayoutDifference { path: "extra".to_owned(), kind: LayoutDifferenceKind::Unexpected },
+    ]);
+
+    assert_eq!(differences[1].to_string(), "intensity: expected offset 12, got 16");
+}
+
+#[test]
+fn draw_mismatch_diff_block() {
+    use glium::Surface;
+    use glium::uniforms::LayoutMismatchError;
+
+    let display = support::build_display();
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 330
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+
+            layout(std140) uniform Block {
+                vec4 color;
+                float intensity;
+            };
+
+            out vec4 f_color;
+
+            void main() {
+                f_color = color * intensity;
+            }
+        ",
+        None)
+    {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    #[derive(Copy, Clone)]
+    struct Block {
+        color: [f32; 3],
+    }
+
+    implement_uniform_block!(Block, color);
+
+    let buffer = match glium::uniforms::UniformBuffer::new(&display, Block { color: [0.0; 3] }) {
+        Ok(b) => b,
+        Err(_) => return,
+    };
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+    let texture = support::build_renderable_texture(&display);
+
+    match texture.as_surface().draw(&vb, &ib, &program, &uniform! { Block: &buffer },
+                                    &Default::default())
+    {
+        Err(glium::DrawError::UniformBlockLayoutMismatch {
+            name, err: LayoutMismatchError::MissingField { .. }
+        }) => {
+            let block = &program.get_uniform_blocks()[&name];
+            assert_eq!(layout::diff_block::<Block>(block), vec![
+                LayoutDifference {
+                    path: "color".to_owned(),
+                    kind: LayoutDifferenceKind::Type { expected: UniformType::FloatVec4,
+                                                       obtained: UniformType::FloatVec3 },
+                },
+                LayoutDifference { path: "intensity".to_owned(), kind: LayoutDifferenceKind::Missing },
+            ]);
+        },
+        r => panic!("{:?}", r),
+    }
+
+    display.assert_no_error(None);
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    use glium::Surface;
+    use glium::uniforms::UniformBlock;
+    use glium::uniforms::layout::{self, Array, Mat3, Padded, Std140, Std140Layout, Std430Layout};
+
+    use super::support;
+
+    #[derive(Copy, Clone, glium::UniformBlock, glium::Std140Layout, glium::Std430Layout)]
+    #[repr(C)]
+    struct Light {
+        position: [f32; 3],
+        intensity: f32,
+        color: [f32; 4],
+    }
+
+    #[derive(Copy, Clone, glium::UniformBlock, glium::Std140Layout)]
+    #[repr(C)]
+    struct Scene {
+        rotation: Mat3,
+        weights: Array<Padded<f32>, 2>,
+        light: Light,
+        ambient: f32,
+    }
+
+    #[test]
+    fn derived_layouts() {
+        assert_eq!(<Light as Std140Layout>::STD140_ALIGNMENT, 16);
+        assert_eq!(<Light as Std140Layout>::STD140_SIZE, 32);
+        assert_eq!(<Light as Std430Layout>::STD430_SIZE, 32);
+        assert_eq!(<Scene as Std140Layout>::STD140_SIZE, 128);
+        assert_eq!(std::mem::size_of::<Scene>(), 128);
+    }
+
+    #[test]
+    fn std140_uniform_buffer() {
+        let display = support::build_display();
+
+        let program = match glium::Program::from_source(&display,
+            "
+                #version 330
+
+                in vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            "
+                #version 330
+
+                struct Light {
+                    vec3 position;
+                    float intensity;
+                    vec4 color;
+                };
+
+                layout(std140) uniform Scene {
+                    mat3 rotation;
+                    float weights[2];
+                    Light light;
+                    float ambient;
+                };
+
+                out vec4 f_color;
+
+                void main() {
+                    f_color = light.color * light.intensity * weights[1] + ambient * rotation[0].x;
+                }
+            ",
+            None)
+        {
+            Ok(p) => p,
+            Err(_) => return,
+        };
+
+        let block = &program.get_uniform_blocks()["Scene"];
+        let differences = layout::diff_layouts(&block.layout, &<Scene as UniformBlock>::build_layout(0));
+        assert!(differences.is_empty(), "{:?}", differences);
+
+        let scene = Scene {
+            rotation: [[0.0; 3]; 3].into(),
+            weights: [Padded(0.0), Padded(1.0)].into(),
+            light: Light { position: [0.0; 3], intensity: 1.0, color: [0.0, 0.0, 1.0, 1.0] },
+            ambient: 0.0,
+        };
+
+        let buffer = match glium::uniforms::UniformBuffer::new(&display, Std140::new(scene)) {
+            Ok(b) => b,
+            Err(_) => return,
+        };
+
+        let (vb, ib) = support::build_rectangle_vb_ib(&display);
+        let texture = support::build_renderable_texture(&display);
+        texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+        texture.as_surface().draw(&vb, &ib, &program, &uniform! { Scene: &buffer },
+                                  &Default::default()).unwrap();
+
+        let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+        assert_eq!(data[0][0], (0, 0, 255, 255));
+
+        display.assert_no_error(None);
+    }
+}